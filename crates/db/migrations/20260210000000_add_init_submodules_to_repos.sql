@@ -0,0 +1,3 @@
+-- Add init_submodules column to repos table
+-- When enabled, submodules are initialized in new worktrees
+ALTER TABLE repos ADD COLUMN init_submodules INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Add sparse_checkout columns for cone-mode sparse worktrees
+-- repos.sparse_checkout holds the default directory list (newline-separated)
+-- workspace_repos.sparse_checkout overrides it for a single workspace
+ALTER TABLE repos ADD COLUMN sparse_checkout TEXT;
+ALTER TABLE workspace_repos ADD COLUMN sparse_checkout TEXT;
@@ -0,0 +1,5 @@
+-- Add per-repo settings for generated Conventional Commits messages
+-- commit_message_template overrides the default "{type}{scope}: {description}" layout
+-- commit_subject_max_length caps the subject line (defaults to 72 when NULL)
+ALTER TABLE repos ADD COLUMN commit_message_template TEXT;
+ALTER TABLE repos ADD COLUMN commit_subject_max_length INTEGER;
@@ -0,0 +1,5 @@
+-- Add per-repo overrides for the identity and signing of agent commits and merges
+-- NULL inherits the global setting
+ALTER TABLE repos ADD COLUMN commit_identity_mode TEXT;
+ALTER TABLE repos ADD COLUMN commit_signing_format TEXT;
+ALTER TABLE repos ADD COLUMN commit_signing_key TEXT;
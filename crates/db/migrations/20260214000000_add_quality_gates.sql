@@ -0,0 +1,69 @@
+-- Per-repo quality gate scripts (lint, test, typecheck, ...) that run in the
+-- worktree before a workspace branch is merged, pushed or opened as a PR
+CREATE TABLE quality_gates (
+    id          BLOB PRIMARY KEY,
+    repo_id     BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    script      TEXT NOT NULL,
+    required    INTEGER NOT NULL DEFAULT 1,
+    sort_order  INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_quality_gates_repo_id ON quality_gates(repo_id);
+
+-- One row per gate execution; the outcome comes from the execution process
+CREATE TABLE quality_gate_runs (
+    id                    BLOB PRIMARY KEY,
+    quality_gate_id       BLOB NOT NULL,
+    workspace_id          BLOB NOT NULL,
+    execution_process_id  BLOB NOT NULL,
+    head_commit           TEXT NOT NULL,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (quality_gate_id) REFERENCES quality_gates(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_quality_gate_runs_workspace_gate
+        ON quality_gate_runs (workspace_id, quality_gate_id, created_at DESC);
+
+-- Add 'qualitygate' to the run_reason CHECK constraint
+
+-- 1. Add the replacement column with the wider CHECK
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                               'cleanupscript',
+                               'archivescript',
+                               'qualitygate',
+                               'codingagent',
+                               'devserver'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop any indexes that reference run_reason
+DROP INDEX IF EXISTS idx_execution_processes_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_status_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_run_reason_created;
+
+-- 4. Remove the old column (requires 3.35+)
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 5. Rename the new column back to the canonical name
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+
+-- 6. Re-create all indexes
+CREATE INDEX idx_execution_processes_run_reason
+        ON execution_processes(run_reason);
+
+CREATE INDEX idx_execution_processes_session_status_run_reason
+        ON execution_processes (session_id, status, run_reason);
+
+CREATE INDEX idx_execution_processes_session_run_reason_created
+        ON execution_processes (session_id, run_reason, created_at DESC);
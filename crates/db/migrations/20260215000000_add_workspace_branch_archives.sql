@@ -0,0 +1,22 @@
+-- Unmerged workspace branches kept before their worktree is cleaned up or the
+-- workspace is deleted. Rows outlive the workspace and task on purpose.
+CREATE TABLE workspace_branch_archives (
+    id              BLOB PRIMARY KEY,
+    workspace_id    BLOB NOT NULL,
+    task_id         BLOB NOT NULL,
+    repo_id         BLOB NOT NULL,
+    workspace_name  TEXT,
+    branch          TEXT NOT NULL,
+    target_branch   TEXT NOT NULL,
+    commit_sha      TEXT NOT NULL,
+    storage         TEXT NOT NULL CHECK (storage IN ('ref', 'bundle')),
+    -- Archive ref name or bundle file path
+    location        TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    UNIQUE (workspace_id, repo_id)
+);
+
+CREATE INDEX idx_workspace_branch_archives_created_at
+        ON workspace_branch_archives(created_at DESC);
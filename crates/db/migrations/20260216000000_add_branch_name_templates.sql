@@ -0,0 +1,4 @@
+-- Add branch naming templates for projects and repos
+-- NULL inherits the project's template, then the global setting
+ALTER TABLE projects ADD COLUMN branch_name_template TEXT;
+ALTER TABLE repos ADD COLUMN branch_name_template TEXT;
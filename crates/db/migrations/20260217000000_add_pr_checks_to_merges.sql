@@ -0,0 +1,4 @@
+-- Track CI checks on open PRs and the follow-ups sent to the agent to fix them
+-- pr_checks holds a JSON array of checks, NULL until the first poll
+ALTER TABLE merges ADD COLUMN pr_checks TEXT;
+ALTER TABLE merges ADD COLUMN pr_ci_fix_attempts INTEGER NOT NULL DEFAULT 0;
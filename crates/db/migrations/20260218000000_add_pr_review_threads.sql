@@ -0,0 +1,22 @@
+-- Review threads of a workspace PR that were sent to the agent as a
+-- follow-up, and what came of each one
+CREATE TABLE pr_review_threads (
+    id                    BLOB PRIMARY KEY,
+    merge_id              BLOB NOT NULL,
+    thread_id             TEXT NOT NULL,
+    path                  TEXT NOT NULL,
+    line                  INTEGER,
+    url                   TEXT,
+    execution_process_id  BLOB,
+    status                TEXT NOT NULL DEFAULT 'in_progress'
+                          CHECK (status IN ('in_progress', 'addressed', 'skipped', 'failed')),
+    note                  TEXT,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (merge_id) REFERENCES merges(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL,
+    UNIQUE (merge_id, thread_id)
+);
+
+CREATE INDEX idx_pr_review_threads_execution_process_id
+        ON pr_review_threads(execution_process_id);
@@ -0,0 +1,9 @@
+-- Track whether open PRs are drafts, and the agent runs refreshing their
+-- descriptions after follow-ups are pushed
+ALTER TABLE merges ADD COLUMN pr_is_draft INTEGER NOT NULL DEFAULT 0;
+-- Coding agent run rewriting the description, cleared once it is checked
+ALTER TABLE merges ADD COLUMN pr_description_process_id BLOB
+    REFERENCES execution_processes(id) ON DELETE SET NULL;
+-- User-maintained section of the description when the refresh started,
+-- restored if the agent drops it
+ALTER TABLE merges ADD COLUMN pr_description_user_section TEXT;
@@ -0,0 +1,17 @@
+-- Git host issues that local tasks were imported from, so that the issue
+-- and the task can follow each other's status
+CREATE TABLE task_issues (
+    id            BLOB PRIMARY KEY,
+    task_id       BLOB NOT NULL UNIQUE,
+    repo_id       BLOB NOT NULL,
+    issue_url     TEXT NOT NULL,
+    issue_number  INTEGER NOT NULL,
+    state         TEXT NOT NULL DEFAULT 'open'
+                  CHECK (state IN ('open', 'closed')),
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_issues_issue_url ON task_issues(issue_url);
@@ -0,0 +1,2 @@
+-- Merge open PRs through their git host once approved with passing checks
+ALTER TABLE merges ADD COLUMN pr_auto_merge INTEGER NOT NULL DEFAULT 0;
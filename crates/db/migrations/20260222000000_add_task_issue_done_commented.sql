@@ -0,0 +1,3 @@
+-- Remember that the issue of a done task got its comment, so a close that
+-- failed is retried without commenting again
+ALTER TABLE task_issues ADD COLUMN done_commented INTEGER NOT NULL DEFAULT 0;
@@ -81,6 +81,29 @@ impl CodingAgentTurn {
         .await
     }
 
+    /// Find the final summary of the most recent coding agent turn in a workspace
+    pub async fn find_latest_summary_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let summary = sqlx::query_scalar!(
+            r#"SELECT cat.summary as "summary!"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON cat.execution_process_id = ep.id
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE s.workspace_id = $1
+                 AND ep.dropped = FALSE
+                 AND cat.summary IS NOT NULL
+               ORDER BY ep.created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(summary)
+    }
+
     pub async fn find_by_agent_session_id(
         pool: &SqlitePool,
         agent_session_id: &str,
@@ -56,6 +56,7 @@ pub enum ExecutionProcessRunReason {
     SetupScript,
     CleanupScript,
     ArchiveScript,
+    QualityGate,
     CodingAgent,
     DevServer,
 }
@@ -156,4 +156,36 @@ impl ExecutionProcessRepoState {
         .fetch_all(pool)
         .await
     }
+
+    /// Repo states of all non-dropped processes in a workspace for one repo,
+    /// oldest first.
+    pub async fn find_by_workspace_and_repo(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessRepoState,
+            r#"SELECT
+                    eprs.id               as "id!: Uuid",
+                    eprs.execution_process_id as "execution_process_id!: Uuid",
+                    eprs.repo_id as "repo_id!: Uuid",
+                    eprs.before_head_commit,
+                    eprs.after_head_commit,
+                    eprs.merge_commit,
+                    eprs.created_at as "created_at!: DateTime<Utc>",
+                    eprs.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_process_repo_states eprs
+               JOIN execution_processes ep ON ep.id = eprs.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1
+                 AND eprs.repo_id = $2
+                 AND ep.dropped = FALSE
+               ORDER BY ep.created_at ASC"#,
+            workspace_id,
+            repo_id
+        )
+        .fetch_all(pool)
+        .await
+    }
 }
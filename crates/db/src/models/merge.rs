@@ -42,6 +42,16 @@ pub struct PrMerge {
     pub created_at: DateTime<Utc>,
     pub target_branch_name: String,
     pub pr_info: PullRequestInfo,
+    /// CI checks on the PR head as of the last poll
+    pub checks: Vec<PrCheck>,
+    /// Follow-ups sent to the agent to fix failing checks since they last passed
+    pub ci_fix_attempts: i64,
+    /// Coding agent run refreshing the PR description, until it is checked
+    pub description_process_id: Option<Uuid>,
+    /// User-maintained section of the description when the refresh started
+    pub description_user_section: Option<String>,
+    /// Merge the PR once it is approved and its required checks pass
+    pub auto_merge: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -51,6 +61,47 @@ pub struct PullRequestInfo {
     pub status: MergeStatus,
     pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
     pub merge_commit_sha: Option<String>,
+    #[serde(default)]
+    pub is_draft: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PrCheckStatus {
+    Pending,
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl PrCheckStatus {
+    /// Combined status of the required checks: failed if any failed, pending
+    /// if any are still running. None when no check is required.
+    pub fn of_required(checks: &[PrCheck]) -> Option<Self> {
+        let mut required = checks.iter().filter(|c| c.required).peekable();
+        required.peek()?;
+        let mut status = PrCheckStatus::Passed;
+        for check in required {
+            match check.status {
+                PrCheckStatus::Failed => return Some(PrCheckStatus::Failed),
+                PrCheckStatus::Pending => status = PrCheckStatus::Pending,
+                PrCheckStatus::Passed | PrCheckStatus::Skipped => {}
+            }
+        }
+        Some(status)
+    }
+}
+
+/// CI check run or commit status reported by the git host for a PR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct PrCheck {
+    pub name: String,
+    pub status: PrCheckStatus,
+    /// Whether the target branch requires the check to pass before merging
+    pub required: bool,
+    /// Link to the check's details or job page
+    pub url: Option<String>,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -73,6 +124,12 @@ struct MergeRow {
     pr_status: Option<MergeStatus>,
     pr_merged_at: Option<DateTime<Utc>>,
     pr_merge_commit_sha: Option<String>,
+    pr_checks: Option<sqlx::types::Json<Vec<PrCheck>>>,
+    pr_ci_fix_attempts: i64,
+    pr_is_draft: bool,
+    pr_auto_merge: bool,
+    pr_description_process_id: Option<Uuid>,
+    pr_description_user_section: Option<String>,
     created_at: DateTime<Utc>,
 }
 
@@ -111,6 +168,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -133,6 +196,7 @@ impl Merge {
         target_branch_name: &str,
         pr_number: i64,
         pr_url: &str,
+        is_draft: bool,
     ) -> Result<PrMerge, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -140,8 +204,8 @@ impl Merge {
         sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
-                id, workspace_id, repo_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name
-            ) VALUES ($1, $2, $3, 'pr', $4, $5, 'open', $6, $7)
+                id, workspace_id, repo_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name, pr_is_draft
+            ) VALUES ($1, $2, $3, 'pr', $4, $5, 'open', $6, $7, $8)
             RETURNING
                 id as "id!: Uuid",
                 workspace_id as "workspace_id!: Uuid",
@@ -153,6 +217,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -162,7 +232,8 @@ impl Merge {
             pr_number,
             pr_url,
             now,
-            target_branch_name
+            target_branch_name,
+            is_draft
         )
         .fetch_one(pool)
         .await
@@ -183,6 +254,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges
@@ -209,6 +286,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges
@@ -250,6 +333,112 @@ impl Merge {
 
         Ok(())
     }
+    /// Store the CI checks from the latest poll of a PR
+    pub async fn update_checks(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        checks: &[PrCheck],
+    ) -> Result<(), sqlx::Error> {
+        let checks = sqlx::types::Json(checks);
+        sqlx::query!(
+            r#"UPDATE merges SET pr_checks = $1 WHERE id = $2"#,
+            checks,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_draft(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        is_draft: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges SET pr_is_draft = $1 WHERE id = $2"#,
+            is_draft,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_auto_merge(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        auto_merge: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges SET pr_auto_merge = $1 WHERE id = $2"#,
+            auto_merge,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record the agent run refreshing the PR description, and the user
+    /// section to restore once it finishes
+    pub async fn start_description_refresh(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        execution_process_id: Uuid,
+        user_section: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges
+            SET pr_description_process_id = $1,
+                pr_description_user_section = $2
+            WHERE id = $3"#,
+            execution_process_id,
+            user_section,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn clear_description_refresh(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges
+            SET pr_description_process_id = NULL,
+                pr_description_user_section = NULL
+            WHERE id = $1"#,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_ci_fix_attempts(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        attempts: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges SET pr_ci_fix_attempts = $1 WHERE id = $2"#,
+            attempts,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Find all merges for a workspace (returns both direct and PR merges)
     pub async fn find_by_workspace_id(
         pool: &SqlitePool,
@@ -269,6 +458,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges
@@ -302,6 +497,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_checks as "pr_checks?: sqlx::types::Json<Vec<PrCheck>>",
+                pr_ci_fix_attempts as "pr_ci_fix_attempts!: i64",
+                pr_is_draft as "pr_is_draft!: bool",
+                pr_auto_merge as "pr_auto_merge!: bool",
+                pr_description_process_id as "pr_description_process_id?: Uuid",
+                pr_description_user_section,
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges
@@ -385,7 +586,13 @@ impl From<MergeRow> for PrMerge {
                 status: row.pr_status.expect("pr merge must have status"),
                 merged_at: row.pr_merged_at,
                 merge_commit_sha: row.pr_merge_commit_sha,
+                is_draft: row.pr_is_draft,
             },
+            checks: row.pr_checks.map(|checks| checks.0).unwrap_or_default(),
+            ci_fix_attempts: row.pr_ci_fix_attempts,
+            description_process_id: row.pr_description_process_id,
+            description_user_section: row.pr_description_user_section,
+            auto_merge: row.pr_auto_merge,
             created_at: row.created_at,
         }
     }
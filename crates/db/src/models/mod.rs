@@ -5,12 +5,16 @@ pub mod execution_process_repo_state;
 pub mod image;
 pub mod merge;
 pub mod migration_state;
+pub mod pr_review_thread;
 pub mod project;
 pub mod project_repo;
+pub mod quality_gate;
 pub mod repo;
 pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_issue;
 pub mod workspace;
+pub mod workspace_branch_archive;
 pub mod workspace_repo;
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "pr_review_thread_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PrReviewThreadStatus {
+    /// The agent is working on the thread
+    InProgress,
+    /// Fixed, pushed and resolved
+    Addressed,
+    /// The agent left the code as it is and explained why in a reply
+    Skipped,
+    /// The agent run, the push or the reply failed
+    Failed,
+}
+
+/// A review thread of a workspace PR that was sent to the agent
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PrReviewThread {
+    pub id: Uuid,
+    pub merge_id: Uuid,
+    /// Provider id of the thread, used to reply to and resolve it
+    pub thread_id: String,
+    pub path: String,
+    pub line: Option<i64>,
+    pub url: Option<String>,
+    /// Agent run that was asked to address the thread
+    pub execution_process_id: Option<Uuid>,
+    pub status: PrReviewThreadStatus,
+    /// Reason the thread was skipped, or the error that failed it
+    pub note: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StartPrReviewThread {
+    pub thread_id: String,
+    pub path: String,
+    pub line: Option<i64>,
+    pub url: Option<String>,
+}
+
+impl PrReviewThread {
+    pub async fn find_by_merge_id(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewThread,
+            r#"SELECT id as "id!: Uuid",
+                      merge_id as "merge_id!: Uuid",
+                      thread_id,
+                      path,
+                      line,
+                      url,
+                      execution_process_id as "execution_process_id?: Uuid",
+                      status as "status!: PrReviewThreadStatus",
+                      note,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_threads
+               WHERE merge_id = $1
+               ORDER BY created_at ASC"#,
+            merge_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Threads an agent run is still working on
+    pub async fn find_in_progress_by_merge_id(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewThread,
+            r#"SELECT id as "id!: Uuid",
+                      merge_id as "merge_id!: Uuid",
+                      thread_id,
+                      path,
+                      line,
+                      url,
+                      execution_process_id as "execution_process_id?: Uuid",
+                      status as "status!: PrReviewThreadStatus",
+                      note,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_threads
+               WHERE merge_id = $1 AND status = 'in_progress'
+               ORDER BY created_at ASC"#,
+            merge_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record that `execution_process_id` was asked to address a thread,
+    /// restarting threads that were tracked before
+    pub async fn start(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        thread: &StartPrReviewThread,
+        execution_process_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PrReviewThread,
+            r#"INSERT INTO pr_review_threads (id, merge_id, thread_id, path, line, url, execution_process_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT (merge_id, thread_id) DO UPDATE SET
+                   path = excluded.path,
+                   line = excluded.line,
+                   url = excluded.url,
+                   execution_process_id = excluded.execution_process_id,
+                   status = 'in_progress',
+                   note = NULL,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         merge_id as "merge_id!: Uuid",
+                         thread_id,
+                         path,
+                         line,
+                         url,
+                         execution_process_id as "execution_process_id?: Uuid",
+                         status as "status!: PrReviewThreadStatus",
+                         note,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            merge_id,
+            thread.thread_id,
+            thread.path,
+            thread.line,
+            thread.url,
+            execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: PrReviewThreadStatus,
+        note: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE pr_review_threads
+               SET status = $1, note = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $3"#,
+            status,
+            note,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
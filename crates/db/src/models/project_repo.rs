@@ -7,7 +7,7 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{CommitIdentityMode, CommitSigningFormat, Repo};
 
 #[derive(Debug, Error)]
 pub enum ProjectRepoError {
@@ -85,6 +85,14 @@ impl ProjectRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.sparse_checkout,
+                      r.commit_message_template,
+                      r.commit_subject_max_length,
+                      r.commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      r.commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      r.commit_signing_key,
+                      r.branch_name_template,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
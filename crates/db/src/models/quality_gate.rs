@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::execution_process::ExecutionProcessStatus;
+
+/// A check script (lint, test, typecheck, secret scan, ...) that runs in the
+/// worktree before a workspace branch is merged, pushed or opened as a PR
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct QualityGate {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    pub name: String,
+    pub script: String,
+    /// Merging, pushing and opening a PR are blocked until a required gate passes
+    pub required: bool,
+    pub sort_order: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct QualityGateInput {
+    /// Existing gate to update; a new gate is created when `None`
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub script: String,
+    pub required: bool,
+}
+
+/// A gate execution in a workspace, tied to the commit it checked
+#[derive(Debug, Clone, FromRow)]
+pub struct QualityGateRun {
+    pub id: Uuid,
+    pub quality_gate_id: Uuid,
+    pub workspace_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub head_commit: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Latest run of a gate together with the state of its execution process
+#[derive(Debug, Clone, FromRow)]
+pub struct LatestQualityGateRun {
+    pub quality_gate_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub head_commit: String,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+}
+
+impl QualityGate {
+    pub async fn find_by_repo_id(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QualityGate,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      name,
+                      script,
+                      required as "required!: bool",
+                      sort_order,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM quality_gates
+               WHERE repo_id = $1
+               ORDER BY sort_order ASC, created_at ASC"#,
+            repo_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QualityGate,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      name,
+                      script,
+                      required as "required!: bool",
+                      sort_order,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM quality_gates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Replace the gates of a repo with `gates`, in order. Gates keep their id
+    /// (and run history) when updated; gates missing from `gates` are deleted.
+    pub async fn sync_for_repo(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        gates: &[QualityGateInput],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let existing = Self::find_by_repo_id(pool, repo_id).await?;
+
+        let mut tx = pool.begin().await?;
+        for gate in existing
+            .iter()
+            .filter(|e| !gates.iter().any(|g| g.id == Some(e.id)))
+        {
+            sqlx::query!(
+                "DELETE FROM quality_gates WHERE id = $1 AND repo_id = $2",
+                gate.id,
+                repo_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for (sort_order, gate) in gates.iter().enumerate() {
+            let sort_order = sort_order as i64;
+            let updated = match gate.id {
+                Some(id) => sqlx::query!(
+                    r#"UPDATE quality_gates
+                           SET name = $3, script = $4, required = $5, sort_order = $6,
+                               updated_at = datetime('now', 'subsec')
+                           WHERE id = $1 AND repo_id = $2"#,
+                    id,
+                    repo_id,
+                    gate.name,
+                    gate.script,
+                    gate.required,
+                    sort_order
+                )
+                .execute(&mut *tx)
+                .await?
+                .rows_affected(),
+                None => 0,
+            };
+            if updated == 0 {
+                let id = Uuid::new_v4();
+                sqlx::query!(
+                    r#"INSERT INTO quality_gates (id, repo_id, name, script, required, sort_order)
+                       VALUES ($1, $2, $3, $4, $5, $6)"#,
+                    id,
+                    repo_id,
+                    gate.name,
+                    gate.script,
+                    gate.required,
+                    sort_order
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Self::find_by_repo_id(pool, repo_id).await
+    }
+}
+
+impl QualityGateRun {
+    pub async fn create(
+        pool: &SqlitePool,
+        quality_gate_id: Uuid,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        head_commit: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            QualityGateRun,
+            r#"INSERT INTO quality_gate_runs (id, quality_gate_id, workspace_id, execution_process_id, head_commit)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         quality_gate_id as "quality_gate_id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         head_commit,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            quality_gate_id,
+            workspace_id,
+            execution_process_id,
+            head_commit
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QualityGateRun,
+            r#"SELECT id as "id!: Uuid",
+                      quality_gate_id as "quality_gate_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      head_commit,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM quality_gate_runs
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The most recent run of each gate in a workspace
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<LatestQualityGateRun>, sqlx::Error> {
+        sqlx::query_as!(
+            LatestQualityGateRun,
+            r#"SELECT qgr.quality_gate_id as "quality_gate_id!: Uuid",
+                      qgr.execution_process_id as "execution_process_id!: Uuid",
+                      qgr.head_commit,
+                      ep.status as "status!: ExecutionProcessStatus",
+                      ep.exit_code
+               FROM quality_gate_runs qgr
+               JOIN execution_processes ep ON ep.id = qgr.execution_process_id
+               WHERE qgr.workspace_id = $1
+                 AND qgr.created_at = (
+                     SELECT MAX(latest.created_at)
+                     FROM quality_gate_runs latest
+                     WHERE latest.workspace_id = qgr.workspace_id
+                       AND latest.quality_gate_id = qgr.quality_gate_id
+                 )"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
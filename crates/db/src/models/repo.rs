@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::rust::double_option;
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -16,6 +16,28 @@ pub enum RepoError {
     NotFound,
 }
 
+/// Who authors commits made by agents and merges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitIdentityMode {
+    /// The repository's git identity
+    #[default]
+    User,
+    /// The configured bot identity
+    Bot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitSigningFormat {
+    #[default]
+    None,
+    Gpg,
+    Ssh,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Repo {
     pub id: Uuid,
@@ -30,6 +52,22 @@ pub struct Repo {
     pub dev_server_script: Option<String>,
     pub default_target_branch: Option<String>,
     pub default_working_dir: Option<String>,
+    pub init_submodules: bool,
+    /// Newline-separated directories for a cone-mode sparse checkout
+    pub sparse_checkout: Option<String>,
+    /// Layout for generated Conventional Commits messages, e.g. `{type}{scope}: {description}`
+    pub commit_message_template: Option<String>,
+    /// Maximum subject line length for generated commit messages (72 when unset)
+    #[ts(type = "number | null")]
+    pub commit_subject_max_length: Option<i64>,
+    /// Overrides the global commit identity when set
+    pub commit_identity_mode: Option<CommitIdentityMode>,
+    /// Overrides the global commit signing format when set
+    pub commit_signing_format: Option<CommitSigningFormat>,
+    /// GPG key id or SSH key path; falls back to the global key, then `user.signingkey`
+    pub commit_signing_key: Option<String>,
+    /// Branch naming template; overrides the project's and the global template
+    pub branch_name_template: Option<String>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -110,6 +148,70 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub default_working_dir: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "boolean | null")]
+    pub init_submodules: Option<Option<bool>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub sparse_checkout: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub commit_message_template: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "number | null")]
+    pub commit_subject_max_length: Option<Option<i64>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "CommitIdentityMode | null")]
+    pub commit_identity_mode: Option<Option<CommitIdentityMode>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "CommitSigningFormat | null")]
+    pub commit_signing_format: Option<Option<CommitSigningFormat>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub commit_signing_key: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub branch_name_template: Option<Option<String>>,
 }
 
 impl Repo {
@@ -130,6 +232,14 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      init_submodules as "init_submodules!: bool",
+                      sparse_checkout,
+                      commit_message_template,
+                      commit_subject_max_length,
+                      commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      commit_signing_key,
+                      branch_name_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -171,6 +281,14 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      init_submodules as "init_submodules!: bool",
+                      sparse_checkout,
+                      commit_message_template,
+                      commit_subject_max_length,
+                      commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      commit_signing_key,
+                      branch_name_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -229,6 +347,14 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         init_submodules as "init_submodules!: bool",
+                         sparse_checkout,
+                         commit_message_template,
+                         commit_subject_max_length,
+                         commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                         commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                         commit_signing_key,
+                         branch_name_template,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -244,7 +370,8 @@ impl Repo {
         let result = sqlx::query!(
             r#"DELETE FROM repos
                WHERE id NOT IN (SELECT repo_id FROM project_repos)
-                 AND id NOT IN (SELECT repo_id FROM workspace_repos)"#
+                 AND id NOT IN (SELECT repo_id FROM workspace_repos)
+                 AND id NOT IN (SELECT repo_id FROM workspace_branch_archives)"#
         )
         .execute(pool)
         .await?;
@@ -266,6 +393,14 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      init_submodules as "init_submodules!: bool",
+                      sparse_checkout,
+                      commit_message_template,
+                      commit_subject_max_length,
+                      commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      commit_signing_key,
+                      branch_name_template,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -292,6 +427,14 @@ impl Repo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.sparse_checkout,
+                      r.commit_message_template,
+                      r.commit_subject_max_length,
+                      r.commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      r.commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      r.commit_signing_key,
+                      r.branch_name_template,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -354,6 +497,38 @@ impl Repo {
             None => existing.default_working_dir,
             Some(v) => v.clone(),
         };
+        let init_submodules = match &payload.init_submodules {
+            None => existing.init_submodules,
+            Some(v) => v.unwrap_or(false),
+        };
+        let sparse_checkout = match &payload.sparse_checkout {
+            None => existing.sparse_checkout,
+            Some(v) => v.clone(),
+        };
+        let commit_message_template = match &payload.commit_message_template {
+            None => existing.commit_message_template,
+            Some(v) => v.clone(),
+        };
+        let commit_subject_max_length = match payload.commit_subject_max_length {
+            None => existing.commit_subject_max_length,
+            Some(v) => v,
+        };
+        let commit_identity_mode = match payload.commit_identity_mode {
+            None => existing.commit_identity_mode,
+            Some(v) => v,
+        };
+        let commit_signing_format = match payload.commit_signing_format {
+            None => existing.commit_signing_format,
+            Some(v) => v,
+        };
+        let commit_signing_key = match &payload.commit_signing_key {
+            None => existing.commit_signing_key,
+            Some(v) => v.clone(),
+        };
+        let branch_name_template = match &payload.branch_name_template {
+            None => existing.branch_name_template,
+            Some(v) => v.clone(),
+        };
 
         sqlx::query_as!(
             Repo,
@@ -367,8 +542,16 @@ impl Repo {
                    dev_server_script = $7,
                    default_target_branch = $8,
                    default_working_dir = $9,
+                   init_submodules = $10,
+                   sparse_checkout = $11,
+                   commit_message_template = $12,
+                   commit_subject_max_length = $13,
+                   commit_identity_mode = $14,
+                   commit_signing_format = $15,
+                   commit_signing_key = $16,
+                   branch_name_template = $17,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $10
+               WHERE id = $18
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -381,6 +564,14 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         init_submodules as "init_submodules!: bool",
+                         sparse_checkout,
+                         commit_message_template,
+                         commit_subject_max_length,
+                         commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                         commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                         commit_signing_key,
+                         branch_name_template,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -392,6 +583,14 @@ impl Repo {
             dev_server_script,
             default_target_branch,
             default_working_dir,
+            init_submodules,
+            sparse_checkout,
+            commit_message_template,
+            commit_subject_max_length,
+            commit_identity_mode,
+            commit_signing_format,
+            commit_signing_key,
+            branch_name_template,
             id
         )
         .fetch_one(pool)
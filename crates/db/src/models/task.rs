@@ -225,7 +225,7 @@ ORDER BY t.created_at DESC"#,
     }
 
     pub async fn create(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         data: &CreateTask,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
@@ -242,7 +242,7 @@ ORDER BY t.created_at DESC"#,
             status,
             data.parent_workspace_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "task_issue_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskIssueState {
+    Open,
+    /// No longer synced: closed on the git host, or the task was done
+    Closed,
+}
+
+/// Git host issue a task was imported from
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskIssue {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Repo whose git host the issue is on
+    pub repo_id: Uuid,
+    pub issue_url: String,
+    pub issue_number: i64,
+    pub state: TaskIssueState,
+    /// Whether the issue got the comment left once the task is done
+    pub done_commented: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TaskIssue {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskIssue,
+            r#"SELECT ti.id as "id!: Uuid",
+                      ti.task_id as "task_id!: Uuid",
+                      ti.repo_id as "repo_id!: Uuid",
+                      ti.issue_url,
+                      ti.issue_number,
+                      ti.state as "state!: TaskIssueState",
+                      ti.done_commented as "done_commented!: bool",
+                      ti.created_at as "created_at!: DateTime<Utc>",
+                      ti.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_issues ti
+               JOIN tasks t ON t.id = ti.task_id
+               WHERE t.project_id = $1
+               ORDER BY ti.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Links whose issue is still open, for syncing status both ways
+    pub async fn find_open(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskIssue,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      issue_url,
+                      issue_number,
+                      state as "state!: TaskIssueState",
+                      done_commented as "done_commented!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_issues
+               WHERE state = 'open'
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        executor: impl Executor<'_, Database = Sqlite>,
+        task_id: Uuid,
+        repo_id: Uuid,
+        issue_url: &str,
+        issue_number: i64,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskIssue,
+            r#"INSERT INTO task_issues (id, task_id, repo_id, issue_url, issue_number)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         issue_url,
+                         issue_number,
+                         state as "state!: TaskIssueState",
+                         done_commented as "done_commented!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            repo_id,
+            issue_url,
+            issue_number
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn set_done_commented(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_issues
+               SET done_commented = 1, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_state(
+        pool: &SqlitePool,
+        id: Uuid,
+        state: TaskIssueState,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_issues
+               SET state = $1, updated_at = datetime('now', 'subsec')
+               WHERE id = $2"#,
+            state,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -312,6 +312,35 @@ impl Workspace {
         Ok(result.exists)
     }
 
+    pub async fn branch_exists(pool: &SqlitePool, branch: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM workspaces WHERE branch = ?) as "exists!: bool""#,
+            branch
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.exists)
+    }
+
+    /// Whether a workspace branch has this name, or clashes with it in the ref
+    /// namespace, like `vk` and `vk/fix`
+    pub async fn branch_conflicts(pool: &SqlitePool, branch: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM workspaces
+                   WHERE branch = $1
+                      OR substr(branch, 1, length($1) + 1) = $1 || '/'
+                      OR substr($1, 1, length(branch) + 1) = branch || '/'
+               ) as "exists!: bool""#,
+            branch
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.exists)
+    }
+
     /// Find workspaces that are expired and eligible for cleanup.
     /// Uses accelerated cleanup (1 hour) for archived workspaces OR tasks not in progress/review.
     /// Uses standard cleanup (72 hours) only for non-archived workspaces on active tasks.
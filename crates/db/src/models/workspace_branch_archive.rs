@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where an archived branch tip is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BranchArchiveStorage {
+    /// A ref under `refs/vk-archive/` in the repository
+    Ref,
+    /// A git bundle file in the app's asset directory
+    Bundle,
+}
+
+/// An unmerged workspace branch kept before its worktree was cleaned up or its
+/// workspace deleted. It outlives both the workspace and the task.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceBranchArchive {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub repo_id: Uuid,
+    pub workspace_name: Option<String>,
+    pub branch: String,
+    pub target_branch: String,
+    pub commit_sha: String,
+    pub storage: BranchArchiveStorage,
+    /// Archive ref name or bundle file path
+    pub location: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateWorkspaceBranchArchive {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub repo_id: Uuid,
+    pub workspace_name: Option<String>,
+    pub branch: String,
+    pub target_branch: String,
+    pub commit_sha: String,
+    pub storage: BranchArchiveStorage,
+    pub location: String,
+}
+
+impl WorkspaceBranchArchive {
+    /// Record the archive of a workspace branch, replacing an earlier archive
+    /// of the same workspace and repo.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        data: &CreateWorkspaceBranchArchive,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            WorkspaceBranchArchive,
+            r#"INSERT INTO workspace_branch_archives
+                   (id, workspace_id, task_id, repo_id, workspace_name, branch, target_branch,
+                    commit_sha, storage, location)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               ON CONFLICT (workspace_id, repo_id) DO UPDATE SET
+                   workspace_name = excluded.workspace_name,
+                   branch = excluded.branch,
+                   target_branch = excluded.target_branch,
+                   commit_sha = excluded.commit_sha,
+                   storage = excluded.storage,
+                   location = excluded.location,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         workspace_name,
+                         branch,
+                         target_branch,
+                         commit_sha,
+                         storage as "storage!: BranchArchiveStorage",
+                         location,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.workspace_id,
+            data.task_id,
+            data.repo_id,
+            data.workspace_name,
+            data.branch,
+            data.target_branch,
+            data.commit_sha,
+            data.storage,
+            data.location
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceBranchArchive,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      workspace_name,
+                      branch,
+                      target_branch,
+                      commit_sha,
+                      storage as "storage!: BranchArchiveStorage",
+                      location,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_branch_archives
+               ORDER BY updated_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceBranchArchive,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      workspace_name,
+                      branch,
+                      target_branch,
+                      commit_sha,
+                      storage as "storage!: BranchArchiveStorage",
+                      location,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_branch_archives
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM workspace_branch_archives WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -6,7 +6,7 @@ use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{CommitIdentityMode, CommitSigningFormat, Repo};
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct WorkspaceRepo {
@@ -14,6 +14,9 @@ pub struct WorkspaceRepo {
     pub workspace_id: Uuid,
     pub repo_id: Uuid,
     pub target_branch: String,
+    /// Per-workspace sparse-checkout override. `None` inherits the repo setting,
+    /// an empty string forces a full checkout.
+    pub sparse_checkout: Option<String>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -24,6 +27,7 @@ pub struct WorkspaceRepo {
 pub struct CreateWorkspaceRepo {
     pub repo_id: Uuid,
     pub target_branch: String,
+    pub sparse_checkout: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -63,18 +67,20 @@ impl WorkspaceRepo {
             let id = Uuid::new_v4();
             let workspace_repo = sqlx::query_as!(
                 WorkspaceRepo,
-                r#"INSERT INTO workspace_repos (id, workspace_id, repo_id, target_branch)
-                   VALUES ($1, $2, $3, $4)
+                r#"INSERT INTO workspace_repos (id, workspace_id, repo_id, target_branch, sparse_checkout)
+                   VALUES ($1, $2, $3, $4, $5)
                    RETURNING id as "id!: Uuid",
                              workspace_id as "workspace_id!: Uuid",
                              repo_id as "repo_id!: Uuid",
                              target_branch,
+                             sparse_checkout,
                              created_at as "created_at!: DateTime<Utc>",
                              updated_at as "updated_at!: DateTime<Utc>""#,
                 id,
                 workspace_id,
                 repo.repo_id,
-                repo.target_branch
+                repo.target_branch,
+                repo.sparse_checkout
             )
             .fetch_one(&mut *tx)
             .await?;
@@ -95,6 +101,7 @@ impl WorkspaceRepo {
                       workspace_id as "workspace_id!: Uuid",
                       repo_id as "repo_id!: Uuid",
                       target_branch,
+                      sparse_checkout,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM workspace_repos
@@ -123,6 +130,14 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.sparse_checkout,
+                      r.commit_message_template,
+                      r.commit_subject_max_length,
+                      r.commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      r.commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      r.commit_signing_key,
+                      r.branch_name_template,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -152,6 +167,14 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.sparse_checkout,
+                      r.commit_message_template,
+                      r.commit_subject_max_length,
+                      r.commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      r.commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      r.commit_signing_key,
+                      r.branch_name_template,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -180,6 +203,14 @@ impl WorkspaceRepo {
                     dev_server_script: row.dev_server_script,
                     default_target_branch: row.default_target_branch,
                     default_working_dir: row.default_working_dir,
+                    init_submodules: row.init_submodules,
+                    sparse_checkout: row.sparse_checkout,
+                    commit_message_template: row.commit_message_template,
+                    commit_subject_max_length: row.commit_subject_max_length,
+                    commit_identity_mode: row.commit_identity_mode,
+                    commit_signing_format: row.commit_signing_format,
+                    commit_signing_key: row.commit_signing_key,
+                    branch_name_template: row.branch_name_template,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -199,6 +230,7 @@ impl WorkspaceRepo {
                       workspace_id as "workspace_id!: Uuid",
                       repo_id as "repo_id!: Uuid",
                       target_branch,
+                      sparse_checkout,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM workspace_repos
@@ -227,6 +259,23 @@ impl WorkspaceRepo {
         Ok(())
     }
 
+    pub async fn update_sparse_checkout(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        sparse_checkout: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE workspace_repos SET sparse_checkout = $1, updated_at = datetime('now') WHERE workspace_id = $2 AND repo_id = $3",
+            sparse_checkout,
+            workspace_id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_target_branch_for_children_of_workspace(
         pool: &SqlitePool,
         parent_workspace_id: Uuid,
@@ -269,6 +318,14 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.init_submodules as "init_submodules!: bool",
+                      r.sparse_checkout,
+                      r.commit_message_template,
+                      r.commit_subject_max_length,
+                      r.commit_identity_mode as "commit_identity_mode: CommitIdentityMode",
+                      r.commit_signing_format as "commit_signing_format: CommitSigningFormat",
+                      r.commit_signing_key,
+                      r.branch_name_template,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -26,7 +26,11 @@ use services::services::{
     file_search::FileSearchCache,
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
+    git_host::GitHostMapping,
+    git_host_credentials::GitHostCredentials,
     image::{ImageError, ImageService},
+    pr_monitor::PrMonitorHandle,
+    pr_webhook_secret::PrWebhookSecret,
     project::ProjectService,
     queued_message::QueuedMessageService,
     remote_client::RemoteClient,
@@ -110,6 +114,18 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn auth_context(&self) -> &AuthContext;
 
+    fn git_host_credentials(&self) -> &Arc<GitHostCredentials>;
+
+    fn pr_webhook_secret(&self) -> &Arc<PrWebhookSecret>;
+
+    fn pr_monitor(&self) -> &PrMonitorHandle;
+
+    /// Configured git host mappings, with their stored API tokens
+    async fn git_hosts(&self) -> Vec<GitHostMapping> {
+        let hosts = self.config().read().await.git_hosts.clone();
+        self.git_host_credentials().with_tokens(&hosts).await
+    }
+
     fn remote_client(&self) -> Result<RemoteClient, RemoteClientNotConfigured> {
         Err(RemoteClientNotConfigured)
     }
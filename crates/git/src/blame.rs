@@ -0,0 +1,23 @@
+use std::path::{Component, Path};
+
+use serde::Serialize;
+use ts_rs::TS;
+
+/// A run of consecutive lines in the worktree copy of a file that were last
+/// changed by the same commit since the workspace base commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+pub struct BlameRange {
+    /// 1-based line number of the first line in the range
+    pub start_line: usize,
+    pub line_count: usize,
+    /// `None` for lines that are not committed yet
+    pub commit_sha: Option<String>,
+}
+
+/// Whether `path` is a relative path that stays inside the worktree.
+pub(crate) fn is_worktree_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
@@ -19,7 +19,7 @@ use std::{
     ffi::{OsStr, OsString},
     io::Write as _,
     path::Path,
-    process::{Command, Stdio},
+    process::{Command, Output, Stdio},
 };
 
 use thiserror::Error;
@@ -72,26 +72,43 @@ pub struct WorktreeEntry {
     pub branch: Option<String>,
 }
 
+/// Submodule declared in `.gitmodules`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleEntry {
+    pub name: String,
+    pub path: String,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct StatusDiffOptions {
     pub path_filter: Option<Vec<String>>, // pathspecs to limit diff
+    /// Similarity percentage for rename/copy detection (`-M<n>%`)
+    pub rename_threshold: Option<u16>,
+    /// Detect copies as well as renames (`-C`)
+    pub detect_copies: bool,
 }
 
 impl GitCli {
     pub fn new() -> Self {
         Self {}
     }
-    /// Run `git -C <repo> worktree add <path> <branch>` (optionally creating the branch with -b)
+    /// Run `git -C <repo> worktree add <path> <branch>` (optionally creating the branch with -b).
+    /// With `sparse_dirs`, only those directories (cone mode) are checked out.
     pub fn worktree_add(
         &self,
         repo_path: &Path,
         worktree_path: &Path,
         branch: &str,
         create_branch: bool,
+        sparse_dirs: Option<&[String]>,
     ) -> Result<(), GitCliError> {
         self.ensure_available()?;
 
         let mut args: Vec<OsString> = vec!["worktree".into(), "add".into()];
+        if sparse_dirs.is_some() {
+            // Populate the working tree only after the cone is configured
+            args.push("--no-checkout".into());
+        }
         if create_branch {
             args.push("-b".into());
             args.push(OsString::from(branch));
@@ -100,6 +117,12 @@ impl GitCli {
         args.push(OsString::from(branch));
         self.git(repo_path, args)?;
 
+        if let Some(dirs) = sparse_dirs {
+            self.sparse_checkout_set(worktree_path, dirs)?;
+            self.git(worktree_path, ["read-tree", "-mu", "HEAD"])?;
+            return Ok(());
+        }
+
         // Good practice: reapply sparse-checkout in the new worktree to ensure materialization matches
         // Non-fatal if it fails or not configured.
         let _ = self.git(worktree_path, ["sparse-checkout", "reapply"]);
@@ -204,12 +227,16 @@ impl GitCli {
                 input.extend_from_slice(&p);
                 input.push(0);
             }
-            let args = vec![
+            let mut args = vec![
                 OsString::from("add"),
                 OsString::from("-A"),
                 OsString::from("--pathspec-from-file=-"),
                 OsString::from("--pathspec-file-nul"),
             ];
+            // Untracked files outside a sparse cone are otherwise rejected
+            if self.is_sparse_checkout(worktree_path) {
+                args.push(OsString::from("--sparse"));
+            }
             self.git_with_stdin(worktree_path, args, Some(&envs), &input)?;
         }
         // git diff --cached
@@ -218,10 +245,17 @@ impl GitCli {
             "core.quotepath=false".into(),
             "diff".into(),
             "--cached".into(),
-            "-M".into(),
-            "--name-status".into(),
-            OsString::from(base_commit.to_string()),
         ];
+        let threshold = opts
+            .rename_threshold
+            .map(|t| format!("{}%", t.min(100)))
+            .unwrap_or_default();
+        args.push(format!("-M{threshold}").into());
+        if opts.detect_copies {
+            args.push(format!("-C{threshold}").into());
+        }
+        args.push("--name-status".into());
+        args.push(OsString::from(base_commit.to_string()));
         args = Self::apply_pathspec_filter(args, opts.path_filter.as_ref());
         let out = self.git_with_env(worktree_path, args, &envs)?;
         Ok(Self::parse_name_status(&out))
@@ -288,10 +322,75 @@ impl GitCli {
     }
 
     /// Stage all changes in the working tree (respects sparse-checkout semantics).
+    /// Files created outside a sparse cone are staged as well.
     pub fn add_all(&self, worktree_path: &Path) -> Result<(), GitCliError> {
-        self.git(
+        let mut args = vec!["add", "-A"];
+        if self.is_sparse_checkout(worktree_path) {
+            args.push("--sparse");
+        }
+        self.git(worktree_path, Self::apply_default_excludes(args))?;
+        Ok(())
+    }
+
+    /// Stage modifications and deletions of tracked files, leaving untracked
+    /// files out
+    pub fn add_tracked(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        let mut args = vec!["add", "-u"];
+        if self.is_sparse_checkout(worktree_path) {
+            args.push("--sparse");
+        }
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
+    /// Return true if sparse-checkout is enabled for this worktree.
+    pub fn is_sparse_checkout(&self, worktree_path: &Path) -> bool {
+        self.git(worktree_path, ["config", "--bool", "core.sparseCheckout"])
+            .is_ok_and(|out| out.trim() == "true")
+    }
+
+    /// Directories in the worktree's sparse-checkout cone (empty when not sparse).
+    pub fn sparse_checkout_list(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
+        if !self.is_sparse_checkout(worktree_path) {
+            return Ok(Vec::new());
+        }
+        let out = self.git(worktree_path, ["sparse-checkout", "list"])?;
+        Ok(out
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Replace the worktree's sparse-checkout cone with `dirs`.
+    pub fn sparse_checkout_set(
+        &self,
+        worktree_path: &Path,
+        dirs: &[String],
+    ) -> Result<(), GitCliError> {
+        let input = dirs.join("\n");
+        self.git_with_stdin(
             worktree_path,
-            Self::apply_default_excludes(vec!["add", "-A"]),
+            ["sparse-checkout", "set", "--cone", "--stdin"],
+            None,
+            input.as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Add `dirs` to the worktree's sparse-checkout cone and check them out.
+    pub fn sparse_checkout_add(
+        &self,
+        worktree_path: &Path,
+        dirs: &[String],
+    ) -> Result<(), GitCliError> {
+        let input = dirs.join("\n");
+        self.git_with_stdin(
+            worktree_path,
+            ["sparse-checkout", "add", "--stdin"],
+            None,
+            input.as_bytes(),
         )?;
         Ok(())
     }
@@ -337,11 +436,56 @@ impl GitCli {
         Ok(entries)
     }
 
-    /// Commit staged changes with the given message.
-    pub fn commit(&self, worktree_path: &Path, message: &str) -> Result<(), GitCliError> {
-        self.git(worktree_path, ["commit", "-m", message])?;
+    /// Commit staged changes with the given message. `config` holds `-c key=value`
+    /// arguments placed before the subcommand.
+    pub fn commit(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        let args = config
+            .iter()
+            .map(String::as_str)
+            .chain(["commit", "-m", message]);
+        self.git(worktree_path, args)?;
         Ok(())
     }
+
+    /// Commit the staged changes and return git's output, which includes what
+    /// the commit hooks printed. `no_verify` skips the `pre-commit` and
+    /// `commit-msg` hooks.
+    pub fn commit_with_output(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        config: &[String],
+        no_verify: bool,
+    ) -> Result<String, GitCliError> {
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["commit", "-m", message]);
+        if no_verify {
+            args.push("--no-verify");
+        }
+        let out = self.git_output_impl(worktree_path, args, None, None)?;
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        Ok(format!("{}\n{}", stderr.trim(), stdout.trim())
+            .trim()
+            .to_string())
+    }
+
+    /// Whether a `pre-commit`, `prepare-commit-msg` or `commit-msg` hook is
+    /// installed, honoring `core.hooksPath`
+    pub fn has_commit_hooks(&self, worktree_path: &Path) -> bool {
+        let Ok(hooks_dir) = self.git(worktree_path, ["rev-parse", "--git-path", "hooks"]) else {
+            return false;
+        };
+        let hooks_dir = worktree_path.join(hooks_dir.trim());
+        ["pre-commit", "prepare-commit-msg", "commit-msg"]
+            .iter()
+            .any(|hook| Self::is_executable_file(&hooks_dir.join(hook)))
+    }
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -530,6 +674,7 @@ impl GitCli {
         new_base: &str,
         old_base: &str,
         task_branch: &str,
+        config: &[String],
     ) -> Result<(), GitCliError> {
         // If a rebase is in progress, refuse to proceed. The caller can
         // choose to abort or continue; we avoid destructive actions here.
@@ -541,13 +686,62 @@ impl GitCli {
             .merge_base(worktree_path, old_base, task_branch)
             .unwrap_or(old_base.to_string());
 
-        self.git(
-            worktree_path,
-            ["rebase", "--onto", new_base, &merge_base, task_branch],
-        )?;
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["rebase", "--onto", new_base, &merge_base, task_branch]);
+        self.git(worktree_path, args)?;
         Ok(())
     }
 
+    /// Run `git rebase -i <upstream>` with a prepared todo list instead of an editor.
+    /// `message` replaces the message of every `reword` step in the todo.
+    pub fn rebase_with_todo(
+        &self,
+        worktree_path: &Path,
+        upstream: &str,
+        todo: &str,
+        message: Option<&str>,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        if self.is_rebase_in_progress(worktree_path).unwrap_or(false) {
+            return Err(GitCliError::RebaseInProgress);
+        }
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let write = |name: &str, contents: &str| {
+            let path = tmp_dir.path().join(name);
+            std::fs::write(&path, contents)
+                .map_err(|e| GitCliError::CommandFailed(format!("write {name} failed: {e}")))?;
+            Ok::<_, GitCliError>(format!("cp {}", Self::shell_quote(&path)))
+        };
+
+        // Git runs both editors through the shell with the file to edit appended
+        let sequence_editor = write("git-rebase-todo", todo)?;
+        let editor = match message {
+            Some(message) => write("COMMIT_EDITMSG", message)?,
+            None => "true".to_string(),
+        };
+        let envs = [
+            (
+                OsString::from("GIT_SEQUENCE_EDITOR"),
+                OsString::from(sequence_editor),
+            ),
+            (OsString::from("GIT_EDITOR"), OsString::from(editor)),
+        ];
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        if message.is_some() {
+            // Keep `#` lines of the supplied message; git's default cleanup strips them
+            args.extend(["-c", "commit.cleanup=verbatim"]);
+        }
+        args.extend(["rebase", "-i", "--no-autosquash", upstream]);
+        self.git_with_env(worktree_path, args, &envs)?;
+        Ok(())
+    }
+
+    fn shell_quote(path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        format!("'{}'", path.replace('\'', r"'\''"))
+    }
+
     /// Return true if there is a rebase in progress in this worktree.
     /// We treat this as true when either of Git's rebase state directories exists:
     /// - rebase-merge (interactive rebase)
@@ -609,13 +803,68 @@ impl GitCli {
 
     /// Continue an in-progress rebase. Returns error if no rebase is in progress
     /// or if there are unresolved conflicts.
-    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+    pub fn continue_rebase(
+        &self,
+        worktree_path: &Path,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
         if !self.is_rebase_in_progress(worktree_path)? {
             return Err(GitCliError::CommandFailed(
                 "No rebase in progress".to_string(),
             ));
         }
-        self.git(worktree_path, ["rebase", "--continue"])
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["rebase", "--continue"]);
+        self.git_with_env(worktree_path, args, &Self::non_interactive_editor_env())
+            .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge using the prepared merge message.
+    pub fn continue_merge(
+        &self,
+        worktree_path: &Path,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        if !self.is_merge_in_progress(worktree_path)? {
+            return Err(GitCliError::CommandFailed(
+                "No merge in progress".to_string(),
+            ));
+        }
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["commit", "--no-edit"]);
+        self.git_with_env(worktree_path, args, &Self::non_interactive_editor_env())
+            .map(|_| ())
+    }
+
+    pub fn continue_cherry_pick(
+        &self,
+        worktree_path: &Path,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        if !self.is_cherry_pick_in_progress(worktree_path)? {
+            return Err(GitCliError::CommandFailed(
+                "No cherry-pick in progress".to_string(),
+            ));
+        }
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["cherry-pick", "--continue"]);
+        self.git_with_env(worktree_path, args, &Self::non_interactive_editor_env())
+            .map(|_| ())
+    }
+
+    pub fn continue_revert(
+        &self,
+        worktree_path: &Path,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        if !self.is_revert_in_progress(worktree_path)? {
+            return Err(GitCliError::CommandFailed(
+                "No revert in progress".to_string(),
+            ));
+        }
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["revert", "--continue"]);
+        self.git_with_env(worktree_path, args, &Self::non_interactive_editor_env())
             .map(|_| ())
     }
 
@@ -647,11 +896,12 @@ impl GitCli {
         base_branch: &str,
         from_branch: &str,
         message: &str,
+        config: &[String],
     ) -> Result<String, GitCliError> {
         self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
         self.git(repo_path, ["merge", "--squash", "--no-commit", from_branch])
             .map(|_| ())?;
-        self.git(repo_path, ["commit", "-m", message]).map(|_| ())?;
+        self.commit(repo_path, message, config)?;
         let sha = self
             .git(repo_path, ["rev-parse", "HEAD"])?
             .trim()
@@ -705,10 +955,292 @@ impl GitCli {
         }
         Ok(files)
     }
+
+    /// List submodules declared in the worktree's `.gitmodules` (empty if there is none).
+    pub fn list_submodules(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<SubmoduleEntry>, GitCliError> {
+        if !worktree_path.join(".gitmodules").is_file() {
+            return Ok(Vec::new());
+        }
+        // Exits non-zero when no key matches, i.e. no submodules are declared
+        let out = match self.git(
+            worktree_path,
+            [
+                "config",
+                "--file",
+                ".gitmodules",
+                "--get-regexp",
+                r"^submodule\..*\.path$",
+            ],
+        ) {
+            Ok(out) => out,
+            Err(GitCliError::CommandFailed(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        // Format: "submodule.<name>.path <path>"
+        Ok(out
+            .lines()
+            .filter_map(|line| {
+                let (key, path) = line.split_once(' ')?;
+                let name = key.strip_prefix("submodule.")?.strip_suffix(".path")?;
+                Some(SubmoduleEntry {
+                    name: name.to_string(),
+                    path: path.trim().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Run `git submodule update --init`, for a single submodule when `submodule_path` is set
+    /// or recursively for all of them otherwise. `reference` borrows objects from an existing
+    /// clone so only missing objects are fetched.
+    pub fn submodule_update(
+        &self,
+        worktree_path: &Path,
+        submodule_path: Option<&str>,
+        reference: Option<&Path>,
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["submodule".into(), "update".into(), "--init".into()];
+        if let Some(reference) = reference {
+            args.push("--reference".into());
+            args.push(reference.as_os_str().into());
+        }
+        match submodule_path {
+            Some(path) => {
+                args.push("--".into());
+                args.push(path.into());
+            }
+            None => args.push("--recursive".into()),
+        }
+        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        self.git_with_env(worktree_path, args, &envs)?;
+        Ok(())
+    }
+
+    /// Return true if any `.gitattributes` in HEAD, at any depth, routes files through LFS.
+    pub fn uses_lfs(&self, worktree_path: &Path) -> bool {
+        // Searches the committed tree, so sparse checkouts and nested attribute files count too;
+        // exits non-zero when nothing matches
+        self.git(
+            worktree_path,
+            [
+                "grep",
+                "-q",
+                "-F",
+                "filter=lfs",
+                "HEAD",
+                "--",
+                ":(glob)**/.gitattributes",
+            ],
+        )
+        .is_ok()
+    }
+
+    /// Return true if the `git lfs` extension is installed.
+    pub fn lfs_available(&self, repo_path: &Path) -> bool {
+        self.git(repo_path, ["lfs", "version"]).is_ok()
+    }
+
+    /// Download and check out Git LFS objects for the current checkout.
+    /// Objects already present in the shared LFS storage are reused.
+    pub fn lfs_pull(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        self.git_with_env(worktree_path, ["lfs", "pull"], &envs)?;
+        Ok(())
+    }
+
+    /// Commits in `range` as a `git format-patch` mailbox.
+    pub fn format_patch(&self, worktree_path: &Path, range: &str) -> Result<Vec<u8>, GitCliError> {
+        self.git_impl(
+            worktree_path,
+            ["format-patch", "--stdout", "--binary", range],
+            None,
+            None,
+        )
+    }
+
+    /// Binary-safe unified diff between two revisions.
+    pub fn diff_binary(
+        &self,
+        worktree_path: &Path,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<u8>, GitCliError> {
+        self.git_impl(
+            worktree_path,
+            ["diff", "--binary", "--full-index", from, to],
+            None,
+            None,
+        )
+    }
+
+    /// Write the commits in `range` and its tip ref to a bundle file.
+    pub fn bundle_create(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        range: &str,
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["bundle".into(), "create".into(), "--quiet".into()];
+        args.push(bundle_path.as_os_str().to_os_string());
+        args.push(range.into());
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
+    /// Fetch `refspec` from a bundle file into `FETCH_HEAD`.
+    pub fn fetch_bundle(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        refspec: &str,
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["fetch".into(), "--no-tags".into()];
+        args.push(bundle_path.as_os_str().to_os_string());
+        args.push(refspec.into());
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
+    pub fn reset_hard(&self, worktree_path: &Path, rev: &str) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["reset", "--hard", "--quiet", rev])?;
+        Ok(())
+    }
+
+    /// Apply a mailbox with `git am`, aborting the session if a patch does not apply.
+    pub fn am(
+        &self,
+        worktree_path: &Path,
+        mailbox: &[u8],
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["am", "--3way"]);
+        if let Err(e) = self.git_with_stdin(worktree_path, args, None, mailbox) {
+            let _ = self.git(worktree_path, ["am", "--abort"]);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Cherry-pick `commits` in order onto HEAD, recording their origin with
+    /// `-x`. With `mainline`, merge commits are replayed against their first
+    /// parent.
+    pub fn cherry_pick(
+        &self,
+        worktree_path: &Path,
+        commits: &[String],
+        mainline: bool,
+        config: &[String],
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<&str> = config.iter().map(String::as_str).collect();
+        args.extend(["cherry-pick", "-x"]);
+        if mainline {
+            args.extend(["-m", "1"]);
+        }
+        args.extend(commits.iter().map(String::as_str));
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
+    /// Apply a unified diff to both the index and the working tree.
+    pub fn apply_to_index(&self, worktree_path: &Path, patch: &[u8]) -> Result<(), GitCliError> {
+        self.git_with_stdin(worktree_path, ["apply", "--index"], None, patch)?;
+        Ok(())
+    }
+
+    /// `git apply` with extra flags such as `--cached` or `-R`.
+    pub fn apply_patch(
+        &self,
+        worktree_path: &Path,
+        patch: &[u8],
+        flags: &[&str],
+    ) -> Result<(), GitCliError> {
+        let args = std::iter::once("apply").chain(flags.iter().copied());
+        self.git_with_stdin(worktree_path, args, None, patch)?;
+        Ok(())
+    }
+
+    /// Patch of the worktree changes that are not staged, including untracked
+    /// files, built in a temporary index so the real one is left untouched.
+    pub fn unstaged_patch(&self, worktree_path: &Path) -> Result<String, GitCliError> {
+        let index_tree = self.git(worktree_path, ["write-tree"])?.trim().to_string();
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let envs = vec![(
+            OsString::from("GIT_INDEX_FILE"),
+            tmp_dir.path().join("index").into_os_string(),
+        )];
+        self.git_with_env(worktree_path, ["read-tree", index_tree.as_str()], &envs)?;
+        let mut add = vec!["add", "-A"];
+        if self.is_sparse_checkout(worktree_path) {
+            add.push("--sparse");
+        }
+        self.git_with_env(worktree_path, Self::apply_default_excludes(add), &envs)?;
+        let mut args = Self::patch_args();
+        args.extend(["--cached".into(), index_tree.into()]);
+        self.git_with_env(worktree_path, args, &envs)
+    }
+
+    /// Patch of the changes staged in the index
+    pub fn staged_patch(&self, worktree_path: &Path) -> Result<String, GitCliError> {
+        let mut args = Self::patch_args();
+        args.extend(["--cached".into(), "HEAD".into()]);
+        self.git(worktree_path, args)
+    }
+
+    /// Patch of the changes committed since `base`
+    pub fn committed_patch(&self, worktree_path: &Path, base: &str) -> Result<String, GitCliError> {
+        let mut args = Self::patch_args();
+        args.extend([base.into(), "HEAD".into()]);
+        self.git(worktree_path, args)
+    }
 }
 
 // Private methods
 impl GitCli {
+    /// Git ignores hooks that are not executable
+    fn is_executable_file(path: &Path) -> bool {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+        }
+        #[cfg(not(unix))]
+        {
+            metadata.is_file()
+        }
+    }
+
+    /// `git diff` arguments for a patch that `git apply` accepts and
+    /// `hunks::parse_patch` can split, regardless of the user's diff config
+    fn patch_args() -> Vec<OsString> {
+        [
+            "-c",
+            "core.quotepath=false",
+            "diff",
+            "--binary",
+            "--no-renames",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect()
+    }
+
+    /// Keep the commit message git prepared instead of launching an editor.
+    fn non_interactive_editor_env() -> [(OsString, OsString); 1] {
+        [(OsString::from("GIT_EDITOR"), OsString::from("true"))]
+    }
+
     fn classify_cli_error(&self, msg: String) -> GitCliError {
         let lower = msg.to_ascii_lowercase();
         if lower.contains("authentication failed")
@@ -761,6 +1293,23 @@ impl GitCli {
         envs: Option<&[(OsString, OsString)]>,
         stdin: Option<&[u8]>,
     ) -> Result<Vec<u8>, GitCliError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.git_output_impl(repo_path, args, envs, stdin)
+            .map(|out| out.stdout)
+    }
+
+    /// Like `git_impl`, but keeps stderr of successful commands, where git
+    /// writes progress and hook output.
+    fn git_output_impl<I, S>(
+        &self,
+        repo_path: &Path,
+        args: I,
+        envs: Option<&[(OsString, OsString)]>,
+        stdin: Option<&[u8]>,
+    ) -> Result<Output, GitCliError>
     where
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
@@ -828,7 +1377,7 @@ impl GitCli {
                 "failed to write to git stdin: {e}"
             )));
         }
-        Ok(out.stdout)
+        Ok(out)
     }
 
     pub fn git<I, S>(&self, repo_path: &Path, args: I) -> Result<String, GitCliError>
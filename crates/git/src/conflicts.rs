@@ -0,0 +1,145 @@
+use serde::Serialize;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// A single conflicted region in a file, split into both sides.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConflictHunk {
+    /// 1-based line number of the opening `<<<<<<<` marker
+    pub start_line: usize,
+    /// Label after the opening marker (e.g. `HEAD` or a commit subject)
+    pub ours_label: Option<String>,
+    pub ours: String,
+    /// Common ancestor content, present only with `merge.conflictStyle=diff3`
+    pub base: Option<String>,
+    /// Label after the closing marker
+    pub theirs_label: Option<String>,
+    pub theirs: String,
+}
+
+/// A conflicted file together with all of its conflict hunks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictedFile {
+    pub path: String,
+    /// Empty for binary files or conflicts without textual markers
+    /// (e.g. delete/modify conflicts)
+    pub hunks: Vec<ConflictHunk>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let label = line[marker.len()..].trim();
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    // Markers are exactly seven characters, optionally followed by a space and a label
+    line.starts_with(marker)
+        && line[marker.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == ' ' || c == '\r')
+}
+
+/// Parse the conflict hunks out of a file's working-tree content.
+/// Unterminated hunks are ignored.
+pub fn parse_conflict_hunks(content: &str) -> Vec<ConflictHunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<(ConflictHunk, Section)> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        if is_marker(line, OURS_MARKER) {
+            current = Some((
+                ConflictHunk {
+                    start_line: idx + 1,
+                    ours_label: marker_label(line, OURS_MARKER),
+                    ours: String::new(),
+                    base: None,
+                    theirs_label: None,
+                    theirs: String::new(),
+                },
+                Section::Ours,
+            ));
+            continue;
+        }
+
+        let Some((hunk, section)) = current.as_mut() else {
+            continue;
+        };
+
+        if *section == Section::Ours && is_marker(line, BASE_MARKER) {
+            hunk.base = Some(String::new());
+            *section = Section::Base;
+        } else if *section != Section::Theirs && is_marker(line, SEPARATOR_MARKER) {
+            *section = Section::Theirs;
+        } else if *section == Section::Theirs && is_marker(line, THEIRS_MARKER) {
+            let (mut hunk, _) = current.take().unwrap();
+            hunk.theirs_label = marker_label(line, THEIRS_MARKER);
+            hunks.push(hunk);
+        } else {
+            let target = match section {
+                Section::Ours => &mut hunk.ours,
+                Section::Base => hunk.base.get_or_insert_with(String::new),
+                Section::Theirs => &mut hunk.theirs,
+            };
+            target.push_str(line);
+            target.push('\n');
+        }
+    }
+
+    hunks
+}
+
+/// Return true if the content still contains a complete conflict hunk.
+pub fn has_conflict_markers(content: &str) -> bool {
+    !parse_conflict_hunks(content).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_merge_style_hunks() {
+        let content =
+            "a\n<<<<<<< HEAD\nours 1\nours 2\n=======\ntheirs\n>>>>>>> abc123 (feature)\nb\n";
+        let hunks = parse_conflict_hunks(content);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].start_line, 2);
+        assert_eq!(hunks[0].ours_label.as_deref(), Some("HEAD"));
+        assert_eq!(hunks[0].ours, "ours 1\nours 2\n");
+        assert_eq!(hunks[0].base, None);
+        assert_eq!(hunks[0].theirs, "theirs\n");
+        assert_eq!(hunks[0].theirs_label.as_deref(), Some("abc123 (feature)"));
+    }
+
+    #[test]
+    fn parses_diff3_style_hunks() {
+        let content = "<<<<<<< ours\nx\n||||||| base\ny\n=======\nz\n>>>>>>> theirs\n";
+        let hunks = parse_conflict_hunks(content);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].ours, "x\n");
+        assert_eq!(hunks[0].base.as_deref(), Some("y\n"));
+        assert_eq!(hunks[0].theirs, "z\n");
+    }
+
+    #[test]
+    fn ignores_lookalike_and_unterminated_markers() {
+        assert!(!has_conflict_markers("========== heading\nfoo\n"));
+        assert!(!has_conflict_markers(
+            "<<<<<<< HEAD\nours\n=======\ntheirs\n"
+        ));
+        assert!(has_conflict_markers(
+            "<<<<<<< HEAD\n=======\n>>>>>>> other\n"
+        ));
+    }
+}
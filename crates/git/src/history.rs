@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+
+/// A commit on a workspace branch since its base commit.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub subject: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    #[ts(type = "Date")]
+    pub authored_at: DateTime<Utc>,
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// A history edit applied with a non-interactive `git rebase -i`.
+#[derive(Debug, Clone)]
+pub enum HistoryRewrite {
+    /// Squash the inclusive range `from..=to` (oldest to newest) into one commit.
+    /// Without a message, the messages of all squashed commits are joined.
+    Squash {
+        from: String,
+        to: String,
+        message: Option<String>,
+    },
+    Reword {
+        commit: String,
+        message: String,
+    },
+    Drop {
+        commit: String,
+    },
+}
+
+/// Rebase todo list for `rewrite` over `commits` (oldest first), plus the
+/// commit message to apply to the reworded commit, if any.
+pub(crate) fn build_rebase_todo(
+    commits: &[CommitInfo],
+    rewrite: &HistoryRewrite,
+) -> Result<(String, Option<String>), String> {
+    let position = |sha: &str| {
+        commits
+            .iter()
+            .position(|c| c.sha == sha || (sha.len() >= 7 && c.sha.starts_with(sha)))
+            .ok_or_else(|| format!("Commit {sha} is not on the workspace branch"))
+    };
+
+    let mut actions: Vec<&str> = vec!["pick"; commits.len()];
+    let message = match rewrite {
+        HistoryRewrite::Squash { from, to, message } => {
+            let (start, end) = (position(from)?, position(to)?);
+            if start >= end {
+                return Err(
+                    "Select at least two commits, from the oldest to the newest, to squash".into(),
+                );
+            }
+            actions[start] = "reword";
+            for action in &mut actions[start + 1..=end] {
+                *action = "fixup";
+            }
+            Some(message.clone().unwrap_or_else(|| {
+                commits[start..=end]
+                    .iter()
+                    .map(|c| c.message.trim_end())
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            }))
+        }
+        HistoryRewrite::Reword { commit, message } => {
+            actions[position(commit)?] = "reword";
+            Some(message.clone())
+        }
+        HistoryRewrite::Drop { commit } => {
+            actions[position(commit)?] = "drop";
+            None
+        }
+    };
+
+    if let Some(message) = &message
+        && message.trim().is_empty()
+    {
+        return Err("Commit message cannot be empty".into());
+    }
+
+    let todo = commits
+        .iter()
+        .zip(actions)
+        .map(|(commit, action)| format!("{action} {}\n", commit.sha))
+        .collect();
+    Ok((todo, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.to_string(),
+            subject: message.lines().next().unwrap_or_default().to_string(),
+            message: message.to_string(),
+            author_name: "Test".to_string(),
+            author_email: "test@example.com".to_string(),
+            authored_at: Utc::now(),
+            files_changed: 1,
+            additions: 1,
+            deletions: 0,
+        }
+    }
+
+    #[test]
+    fn squash_rewords_first_and_fixes_up_the_rest() {
+        let commits = [
+            commit("aaaaaaa1", "one\n"),
+            commit("bbbbbbb2", "two\n"),
+            commit("ccccccc3", "three\n"),
+            commit("ddddddd4", "four\n"),
+        ];
+        let rewrite = HistoryRewrite::Squash {
+            from: "bbbbbbb".to_string(),
+            to: "ccccccc3".to_string(),
+            message: None,
+        };
+        let (todo, message) = build_rebase_todo(&commits, &rewrite).unwrap();
+        assert_eq!(
+            todo,
+            "pick aaaaaaa1\nreword bbbbbbb2\nfixup ccccccc3\npick ddddddd4\n"
+        );
+        assert_eq!(message.as_deref(), Some("two\n\nthree"));
+    }
+
+    #[test]
+    fn rejects_unknown_commits_and_reversed_ranges() {
+        let commits = [commit("aaaaaaa1", "one"), commit("bbbbbbb2", "two")];
+        let drop_unknown = HistoryRewrite::Drop {
+            commit: "fffffff".to_string(),
+        };
+        assert!(build_rebase_todo(&commits, &drop_unknown).is_err());
+        let reversed = HistoryRewrite::Squash {
+            from: "bbbbbbb2".to_string(),
+            to: "aaaaaaa1".to_string(),
+            message: Some("squashed".to_string()),
+        };
+        assert!(build_rebase_todo(&commits, &reversed).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Hunk-level review of workspace changes: splitting a `git diff` patch into
+//! files and hunks, and rebuilding a patch from a selection of them.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Which changes of a workspace are reviewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum HunkScope {
+    /// Changes in the worktree that are not staged yet, including untracked files
+    Uncommitted,
+    /// Uncommitted changes that were accepted, i.e. staged in the index
+    Staged,
+    /// Changes committed on the workspace branch since its base commit
+    Committed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+pub struct DiffHunk {
+    /// The `@@ -a,b +c,d @@` line, which identifies the hunk within its file
+    pub header: String,
+    /// Hunk lines with their ` `, `+` or `-` prefix
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+pub struct FileHunks {
+    pub path: String,
+    /// Empty for binary files and mode-only changes, which can only be
+    /// selected as a whole
+    pub hunks: Vec<DiffHunk>,
+    /// `diff --git` line and extended headers, up to the first hunk
+    #[serde(skip)]
+    #[ts(skip)]
+    header: String,
+    /// The complete patch of the file
+    #[serde(skip)]
+    #[ts(skip)]
+    patch: String,
+}
+
+/// Hunks of one file to act on
+#[derive(Debug, Clone, Deserialize, Serialize, TS)]
+pub struct HunkSelection {
+    pub path: String,
+    /// Headers of the selected hunks; the whole file when `None`
+    #[serde(default)]
+    #[ts(optional)]
+    pub hunks: Option<Vec<String>>,
+}
+
+/// Split a patch produced with `--no-renames` and the default `a/` and `b/`
+/// prefixes into files and hunks.
+pub(crate) fn parse_patch(patch: &str) -> Vec<FileHunks> {
+    let mut files = Vec::new();
+    let mut sections = Vec::new();
+    let mut current = String::new();
+    for line in patch.split_inclusive('\n') {
+        if line.starts_with("diff --git ") && !current.is_empty() {
+            sections.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+
+    for section in sections {
+        let mut header = String::new();
+        let mut hunks: Vec<DiffHunk> = Vec::new();
+        for line in section.split_inclusive('\n') {
+            if line.starts_with("@@") {
+                hunks.push(DiffHunk {
+                    header: line.trim_end_matches('\n').to_string(),
+                    lines: Vec::new(),
+                });
+            } else if let Some(hunk) = hunks.last_mut() {
+                hunk.lines.push(line.trim_end_matches('\n').to_string());
+            } else {
+                header.push_str(line);
+            }
+        }
+        let Some(path) = file_path(&header) else {
+            continue;
+        };
+        files.push(FileHunks {
+            path,
+            hunks,
+            header,
+            patch: section,
+        });
+    }
+    files
+}
+
+/// Patch containing only the selected files and hunks. Fails when a selected
+/// file or hunk is not part of `files`, e.g. because the diff changed since it
+/// was listed.
+pub(crate) fn select_patch(
+    files: &[FileHunks],
+    selection: &[HunkSelection],
+) -> Result<String, String> {
+    let mut patch = String::new();
+    for selected in selection {
+        let file = files
+            .iter()
+            .find(|f| f.path == selected.path)
+            .ok_or_else(|| format!("'{}' has no changes", selected.path))?;
+        let Some(headers) = &selected.hunks else {
+            patch.push_str(&file.patch);
+            continue;
+        };
+        if headers.is_empty() {
+            continue;
+        }
+        patch.push_str(&file.header);
+        for header in headers {
+            let hunk = file
+                .hunks
+                .iter()
+                .find(|h| &h.header == header)
+                .ok_or_else(|| format!("'{}' has no hunk '{header}'", file.path))?;
+            patch.push_str(&hunk.header);
+            patch.push('\n');
+            for line in &hunk.lines {
+                patch.push_str(line);
+                patch.push('\n');
+            }
+        }
+    }
+    Ok(patch)
+}
+
+/// Path of a file section, from its `+++`/`---` lines, or the `diff --git`
+/// line for binary and mode-only changes.
+fn file_path(header: &str) -> Option<String> {
+    let mut old_path = None;
+    for line in header.lines() {
+        if let Some(path) = line.strip_prefix("+++ b/") {
+            return Some(path.to_string());
+        }
+        if let Some(path) = line.strip_prefix("--- a/") {
+            old_path = Some(path.to_string());
+        }
+    }
+    if old_path.is_some() {
+        return old_path;
+    }
+    // `diff --git a/<path> b/<path>` with the same path on both sides
+    let paths = header.lines().next()?.strip_prefix("diff --git a/")?;
+    let len = paths.len().checked_sub(3)? / 2;
+    (paths.get(len..len + 3) == Some(" b/")).then(|| paths[..len].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    old();
++    new();
+ }
+@@ -10,2 +10,3 @@ fn other() {
+ a
++b
+ c
+diff --git a/my file.bin b/my file.bin
+new file mode 100644
+index 0000000..3333333
+Binary files /dev/null and b/my file.bin differ
+";
+
+    #[test]
+    fn parses_files_hunks_and_binary_paths() {
+        let files = parse_patch(PATCH);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/main.rs");
+        assert_eq!(files[0].hunks.len(), 2);
+        assert_eq!(files[0].hunks[1].header, "@@ -10,2 +10,3 @@ fn other() {");
+        assert_eq!(files[0].hunks[1].lines, vec![" a", "+b", " c"]);
+        assert_eq!(files[1].path, "my file.bin");
+        assert!(files[1].hunks.is_empty());
+    }
+
+    #[test]
+    fn selects_hunks_and_rejects_stale_selections() {
+        let files = parse_patch(PATCH);
+        let patch = select_patch(
+            &files,
+            &[HunkSelection {
+                path: "src/main.rs".to_string(),
+                hunks: Some(vec!["@@ -10,2 +10,3 @@ fn other() {".to_string()]),
+            }],
+        )
+        .unwrap();
+        assert!(patch.starts_with("diff --git a/src/main.rs b/src/main.rs\n"));
+        assert!(patch.contains("+b\n"));
+        assert!(!patch.contains("new();"));
+
+        let stale = HunkSelection {
+            path: "src/main.rs".to_string(),
+            hunks: Some(vec!["@@ -5,1 +5,1 @@".to_string()]),
+        };
+        assert!(select_patch(&files, &[stale]).is_err());
+    }
+}
@@ -8,16 +8,39 @@ use git2::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
-use utils::diff::{Diff, DiffChangeKind, FileDiffDetails, compute_line_change_counts};
+use utils::diff::{
+    self, Diff, DiffChangeKind, DiffViewOptions, FileDiffDetails, compute_line_change_counts,
+};
 
+mod blame;
 mod cli;
+mod conflicts;
+mod history;
+mod hunks;
+mod signing;
+mod sparse;
+mod transfer;
 mod validation;
 
+pub use blame::BlameRange;
 use cli::{ChangeType, StatusDiffEntry, StatusDiffOptions};
-pub use cli::{GitCli, GitCliError, StatusEntry, WorktreeStatus};
+pub use cli::{GitCli, GitCliError, StatusEntry, SubmoduleEntry, WorktreeStatus};
+pub use conflicts::{ConflictHunk, ConflictedFile, has_conflict_markers, parse_conflict_hunks};
+pub use history::{CommitInfo, HistoryRewrite};
+pub use hunks::{DiffHunk, FileHunks, HunkScope, HunkSelection};
+pub use signing::{
+    CommitHooks, CommitIdentity, CommitOptions, CommitSigning, FALLBACK_COMMIT_EMAIL,
+    FALLBACK_COMMIT_NAME, SigningFormat,
+};
+pub use sparse::SparseCone;
+pub use transfer::ExportFormat;
 pub use utils::path::ALWAYS_SKIP_DIRS;
 pub use validation::is_valid_branch_prefix;
 
+/// Hidden namespace that keeps archived workspace branches reachable after the
+/// branch itself is deleted
+pub const ARCHIVE_REF_PREFIX: &str = "refs/vk-archive/";
+
 /// Statistics for a single file based on git history
 #[derive(Clone, Debug)]
 pub struct FileStat {
@@ -52,6 +75,14 @@ pub enum GitServiceError {
     WorktreeDirty(String, String),
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("Commit signing failed: {0}")]
+    SigningFailed(String),
+    #[error("Commit hooks failed:\n{0}")]
+    CommitHooksFailed(String),
+    #[error("Patch does not apply: {0}")]
+    PatchFailed(String),
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
@@ -190,8 +221,8 @@ impl GitService {
         let has_email = cfg.get_string("user.email").is_ok();
         if !(has_name && has_email) {
             let mut cfg = repo.config()?;
-            cfg.set_str("user.name", "Vibe Kanban")?;
-            cfg.set_str("user.email", "noreply@vibekanban.com")?;
+            cfg.set_str("user.name", FALLBACK_COMMIT_NAME)?;
+            cfg.set_str("user.email", FALLBACK_COMMIT_EMAIL)?;
         }
         Ok(())
     }
@@ -203,11 +234,49 @@ impl GitService {
     ) -> Result<git2::Signature<'a>, GitServiceError> {
         match repo.signature() {
             Ok(sig) => Ok(sig),
-            Err(_) => git2::Signature::now("Vibe Kanban", "noreply@vibekanban.com")
+            Err(_) => git2::Signature::now(FALLBACK_COMMIT_NAME, FALLBACK_COMMIT_EMAIL)
                 .map_err(GitServiceError::from),
         }
     }
 
+    /// Signature for libgit2 commits: the identity from `options`, else the
+    /// repository identity with the fallback.
+    fn commit_signature<'a>(
+        &self,
+        repo: &'a Repository,
+        options: &CommitOptions,
+    ) -> Result<git2::Signature<'a>, GitServiceError> {
+        match &options.identity {
+            Some(identity) => {
+                git2::Signature::now(&identity.name, &identity.email).map_err(GitServiceError::from)
+            }
+            None => self.signature_with_fallback(repo),
+        }
+    }
+
+    /// Create a commit with libgit2 without updating any reference, adding the
+    /// co-author trailers and signing it as `options` requires.
+    fn create_commit(
+        &self,
+        repo: &Repository,
+        signature: &git2::Signature,
+        message: &str,
+        tree: &git2::Tree,
+        parents: &[&git2::Commit],
+        options: &CommitOptions,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let message = options.message_with_trailers(message);
+        let Some(signing) = &options.signing else {
+            return Ok(repo.commit(None, signature, signature, &message, tree, parents)?);
+        };
+        let buffer = repo.commit_create_buffer(signature, signature, &message, tree, parents)?;
+        let content = buffer.as_str().ok_or_else(|| {
+            GitServiceError::SigningFailed("commit content is not valid UTF-8".to_string())
+        })?;
+        let gpgsig = signing.sign(repo, content)?;
+        Ok(repo.commit_signed(content, &gpgsig, None)?)
+    }
+
     fn default_remote(
         &self,
         repo: &Repository,
@@ -302,6 +371,30 @@ impl GitService {
     }
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_with_options(path, message, &CommitOptions::default())
+    }
+
+    /// Stage and commit all changes with the identity, trailers and signing from `options`.
+    pub fn commit_with_options(
+        &self,
+        path: &Path,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<bool, GitServiceError> {
+        self.commit_with_hooks(path, message, options)
+            .map(|output| output.is_some())
+    }
+
+    /// Like [`Self::commit_with_options`], returning what the repository's commit
+    /// hooks printed (empty when none ran), or `None` when there was nothing to
+    /// commit. A commit rejected by the hooks fails with
+    /// [`GitServiceError::CommitHooksFailed`] carrying their output.
+    pub fn commit_with_hooks(
+        &self,
+        path: &Path,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<Option<String>, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
         let git = GitCli::new();
         let has_changes = git
@@ -309,16 +402,44 @@ impl GitService {
             .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
         if !has_changes {
             tracing::debug!("No changes to commit!");
-            return Ok(false);
+            return Ok(None);
         }
 
         git.add_all(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
         // Only ensure identity once we know we're about to commit
-        self.ensure_cli_commit_identity(path)?;
-        git.commit(path, message)
-            .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
-        Ok(true)
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(path)?;
+        }
+
+        let message = options.message_with_trailers(message);
+        let config = options.cli_config_args();
+        let skip_hooks = options.hooks == CommitHooks::Skip;
+        let has_hooks = !skip_hooks && git.has_commit_hooks(path);
+        let commit = || git.commit_with_output(path, &message, &config, skip_hooks);
+        let commit_failed =
+            |e: GitCliError| GitServiceError::InvalidRepository(format!("git commit failed: {e}"));
+
+        let output = match commit() {
+            Ok(output) => output,
+            Err(GitCliError::CommandFailed(output)) if has_hooks => {
+                if options.hooks != CommitHooks::RunAndRetry {
+                    return Err(GitServiceError::CommitHooksFailed(output));
+                }
+                git.add_all(path).map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git add failed: {e}"))
+                })?;
+                match commit() {
+                    Ok(retried) => format!("{output}\n--- retried after re-staging\n{retried}"),
+                    Err(GitCliError::CommandFailed(output)) => {
+                        return Err(GitServiceError::CommitHooksFailed(output));
+                    }
+                    Err(e) => return Err(commit_failed(e)),
+                }
+            }
+            Err(e) => return Err(commit_failed(e)),
+        };
+        Ok(Some(if has_hooks { output } else { String::new() }))
     }
 
     /// Get diffs between branches or worktree changes
@@ -326,6 +447,30 @@ impl GitService {
         &self,
         target: DiffTarget,
         path_filter: Option<&[&str]>,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        self.get_diffs_with_options(target, path_filter, &DiffViewOptions::default())
+    }
+
+    /// Get diffs with whitespace, rename/copy detection, context and word-level
+    /// options applied
+    pub fn get_diffs_with_options(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        options: &DiffViewOptions,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        let mut diffs = self.compute_diffs(target, path_filter, options)?;
+        if *options != DiffViewOptions::default() {
+            diffs.retain_mut(|d| diff::apply_view_options(d, options));
+        }
+        Ok(diffs)
+    }
+
+    fn compute_diffs(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        options: &DiffViewOptions,
     ) -> Result<Vec<Diff>, GitServiceError> {
         match target {
             DiffTarget::Worktree {
@@ -346,6 +491,8 @@ impl GitService {
                 let git = GitCli::new();
                 let cli_opts = StatusDiffOptions {
                     path_filter: path_filter.map(|fs| fs.iter().map(|s| s.to_string()).collect()),
+                    rename_threshold: options.rename_threshold,
+                    detect_copies: options.detect_copies,
                 };
                 let entries = git
                     .diff_status(worktree_path, base_commit, cli_opts)
@@ -388,8 +535,7 @@ impl GitService {
                     Some(&mut diff_opts),
                 )?;
 
-                // Enable rename detection
-                let mut find_opts = DiffFindOptions::new();
+                let mut find_opts = Self::find_similar_options(options);
                 diff.find_similar(Some(&mut find_opts))?;
 
                 self.convert_diff_to_file_diffs(diff, &repo)
@@ -433,8 +579,7 @@ impl GitService {
                     Some(&mut diff_opts),
                 )?;
 
-                // Enable rename detection
-                let mut find_opts = git2::DiffFindOptions::new();
+                let mut find_opts = Self::find_similar_options(options);
                 diff.find_similar(Some(&mut find_opts))?;
 
                 self.convert_diff_to_file_diffs(diff, &repo)
@@ -442,6 +587,22 @@ impl GitService {
         }
     }
 
+    /// Rename (and optionally copy) detection for tree-to-tree diffs
+    fn find_similar_options(options: &DiffViewOptions) -> DiffFindOptions {
+        let mut find_opts = DiffFindOptions::new();
+        if let Some(threshold) = options.rename_threshold {
+            let threshold = threshold.min(100);
+            find_opts.renames(true);
+            find_opts.rename_threshold(threshold);
+            find_opts.copy_threshold(threshold);
+        }
+        if options.detect_copies {
+            find_opts.renames(true);
+            find_opts.copies(true);
+        }
+        find_opts
+    }
+
     /// Convert git2::Diff to our Diff structs
     fn convert_diff_to_file_diffs(
         &self,
@@ -495,10 +656,14 @@ impl GitService {
                     if content_omitted {
                         (path_opt, None)
                     } else {
-                        let details = delta
-                            .old_file()
-                            .path()
-                            .map(|p| self.create_file_details(p, &delta.old_file().id(), repo));
+                        let details = delta.old_file().path().map(|p| {
+                            self.create_file_details(
+                                p,
+                                &delta.old_file().id(),
+                                delta.old_file().mode(),
+                                repo,
+                            )
+                        });
                         (
                             details.as_ref().and_then(|f| f.file_name.clone()),
                             details.and_then(|f| f.content),
@@ -516,10 +681,14 @@ impl GitService {
                     if content_omitted {
                         (path_opt, None)
                     } else {
-                        let details = delta
-                            .new_file()
-                            .path()
-                            .map(|p| self.create_file_details(p, &delta.new_file().id(), repo));
+                        let details = delta.new_file().path().map(|p| {
+                            self.create_file_details(
+                                p,
+                                &delta.new_file().id(),
+                                delta.new_file().mode(),
+                                repo,
+                            )
+                        });
                         (
                             details.as_ref().and_then(|f| f.file_name.clone()),
                             details.and_then(|f| f.content),
@@ -568,6 +737,8 @@ impl GitService {
                     additions,
                     deletions,
                     repo_id: None,
+                    hunks: None,
+                    word_highlights: None,
                 });
 
                 delta_index += 1;
@@ -600,6 +771,52 @@ impl GitService {
         }
     }
 
+    /// Render a submodule pointer the same way `git diff` does
+    fn submodule_pointer_content(commit_id: git2::Oid) -> String {
+        format!("Subproject commit {commit_id}\n")
+    }
+
+    /// Commit checked out in a submodule of the working tree, falling back to the
+    /// index entry when the submodule is not initialized
+    fn submodule_workdir_commit(repo: &Repository, rel_path: &Path) -> Option<git2::Oid> {
+        let workdir = repo.workdir()?;
+        Repository::open(workdir.join(rel_path))
+            .ok()
+            .and_then(|sub| sub.head().ok()?.target())
+            .or_else(|| {
+                repo.index()
+                    .ok()?
+                    .get_path(rel_path, 0)
+                    .map(|entry| entry.id)
+            })
+    }
+
+    /// Paths outside a sparse-checkout cone are not on disk; read their committed content instead
+    fn read_skipped_file_from_head(repo: &Repository, rel_path: &Path) -> Option<String> {
+        if repo.workdir()?.join(rel_path).exists() {
+            return None;
+        }
+        let entry = repo
+            .head()
+            .ok()?
+            .peel_to_tree()
+            .ok()?
+            .get_path(rel_path)
+            .ok()?;
+        let blob = repo.find_blob(entry.id()).ok()?;
+        if blob.size() > MAX_INLINE_DIFF_BYTES {
+            return None;
+        }
+        Self::blob_to_string(&blob)
+    }
+
+    /// Return true if the content is a Git LFS pointer rather than the real file
+    fn is_lfs_pointer(content: &str) -> bool {
+        content.len() < 1024
+            && content.starts_with("version https://git-lfs.github.com/spec/v1\n")
+            && content.lines().any(|line| line.starts_with("oid sha256:"))
+    }
+
     /// Helper function to read file content from filesystem with safety guards
     fn read_file_to_string(repo: &Repository, rel_path: &Path) -> Option<String> {
         let workdir = repo.workdir()?;
@@ -645,12 +862,16 @@ impl GitService {
         &self,
         path: &Path,
         blob_id: &git2::Oid,
+        mode: git2::FileMode,
         repo: &Repository,
     ) -> FileDiffDetails {
         let file_name = path.to_string_lossy().to_string();
 
-        // Try to get content from blob first (for non-zero OIDs)
-        let content = if !blob_id.is_zero() {
+        // Submodule entries point at a commit, not a blob
+        let content = if mode == git2::FileMode::Commit {
+            Some(Self::submodule_pointer_content(*blob_id))
+        } else if !blob_id.is_zero() {
+            // Try to get content from blob first (for non-zero OIDs)
             repo.find_blob(*blob_id)
                 .ok()
                 .and_then(|blob| Self::blob_to_string(&blob))
@@ -700,6 +921,24 @@ impl GitService {
             ChangeType::Unknown(_) => (e.old_path.clone(), Some(e.path.clone())),
         };
 
+        // Submodules are diffed by the commit they point at
+        let new_submodule_commit = new_path_opt.as_ref().and_then(|newp| {
+            let rel = std::path::Path::new(newp);
+            let was_submodule = old_path_opt.as_ref().is_some_and(|oldp| {
+                base_tree
+                    .get_path(std::path::Path::new(oldp))
+                    .is_ok_and(|entry| entry.kind() == Some(git2::ObjectType::Commit))
+            });
+            let is_submodule = repo
+                .workdir()
+                .is_some_and(|workdir| workdir.join(rel).join(".git").exists());
+            if was_submodule || is_submodule {
+                Self::submodule_workdir_commit(repo, rel)
+            } else {
+                None
+            }
+        });
+
         // Decide if we should omit content by size (either side)
         let mut content_omitted = false;
         // Old side (from base tree)
@@ -738,6 +977,9 @@ impl GitService {
                         .find_blob(entry.id())
                         .ok()
                         .and_then(|b| Self::blob_to_string(&b)),
+                    Ok(entry) if entry.kind() == Some(git2::ObjectType::Commit) => {
+                        Some(Self::submodule_pointer_content(entry.id()))
+                    }
                     _ => None,
                 }
             } else {
@@ -745,15 +987,30 @@ impl GitService {
             };
 
             // Load new content from filesystem (worktree) when available
-            let new_content = if let Some(ref newp) = new_path_opt {
+            let new_content = if let Some(commit_id) = new_submodule_commit {
+                Some(Self::submodule_pointer_content(commit_id))
+            } else if let Some(ref newp) = new_path_opt {
                 let rel = std::path::Path::new(newp);
                 Self::read_file_to_string(repo, rel)
+                    .or_else(|| Self::read_skipped_file_from_head(repo, rel))
             } else {
                 None
             };
             (old_content, new_content)
         };
 
+        // LFS files are committed as pointers but checked out with their real content;
+        // diffing one against the other is meaningless, so treat them like large files
+        let (old_content, new_content) = if new_path_opt.is_some()
+            && old_content.as_deref().is_some_and(Self::is_lfs_pointer)
+            && !new_content.as_deref().is_some_and(Self::is_lfs_pointer)
+        {
+            content_omitted = true;
+            (None, None)
+        } else {
+            (old_content, new_content)
+        };
+
         // If reported as Modified but content is identical, treat as a permission-only change
         if matches!(change, DiffChangeKind::Modified)
             && old_content.is_some()
@@ -790,6 +1047,8 @@ impl GitService {
             additions,
             deletions,
             repo_id: None,
+            hunks: None,
+            word_highlights: None,
         }
     }
 
@@ -822,6 +1081,27 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_options(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+            &CommitOptions::default(),
+        )
+    }
+
+    /// Squash-merge a task branch into the base branch, authoring and signing
+    /// the merge commit as `options` requires.
+    pub fn merge_changes_with_options(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+        options: &CommitOptions,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -858,13 +1138,16 @@ impl GitService {
                 }
 
                 // Use CLI merge in base context
-                self.ensure_cli_commit_identity(&base_checkout_path)?;
+                if options.identity.is_none() {
+                    self.ensure_cli_commit_identity(&base_checkout_path)?;
+                }
                 let sha = git_cli
                     .merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
-                        commit_message,
+                        &options.message_with_trailers(commit_message),
+                        &options.cli_config_args(),
                     )
                     .map_err(|e| {
                         GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
@@ -890,14 +1173,13 @@ impl GitService {
                 let task_commit = task_branch.get().peel_to_commit()?;
 
                 // Create the squash commit in-memory (no checkout) and update the base branch ref
-                let signature = self.signature_with_fallback(&task_repo)?;
                 let squash_commit_id = self.perform_squash_merge(
                     &task_repo,
                     &base_commit,
                     &task_commit,
-                    &signature,
                     commit_message,
                     base_branch_name,
+                    options,
                 )?;
 
                 // Update the task branch to the new squash commit so follow-up
@@ -998,6 +1280,24 @@ impl GitService {
         }
     }
 
+    /// Ignored files and directories in the worktree, such as `node_modules`
+    /// or `target`, relative to the worktree root. Ignored directories are
+    /// listed once rather than file by file.
+    pub fn ignored_paths(&self, worktree_path: &Path) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        let mut status_options = git2::StatusOptions::new();
+        status_options
+            .include_untracked(false)
+            .include_ignored(true)
+            .recurse_ignored_dirs(false);
+        let statuses = repo.statuses(Some(&mut status_options))?;
+        Ok(statuses
+            .iter()
+            .filter(|entry| entry.status().is_ignored())
+            .filter_map(|entry| entry.path().map(|p| p.trim_end_matches('/').to_string()))
+            .collect())
+    }
+
     /// Check if the worktree is clean (no uncommitted changes to tracked files)
     fn check_worktree_clean(&self, repo: &Repository) -> Result<(), GitServiceError> {
         let mut status_options = git2::StatusOptions::new();
@@ -1008,9 +1308,22 @@ impl GitService {
         let statuses = repo.statuses(Some(&mut status_options))?;
 
         if !statuses.is_empty() {
+            // libgit2 ignores sparse-checkout, so paths outside the cone look deleted
+            let index = repo.index()?;
+            let is_outside_sparse_cone = |path: &str| {
+                index.get_path(Path::new(path), 0).is_some_and(|e| {
+                    e.flags_extended & git2::IndexEntryExtendedFlag::SKIP_WORKTREE.bits() != 0
+                })
+            };
+
             let mut dirty_files = Vec::new();
             for entry in statuses.iter() {
                 let status = entry.status();
+                if status == git2::Status::WT_DELETED
+                    && entry.path().is_some_and(is_outside_sparse_cone)
+                {
+                    continue;
+                }
                 // Only consider files that are actually tracked and modified
                 if status.intersects(
                     git2::Status::INDEX_MODIFIED
@@ -1218,13 +1531,116 @@ impl GitService {
         worktree_path: &Path,
         branch: &str,
         create_branch: bool,
+    ) -> Result<(), GitServiceError> {
+        self.add_worktree_with_sparse_cone(repo_path, worktree_path, branch, create_branch, None)
+    }
+
+    /// Add a worktree that only checks out the directories in `sparse_cone`
+    /// (the whole tree when `None`)
+    pub fn add_worktree_with_sparse_cone(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        create_branch: bool,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<(), GitServiceError> {
         let git = GitCli::new();
-        git.worktree_add(repo_path, worktree_path, branch, create_branch)
+        git.worktree_add(
+            repo_path,
+            worktree_path,
+            branch,
+            create_branch,
+            sparse_cone.map(|cone| cone.dirs()),
+        )
+        .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Sparse-checkout cone of a worktree, or `None` for a full checkout
+    pub fn get_sparse_cone(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Option<SparseCone>, GitServiceError> {
+        let dirs = GitCli::new()
+            .sparse_checkout_list(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(SparseCone::from_dirs(dirs))
+    }
+
+    /// Add directories to a sparse worktree's cone and check them out.
+    /// Returns the widened cone, or `None` if the worktree is a full checkout.
+    pub fn widen_sparse_checkout(
+        &self,
+        worktree_path: &Path,
+        dirs: &[String],
+    ) -> Result<Option<SparseCone>, GitServiceError> {
+        let Some(cone) = self.get_sparse_cone(worktree_path)? else {
+            return Ok(None);
+        };
+        let widened = cone.widen(dirs);
+        GitCli::new()
+            .sparse_checkout_add(worktree_path, widened.dirs())
             .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(Some(widened))
+    }
+
+    /// Populate submodules and Git LFS content in a freshly created worktree.
+    /// Submodules borrow objects from the main repository's own submodule clones,
+    /// so only objects it doesn't already have are fetched.
+    pub fn prepare_worktree_checkout(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        init_submodules: bool,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+
+        if init_submodules {
+            let submodules = git
+                .list_submodules(worktree_path)
+                .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+            if !submodules.is_empty() {
+                let modules_dir = self.open_repo(repo_path)?.commondir().join("modules");
+                for submodule in &submodules {
+                    let reference = modules_dir.join(&submodule.name);
+                    let reference = reference.join("objects").is_dir().then_some(reference);
+                    git.submodule_update(
+                        worktree_path,
+                        Some(&submodule.path),
+                        reference.as_deref(),
+                    )
+                    .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+                }
+                // Nested submodules have no local clone to borrow from
+                git.submodule_update(worktree_path, None, None)
+                    .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+            }
+        }
+
+        if git.uses_lfs(worktree_path) {
+            if git.lfs_available(worktree_path) {
+                git.lfs_pull(worktree_path)
+                    .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+            } else {
+                tracing::warn!(
+                    "Repository at {} uses Git LFS but git-lfs is not installed; LFS files will stay as pointers",
+                    repo_path.display()
+                );
+            }
+        }
+
         Ok(())
     }
 
+    /// Paths of the submodules declared in the worktree's `.gitmodules`
+    pub fn get_submodule_paths(&self, worktree_path: &Path) -> Vec<String> {
+        GitCli::new()
+            .list_submodules(worktree_path)
+            .map(|subs| subs.into_iter().map(|s| s.path).collect())
+            .unwrap_or_default()
+    }
+
     /// Remove a worktree
     pub fn remove_worktree(
         &self,
@@ -1269,6 +1685,195 @@ impl GitService {
         Ok(())
     }
 
+    /// Commits to replay when backporting `branch_name` onto another branch,
+    /// oldest first: `merge_commit` when the branch was merged and that commit
+    /// exists locally, otherwise the branch's own commits since it forked from
+    /// `target_branch`.
+    pub fn backport_commits(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        target_branch: &str,
+        merge_commit: Option<&str>,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        if let Some(sha) = merge_commit
+            && let Ok(oid) = git2::Oid::from_str(sha)
+            && repo.find_commit(oid).is_ok()
+        {
+            return Ok(vec![oid.to_string()]);
+        }
+
+        let base_commit = self.get_base_commit(repo_path, branch_name, target_branch)?;
+        let tip = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk.push(tip)?;
+        revwalk.hide(base_commit.as_oid())?;
+        revwalk
+            .map(|oid| Ok(oid?.to_string()))
+            .collect::<Result<Vec<_>, GitError>>()
+            .map_err(GitServiceError::from)
+    }
+
+    /// Cherry-pick `commits` onto the worktree's branch. On conflicts the
+    /// cherry-pick is left in progress so it can be resolved, continued or
+    /// aborted like any other conflicted operation. The replayed commits are
+    /// committed and signed as `options` requires.
+    pub fn cherry_pick_commits(
+        &self,
+        worktree_path: &Path,
+        commits: &[String],
+        options: &CommitOptions,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+        let mainline = commits.iter().any(|sha| {
+            git2::Oid::from_str(sha)
+                .and_then(|oid| repo.find_commit(oid))
+                .is_ok_and(|commit| commit.parent_count() > 1)
+        });
+
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
+        let git = GitCli::new();
+        match git.cherry_pick(worktree_path, commits, mainline, &options.cli_config_args()) {
+            Ok(()) => Ok(()),
+            Err(GitCliError::CommandFailed(stderr))
+                if git
+                    .is_cherry_pick_in_progress(worktree_path)
+                    .unwrap_or(false) =>
+            {
+                let conflicted_files = git.get_conflicted_files(worktree_path).unwrap_or_default();
+                if conflicted_files.is_empty() {
+                    // Nothing to resolve (e.g. the change is already present), so
+                    // don't leave the worktree mid cherry-pick
+                    let _ = git.abort_cherry_pick(worktree_path);
+                    return Err(GitServiceError::InvalidRepository(format!(
+                        "Cherry-pick failed: {stderr}"
+                    )));
+                }
+                Err(GitServiceError::MergeConflicts {
+                    message: format!(
+                        "Cherry-pick encountered merge conflicts. Conflicted files: {}. \
+                         Resolve conflicts and then continue or abort.",
+                        conflicted_files.join(", ")
+                    ),
+                    conflicted_files,
+                })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Tip of `branch_name` when it has commits that `target_branch` lacks.
+    /// `None` when the branch no longer exists or is fully merged.
+    pub fn unmerged_branch_tip(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        target_branch: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let Ok(branch) = repo.find_branch(branch_name, BranchType::Local) else {
+            return Ok(None);
+        };
+        let tip = branch.get().peel_to_commit()?.id();
+        let target = match Self::find_branch(&repo, target_branch) {
+            Ok(target) => target.get().peel_to_commit()?.id(),
+            Err(GitServiceError::BranchNotFound(_)) => return Ok(Some(tip.to_string())),
+            Err(e) => return Err(e),
+        };
+        if tip == target || repo.graph_descendant_of(target, tip)? {
+            return Ok(None);
+        }
+        Ok(Some(tip.to_string()))
+    }
+
+    /// Point `refname` at `sha`, e.g. to keep a branch tip reachable under
+    /// [`ARCHIVE_REF_PREFIX`] after the branch is deleted.
+    pub fn write_ref(
+        &self,
+        repo_path: &Path,
+        refname: &str,
+        sha: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        repo.reference(
+            refname,
+            git2::Oid::from_str(sha)?,
+            true,
+            "Archive workspace branch",
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_ref(&self, repo_path: &Path, refname: &str) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        match repo.find_reference(refname) {
+            Ok(mut reference) => Ok(reference.delete()?),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the commits of `branch_name` that `target_branch` lacks to a
+    /// bundle file, or the whole branch when the target branch is gone.
+    pub fn write_branch_bundle(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        target_branch: &str,
+        bundle_path: &Path,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch_ref = format!("refs/heads/{branch_name}");
+        let range = match Self::find_branch(&repo, target_branch) {
+            Ok(target) => format!("{}..{branch_ref}", target.get().peel_to_commit()?.id()),
+            Err(_) => branch_ref,
+        };
+        if let Some(parent) = bundle_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        GitCli::new().bundle_create(repo_path, bundle_path, &range)?;
+        Ok(())
+    }
+
+    /// Recreate `branch_name` at `sha`, first fetching the commits from a bundle
+    /// written by [`Self::write_branch_bundle`] when one is given. An existing
+    /// branch is accepted only if it already points at `sha`.
+    pub fn restore_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        sha: &str,
+        bundle_path: Option<&Path>,
+    ) -> Result<(), GitServiceError> {
+        if let Some(bundle_path) = bundle_path {
+            GitCli::new().fetch_bundle(
+                repo_path,
+                bundle_path,
+                &format!("refs/heads/{branch_name}"),
+            )?;
+        }
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.find_commit(git2::Oid::from_str(sha)?)?;
+        match repo.find_branch(branch_name, BranchType::Local) {
+            Ok(branch) if branch.get().target() == Some(commit.id()) => Ok(()),
+            Ok(_) => Err(GitServiceError::InvalidRepository(format!(
+                "Branch {branch_name} already exists at a different commit"
+            ))),
+            Err(_) => {
+                repo.branch(branch_name, &commit, false)?;
+                Ok(())
+            }
+        }
+    }
+
     pub fn get_all_branches(&self, repo_path: &Path) -> Result<Vec<GitBranch>, git2::Error> {
         let repo = Repository::open(repo_path)?;
         let current_branch = self.get_current_branch(repo_path).unwrap_or_default();
@@ -1339,9 +1944,9 @@ impl GitService {
         repo: &Repository,
         base_commit: &git2::Commit,
         task_commit: &git2::Commit,
-        signature: &git2::Signature,
         commit_message: &str,
         base_branch_name: &str,
+        options: &CommitOptions,
     ) -> Result<git2::Oid, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
@@ -1364,13 +1969,14 @@ impl GitService {
         let tree = repo.find_tree(tree_id)?;
 
         // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
-            None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
-            commit_message, // Custom message
-            &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
+        let signature = self.commit_signature(repo, options)?;
+        let squash_commit_id = self.create_commit(
+            repo,
+            &signature,
+            commit_message,
+            &tree,
+            &[base_commit],
+            options,
         )?;
 
         // Update the base branch reference to point to the new commit
@@ -1388,6 +1994,7 @@ impl GitService {
         new_base_branch: &str,
         old_base_branch: &str,
         task_branch: &str,
+        options: &CommitOptions,
     ) -> Result<String, GitServiceError> {
         let worktree_repo = Repository::open(worktree_path)?;
         let main_repo = self.open_repo(repo_path)?;
@@ -1412,53 +2019,36 @@ impl GitService {
         }
 
         // Ensure identity for any commits produced by rebase
-        self.ensure_cli_commit_identity(worktree_path)?;
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
         // Use git CLI rebase to carry out the operation safely
-        match git.rebase_onto(worktree_path, new_base_branch, old_base_branch, task_branch) {
+        match git.rebase_onto(
+            worktree_path,
+            new_base_branch,
+            old_base_branch,
+            task_branch,
+            &options.cli_config_args(),
+        ) {
             Ok(()) => {}
             Err(GitCliError::RebaseInProgress) => {
                 return Err(GitServiceError::RebaseInProgress);
             }
             Err(GitCliError::CommandFailed(stderr)) => {
                 // If the CLI indicates conflicts, return a concise, actionable error.
-                let looks_like_conflict = stderr.contains("could not apply")
-                    || stderr.contains("CONFLICT")
-                    || stderr.to_lowercase().contains("resolve all conflicts");
-                if looks_like_conflict {
-                    // Determine current attempt branch name for clarity
-                    let attempt_branch = worktree_repo
-                        .head()
-                        .ok()
-                        .and_then(|h| h.shorthand().map(|s| s.to_string()))
-                        .unwrap_or_else(|| "(unknown)".to_string());
-                    // List conflicted files (best-effort)
-                    let conflicted_files =
-                        git.get_conflicted_files(worktree_path).unwrap_or_default();
-                    let files_part = if conflicted_files.is_empty() {
-                        "".to_string()
-                    } else {
-                        let mut sample = conflicted_files.clone();
-                        let total = sample.len();
-                        sample.truncate(10);
-                        let list = sample.join(", ");
-                        if total > sample.len() {
-                            format!(
-                                " Conflicted files (showing {} of {}): {}.",
-                                sample.len(),
-                                total,
-                                list
-                            )
-                        } else {
-                            format!(" Conflicted files: {list}.")
-                        }
-                    };
-                    let msg = format!(
-                        "Rebase encountered merge conflicts while rebasing '{attempt_branch}' onto '{new_base_branch}'.{files_part} Resolve conflicts and then continue or abort."
-                    );
-                    return Err(GitServiceError::MergeConflicts {
-                        message: msg,
-                        conflicted_files,
-                    });
+                // Determine current attempt branch name for clarity
+                let attempt_branch = worktree_repo
+                    .head()
+                    .ok()
+                    .and_then(|h| h.shorthand().map(|s| s.to_string()))
+                    .unwrap_or_else(|| "(unknown)".to_string());
+                if let Some(err) = Self::rebase_conflicts_error(
+                    &git,
+                    worktree_path,
+                    &stderr,
+                    &format!("while rebasing '{attempt_branch}' onto '{new_base_branch}'"),
+                ) {
+                    return Err(err);
                 }
                 return Err(GitServiceError::InvalidRepository(format!(
                     "Rebase failed: {}",
@@ -1477,6 +2067,430 @@ impl GitService {
         Ok(final_commit.id().to_string())
     }
 
+    /// Map a failed CLI rebase that stopped on conflicts to `MergeConflicts`.
+    /// Returns `None` when the failure was not caused by conflicts.
+    fn rebase_conflicts_error(
+        git: &GitCli,
+        worktree_path: &Path,
+        stderr: &str,
+        context: &str,
+    ) -> Option<GitServiceError> {
+        let looks_like_conflict = stderr.contains("could not apply")
+            || stderr.contains("CONFLICT")
+            || stderr.to_lowercase().contains("resolve all conflicts");
+        if !looks_like_conflict {
+            return None;
+        }
+        // List conflicted files (best-effort)
+        let conflicted_files = git.get_conflicted_files(worktree_path).unwrap_or_default();
+        let files_part = if conflicted_files.is_empty() {
+            "".to_string()
+        } else {
+            let mut sample = conflicted_files.clone();
+            let total = sample.len();
+            sample.truncate(10);
+            let list = sample.join(", ");
+            if total > sample.len() {
+                format!(
+                    " Conflicted files (showing {} of {}): {}.",
+                    sample.len(),
+                    total,
+                    list
+                )
+            } else {
+                format!(" Conflicted files: {list}.")
+            }
+        };
+        Some(GitServiceError::MergeConflicts {
+            message: format!(
+                "Rebase encountered merge conflicts {context}.{files_part} Resolve conflicts and then continue or abort."
+            ),
+            conflicted_files,
+        })
+    }
+
+    /// List commits on the worktree's branch since `base_commit`, newest first
+    pub fn list_commits(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+    ) -> Result<Vec<CommitInfo>, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push_head()?;
+        revwalk.hide(base_commit.as_oid())?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            let stats = diff.stats()?;
+            let author = commit.author();
+            commits.push(CommitInfo {
+                sha: commit.id().to_string(),
+                subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                message: commit.message().unwrap_or_default().to_string(),
+                author_name: author.name().unwrap_or_default().to_string(),
+                author_email: author.email().unwrap_or_default().to_string(),
+                authored_at: DateTime::from_timestamp(author.when().seconds(), 0)
+                    .unwrap_or_default(),
+                files_changed: stats.files_changed(),
+                additions: stats.insertions(),
+                deletions: stats.deletions(),
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Blame the worktree copy of `path`, including uncommitted edits, back to
+    /// `base_commit`. Lines unchanged since the base commit are left out.
+    pub fn blame_since(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        path: &str,
+    ) -> Result<Vec<BlameRange>, GitServiceError> {
+        if !blame::is_worktree_relative(path) {
+            return Err(GitServiceError::InvalidPath(path.to_string()));
+        }
+        let contents = match std::fs::read(worktree_path.join(path)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let line_count = contents.iter().filter(|b| **b == b'\n').count()
+            + usize::from(!contents.is_empty() && !contents.ends_with(b"\n"));
+
+        let repo = self.open_repo(worktree_path)?;
+        let mut opts = git2::BlameOptions::new();
+        opts.oldest_commit(base_commit.as_oid());
+        let committed = match repo.blame_file(Path::new(path), Some(&mut opts)) {
+            Ok(blame) => blame,
+            // Not in HEAD yet, so every line is uncommitted
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                if line_count == 0 {
+                    return Ok(Vec::new());
+                }
+                return Ok(vec![BlameRange {
+                    start_line: 1,
+                    line_count,
+                    commit_sha: None,
+                }]);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let blame = committed.blame_buffer(&contents)?;
+
+        let mut ranges = Vec::new();
+        for hunk in blame.iter() {
+            let oid = hunk.final_commit_id();
+            if hunk.is_boundary() || oid == base_commit.as_oid() {
+                continue;
+            }
+            ranges.push(BlameRange {
+                start_line: hunk.final_start_line(),
+                line_count: hunk.lines_in_hunk(),
+                commit_sha: (!oid.is_zero()).then(|| oid.to_string()),
+            });
+        }
+        Ok(ranges)
+    }
+
+    /// Package the commits since `base_commit` on the worktree's branch.
+    pub fn export_changes(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        format: ExportFormat,
+    ) -> Result<Vec<u8>, GitServiceError> {
+        let git = GitCli::new();
+        let range = format!("{base_commit}..HEAD");
+        match format {
+            ExportFormat::Mbox => Ok(git.format_patch(worktree_path, &range)?),
+            ExportFormat::Diff => {
+                Ok(git.diff_binary(worktree_path, &base_commit.to_string(), "HEAD")?)
+            }
+            ExportFormat::Bundle => {
+                let tmp_dir = tempfile::TempDir::new()?;
+                let bundle_path = tmp_dir.path().join("export.bundle");
+                git.bundle_create(worktree_path, &bundle_path, &range)?;
+                Ok(std::fs::read(&bundle_path)?)
+            }
+        }
+    }
+
+    /// Apply an export produced by [`Self::export_changes`] to a clean worktree.
+    /// A bundle moves the branch to the bundled commits, a mailbox is applied
+    /// commit by commit, and a plain diff is committed with `diff_message`.
+    /// Commits written here are committed and signed as `options` requires.
+    pub fn import_changes(
+        &self,
+        worktree_path: &Path,
+        contents: &[u8],
+        diff_message: &str,
+        options: &CommitOptions,
+    ) -> Result<ExportFormat, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+
+        let git = GitCli::new();
+        let format = ExportFormat::detect(contents);
+        match format {
+            ExportFormat::Bundle => {
+                let tmp_dir = tempfile::TempDir::new()?;
+                let bundle_path = tmp_dir.path().join("import.bundle");
+                std::fs::write(&bundle_path, contents)?;
+                git.fetch_bundle(worktree_path, &bundle_path, "HEAD")?;
+                git.reset_hard(worktree_path, "FETCH_HEAD")?;
+            }
+            ExportFormat::Mbox => {
+                if options.identity.is_none() {
+                    self.ensure_cli_commit_identity(worktree_path)?;
+                }
+                git.am(worktree_path, contents, &options.cli_config_args())?;
+            }
+            ExportFormat::Diff => {
+                git.apply_to_index(worktree_path, contents)?;
+                self.commit_with_options(worktree_path, diff_message, options)?;
+            }
+        }
+        Ok(format)
+    }
+
+    /// Files and hunks of the changes in `scope`
+    pub fn list_hunks(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        scope: HunkScope,
+    ) -> Result<Vec<FileHunks>, GitServiceError> {
+        let git = GitCli::new();
+        let patch = match scope {
+            HunkScope::Uncommitted => git.unstaged_patch(worktree_path)?,
+            HunkScope::Staged => git.staged_patch(worktree_path)?,
+            HunkScope::Committed => git.committed_patch(worktree_path, &base_commit.to_string())?,
+        };
+        Ok(hunks::parse_patch(&patch))
+    }
+
+    /// Stage the selected unstaged hunks, marking them as accepted. Commits
+    /// still include all changes. Returns the staged patch.
+    pub fn stage_hunks(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        selection: &[HunkSelection],
+    ) -> Result<String, GitServiceError> {
+        let patch = self.selected_patch(
+            worktree_path,
+            base_commit,
+            HunkScope::Uncommitted,
+            selection,
+        )?;
+        GitCli::new()
+            .apply_patch(worktree_path, patch.as_bytes(), &["--cached"])
+            .map_err(Self::patch_failed)?;
+        Ok(patch)
+    }
+
+    /// Move the selected staged hunks back to the unstaged changes. Returns
+    /// the unstaged patch.
+    pub fn unstage_hunks(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        selection: &[HunkSelection],
+    ) -> Result<String, GitServiceError> {
+        let patch =
+            self.selected_patch(worktree_path, base_commit, HunkScope::Staged, selection)?;
+        GitCli::new()
+            .apply_patch(worktree_path, patch.as_bytes(), &["-R", "--cached"])
+            .map_err(Self::patch_failed)?;
+        Ok(patch)
+    }
+
+    /// Discard the selected unstaged hunks from the worktree, or the selected
+    /// staged hunks from both the index and the worktree. Returns the
+    /// discarded patch.
+    pub fn discard_hunks(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        scope: HunkScope,
+        selection: &[HunkSelection],
+    ) -> Result<String, GitServiceError> {
+        let flags: &[&str] = match scope {
+            HunkScope::Uncommitted => &["-R"],
+            HunkScope::Staged => &["-R", "--index"],
+            HunkScope::Committed => {
+                return Err(GitServiceError::PatchFailed(
+                    "Committed hunks are reverted, not discarded".to_string(),
+                ));
+            }
+        };
+        let patch = self.selected_patch(worktree_path, base_commit, scope, selection)?;
+        let git = GitCli::new();
+        if scope == HunkScope::Staged {
+            // Entries staged from a patch carry no stat data, and `--index`
+            // takes them for out of date until the index is refreshed
+            let _ = git.git(worktree_path, ["update-index", "-q", "--refresh"]);
+        }
+        git.apply_patch(worktree_path, patch.as_bytes(), flags)
+            .map_err(Self::patch_failed)?;
+        Ok(patch)
+    }
+
+    /// Revert the selected hunks committed since `base_commit` in a new commit.
+    /// Tracked files must be clean. Returns the new HEAD commit and the
+    /// reverted patch.
+    pub fn revert_hunks(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        selection: &[HunkSelection],
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<(String, String), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+        let patch =
+            self.selected_patch(worktree_path, base_commit, HunkScope::Committed, selection)?;
+
+        let git = GitCli::new();
+        git.apply_patch(worktree_path, patch.as_bytes(), &["-R", "--index"])
+            .map_err(Self::patch_failed)?;
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
+        // Commit only the index, so untracked files stay out of the revert
+        let message = options.message_with_trailers(message);
+        let config = options.cli_config_args();
+        let skip_hooks = options.hooks == CommitHooks::Skip;
+        let has_hooks = !skip_hooks && git.has_commit_hooks(worktree_path);
+        let commit = || git.commit_with_output(worktree_path, &message, &config, skip_hooks);
+        let mut result = commit();
+        if matches!(result, Err(GitCliError::CommandFailed(_)))
+            && has_hooks
+            && options.hooks == CommitHooks::RunAndRetry
+        {
+            // Re-stage tracked files the hooks rewrote, still leaving untracked
+            // files out
+            result = git.add_tracked(worktree_path).and_then(|()| commit());
+        }
+        if let Err(e) = result {
+            if let Err(undo) = git.apply_patch(worktree_path, patch.as_bytes(), &["--index"]) {
+                tracing::warn!("Failed to restore hunks after a failed revert commit: {undo}");
+            }
+            return Err(match e {
+                GitCliError::CommandFailed(output) if has_hooks => {
+                    GitServiceError::CommitHooksFailed(output)
+                }
+                e => GitServiceError::InvalidRepository(format!("git commit failed: {e}")),
+            });
+        }
+
+        let head = repo.head()?.peel_to_commit()?;
+        Ok((head.id().to_string(), patch))
+    }
+
+    fn selected_patch(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        scope: HunkScope,
+        selection: &[HunkSelection],
+    ) -> Result<String, GitServiceError> {
+        let files = self.list_hunks(worktree_path, base_commit, scope)?;
+        let patch = hunks::select_patch(&files, selection).map_err(GitServiceError::PatchFailed)?;
+        if patch.is_empty() {
+            return Err(GitServiceError::PatchFailed(
+                "No hunks selected".to_string(),
+            ));
+        }
+        Ok(patch)
+    }
+
+    fn patch_failed(e: GitCliError) -> GitServiceError {
+        match e {
+            GitCliError::CommandFailed(output) => GitServiceError::PatchFailed(output),
+            e => GitServiceError::GitCLI(e),
+        }
+    }
+
+    /// Squash, reword or drop commits since `base_commit` with a non-interactive
+    /// rebase in the worktree, committing and signing as `options` requires.
+    /// Returns the new HEAD commit.
+    pub fn rewrite_history(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        rewrite: &HistoryRewrite,
+        options: &CommitOptions,
+    ) -> Result<String, GitServiceError> {
+        let worktree_repo = Repository::open(worktree_path)?;
+        self.check_worktree_clean(&worktree_repo)?;
+
+        let git = GitCli::new();
+        if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
+            return Err(GitServiceError::RebaseInProgress);
+        }
+
+        let mut commits = self.list_commits(worktree_path, base_commit)?;
+        if commits.is_empty() {
+            return Err(GitServiceError::InvalidRepository(
+                "No commits since the base commit".to_string(),
+            ));
+        }
+        for commit in &commits {
+            let oid = git2::Oid::from_str(&commit.sha)?;
+            if worktree_repo.find_commit(oid)?.parent_count() > 1 {
+                return Err(GitServiceError::InvalidRepository(
+                    "Cannot rewrite history that contains merge commits".to_string(),
+                ));
+            }
+        }
+        commits.reverse();
+        let (todo, message) = history::build_rebase_todo(&commits, rewrite)
+            .map_err(GitServiceError::InvalidRepository)?;
+
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
+        match git.rebase_with_todo(
+            worktree_path,
+            &base_commit.to_string(),
+            &todo,
+            message.as_deref(),
+            &options.cli_config_args(),
+        ) {
+            Ok(()) => {}
+            Err(GitCliError::RebaseInProgress) => return Err(GitServiceError::RebaseInProgress),
+            Err(GitCliError::CommandFailed(stderr)) => {
+                if let Some(err) = Self::rebase_conflicts_error(
+                    &git,
+                    worktree_path,
+                    &stderr,
+                    "while rewriting the branch history",
+                ) {
+                    return Err(err);
+                }
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "History rewrite failed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )));
+            }
+            Err(e) => return Err(GitServiceError::GitCLI(e)),
+        }
+
+        let head = worktree_repo.head()?.peel_to_commit()?;
+        Ok(head.id().to_string())
+    }
+
     pub fn find_branch_type(
         &self,
         repo_path: &Path,
@@ -1511,6 +2525,49 @@ impl GitService {
         }
     }
 
+    /// Whether a new branch named `branch_name` would clash with an existing
+    /// one: the same name, or a name that is a directory or file of it in the
+    /// ref namespace, like `vk` and `vk/fix`. Remote-tracking branches are
+    /// compared without their remote name, since the push would clash there.
+    pub fn branch_name_conflicts(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<bool, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote_prefixes: Vec<String> = repo
+            .remotes()?
+            .iter()
+            .flatten()
+            .map(|remote| format!("{remote}/"))
+            .collect();
+        for branch in repo.branches(None)? {
+            let (branch, branch_type) = branch?;
+            let Some(name) = branch.name()? else {
+                continue;
+            };
+            let name = match branch_type {
+                BranchType::Local => name,
+                BranchType::Remote => {
+                    match remote_prefixes
+                        .iter()
+                        .find_map(|prefix| name.strip_prefix(prefix.as_str()))
+                    {
+                        Some("HEAD") | None => continue,
+                        Some(name) => name,
+                    }
+                }
+            };
+            if name == branch_name
+                || name.starts_with(&format!("{branch_name}/"))
+                || branch_name.starts_with(&format!("{name}/"))
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     pub fn rename_local_branch(
         &self,
         worktree_path: &Path,
@@ -1572,6 +2629,100 @@ impl GitService {
         })
     }
 
+    /// Read each conflicted file and split its conflict markers into hunks.
+    pub fn get_conflicted_file_details(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<ConflictedFile>, GitServiceError> {
+        let files = self.get_conflicted_files(worktree_path)?;
+        Ok(files
+            .into_iter()
+            .map(|path| {
+                let hunks = std::fs::read(worktree_path.join(&path))
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .map(|content| parse_conflict_hunks(&content))
+                    .unwrap_or_default();
+                ConflictedFile { path, hunks }
+            })
+            .collect())
+    }
+
+    /// Return the subset of `paths` whose working-tree content still has conflict markers.
+    pub fn files_with_conflict_markers(
+        &self,
+        worktree_path: &Path,
+        paths: &[String],
+    ) -> Vec<String> {
+        paths
+            .iter()
+            .filter(|path| {
+                std::fs::read(worktree_path.join(path))
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .is_some_and(|content| has_conflict_markers(&content))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Stage the resolved files and continue the in-progress rebase, merge,
+    /// cherry-pick, or revert. Refuses to continue while conflict markers remain.
+    /// Returns the operation that was continued, or None if nothing was in progress.
+    /// Commits it writes are committed and signed as `options` requires.
+    pub fn complete_conflict_resolution(
+        &self,
+        worktree_path: &Path,
+        options: &CommitOptions,
+    ) -> Result<Option<ConflictOp>, GitServiceError> {
+        let Some(op) = self.detect_conflict_op(worktree_path)? else {
+            return Ok(None);
+        };
+
+        let conflicted_files = self.get_conflicted_files(worktree_path)?;
+        let unresolved = self.files_with_conflict_markers(worktree_path, &conflicted_files);
+        if !unresolved.is_empty() {
+            return Err(GitServiceError::MergeConflicts {
+                message: format!(
+                    "Conflict markers remain in {} file(s); resolve them before continuing",
+                    unresolved.len()
+                ),
+                conflicted_files: unresolved,
+            });
+        }
+
+        let git = GitCli::new();
+        git.add_all(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
+        let config = options.cli_config_args();
+        let result = match op {
+            ConflictOp::Rebase => git.continue_rebase(worktree_path, &config),
+            ConflictOp::Merge => git.continue_merge(worktree_path, &config),
+            ConflictOp::CherryPick => git.continue_cherry_pick(worktree_path, &config),
+            ConflictOp::Revert => git.continue_revert(worktree_path, &config),
+        };
+
+        if let Err(e) = result {
+            // A rebase may stop again on a later commit with fresh conflicts
+            let conflicted_files = self.get_conflicted_files(worktree_path).unwrap_or_default();
+            if !conflicted_files.is_empty() {
+                return Err(GitServiceError::MergeConflicts {
+                    message: format!("Continuing stopped on new conflicts: {e}"),
+                    conflicted_files,
+                });
+            }
+            return Err(GitServiceError::InvalidRepository(format!(
+                "failed to continue after conflict resolution: {e}"
+            )));
+        }
+
+        Ok(Some(op))
+    }
+
     /// Abort an in-progress rebase in this worktree (no-op if none).
     pub fn abort_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
@@ -1580,12 +2731,21 @@ impl GitService {
         })
     }
 
-    /// Continue an in-progress rebase. Fails if there are unresolved conflicts.
-    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+    /// Continue an in-progress rebase, committing and signing as `options`
+    /// requires. Fails if there are unresolved conflicts.
+    pub fn continue_rebase(
+        &self,
+        worktree_path: &Path,
+        options: &CommitOptions,
+    ) -> Result<(), GitServiceError> {
+        if options.identity.is_none() {
+            self.ensure_cli_commit_identity(worktree_path)?;
+        }
         let git = GitCli::new();
-        git.continue_rebase(worktree_path).map_err(|e| {
-            GitServiceError::InvalidRepository(format!("git rebase --continue failed: {e}"))
-        })
+        git.continue_rebase(worktree_path, &options.cli_config_args())
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git rebase --continue failed: {e}"))
+            })
     }
 
     pub fn abort_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
@@ -1713,6 +2873,22 @@ impl GitService {
             .map_err(GitServiceError::from)
     }
 
+    /// Fetch a pull request from `pr_ref` on the remote into `branch_name` and
+    /// check it out in the worktree
+    pub fn checkout_pull_request(
+        &self,
+        worktree_path: &Path,
+        remote_url: &str,
+        pr_ref: &str,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let git_cli = GitCli::new();
+        let refspec = format!("+{pr_ref}:refs/heads/{branch_name}");
+        git_cli.fetch_with_refspec(worktree_path, remote_url, &refspec)?;
+        git_cli.git(worktree_path, ["checkout", branch_name])?;
+        Ok(())
+    }
+
     pub fn resolve_remote_for_branch(
         &self,
         repo_path: &Path,
@@ -0,0 +1,239 @@
+//! Identity, `Co-authored-by` trailers, GPG/SSH signing and hook handling for
+//! commits created by Vibe Kanban, shared by the git CLI and libgit2 commit
+//! paths.
+
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use git2::Repository;
+use utils::shell::resolve_executable_path_blocking;
+
+use crate::GitServiceError;
+
+/// Identity used when neither the options nor the repository configure one
+pub const FALLBACK_COMMIT_NAME: &str = "Vibe Kanban";
+pub const FALLBACK_COMMIT_EMAIL: &str = "noreply@vibekanban.com";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+impl CommitIdentity {
+    pub fn new(name: impl Into<String>, email: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            email: email.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningFormat {
+    Gpg,
+    Ssh,
+}
+
+/// Whether the repository's `pre-commit` and `commit-msg` hooks run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CommitHooks {
+    #[default]
+    Run,
+    /// Commit with `--no-verify`
+    Skip,
+    /// When the hooks reject the commit, re-stage the files they rewrote (e.g.
+    /// formatters) and try once more
+    RunAndRetry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSigning {
+    pub format: SigningFormat,
+    /// GPG key id, or SSH key path / literal public key. Falls back to the
+    /// repository's `user.signingkey`.
+    pub key: Option<String>,
+}
+
+/// How commits created by Vibe Kanban are authored and signed
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Author and committer; the repository's configured identity when `None`
+    pub identity: Option<CommitIdentity>,
+    /// Credited with `Co-authored-by` trailers
+    pub co_authors: Vec<CommitIdentity>,
+    pub signing: Option<CommitSigning>,
+    /// Only honored by the git CLI commit path
+    pub hooks: CommitHooks,
+}
+
+impl CommitOptions {
+    /// `message` with a `Co-authored-by` trailer for every co-author it does
+    /// not already credit
+    pub fn message_with_trailers(&self, message: &str) -> String {
+        let trailers: Vec<String> = self
+            .co_authors
+            .iter()
+            .map(|author| format!("Co-authored-by: {} <{}>", author.name, author.email))
+            .filter(|trailer| !message.contains(trailer.as_str()))
+            .collect();
+        if trailers.is_empty() {
+            return message.to_string();
+        }
+
+        let message = message.trim_end();
+        // Extend an existing trailer block instead of starting another paragraph
+        let extends_trailers = message
+            .rsplit_once("\n\n")
+            .is_some_and(|(_, last)| last.lines().all(is_trailer));
+        let separator = if extends_trailers { "\n" } else { "\n\n" };
+        format!("{message}{separator}{}", trailers.join("\n"))
+    }
+
+    /// `-c key=value` arguments applying the identity and signing to a git CLI command
+    pub(crate) fn cli_config_args(&self) -> Vec<String> {
+        let mut config = Vec::new();
+        if let Some(identity) = &self.identity {
+            config.push(format!("user.name={}", identity.name));
+            config.push(format!("user.email={}", identity.email));
+        }
+        if let Some(signing) = &self.signing {
+            config.push("commit.gpgsign=true".to_string());
+            config.push(format!(
+                "gpg.format={}",
+                match signing.format {
+                    SigningFormat::Gpg => "openpgp",
+                    SigningFormat::Ssh => "ssh",
+                }
+            ));
+            if let Some(key) = signing.key.as_deref().filter(|k| !k.trim().is_empty()) {
+                config.push(format!("user.signingkey={key}"));
+            }
+        }
+        config
+            .into_iter()
+            .flat_map(|c| ["-c".to_string(), c])
+            .collect()
+    }
+}
+
+impl CommitSigning {
+    /// Armored detached signature of a commit buffer, for the `gpgsig` header.
+    /// Honors `gpg.program` and `gpg.ssh.program` like `git commit -S` does.
+    pub(crate) fn sign(&self, repo: &Repository, content: &str) -> Result<String, GitServiceError> {
+        let config = repo.config()?;
+        let key = self
+            .key
+            .clone()
+            .filter(|k| !k.trim().is_empty())
+            .or_else(|| config.get_string("user.signingkey").ok());
+
+        match self.format {
+            SigningFormat::Gpg => {
+                let program = config
+                    .get_string("gpg.program")
+                    .unwrap_or_else(|_| "gpg".to_string());
+                let mut args = vec!["--status-fd=2".to_string(), "-bsa".to_string()];
+                if let Some(key) = key {
+                    args.extend(["-u".to_string(), key]);
+                }
+                run_signer(&program, &args, content)
+            }
+            SigningFormat::Ssh => {
+                let program = config
+                    .get_string("gpg.ssh.program")
+                    .unwrap_or_else(|_| "ssh-keygen".to_string());
+                let key = key.ok_or_else(|| {
+                    GitServiceError::SigningFailed("SSH signing requires a signing key".to_string())
+                })?;
+
+                // Literal public keys go through a temporary file; ssh-keygen
+                // then signs with the matching key from the agent
+                let literal = key
+                    .strip_prefix("key::")
+                    .or_else(|| key.starts_with("ssh-").then_some(key.as_str()));
+                let literal_file = literal
+                    .map(|literal| {
+                        let mut file = tempfile::NamedTempFile::new()?;
+                        file.write_all(literal.as_bytes())?;
+                        Ok::<_, std::io::Error>(file)
+                    })
+                    .transpose()?;
+                let key_path = match &literal_file {
+                    Some(file) => file.path().to_path_buf(),
+                    None => expand_home(&key),
+                };
+
+                let args = ["-Y", "sign", "-n", "git", "-f"]
+                    .into_iter()
+                    .map(str::to_string)
+                    .chain([key_path.to_string_lossy().into_owned()])
+                    .collect::<Vec<_>>();
+                run_signer(&program, &args, content)
+            }
+        }
+    }
+}
+
+fn is_trailer(line: &str) -> bool {
+    line.split_once(": ").is_some_and(|(token, _)| {
+        !token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn run_signer(program: &str, args: &[String], content: &str) -> Result<String, GitServiceError> {
+    let executable = resolve_executable_path_blocking(program)
+        .ok_or_else(|| GitServiceError::SigningFailed(format!("{program} not found")))?;
+    let mut child = Command::new(executable)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        return Err(GitServiceError::SigningFailed(format!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )));
+    }
+    String::from_utf8(out.stdout)
+        .map_err(|_| GitServiceError::SigningFailed(format!("{program} returned invalid output")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailers_extend_existing_block_and_skip_duplicates() {
+        let options = CommitOptions {
+            co_authors: vec![CommitIdentity::new("Claude Code", "noreply@vibekanban.com")],
+            ..Default::default()
+        };
+        assert_eq!(
+            options.message_with_trailers("feat: add x\n"),
+            "feat: add x\n\nCo-authored-by: Claude Code <noreply@vibekanban.com>"
+        );
+        assert_eq!(
+            options.message_with_trailers("feat: add x\n\nbody\n\nSigned-off-by: A <a@b.c>"),
+            "feat: add x\n\nbody\n\nSigned-off-by: A <a@b.c>\n\
+             Co-authored-by: Claude Code <noreply@vibekanban.com>"
+        );
+        let credited = "fix: y\n\nCo-authored-by: Claude Code <noreply@vibekanban.com>";
+        assert_eq!(options.message_with_trailers(credited), credited);
+    }
+}
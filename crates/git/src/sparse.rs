@@ -0,0 +1,120 @@
+/// Directories materialized by a cone-mode sparse checkout.
+///
+/// Cone mode always includes files at the repository root and files directly
+/// inside every ancestor of a listed directory, plus everything below the
+/// listed directories themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseCone {
+    dirs: Vec<String>,
+}
+
+impl SparseCone {
+    /// Parse a newline- or comma-separated list of directories.
+    /// Returns `None` when no directories are listed, i.e. a full checkout.
+    pub fn parse(patterns: &str) -> Option<Self> {
+        Self::from_dirs(patterns.split([',', '\n']))
+    }
+
+    pub fn from_dirs<I, S>(dirs: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = Vec::new();
+        for dir in dirs {
+            let dir = dir
+                .as_ref()
+                .trim()
+                .trim_start_matches("./")
+                .trim_matches('/')
+                .replace('\\', "/");
+            if !dir.is_empty() && !normalized.contains(&dir) {
+                normalized.push(dir);
+            }
+        }
+        (!normalized.is_empty()).then_some(Self { dirs: normalized })
+    }
+
+    pub fn dirs(&self) -> &[String] {
+        &self.dirs
+    }
+
+    /// Serialize back to the newline-separated form stored in the database
+    pub fn to_patterns(&self) -> String {
+        self.dirs.join("\n")
+    }
+
+    /// Return a cone that also includes `extra` directories
+    pub fn widen<I, S>(&self, extra: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let dirs = self
+            .dirs
+            .iter()
+            .cloned()
+            .chain(extra.into_iter().map(|d| d.as_ref().to_string()));
+        Self::from_dirs(dirs).unwrap_or_else(|| self.clone())
+    }
+
+    /// Return true if a repository-relative file path is checked out by this cone
+    pub fn contains_file(&self, path: &str) -> bool {
+        match path.rsplit_once('/') {
+            // Files at the root are always included
+            None => true,
+            Some((parent, _)) => self.contains_dir(parent),
+        }
+    }
+
+    /// Return true if a repository-relative directory is (at least partly) checked out
+    pub fn contains_dir(&self, path: &str) -> bool {
+        let path = path.trim_matches('/');
+        path.is_empty()
+            || self.is_ancestor_of_cone(path)
+            || self
+                .dirs
+                .iter()
+                .any(|dir| path == dir || is_below(path, dir))
+    }
+
+    fn is_ancestor_of_cone(&self, path: &str) -> bool {
+        self.dirs.iter().any(|dir| is_below(dir, path))
+    }
+}
+
+fn is_below(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_normalizes_directories() {
+        let cone = SparseCone::parse("packages/web/\n ./libs/core , packages/web\n").unwrap();
+        assert_eq!(cone.dirs(), ["packages/web", "libs/core"]);
+        assert_eq!(SparseCone::parse(" \n, "), None);
+    }
+
+    #[test]
+    fn matches_cone_mode_semantics() {
+        let cone = SparseCone::parse("packages/web").unwrap();
+        assert!(cone.contains_file("README.md"));
+        assert!(cone.contains_file("packages/package.json"));
+        assert!(cone.contains_file("packages/web/src/index.ts"));
+        assert!(!cone.contains_file("packages/api/src/main.rs"));
+        assert!(!cone.contains_file("docs/guide.md"));
+        assert!(cone.contains_dir("packages"));
+        assert!(!cone.contains_dir("packages/webapp"));
+        assert!(!cone.contains_dir("docs"));
+    }
+
+    #[test]
+    fn widen_adds_new_directories_once() {
+        let cone = SparseCone::parse("a").unwrap().widen(["b", "a/"]);
+        assert_eq!(cone.to_patterns(), "a\nb");
+    }
+}
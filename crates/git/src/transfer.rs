@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// How workspace changes are packaged to hand them over without a shared remote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// `git format-patch` mailbox, one message per commit
+    Mbox,
+    /// A single binary-safe unified diff of all commits
+    Diff,
+    /// A git bundle of the workspace branch since its base commit
+    Bundle,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Mbox => "mbox",
+            ExportFormat::Diff => "diff",
+            ExportFormat::Bundle => "bundle",
+        }
+    }
+
+    /// Guess the format of an uploaded export from its contents.
+    pub fn detect(contents: &[u8]) -> Self {
+        if contents.starts_with(b"# v2 git bundle") || contents.starts_with(b"# v3 git bundle") {
+            ExportFormat::Bundle
+        } else if contents.starts_with(b"From ") {
+            ExportFormat::Mbox
+        } else {
+            ExportFormat::Diff
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_format_from_contents() {
+        assert_eq!(
+            ExportFormat::detect(b"# v2 git bundle\nabc HEAD\n"),
+            ExportFormat::Bundle
+        );
+        assert_eq!(
+            ExportFormat::detect(b"From 1234 Mon Sep 17 00:00:00 2001\n"),
+            ExportFormat::Mbox
+        );
+        assert_eq!(
+            ExportFormat::detect(b"diff --git a/x b/x\n"),
+            ExportFormat::Diff
+        );
+    }
+}
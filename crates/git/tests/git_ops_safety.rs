@@ -4,7 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use git::{GitCli, GitCliError, GitService};
+use git::{CommitOptions, GitCli, GitCliError, GitService};
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
@@ -420,6 +420,7 @@ fn rebase_preserves_untracked_files() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_ok(), "rebase should succeed: {res:?}");
 
@@ -442,6 +443,7 @@ fn rebase_aborts_on_uncommitted_tracked_changes() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_err(), "rebase should fail on dirty worktree");
 
@@ -463,6 +465,7 @@ fn rebase_aborts_if_untracked_would_be_overwritten_by_base() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(
         res.is_err(),
@@ -697,6 +700,7 @@ fn rebase_refuses_to_abort_existing_rebase() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect_err("first rebase should error and leave in-progress state");
 
@@ -708,11 +712,60 @@ fn rebase_refuses_to_abort_existing_rebase() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_err(), "should error because rebase is in progress");
     // Note: We do not auto-abort; user should resolve or abort explicitly
 }
 
+#[test]
+fn conflict_resolution_continues_rebase_once_markers_are_gone() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    let svc = GitService::new();
+    let _ = svc
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            &CommitOptions::default(),
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    let details = svc.get_conflicted_file_details(&worktree_path).unwrap();
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].path, "conflict.txt");
+    assert_eq!(details[0].hunks.len(), 1);
+    assert_eq!(details[0].hunks[0].ours, "new-base version\n");
+    assert_eq!(details[0].hunks[0].theirs, "feature version\n");
+
+    // Markers still present: refuse to continue and keep the rebase in progress
+    let err = svc
+        .complete_conflict_resolution(&worktree_path, &CommitOptions::default())
+        .expect_err("should refuse while markers remain");
+    assert!(matches!(err, git::GitServiceError::MergeConflicts { .. }));
+    assert!(svc.is_rebase_in_progress(&worktree_path).unwrap());
+
+    write_file(&worktree_path, "conflict.txt", "resolved version\n");
+    let op = svc
+        .complete_conflict_resolution(&worktree_path, &CommitOptions::default())
+        .unwrap();
+    assert_eq!(op, Some(git::ConflictOp::Rebase));
+    assert!(!svc.is_rebase_in_progress(&worktree_path).unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "resolved version\n"
+    );
+    let head = svc.get_head_info(&worktree_path).unwrap();
+    assert_eq!(
+        svc.get_commit_subject(&worktree_path, &head.oid).unwrap(),
+        "feature conflicting change"
+    );
+}
+
 #[test]
 fn rebase_fast_forwards_when_no_unique_commits() {
     let td = TempDir::new().unwrap();
@@ -728,6 +781,7 @@ fn rebase_fast_forwards_when_no_unique_commits() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
     let after_oid = g.get_head_info(&worktree_path).unwrap().oid;
@@ -759,6 +813,7 @@ fn rebase_applies_multiple_commits_onto_ahead_base() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
 
@@ -904,6 +959,7 @@ fn rebase_preserves_rename_changes() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
     // after rebase, renamed file present; original absent
@@ -7,7 +7,7 @@ use std::{
 use git::{DiffTarget, GitCli, GitService};
 use git2::{Repository, build::CheckoutBuilder};
 use tempfile::TempDir;
-use utils::diff::DiffChangeKind;
+use utils::diff::{DiffChangeKind, DiffViewOptions};
 
 fn add_path(repo_path: &Path, path: &str) {
     let git = GitCli::new();
@@ -395,6 +395,168 @@ fn worktree_diff_respects_path_filter() {
     );
 }
 
+#[test]
+fn worktree_diff_view_options_ignore_whitespace_and_tune_renames() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+
+    write_file(&repo_path, "fmt.rs", "fn a() {\n  b();\n}\n");
+    write_file(&repo_path, "old.txt", "one\ntwo\nthree\nfour\n");
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+    create_branch(&repo_path, "feature");
+
+    // Reformat one file, move and lightly edit another
+    write_file(&repo_path, "fmt.rs", "fn a() {\n    b();\n}\n");
+    fs::remove_file(repo_path.join("old.txt")).unwrap();
+    write_file(&repo_path, "new.txt", "one\ntwo\nthree\nFOUR\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs_with = |options: DiffViewOptions| {
+        s.get_diffs_with_options(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+            &options,
+        )
+        .unwrap()
+    };
+
+    let default = diffs_with(DiffViewOptions::default());
+    assert!(
+        default
+            .iter()
+            .any(|d| d.new_path.as_deref() == Some("fmt.rs"))
+    );
+    assert!(
+        default
+            .iter()
+            .any(|d| matches!(d.change, DiffChangeKind::Renamed))
+    );
+
+    let tuned = diffs_with(DiffViewOptions {
+        ignore_whitespace: true,
+        rename_threshold: Some(90),
+        ..Default::default()
+    });
+    assert!(
+        !tuned
+            .iter()
+            .any(|d| d.new_path.as_deref() == Some("fmt.rs"))
+    );
+    assert!(
+        !tuned
+            .iter()
+            .any(|d| matches!(d.change, DiffChangeKind::Renamed))
+    );
+    assert_eq!(tuned.len(), 2);
+}
+
+#[test]
+fn uses_lfs_detects_nested_gitattributes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let git = GitCli::new();
+
+    write_file(&repo_path, "README.md", "readme\n");
+    let _ = s.commit(&repo_path, "readme").unwrap();
+    assert!(!git.uses_lfs(&repo_path));
+
+    write_file(
+        &repo_path,
+        "assets/.gitattributes",
+        "*.bin filter=lfs diff=lfs\n",
+    );
+    let _ = s.commit(&repo_path, "track assets with lfs").unwrap();
+    assert!(git.uses_lfs(&repo_path));
+}
+
+#[test]
+fn branch_name_conflicts_detects_ref_directory_clashes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "README.md", "readme\n");
+    let _ = s.commit(&repo_path, "readme").unwrap();
+    create_branch(&repo_path, "vk");
+    create_branch(&repo_path, "feature/login/ui");
+
+    assert!(s.branch_name_conflicts(&repo_path, "vk").unwrap());
+    assert!(s.branch_name_conflicts(&repo_path, "vk/abcd-fix").unwrap());
+    assert!(
+        s.branch_name_conflicts(&repo_path, "feature/login")
+            .unwrap()
+    );
+    assert!(!s.branch_name_conflicts(&repo_path, "vk-2").unwrap());
+    assert!(!s.branch_name_conflicts(&repo_path, "feature/log").unwrap());
+}
+
+#[test]
+fn submodule_pointer_changes_render_as_subproject_commits() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    // Nested repository recorded as a gitlink
+    let sub_path = repo_path.join("sub");
+    s.initialize_repo_with_main_branch(&sub_path).unwrap();
+    configure_user(&sub_path, "Test User", "test@example.com");
+    let old_sub_head = s.get_head_info(&sub_path).unwrap().oid;
+    add_path(&repo_path, "sub");
+    let _ = s.commit(&repo_path, "add submodule").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+
+    // Move the submodule forward without committing in the superproject
+    write_file(&sub_path, "lib.txt", "lib\n");
+    let _ = s.commit(&sub_path, "sub change").unwrap();
+    let new_sub_head = s.get_head_info(&sub_path).unwrap().oid;
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    let diff = &diffs[0];
+    assert_eq!(diff.new_path.as_deref(), Some("sub"));
+    assert_eq!(
+        diff.old_content.as_deref(),
+        Some(format!("Subproject commit {old_sub_head}\n").as_str())
+    );
+    assert_eq!(
+        diff.new_content.as_deref(),
+        Some(format!("Subproject commit {new_sub_head}\n").as_str())
+    );
+    assert_eq!((diff.additions, diff.deletions), (Some(1), Some(1)));
+
+    // Committed pointer updates render the same way between branches
+    let _ = s.commit(&repo_path, "bump submodule").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Branch {
+                repo_path: Path::new(&repo_path),
+                branch_name: "feature",
+                base_branch: "main",
+            },
+            None,
+        )
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(
+        diffs[0].new_content.as_deref(),
+        Some(format!("Subproject commit {new_sub_head}\n").as_str())
+    );
+}
+
 #[test]
 fn get_branch_oid_nonexistent_errors() {
     let td = TempDir::new().unwrap();
@@ -463,6 +625,738 @@ fn worktree_diff_permission_only_change() {
     assert_eq!(d.old_content, d.new_content);
 }
 
+#[test]
+fn sparse_worktree_checks_out_cone_and_widens_on_demand() {
+    use git::SparseCone;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "README.md", "root\n");
+    write_file(&repo_path, "apps/web/index.ts", "web\n");
+    write_file(&repo_path, "apps/api/main.rs", "api\n");
+    write_file(&repo_path, "docs/guide.md", "guide\n");
+    let _ = s.commit(&repo_path, "monorepo").unwrap();
+    create_branch(&repo_path, "feature");
+
+    let worktree_path = td.path().join("wt_sparse");
+    let cone = SparseCone::parse("apps/web").unwrap();
+    s.add_worktree_with_sparse_cone(&repo_path, &worktree_path, "feature", false, Some(&cone))
+        .unwrap();
+    assert!(worktree_path.join("README.md").exists());
+    assert!(worktree_path.join("apps/web/index.ts").exists());
+    assert!(!worktree_path.join("apps/api").exists());
+    assert!(!worktree_path.join("docs").exists());
+    assert_eq!(s.get_sparse_cone(&worktree_path).unwrap(), Some(cone));
+
+    // Files outside the cone neither show up as deleted nor break diffs and commits
+    write_file(&worktree_path, "apps/web/index.ts", "web v2\n");
+    write_file(&worktree_path, "docs/new.md", "new\n");
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    let mut paths: Vec<_> = diffs.iter().filter_map(|d| d.new_path.clone()).collect();
+    paths.sort();
+    assert_eq!(paths, ["apps/web/index.ts", "docs/new.md"]);
+    assert!(
+        !diffs
+            .iter()
+            .any(|d| matches!(d.change, DiffChangeKind::Deleted))
+    );
+    assert!(s.commit(&worktree_path, "edit").unwrap());
+    assert!(s.is_worktree_clean(&worktree_path).unwrap());
+
+    let widened = s
+        .widen_sparse_checkout(&worktree_path, &["docs".to_string()])
+        .unwrap()
+        .unwrap();
+    assert_eq!(widened.dirs(), ["apps/web", "docs"]);
+    assert!(worktree_path.join("docs/guide.md").exists());
+    assert!(worktree_path.join("docs/new.md").exists());
+    assert!(!worktree_path.join("apps/api").exists());
+}
+
+#[test]
+fn rewrite_history_rewords_squashes_and_drops_commits() {
+    use git::{CommitOptions, HistoryRewrite};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_history");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+
+    for (file, msg) in [
+        ("a.txt", "add a"),
+        ("b.txt", "add b"),
+        ("c.txt", "add c"),
+        ("d.txt", "add d"),
+    ] {
+        write_file(&worktree_path, file, &format!("{file}\n"));
+        assert!(s.commit(&worktree_path, msg).unwrap());
+    }
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let subjects = |s: &GitService| -> Vec<String> {
+        s.list_commits(&worktree_path, &base_commit)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.subject)
+            .collect()
+    };
+
+    let commits = s.list_commits(&worktree_path, &base_commit).unwrap();
+    assert_eq!(subjects(&s), ["add d", "add c", "add b", "add a"]);
+    assert_eq!((commits[0].files_changed, commits[0].additions), (1, 1));
+    assert_eq!(commits[0].author_email, "test@example.com");
+
+    let oldest = commits[3].sha.clone();
+    s.rewrite_history(
+        &worktree_path,
+        &base_commit,
+        &HistoryRewrite::Reword {
+            commit: commits[2].sha.clone(),
+            message: "#42 add b, reworded".to_string(),
+        },
+        &CommitOptions::default(),
+    )
+    .unwrap();
+    let commits = s.list_commits(&worktree_path, &base_commit).unwrap();
+    assert_eq!(
+        subjects(&s),
+        ["add d", "add c", "#42 add b, reworded", "add a"]
+    );
+    // Commits before the rewritten one keep their hashes
+    assert_eq!(commits[3].sha, oldest);
+
+    s.rewrite_history(
+        &worktree_path,
+        &base_commit,
+        &HistoryRewrite::Squash {
+            from: commits[2].sha.clone(),
+            to: commits[0].sha.clone(),
+            message: Some("add b to d".to_string()),
+        },
+        &CommitOptions::default(),
+    )
+    .unwrap();
+    let commits = s.list_commits(&worktree_path, &base_commit).unwrap();
+    assert_eq!(subjects(&s), ["add b to d", "add a"]);
+    assert_eq!(commits[0].files_changed, 3);
+
+    s.rewrite_history(
+        &worktree_path,
+        &base_commit,
+        &HistoryRewrite::Drop {
+            commit: commits[1].sha.clone(),
+        },
+        &CommitOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(subjects(&s), ["add b to d"]);
+    assert!(!worktree_path.join("a.txt").exists());
+    assert!(worktree_path.join("d.txt").exists());
+    assert!(s.is_worktree_clean(&worktree_path).unwrap());
+}
+
+#[test]
+fn blame_since_attributes_changed_lines_to_commits_and_worktree() {
+    use git::{BlameRange, GitServiceError};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "notes.txt", "one\ntwo\nthree\n");
+    assert!(s.commit(&repo_path, "add notes").unwrap());
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_blame");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+
+    write_file(&worktree_path, "notes.txt", "one\nTWO\nthree\n");
+    assert!(s.commit(&worktree_path, "shout two").unwrap());
+    let head = s.get_head_info(&worktree_path).unwrap().oid;
+    write_file(&worktree_path, "notes.txt", "one\nTWO\nthree\nfour\n");
+    write_file(&worktree_path, "new.txt", "a\nb");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    assert_eq!(
+        s.blame_since(&worktree_path, &base_commit, "notes.txt")
+            .unwrap(),
+        [
+            BlameRange {
+                start_line: 2,
+                line_count: 1,
+                commit_sha: Some(head),
+            },
+            BlameRange {
+                start_line: 4,
+                line_count: 1,
+                commit_sha: None,
+            },
+        ]
+    );
+    assert_eq!(
+        s.blame_since(&worktree_path, &base_commit, "new.txt")
+            .unwrap(),
+        [BlameRange {
+            start_line: 1,
+            line_count: 2,
+            commit_sha: None,
+        }]
+    );
+    assert!(matches!(
+        s.blame_since(&worktree_path, &base_commit, "../repo/notes.txt"),
+        Err(GitServiceError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn export_and_import_changes_round_trip_each_format() {
+    use git::{CommitOptions, ExportFormat};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_export");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    write_file(&worktree_path, "a.txt", "a\n");
+    assert!(s.commit(&worktree_path, "add a").unwrap());
+    write_file(&worktree_path, "b.txt", "b\n");
+    assert!(s.commit(&worktree_path, "add b").unwrap());
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let head = s.get_head_info(&worktree_path).unwrap().oid;
+
+    for (i, format) in [ExportFormat::Mbox, ExportFormat::Diff, ExportFormat::Bundle]
+        .into_iter()
+        .enumerate()
+    {
+        let exported = s
+            .export_changes(&worktree_path, &base_commit, format)
+            .unwrap();
+        assert_eq!(ExportFormat::detect(&exported), format);
+
+        let branch = format!("import-{i}");
+        create_branch(&repo_path, &branch);
+        let import_path = td.path().join(&branch);
+        s.add_worktree(&repo_path, &import_path, &branch, false)
+            .unwrap();
+        let imported = s
+            .import_changes(
+                &import_path,
+                &exported,
+                "Apply imported diff",
+                &CommitOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(imported, format);
+        assert_eq!(
+            fs::read_to_string(import_path.join("b.txt")).unwrap(),
+            "b\n"
+        );
+
+        let subjects: Vec<String> = s
+            .list_commits(&import_path, &base_commit)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.subject)
+            .collect();
+        match format {
+            ExportFormat::Mbox => assert_eq!(subjects, ["add b", "add a"]),
+            ExportFormat::Diff => assert_eq!(subjects, ["Apply imported diff"]),
+            ExportFormat::Bundle => {
+                assert_eq!(s.get_head_info(&import_path).unwrap().oid, head)
+            }
+        }
+    }
+}
+
+#[test]
+fn archived_branches_restore_from_ref_and_bundle() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    create_branch(&repo_path, "merged");
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_archive");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    write_file(&worktree_path, "work.txt", "unmerged work\n");
+    assert!(s.commit(&worktree_path, "unmerged work").unwrap());
+
+    assert_eq!(
+        s.unmerged_branch_tip(&repo_path, "merged", "main").unwrap(),
+        None
+    );
+    assert_eq!(
+        s.unmerged_branch_tip(&repo_path, "missing", "main")
+            .unwrap(),
+        None
+    );
+    let tip = s
+        .unmerged_branch_tip(&repo_path, "feature", "main")
+        .unwrap()
+        .unwrap();
+
+    let archive_ref = format!("{}feature", git::ARCHIVE_REF_PREFIX);
+    let bundle_path = td.path().join("archives/feature.bundle");
+    s.write_ref(&repo_path, &archive_ref, &tip).unwrap();
+    s.write_branch_bundle(&repo_path, "feature", "main", &bundle_path)
+        .unwrap();
+    s.remove_worktree(&repo_path, &worktree_path, true).unwrap();
+    s.delete_branch(&repo_path, "feature").unwrap();
+
+    s.restore_branch(&repo_path, "feature", &tip, None).unwrap();
+    assert_eq!(s.get_branch_oid(&repo_path, "feature").unwrap(), tip);
+    // Restoring again is a no-op while the branch still points at the tip
+    s.restore_branch(&repo_path, "feature", &tip, None).unwrap();
+
+    s.delete_branch(&repo_path, "feature").unwrap();
+    s.restore_branch(&repo_path, "feature", &tip, Some(&bundle_path))
+        .unwrap();
+    assert_eq!(s.get_branch_oid(&repo_path, "feature").unwrap(), tip);
+    assert!(
+        s.restore_branch(&repo_path, "main", &tip, None).is_err(),
+        "an unrelated existing branch must not be moved"
+    );
+
+    s.delete_ref(&repo_path, &archive_ref).unwrap();
+    s.delete_ref(&repo_path, &archive_ref).unwrap();
+}
+
+#[test]
+fn backport_cherry_picks_branch_commits_and_stops_on_conflicts() {
+    use git::{CommitOptions, ConflictOp, GitServiceError};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "shared.txt", "base\n");
+    assert!(s.commit(&repo_path, "add shared").unwrap());
+    create_branch(&repo_path, "release");
+    create_branch(&repo_path, "feature");
+
+    let feature_wt = td.path().join("wt_feature");
+    s.add_worktree(&repo_path, &feature_wt, "feature", false)
+        .unwrap();
+    write_file(&feature_wt, "fix.txt", "fix\n");
+    assert!(s.commit(&feature_wt, "add fix").unwrap());
+    write_file(&feature_wt, "shared.txt", "fixed\n");
+    assert!(s.commit(&feature_wt, "update shared").unwrap());
+
+    let commits = s
+        .backport_commits(&repo_path, "feature", "main", None)
+        .unwrap();
+    assert_eq!(commits.len(), 2);
+    assert_eq!(
+        commits.last().unwrap(),
+        &s.get_branch_oid(&repo_path, "feature").unwrap()
+    );
+    // A merge commit that exists locally is replayed on its own
+    let merged = s.get_branch_oid(&repo_path, "main").unwrap();
+    assert_eq!(
+        s.backport_commits(&repo_path, "feature", "main", Some(&merged))
+            .unwrap(),
+        vec![merged]
+    );
+
+    checkout_branch(&repo_path, "release");
+    create_branch(&repo_path, "backport-clean");
+    let clean_wt = td.path().join("wt_clean");
+    s.add_worktree(&repo_path, &clean_wt, "backport-clean", false)
+        .unwrap();
+    s.cherry_pick_commits(&clean_wt, &commits, &CommitOptions::default())
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(clean_wt.join("fix.txt")).unwrap(),
+        "fix\n"
+    );
+    assert_eq!(
+        fs::read_to_string(clean_wt.join("shared.txt")).unwrap(),
+        "fixed\n"
+    );
+
+    write_file(&repo_path, "shared.txt", "diverged\n");
+    assert!(s.commit(&repo_path, "diverge release").unwrap());
+    create_branch(&repo_path, "backport-conflict");
+    let conflict_wt = td.path().join("wt_conflict");
+    s.add_worktree(&repo_path, &conflict_wt, "backport-conflict", false)
+        .unwrap();
+    match s.cherry_pick_commits(&conflict_wt, &commits, &CommitOptions::default()) {
+        Err(GitServiceError::MergeConflicts {
+            conflicted_files, ..
+        }) => assert_eq!(conflicted_files, vec!["shared.txt".to_string()]),
+        other => panic!("expected conflicts, got {other:?}"),
+    }
+    assert_eq!(
+        s.detect_conflict_op(&conflict_wt).unwrap(),
+        Some(ConflictOp::CherryPick)
+    );
+    s.abort_conflicts(&conflict_wt).unwrap();
+    assert_eq!(s.detect_conflict_op(&conflict_wt).unwrap(), None);
+}
+
+#[test]
+fn ignored_paths_lists_ignored_dirs_once() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, ".gitignore", "node_modules/\n*.log\n");
+    assert!(s.commit(&repo_path, "ignore build output").unwrap());
+    write_file(&repo_path, "node_modules/a/index.js", "a\n");
+    write_file(&repo_path, "node_modules/b/index.js", "b\n");
+    write_file(&repo_path, "debug.log", "log\n");
+    write_file(&repo_path, "untracked.txt", "u\n");
+
+    let mut ignored = s.ignored_paths(&repo_path).unwrap();
+    ignored.sort();
+    assert_eq!(ignored, vec!["debug.log", "node_modules"]);
+}
+
+#[test]
+fn hunks_stage_discard_and_revert_committed_changes() {
+    use git::{CommitOptions, HunkScope, HunkSelection};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let lines: Vec<String> = (1..=12).map(|i| format!("l{i}")).collect();
+    write_file(&repo_path, "notes.txt", &(lines.join("\n") + "\n"));
+    assert!(s.commit(&repo_path, "add notes").unwrap());
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_hunks");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+
+    let edited = |changes: &[(usize, &str)]| {
+        let mut edited = lines.clone();
+        for (i, line) in changes {
+            edited[*i] = line.to_string();
+        }
+        edited.join("\n") + "\n"
+    };
+    write_file(
+        &worktree_path,
+        "notes.txt",
+        &edited(&[(0, "L1"), (11, "L12")]),
+    );
+    assert!(s.commit(&worktree_path, "shout").unwrap());
+
+    let committed = s
+        .list_hunks(&worktree_path, &base_commit, HunkScope::Committed)
+        .unwrap();
+    assert_eq!(committed.len(), 1);
+    assert_eq!(committed[0].hunks.len(), 2);
+    let (head, reverted) = s
+        .revert_hunks(
+            &worktree_path,
+            &base_commit,
+            &[HunkSelection {
+                path: "notes.txt".to_string(),
+                hunks: Some(vec![committed[0].hunks[1].header.clone()]),
+            }],
+            "Revert rejected changes",
+            &CommitOptions::default(),
+        )
+        .unwrap();
+    assert!(reverted.contains("+L12"));
+    assert_eq!(s.get_head_info(&worktree_path).unwrap().oid, head);
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("notes.txt")).unwrap(),
+        edited(&[(0, "L1")])
+    );
+
+    write_file(
+        &worktree_path,
+        "notes.txt",
+        &edited(&[(0, "L1"), (5, "X6")]),
+    );
+    write_file(&worktree_path, "new.txt", "new\n");
+    let uncommitted = s
+        .list_hunks(&worktree_path, &base_commit, HunkScope::Uncommitted)
+        .unwrap();
+    let paths: Vec<&str> = uncommitted.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, ["new.txt", "notes.txt"]);
+    s.stage_hunks(
+        &worktree_path,
+        &base_commit,
+        &[HunkSelection {
+            path: "new.txt".to_string(),
+            hunks: None,
+        }],
+    )
+    .unwrap();
+    s.discard_hunks(
+        &worktree_path,
+        &base_commit,
+        HunkScope::Uncommitted,
+        &[HunkSelection {
+            path: "notes.txt".to_string(),
+            hunks: Some(vec![uncommitted[1].hunks[0].header.clone()]),
+        }],
+    )
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("notes.txt")).unwrap(),
+        edited(&[(0, "L1")])
+    );
+    assert!(
+        s.list_hunks(&worktree_path, &base_commit, HunkScope::Uncommitted)
+            .unwrap()
+            .is_empty()
+    );
+    let staged = s
+        .list_hunks(&worktree_path, &base_commit, HunkScope::Staged)
+        .unwrap();
+    let paths: Vec<&str> = staged.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, ["new.txt"]);
+
+    s.unstage_hunks(
+        &worktree_path,
+        &base_commit,
+        &[HunkSelection {
+            path: "new.txt".to_string(),
+            hunks: None,
+        }],
+    )
+    .unwrap();
+    assert!(
+        s.list_hunks(&worktree_path, &base_commit, HunkScope::Staged)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(worktree_path.join("new.txt").exists());
+
+    s.stage_hunks(
+        &worktree_path,
+        &base_commit,
+        &[HunkSelection {
+            path: "new.txt".to_string(),
+            hunks: None,
+        }],
+    )
+    .unwrap();
+    s.discard_hunks(
+        &worktree_path,
+        &base_commit,
+        HunkScope::Staged,
+        &[HunkSelection {
+            path: "new.txt".to_string(),
+            hunks: None,
+        }],
+    )
+    .unwrap();
+    assert!(!worktree_path.join("new.txt").exists());
+    let staged = GitCli::new()
+        .git(&worktree_path, ["diff", "--cached", "--name-only"])
+        .unwrap();
+    assert!(staged.trim().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn commit_hooks_run_skip_or_retry_after_reformatting() {
+    use std::os::unix::fs::PermissionsExt;
+
+    use git::{CommitHooks, CommitOptions, GitServiceError, HunkSelection};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    // Formatter-style hook: rewrites unformatted files and rejects the commit
+    let hook = repo_path.join(".git/hooks/pre-commit");
+    fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    fs::write(
+        &hook,
+        "#!/bin/sh\n\
+         if grep -q ' $' a.txt; then\n\
+         sed -i.bak 's/ *$//' a.txt && rm a.txt.bak\n\
+         echo 'trailing whitespace fixed'\n\
+         exit 1\n\
+         fi\n",
+    )
+    .unwrap();
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+    let options = |hooks| CommitOptions {
+        hooks,
+        ..Default::default()
+    };
+
+    write_file(&repo_path, "a.txt", "one \n");
+    let err = s
+        .commit_with_hooks(&repo_path, "run", &options(CommitHooks::Run))
+        .unwrap_err();
+    assert!(
+        matches!(&err, GitServiceError::CommitHooksFailed(output) if output.contains("whitespace fixed"))
+    );
+
+    write_file(&repo_path, "a.txt", "two \n");
+    let output = s
+        .commit_with_hooks(&repo_path, "retry", &options(CommitHooks::RunAndRetry))
+        .unwrap()
+        .unwrap();
+    assert!(output.contains("whitespace fixed"));
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "two\n"
+    );
+    assert!(!GitCli::new().has_changes(&repo_path).unwrap());
+
+    write_file(&repo_path, "a.txt", "three \n");
+    let output = s
+        .commit_with_hooks(&repo_path, "skip", &options(CommitHooks::Skip))
+        .unwrap();
+    assert_eq!(output.as_deref(), Some(""));
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "three \n"
+    );
+
+    // Reverting back to unformatted content retries after the hook reformats it
+    create_branch(&repo_path, "reviewed");
+    let base_commit = s.get_base_commit(&repo_path, "main", "reviewed").unwrap();
+    write_file(&repo_path, "a.txt", "four\n");
+    s.commit_with_hooks(&repo_path, "four", &options(CommitHooks::Skip))
+        .unwrap();
+    s.revert_hunks(
+        &repo_path,
+        &base_commit,
+        &[HunkSelection {
+            path: "a.txt".to_string(),
+            hunks: None,
+        }],
+        "Revert rejected changes",
+        &options(CommitHooks::RunAndRetry),
+    )
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "three\n"
+    );
+    assert!(!GitCli::new().has_changes(&repo_path).unwrap());
+}
+
+#[test]
+#[cfg_attr(not(unix), ignore = "requires ssh-keygen")]
+fn commits_and_squash_merges_apply_identity_trailers_and_ssh_signature() {
+    use git::{CommitIdentity, CommitOptions, CommitSigning, HistoryRewrite, SigningFormat};
+
+    let td = TempDir::new().unwrap();
+    let key_path = td.path().join("signing_key");
+    let keygen = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "bot", "-f"])
+        .arg(&key_path)
+        .status();
+    assert!(
+        keygen.is_ok_and(|status| status.success()),
+        "ssh-keygen is required for this test"
+    );
+    let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+    let allowed_signers = td.path().join("allowed_signers");
+    fs::write(&allowed_signers, format!("bot@example.com {public_key}")).unwrap();
+
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    create_branch(&repo_path, "feature");
+    let worktree_path = td.path().join("wt_signed");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+
+    let options = CommitOptions {
+        identity: Some(CommitIdentity::new("Bot", "bot@example.com")),
+        co_authors: vec![CommitIdentity::new("Claude Code", "noreply@vibekanban.com")],
+        signing: Some(CommitSigning {
+            format: SigningFormat::Ssh,
+            key: Some(key_path.to_string_lossy().into_owned()),
+        }),
+        ..Default::default()
+    };
+    let verify = |sha: &str| {
+        let allowed = format!("gpg.ssh.allowedSignersFile={}", allowed_signers.display());
+        GitCli::new()
+            .git(&repo_path, ["-c", allowed.as_str(), "verify-commit", sha])
+            .is_ok()
+    };
+    let message = |sha: &str| {
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.find_commit(git2::Oid::from_str(sha).unwrap()).unwrap();
+        commit.message().unwrap().to_string()
+    };
+
+    // CLI commit path
+    write_file(&worktree_path, "f.txt", "feat\n");
+    assert!(
+        s.commit_with_options(&worktree_path, "feat: add f", &options)
+            .unwrap()
+    );
+    let head = s.get_head_info(&worktree_path).unwrap().oid;
+    assert_eq!(
+        get_commit_author(&repo_path, &head),
+        (Some("Bot".to_string()), Some("bot@example.com".to_string()))
+    );
+    assert_eq!(
+        message(&head),
+        "feat: add f\n\nCo-authored-by: Claude Code <noreply@vibekanban.com>\n"
+    );
+    assert!(verify(&head));
+
+    // libgit2 squash merge path (base branch not checked out)
+    create_branch(&repo_path, "dev");
+    checkout_branch(&repo_path, "dev");
+    let merge_sha = s
+        .merge_changes_with_options(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "feat: squash",
+            &options,
+        )
+        .unwrap();
+    assert_eq!(
+        get_commit_author(&repo_path, &merge_sha).0.as_deref(),
+        Some("Bot")
+    );
+    assert!(message(&merge_sha).ends_with("Co-authored-by: Claude Code <noreply@vibekanban.com>"));
+    assert!(verify(&merge_sha));
+
+    // Unsigned commits fail verification
+    write_file(&worktree_path, "g.txt", "g\n");
+    assert!(s.commit(&worktree_path, "unsigned").unwrap());
+    let unsigned = s.get_head_info(&worktree_path).unwrap().oid;
+    assert!(!verify(&unsigned));
+
+    // Rebase-based history rewrites sign the commits they write
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let reworded = s
+        .rewrite_history(
+            &worktree_path,
+            &base_commit,
+            &HistoryRewrite::Reword {
+                commit: unsigned,
+                message: "feat: add g".to_string(),
+            },
+            &options,
+        )
+        .unwrap();
+    assert_eq!(
+        get_commit_author(&repo_path, &reworded).0.as_deref(),
+        Some("Test User")
+    );
+    assert!(verify(&reworded));
+}
+
 #[test]
 fn squash_merge_libgit2_sets_author_without_user() {
     // Verify merge_changes (libgit2 path) uses fallback author when no config exists
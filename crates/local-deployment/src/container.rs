@@ -36,16 +36,23 @@ use executors::{
     env::{ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, CancellationToken, ExecutorExitResult, ExecutorExitSignal},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    profile::ExecutorProfileId,
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
-use git::GitService;
+use git::{CommitHooks, CommitOptions, GitService, GitServiceError};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::{Config, DEFAULT_COMMIT_REMINDER_PROMPT},
+    branch_archive, commit_identity,
+    commit_message::{self, CommitMessageInput},
+    config::{
+        BranchArchiveMode, CommitIdentityConfig, Config, DEFAULT_COMMIT_REMINDER_PROMPT,
+        QuotaExceededAction,
+    },
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
+    disk_usage,
     image::ImageService,
     notification::NotificationService,
     queued_message::QueuedMessageService,
@@ -56,6 +63,7 @@ use services::services::{
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
+    diff::DiffViewOptions,
     log_msg::LogMsg,
     msg_store::MsgStore,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
@@ -64,6 +72,9 @@ use uuid::Uuid;
 
 use crate::{command, copy};
 
+/// Opens the follow-up sent to the agent when commit hooks reject its changes
+const COMMIT_HOOK_FOLLOW_UP_HEADER: &str = "The repository's commit hooks rejected the commit of your changes, which are still uncommitted. Fix the reported problems; your changes will be committed again when you finish.";
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -206,7 +217,11 @@ impl LocalContainerService {
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
     }
 
-    pub async fn cleanup_expired_workspaces(db: &DBService) -> Result<(), DeploymentError> {
+    pub async fn cleanup_expired_workspaces(
+        db: &DBService,
+        git: &GitService,
+        branch_archive: BranchArchiveMode,
+    ) -> Result<(), DeploymentError> {
         if std::env::var("DISABLE_WORKTREE_CLEANUP").is_ok() {
             tracing::info!(
                 "Expired workspace cleanup is disabled via DISABLE_WORKTREE_CLEANUP environment variable"
@@ -224,13 +239,111 @@ impl LocalContainerService {
             expired_workspaces.len()
         );
         for workspace in &expired_workspaces {
+            branch_archive::archive_workspace_branches(&db.pool, git, workspace, branch_archive)
+                .await;
             Self::cleanup_workspace(db, workspace).await;
         }
         Ok(())
     }
 
+    /// Compare the workspace base directory against the configured quota and
+    /// apply the configured action. Only refusing new workspaces is an error.
+    pub async fn enforce_quota(
+        db: &DBService,
+        git: &GitService,
+        config: &Arc<RwLock<Config>>,
+    ) -> Result<(), ContainerError> {
+        let (quota, branch_archive) = {
+            let config = config.read().await;
+            (config.workspace_quota.clone(), config.branch_archive)
+        };
+        let Some(limit) = quota.limit_bytes() else {
+            return Ok(());
+        };
+        let mut used = disk_usage::cached_total_workspace_bytes().await;
+        if used <= limit {
+            return Ok(());
+        }
+        let message = format!(
+            "Workspaces use {} MB, over the {} MB quota",
+            used / (1024 * 1024),
+            limit / (1024 * 1024)
+        );
+
+        match quota.action {
+            QuotaExceededAction::Warn => {
+                tracing::warn!("{}", message);
+                Ok(())
+            }
+            QuotaExceededAction::Refuse => Err(ContainerError::QuotaExceeded(format!(
+                "{message}. Delete or archive workspaces to free up space."
+            ))),
+            QuotaExceededAction::CleanupArchived => {
+                tracing::info!("{}; cleaning up archived workspaces", message);
+                let mut candidates: Vec<Workspace> = Workspace::fetch_all(&db.pool, None)
+                    .await?
+                    .into_iter()
+                    .filter(|w| w.archived && w.container_ref.is_some())
+                    .collect();
+                candidates.sort_by_key(|w| w.updated_at);
+
+                for workspace in &candidates {
+                    if used <= limit {
+                        break;
+                    }
+                    // Leave workspaces with running agents or dev servers alone
+                    let pool = &db.pool;
+                    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                        pool,
+                        workspace.id,
+                    )
+                    .await?
+                        || !ExecutionProcess::find_running_dev_servers_by_workspace(
+                            pool,
+                            workspace.id,
+                        )
+                        .await?
+                        .is_empty()
+                    {
+                        continue;
+                    }
+                    let size = disk_usage::workspace_disk_usage(&db.pool, git, workspace)
+                        .await?
+                        .total_bytes;
+                    branch_archive::archive_workspace_branches(
+                        &db.pool,
+                        git,
+                        workspace,
+                        branch_archive,
+                    )
+                    .await;
+                    Self::cleanup_workspace(db, workspace).await;
+                    disk_usage::invalidate_total_workspace_bytes();
+                    used = used.saturating_sub(size);
+                    tracing::info!(
+                        "Cleaned up archived workspace {} to free {} MB",
+                        workspace.id,
+                        size / (1024 * 1024)
+                    );
+                }
+
+                if used > limit {
+                    tracing::warn!(
+                        "Workspaces still use {} MB after cleaning up archived workspaces, over \
+                         the {} MB quota",
+                        used / (1024 * 1024),
+                        limit / (1024 * 1024)
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
     pub fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
+        let git = self.git.clone();
+        let config = self.config.clone();
         let cleanup_expired = Self::cleanup_expired_workspaces;
         tokio::spawn(async move {
             WorkspaceManager::cleanup_orphan_workspaces(&db.pool).await;
@@ -240,9 +353,15 @@ impl LocalContainerService {
             loop {
                 cleanup_interval.tick().await;
                 tracing::info!("Starting periodic workspace cleanup...");
-                cleanup_expired(&db).await.unwrap_or_else(|e| {
-                    tracing::error!("Failed to clean up expired workspaces: {}", e)
-                });
+                let branch_archive = config.read().await.branch_archive;
+                cleanup_expired(&db, &git, branch_archive)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to clean up expired workspaces: {}", e)
+                    });
+                if let Err(e) = Self::enforce_quota(&db, &git, &config).await {
+                    tracing::warn!("Workspace quota check: {}", e);
+                }
             }
         });
     }
@@ -314,6 +433,65 @@ impl LocalContainerService {
         }
     }
 
+    /// Pair each repo with its commit message. With Conventional Commits enabled,
+    /// messages are built per repo from the agent summary and the staged paths.
+    async fn commit_messages(
+        &self,
+        ctx: &ExecutionContext,
+        repos_with_changes: Vec<(Repo, PathBuf)>,
+    ) -> Vec<(Repo, PathBuf, String)> {
+        if !self.config.read().await.conventional_commits_enabled {
+            let message = self.get_commit_message(ctx).await;
+            return repos_with_changes
+                .into_iter()
+                .map(|(repo, path)| (repo, path, message.clone()))
+                .collect();
+        }
+
+        let summary = match ctx.execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent => {
+                CodingAgentTurn::find_by_execution_process_id(
+                    &self.db().pool,
+                    ctx.execution_process.id,
+                )
+                .await
+                .ok()
+                .flatten()
+                .and_then(|turn| turn.summary)
+            }
+            ExecutionProcessRunReason::CleanupScript => {
+                Some("chore: apply cleanup script changes".to_string())
+            }
+            _ => None,
+        };
+
+        repos_with_changes
+            .into_iter()
+            .map(|(repo, path)| {
+                let changed_paths: Vec<String> = self
+                    .git()
+                    .get_worktree_status(&path)
+                    .map(|status| {
+                        status
+                            .entries
+                            .iter()
+                            .map(|e| String::from_utf8_lossy(&e.path).into_owned())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let message = commit_message::repo_commit_message(
+                    &repo,
+                    &CommitMessageInput {
+                        task_title: &ctx.task.title,
+                        summary: summary.as_deref(),
+                        changed_paths: &changed_paths,
+                    },
+                );
+                (repo, path, message)
+            })
+            .collect()
+    }
+
     /// Check which repos have uncommitted changes. Fails if any repo is inaccessible.
     fn check_repos_for_changes(
         &self,
@@ -375,32 +553,176 @@ impl LocalContainerService {
         Ok(false)
     }
 
+    /// Continue any rebase/merge whose conflicts were resolved during the execution.
+    /// Repos with an operation still in progress are excluded from the auto-commit so
+    /// we never record a regular commit in the middle of a rebase. Returns the repos
+    /// that are safe to auto-commit and whether any operation was continued.
+    fn continue_resolved_conflicts(
+        &self,
+        workspace_root: &Path,
+        repos: &[Repo],
+        identity: &CommitIdentityConfig,
+    ) -> (Vec<Repo>, bool) {
+        let mut commit_candidates = Vec::with_capacity(repos.len());
+        let mut any_continued = false;
+
+        for repo in repos {
+            let worktree_path = workspace_root.join(&repo.name);
+            if !matches!(self.git().detect_conflict_op(&worktree_path), Ok(Some(_))) {
+                commit_candidates.push(repo.clone());
+                continue;
+            }
+
+            let options = commit_identity::commit_options(identity, repo, None);
+            match self
+                .git()
+                .complete_conflict_resolution(&worktree_path, &options)
+            {
+                Ok(Some(op)) => {
+                    any_continued = true;
+                    tracing::info!(
+                        "Continued {:?} in repo '{}' after conflict resolution",
+                        op,
+                        repo.name
+                    );
+                    // The operation may have completed, leaving new changes to commit
+                    if matches!(self.git().detect_conflict_op(&worktree_path), Ok(None)) {
+                        commit_candidates.push(repo.clone());
+                    }
+                }
+                Ok(None) => commit_candidates.push(repo.clone()),
+                Err(e) => {
+                    tracing::warn!(
+                        "Conflicts in repo '{}' are not fully resolved, leaving operation in progress: {}",
+                        repo.name,
+                        e
+                    );
+                }
+            }
+        }
+
+        (commit_candidates, any_continued)
+    }
+
     /// Commit changes to each repo. Logs failures but continues with other repos.
-    fn commit_repos(&self, repos_with_changes: Vec<(Repo, PathBuf)>, message: &str) -> bool {
+    /// Commit hook output is written to `msg_store`; returns whether anything was
+    /// committed and the repos whose hooks rejected the commit, with their output.
+    fn commit_repos(
+        &self,
+        repos_with_messages: Vec<(Repo, PathBuf, String)>,
+        identity: &CommitIdentityConfig,
+        hooks: CommitHooks,
+        executor_profile: Option<&ExecutorProfileId>,
+        msg_store: Option<&MsgStore>,
+    ) -> (bool, Vec<(String, String)>) {
         let mut any_committed = false;
+        let mut hook_failures = Vec::new();
 
-        for (repo, worktree_path) in repos_with_changes {
+        for (repo, worktree_path, message) in repos_with_messages {
             tracing::debug!(
                 "Committing changes for repo '{}' at {:?}",
                 repo.name,
                 &worktree_path
             );
 
-            match self.git().commit(&worktree_path, message) {
-                Ok(true) => {
+            let options = CommitOptions {
+                hooks,
+                ..commit_identity::commit_options(identity, &repo, executor_profile)
+            };
+            match self
+                .git()
+                .commit_with_hooks(&worktree_path, &message, &options)
+            {
+                Ok(Some(hook_output)) => {
                     any_committed = true;
                     tracing::info!("Committed changes in repo '{}'", repo.name);
+                    if let Some(store) = msg_store
+                        && !hook_output.is_empty()
+                    {
+                        store.push_stderr(format!(
+                            "Commit hooks in repo '{}':\n{}\n",
+                            repo.name, hook_output
+                        ));
+                    }
                 }
-                Ok(false) => {
+                Ok(None) => {
                     tracing::warn!("No changes committed in repo '{}' (unexpected)", repo.name);
                 }
+                Err(GitServiceError::CommitHooksFailed(output)) => {
+                    tracing::warn!("Commit hooks failed in repo '{}'", repo.name);
+                    if let Some(store) = msg_store {
+                        store.push_stderr(format!(
+                            "Commit hooks failed in repo '{}':\n{}\n",
+                            repo.name, output
+                        ));
+                    }
+                    hook_failures.push((repo.name.clone(), output));
+                }
                 Err(e) => {
                     tracing::warn!("Failed to commit in repo '{}': {}", repo.name, e);
                 }
             }
         }
 
-        any_committed
+        (any_committed, hook_failures)
+    }
+
+    /// Queue a follow-up asking the agent to fix what the commit hooks
+    /// reported. Nothing is queued when the user already queued a message, or
+    /// when the latest agent run was such a follow-up, so a hook the agent
+    /// cannot satisfy does not loop.
+    async fn queue_commit_hook_follow_up(
+        &self,
+        ctx: &ExecutionContext,
+        executor_profile: Option<ExecutorProfileId>,
+        hook_failures: &[(String, String)],
+    ) {
+        let Some(executor_profile_id) = executor_profile else {
+            return;
+        };
+        if self.queued_message_service.has_queued(ctx.session.id) {
+            tracing::info!(
+                "Not queueing a commit hook follow-up for session {}: a message is already queued",
+                ctx.session.id
+            );
+            return;
+        }
+        let pool = &self.db().pool;
+        let latest_prompt = match ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            ctx.session.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+        {
+            Ok(Some(process)) => CodingAgentTurn::find_by_execution_process_id(pool, process.id)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|turn| turn.prompt),
+            _ => None,
+        };
+        if latest_prompt.is_some_and(|prompt| prompt.starts_with(COMMIT_HOOK_FOLLOW_UP_HEADER)) {
+            tracing::warn!(
+                "Commit hooks still fail in workspace {} after a follow-up, leaving the changes uncommitted",
+                ctx.workspace.id
+            );
+            return;
+        }
+
+        let mut message = COMMIT_HOOK_FOLLOW_UP_HEADER.to_string();
+        for (repo_name, output) in hook_failures {
+            message.push_str(&format!(
+                "\n\nRepository `{repo_name}`:\n```\n{output}\n```"
+            ));
+        }
+        self.queued_message_service.queue_message(
+            ctx.session.id,
+            DraftFollowUpData {
+                message,
+                executor_profile_id,
+            },
+        );
     }
 
     /// Spawn a background task that polls the child process for completion and
@@ -534,6 +856,10 @@ impl LocalContainerService {
                     }
                 }
 
+                if let Err(e) = container.start_next_quality_gate(&ctx).await {
+                    tracing::error!("Failed to start next quality gate: {}", e);
+                }
+
                 if container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
@@ -785,6 +1111,38 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Build worktree inputs for every repo in a workspace, applying the
+    /// workspace's target branches and sparse-checkout overrides.
+    async fn workspace_inputs(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<RepoWorkspaceInput>, ContainerError> {
+        let workspace_repos =
+            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace_id).await?;
+        if workspace_repos.is_empty() {
+            return Err(ContainerError::Other(anyhow!(
+                "Workspace has no repositories configured"
+            )));
+        }
+
+        let repositories =
+            WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace_id).await?;
+
+        let workspace_repos: HashMap<_, _> = workspace_repos
+            .into_iter()
+            .map(|wr| (wr.repo_id, wr))
+            .collect();
+
+        Ok(repositories
+            .into_iter()
+            .map(|repo| match workspace_repos.get(&repo.id) {
+                Some(wr) => RepoWorkspaceInput::new(repo, wr.target_branch.clone())
+                    .with_sparse_checkout_override(wr.sparse_checkout.as_deref()),
+                None => RepoWorkspaceInput::new(repo, String::new()),
+            })
+            .collect())
+    }
+
     /// Copy project files and images to the workspace.
     /// Skips files/images that already exist (fast no-op if all exist).
     async fn copy_files_and_images(
@@ -999,6 +1357,14 @@ impl ContainerService for LocalContainerService {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn git_branch_template(&self) -> Option<String> {
+        self.config.read().await.git_branch_template.clone()
+    }
+
+    async fn git_branch_user(&self) -> Option<String> {
+        self.config.read().await.github.username.clone()
+    }
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
         PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
     }
@@ -1013,28 +1379,10 @@ impl ContainerService for LocalContainerService {
             LocalContainerService::dir_name_from_workspace(&workspace.id, &task.title);
         let workspace_dir = WorkspaceManager::get_workspace_base_dir().join(&workspace_dir_name);
 
-        let workspace_repos =
-            WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id).await?;
-        if workspace_repos.is_empty() {
-            return Err(ContainerError::Other(anyhow!(
-                "Workspace has no repositories configured"
-            )));
-        }
-
-        let repositories =
-            WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
-
-        let target_branches: HashMap<_, _> = workspace_repos
-            .iter()
-            .map(|wr| (wr.repo_id, wr.target_branch.clone()))
-            .collect();
-
-        let workspace_inputs: Vec<RepoWorkspaceInput> = repositories
+        let workspace_inputs = self.workspace_inputs(workspace.id).await?;
+        let repositories: Vec<Repo> = workspace_inputs
             .iter()
-            .map(|repo| {
-                let target_branch = target_branches.get(&repo.id).cloned().unwrap_or_default();
-                RepoWorkspaceInput::new(repo.clone(), target_branch)
-            })
+            .map(|input| input.repo.clone())
             .collect();
 
         let created_workspace = WorkspaceManager::create_workspace(
@@ -1070,19 +1418,20 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn enforce_workspace_quota(&self) -> Result<(), ContainerError> {
+        Self::enforce_quota(&self.db, &self.git, &self.config).await
+    }
+
     async fn ensure_container_exists(
         &self,
         workspace: &Workspace,
     ) -> Result<ContainerRef, ContainerError> {
         Workspace::touch(&self.db.pool, workspace.id).await?;
-        let repositories =
-            WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
-
-        if repositories.is_empty() {
-            return Err(ContainerError::Other(anyhow!(
-                "Workspace has no repositories configured"
-            )));
-        }
+        let workspace_inputs = self.workspace_inputs(workspace.id).await?;
+        let repositories: Vec<Repo> = workspace_inputs
+            .iter()
+            .map(|input| input.repo.clone())
+            .collect();
 
         let workspace_dir = if let Some(container_ref) = &workspace.container_ref {
             PathBuf::from(container_ref)
@@ -1096,8 +1445,12 @@ impl ContainerService for LocalContainerService {
             WorkspaceManager::get_workspace_base_dir().join(&workspace_dir_name)
         };
 
-        WorkspaceManager::ensure_workspace_exists(&workspace_dir, &repositories, &workspace.branch)
-            .await?;
+        WorkspaceManager::ensure_workspace_exists(
+            &workspace_dir,
+            &workspace_inputs,
+            &workspace.branch,
+        )
+        .await?;
 
         if workspace.container_ref.is_none() {
             Workspace::update_container_ref(
@@ -1182,6 +1535,13 @@ impl ContainerService for LocalContainerService {
             .commit_reminder_prompt
             .clone()
             .unwrap_or_else(|| DEFAULT_COMMIT_REMINDER_PROMPT.to_string());
+        // The request is only added to the spawned prompt, so the stored prompt
+        // stays as the user wrote it
+        let executor_action = if config.conventional_commits_enabled {
+            commit_message::with_commit_message_request(executor_action)
+        } else {
+            executor_action.clone()
+        };
         drop(config);
         let mut env = ExecutionEnv::new(
             repo_context,
@@ -1302,11 +1662,12 @@ impl ContainerService for LocalContainerService {
             let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
         }
 
-        // Update task status to InReview when execution is stopped
+        // Update task status to InReview when execution is stopped, unless it
+        // was a dev server or quality gate running alongside the task
         if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
             && !matches!(
                 ctx.execution_process.run_reason,
-                ExecutionProcessRunReason::DevServer
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::QualityGate
             )
             && let Err(e) =
                 Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
@@ -1329,6 +1690,7 @@ impl ContainerService for LocalContainerService {
         &self,
         workspace: &Workspace,
         stats_only: bool,
+        view_options: DiffViewOptions,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
         let workspace_repos =
@@ -1386,6 +1748,7 @@ impl ContainerService for LocalContainerService {
                     base_commit: base_commit.clone(),
                     stats_only,
                     path_prefix: Some(repo.name.clone()),
+                    view_options: view_options.clone(),
                 })
                 .await?;
 
@@ -1408,8 +1771,6 @@ impl ContainerService for LocalContainerService {
             return Ok(false);
         }
 
-        let message = self.get_commit_message(ctx).await;
-
         let container_ref = ctx
             .workspace
             .container_ref
@@ -1417,13 +1778,45 @@ impl ContainerService for LocalContainerService {
             .ok_or_else(|| ContainerError::Other(anyhow!("Container reference not found")))?;
         let workspace_root = PathBuf::from(container_ref);
 
-        let repos_with_changes = self.check_repos_for_changes(&workspace_root, &ctx.repos)?;
+        let (identity, hooks) = {
+            let config = self.config.read().await;
+            (
+                config.commit_identity.clone(),
+                commit_identity::commit_hooks(config.commit_hooks),
+            )
+        };
+
+        let (repos, any_continued) =
+            self.continue_resolved_conflicts(&workspace_root, &ctx.repos, &identity);
+
+        let repos_with_changes = self.check_repos_for_changes(&workspace_root, &repos)?;
         if repos_with_changes.is_empty() {
             tracing::debug!("No changes to commit in any repository");
-            return Ok(false);
+            return Ok(any_continued);
         }
 
-        Ok(self.commit_repos(repos_with_changes, &message))
+        let repos_with_messages = self.commit_messages(ctx, repos_with_changes).await;
+        let executor_profile =
+            ExecutionProcess::latest_executor_profile_for_session(&self.db().pool, ctx.session.id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load executor profile for commit: {}", e);
+                    None
+                });
+        let msg_store = self.get_msg_store_by_id(&ctx.execution_process.id).await;
+        let (any_committed, hook_failures) = self.commit_repos(
+            repos_with_messages,
+            &identity,
+            hooks,
+            executor_profile.as_ref(),
+            msg_store.as_deref(),
+        );
+        if !hook_failures.is_empty() {
+            self.queue_commit_hook_follow_up(ctx, executor_profile, &hook_failures)
+                .await;
+        }
+        // Changes the hooks rejected are still pending, like other commit failures
+        Ok(any_committed || any_continued || !hook_failures.is_empty())
     }
 
     /// Copy files from the original project directory to the worktree.
@@ -1441,7 +1834,19 @@ impl ContainerService for LocalContainerService {
         tokio::time::timeout(
             std::time::Duration::from_secs(30),
             tokio::task::spawn_blocking(move || {
-                copy::copy_project_files_impl(&source_dir, &target_dir, &copy_files)
+                // Files outside a sparse worktree's cone would show up as untracked noise
+                let sparse_cone = GitService::new()
+                    .get_sparse_cone(&target_dir)
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to read sparse-checkout cone: {}", e);
+                        None
+                    });
+                copy::copy_project_files_impl(
+                    &source_dir,
+                    &target_dir,
+                    &copy_files,
+                    sparse_cone.as_ref(),
+                )
             }),
         )
         .await
@@ -14,9 +14,12 @@ use services::services::{
     events::EventService,
     file_search::FileSearchCache,
     filesystem::FilesystemService,
+    git_host_credentials::GitHostCredentials,
     image::ImageService,
+    issue_sync::IssueSyncService,
     oauth_credentials::OAuthCredentials,
-    pr_monitor::PrMonitorService,
+    pr_monitor::{PrMonitorHandle, PrMonitorService},
+    pr_webhook_secret::PrWebhookSecret,
     project::ProjectService,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
@@ -26,7 +29,7 @@ use services::services::{
 use tokio::sync::RwLock;
 use utils::{
     api::oauth::LoginStatus,
-    assets::{config_path, credentials_path},
+    assets::{config_path, credentials_path, git_host_credentials_path, pr_webhook_secret_path},
     msg_store::MsgStore,
 };
 use uuid::Uuid;
@@ -55,6 +58,9 @@ pub struct LocalDeployment {
     queued_message_service: QueuedMessageService,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
     auth_context: AuthContext,
+    git_host_credentials: Arc<GitHostCredentials>,
+    pr_webhook_secret: Arc<PrWebhookSecret>,
+    pr_monitor: PrMonitorHandle,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
     pty: PtyService,
 }
@@ -89,6 +95,25 @@ impl Deployment for LocalDeployment {
             }
         }
 
+        let git_host_credentials = Arc::new(GitHostCredentials::new(git_host_credentials_path()));
+        if let Err(e) = git_host_credentials.load().await {
+            tracing::warn!(?e, "failed to load git host credentials");
+        }
+
+        // Tokens used to live in the config file; move them out before it
+        // is rewritten without them
+        git_host_credentials
+            .import_legacy_tokens(&mut raw_config.git_hosts)
+            .await?;
+
+        let pr_webhook_secret = Arc::new(PrWebhookSecret::new(pr_webhook_secret_path()));
+        if let Err(e) = pr_webhook_secret.load().await {
+            tracing::warn!(?e, "failed to load webhook secret");
+        }
+        pr_webhook_secret
+            .import_legacy_secret(&mut raw_config.pr_webhooks)
+            .await?;
+
         // Always save config (may have been migrated or version updated)
         save_config_to_file(&raw_config, &config_path()).await?;
 
@@ -189,7 +214,7 @@ impl Deployment for LocalDeployment {
         let file_search_cache = Arc::new(FileSearchCache::new());
 
         let pty = PtyService::new();
-        {
+        let pr_monitor = {
             let db = db.clone();
             let analytics = analytics.as_ref().map(|s| AnalyticsContext {
                 user_id: user_id.clone(),
@@ -197,8 +222,18 @@ impl Deployment for LocalDeployment {
             });
             let container = container.clone();
             let rc = remote_client.clone().ok();
-            PrMonitorService::spawn(db, analytics, container, rc).await;
-        }
+            PrMonitorService::spawn(
+                db,
+                config.clone(),
+                git_host_credentials.clone(),
+                pr_webhook_secret.clone(),
+                analytics,
+                container,
+                rc,
+            )
+            .await
+        };
+        IssueSyncService::spawn(db.clone(), config.clone(), git_host_credentials.clone()).await;
 
         let deployment = Self {
             config,
@@ -217,6 +252,9 @@ impl Deployment for LocalDeployment {
             queued_message_service,
             remote_client,
             auth_context,
+            git_host_credentials,
+            pr_webhook_secret,
+            pr_monitor,
             oauth_handoffs,
             pty,
         };
@@ -283,6 +321,18 @@ impl Deployment for LocalDeployment {
     fn auth_context(&self) -> &AuthContext {
         &self.auth_context
     }
+
+    fn git_host_credentials(&self) -> &Arc<GitHostCredentials> {
+        &self.git_host_credentials
+    }
+
+    fn pr_webhook_secret(&self) -> &Arc<PrWebhookSecret> {
+        &self.pr_webhook_secret
+    }
+
+    fn pr_monitor(&self) -> &PrMonitorHandle {
+        &self.pr_monitor
+    }
 }
 
 impl LocalDeployment {
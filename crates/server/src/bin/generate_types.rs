@@ -18,11 +18,17 @@ fn generate_types_content() -> String {
         db::models::project::SearchMatchType::decl(),
         db::models::repo::Repo::decl(),
         db::models::repo::UpdateRepo::decl(),
+        db::models::repo::CommitIdentityMode::decl(),
+        db::models::repo::CommitSigningFormat::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
+        db::models::quality_gate::QualityGate::decl(),
+        db::models::quality_gate::QualityGateInput::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
+        db::models::workspace_branch_archive::BranchArchiveStorage::decl(),
+        db::models::workspace_branch_archive::WorkspaceBranchArchive::decl(),
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
@@ -58,13 +64,22 @@ fn generate_types_content() -> String {
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
+        db::models::merge::PrCheck::decl(),
+        db::models::merge::PrCheckStatus::decl(),
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
+        db::models::pr_review_thread::PrReviewThread::decl(),
+        db::models::pr_review_thread::PrReviewThreadStatus::decl(),
+        db::models::task_issue::TaskIssue::decl(),
+        db::models::task_issue::TaskIssueState::decl(),
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
+        utils::diff::DiffViewOptions::decl(),
+        utils::diff::DiffSide::decl(),
+        utils::diff::DiffLineHighlight::decl(),
         utils::response::ApiResponse::<()>::decl(),
         utils::api::oauth::LoginStatus::decl(),
         utils::api::oauth::ProfileResponse::decl(),
@@ -105,6 +120,8 @@ fn generate_types_content() -> String {
         server::routes::config::McpServerQuery::decl(),
         server::routes::config::UpdateMcpServersBody::decl(),
         server::routes::config::GetMcpServerResponse::decl(),
+        server::routes::config::SetGitHostTokenRequest::decl(),
+        server::routes::config::SetPrWebhookSecretRequest::decl(),
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
@@ -113,7 +130,11 @@ fn generate_types_content() -> String {
         server::routes::sessions::ResetProcessRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
+        server::routes::task_attempts::WidenSparseCheckoutRequest::decl(),
+        server::routes::task_attempts::WidenSparseCheckoutResponse::decl(),
         server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
+        server::routes::task_attempts::MergeCommitMessageResponse::decl(),
+        server::routes::task_attempts::MergeError::decl(),
         server::routes::task_attempts::PushTaskAttemptRequest::decl(),
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
@@ -122,6 +143,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::ImportIssuesRequest::decl(),
         server::routes::task_attempts::pr::CreatePrApiRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -133,20 +155,55 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         server::routes::task_attempts::ContinueRebaseRequest::decl(),
         server::routes::task_attempts::AbortConflictsRequest::decl(),
+        server::routes::task_attempts::ResolveConflictsWithAgentRequest::decl(),
+        server::routes::task_attempts::ResolveConflictsError::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
+        server::routes::task_attempts::commits::WorkspaceCommit::decl(),
+        server::routes::task_attempts::commits::FileBlameRange::decl(),
+        server::routes::task_attempts::transfer::ImportWorkspaceRequest::decl(),
+        server::routes::task_attempts::transfer::ImportWorkspaceError::decl(),
+        server::routes::task_attempts::backport::BackportRequest::decl(),
+        server::routes::task_attempts::backport::BackportStatus::decl(),
+        server::routes::task_attempts::backport::BackportPr::decl(),
+        server::routes::task_attempts::backport::BackportResult::decl(),
+        server::routes::task_attempts::commits::SquashCommitsRequest::decl(),
+        server::routes::task_attempts::commits::RewordCommitRequest::decl(),
+        server::routes::task_attempts::commits::DropCommitRequest::decl(),
+        server::routes::task_attempts::commits::RewriteHistoryResponse::decl(),
+        server::routes::task_attempts::hunks::AcceptHunksRequest::decl(),
+        server::routes::task_attempts::hunks::UnstageHunksRequest::decl(),
+        server::routes::task_attempts::hunks::RejectHunksRequest::decl(),
+        server::routes::task_attempts::hunks::RejectHunksResponse::decl(),
+        server::routes::task_attempts::hunks::HunkReviewError::decl(),
         server::routes::task_attempts::PushError::decl(),
         server::routes::task_attempts::pr::PrError::decl(),
         server::routes::task_attempts::RunScriptError::decl(),
+        server::routes::task_attempts::quality_gates::RunQualityGatesRequest::decl(),
+        server::routes::task_attempts::quality_gates::SendQualityGateFailureRequest::decl(),
+        server::routes::task_attempts::quality_gates::QualityGateFollowUpError::decl(),
+        services::services::quality_gates::QualityGateStatus::decl(),
+        services::services::quality_gates::QualityGateResult::decl(),
         server::routes::task_attempts::pr::AttachPrResponse::decl(),
         server::routes::task_attempts::pr::AttachExistingPrRequest::decl(),
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
         server::routes::task_attempts::pr::GetPrCommentsError::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
+        server::routes::task_attempts::pr::AddressPrReviewRequest::decl(),
+        server::routes::task_attempts::pr::AddressPrReviewResponse::decl(),
+        server::routes::task_attempts::pr::AddressPrReviewError::decl(),
+        server::routes::task_attempts::pr::SetPrDraftRequest::decl(),
+        server::routes::task_attempts::pr::SetPrDraftError::decl(),
+        server::routes::task_attempts::pr::SetPrAutoMergeRequest::decl(),
+        server::routes::task_attempts::pr::SetPrAutoMergeError::decl(),
         services::services::git_host::UnifiedPrComment::decl(),
         services::services::git_host::ProviderKind::decl(),
+        services::services::git_host::GitHostMapping::decl(),
+        services::services::git_host::MergeMethod::decl(),
         services::services::git_host::OpenPrInfo::decl(),
+        services::services::git_host::GitHostIssue::decl(),
         git::GitRemote::decl(),
         server::routes::repo::ListPrsError::decl(),
+        server::routes::repo::ListIssuesError::decl(),
         server::routes::task_attempts::pr::CreateWorkspaceFromPrBody::decl(),
         server::routes::task_attempts::pr::CreateWorkspaceFromPrResponse::decl(),
         server::routes::task_attempts::pr::CreateFromPrError::decl(),
@@ -156,6 +213,9 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
+        services::services::disk_usage::RepoDiskUsage::decl(),
+        services::services::disk_usage::WorkspaceDiskUsage::decl(),
+        services::services::disk_usage::DiskUsageReport::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
@@ -170,10 +230,26 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::SendMessageShortcut::decl(),
+        services::services::config::BranchArchiveMode::decl(),
+        services::services::config::QuotaExceededAction::decl(),
+        services::services::config::WorkspaceQuotaConfig::decl(),
+        services::services::config::CommitHookMode::decl(),
+        services::services::config::CiAutoFixConfig::decl(),
+        services::services::config::PrWebhookConfig::decl(),
+        services::services::config::PrLifecycleConfig::decl(),
+        services::services::config::IssueDoneAction::decl(),
+        services::services::config::CommitIdentityConfig::decl(),
         git::GitBranch::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         git::ConflictOp::decl(),
+        git::CommitInfo::decl(),
+        git::BlameRange::decl(),
+        git::ExportFormat::decl(),
+        git::HunkScope::decl(),
+        git::DiffHunk::decl(),
+        git::FileHunks::decl(),
+        git::HunkSelection::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
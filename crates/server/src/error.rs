@@ -15,11 +15,13 @@ use git::GitServiceError;
 use git2::Error as Git2Error;
 use local_deployment::pty::PtyError;
 use services::services::{
+    branch_archive::BranchArchiveError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git_host::GitHostError,
     image::ImageError,
     migration::MigrationError,
+    pr_review::PrReviewError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
@@ -97,6 +99,27 @@ impl From<Git2Error> for ApiError {
     }
 }
 
+impl From<BranchArchiveError> for ApiError {
+    fn from(err: BranchArchiveError) -> Self {
+        match err {
+            BranchArchiveError::GitService(e) => ApiError::GitService(e),
+            BranchArchiveError::Database(e) => ApiError::Database(e),
+            BranchArchiveError::Io(e) => ApiError::Io(e),
+        }
+    }
+}
+
+impl From<PrReviewError> for ApiError {
+    fn from(err: PrReviewError) -> Self {
+        match err {
+            PrReviewError::GitHost(e) => ApiError::GitHost(e),
+            PrReviewError::GitService(e) => ApiError::GitService(e),
+            PrReviewError::Container(e) => ApiError::Container(e),
+            PrReviewError::Sqlx(e) => ApiError::Database(e),
+        }
+    }
+}
+
 impl From<RemoteClientNotConfigured> for ApiError {
     fn from(_: RemoteClientNotConfigured) -> Self {
         ApiError::BadRequest("Remote client not configured".to_string())
@@ -316,6 +339,24 @@ impl IntoResponse for ApiError {
                 "GitServiceError",
                 "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.",
             ),
+            ApiError::GitService(git::GitServiceError::SigningFailed(message)) => {
+                ErrorInfo::bad_request(
+                    "GitServiceError",
+                    format!("Commit signing failed: {message}"),
+                )
+            }
+            ApiError::GitService(git::GitServiceError::CommitHooksFailed(output)) => {
+                ErrorInfo::bad_request("GitServiceError", format!("Commit hooks failed:\n{output}"))
+            }
+            ApiError::GitService(git::GitServiceError::PatchFailed(message)) => {
+                ErrorInfo::conflict(
+                    "GitServiceError",
+                    format!("Patch does not apply: {message}"),
+                )
+            }
+            ApiError::GitService(git::GitServiceError::InvalidPath(path)) => {
+                ErrorInfo::bad_request("GitServiceError", format!("Invalid path: {path}"))
+            }
             ApiError::GitService(_) => ErrorInfo::internal("GitServiceError"),
             ApiError::GitHost(_) => ErrorInfo::internal("GitHostError"),
 
@@ -374,6 +415,11 @@ impl IntoResponse for ApiError {
             ),
 
             ApiError::Deployment(_) => ErrorInfo::internal("DeploymentError"),
+            ApiError::Container(ContainerError::QuotaExceeded(msg)) => ErrorInfo::with_status(
+                StatusCode::INSUFFICIENT_STORAGE,
+                "QuotaExceeded",
+                msg.clone(),
+            ),
             ApiError::Container(_) => ErrorInfo::internal("ContainerError"),
             ApiError::Executor(_) => ErrorInfo::internal("ExecutorError"),
             ApiError::CommandBuilder(_) => ErrorInfo::internal("CommandBuildError"),
@@ -307,6 +307,27 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpWidenSparseCheckoutRequest {
+    #[schemars(
+        description = "The workspace ID. Defaults to the active workspace session when omitted"
+    )]
+    pub workspace_id: Option<Uuid>,
+    #[schemars(description = "The repository ID")]
+    pub repo_id: Uuid,
+    #[schemars(description = "Repository-relative directories to check out, e.g. 'packages/api'")]
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct McpWidenSparseCheckoutResponse {
+    pub repo_id: Uuid,
+    #[schemars(
+        description = "Newline-separated directories now checked out, or null for a full checkout"
+    )]
+    pub sparse_checkout: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -890,6 +911,7 @@ impl TaskServer {
             .map(|r| WorkspaceRepoInput {
                 repo_id: r.repo_id,
                 target_branch: r.base_branch,
+                sparse_checkout: None,
             })
             .collect();
 
@@ -999,12 +1021,49 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Check out additional directories in a sparse-checkout worktree. Use this when the files you need are missing because the workspace only contains part of a monorepo. `repo_id` and `paths` are required."
+    )]
+    async fn widen_sparse_checkout(
+        &self,
+        Parameters(McpWidenSparseCheckoutRequest {
+            workspace_id,
+            repo_id,
+            paths,
+        }): Parameters<McpWidenSparseCheckoutRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(workspace_id) =
+            workspace_id.or_else(|| self.context.as_ref().map(|ctx| ctx.workspace_id))
+        else {
+            return Self::err(
+                "`workspace_id` is required outside an active workspace session",
+                None,
+            );
+        };
+
+        let url = self.url(&format!(
+            "/api/task-attempts/{}/sparse-checkout/widen",
+            workspace_id
+        ));
+        let payload = serde_json::json!({
+            "repo_id": repo_id,
+            "paths": paths,
+        });
+        let response: McpWidenSparseCheckoutResponse =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(r) => r,
+                Err(e) => return Ok(e),
+            };
+
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script', 'widen_sparse_checkout'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
@@ -9,7 +9,7 @@ use axum::{
     },
     http,
     response::{IntoResponse, Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{delete, get, put},
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
@@ -22,6 +22,7 @@ use executors::{
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use services::services::{
+    branch_name,
     config::{
         Config, ConfigError, SoundFile,
         editor::{EditorConfig, EditorType},
@@ -43,6 +44,17 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
+        .route(
+            "/git-host-tokens",
+            get(list_git_host_tokens).put(set_git_host_token),
+        )
+        .route("/git-host-tokens/{host}", delete(delete_git_host_token))
+        .route(
+            "/pr-webhook-secret",
+            get(get_pr_webhook_secret_status)
+                .put(set_pr_webhook_secret)
+                .delete(delete_pr_webhook_secret),
+        )
         .route(
             "/editors/check-availability",
             get(check_editor_availability),
@@ -139,6 +151,12 @@ async fn update_config(
         ));
     }
 
+    if let Some(template) = &new_config.git_branch_template
+        && let Err(message) = branch_name::validate_template(deployment.git(), template)
+    {
+        return ResponseJson(ApiResponse::error(&message));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
@@ -454,6 +472,75 @@ async fn update_profiles(
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct SetGitHostTokenRequest {
+    pub host: String,
+    pub token: String,
+}
+
+/// Hosts with a stored API token; the tokens themselves are never returned
+async fn list_git_host_tokens(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<String>>> {
+    let hosts = deployment.git_host_credentials().hosts().await;
+    ResponseJson(ApiResponse::success(hosts))
+}
+
+async fn set_git_host_token(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetGitHostTokenRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let host = payload.host.trim();
+    let token = payload.token.trim();
+    if host.is_empty() || token.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Host and token must not be empty",
+        )));
+    }
+    deployment.git_host_credentials().set(host, token).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn delete_git_host_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(host): Path<String>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment.git_host_credentials().remove(&host).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct SetPrWebhookSecretRequest {
+    pub secret: String,
+}
+
+/// Whether a webhook secret is stored; the secret itself is never returned
+async fn get_pr_webhook_secret_status(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<bool>> {
+    let is_set = deployment.pr_webhook_secret().is_set().await;
+    ResponseJson(ApiResponse::success(is_set))
+}
+
+async fn set_pr_webhook_secret(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetPrWebhookSecretRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let secret = payload.secret.trim();
+    if secret.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error("Secret must not be empty")));
+    }
+    deployment.pr_webhook_secret().set(secret).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn delete_pr_webhook_secret(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment.pr_webhook_secret().clear().await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CheckEditorAvailabilityQuery {
     editor_type: EditorType,
@@ -19,7 +19,7 @@ use db::models::{
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use services::services::{file_search::SearchQuery, project::ProjectServiceError};
+use services::services::{branch_name, file_search::SearchQuery, project::ProjectServiceError};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -131,6 +131,12 @@ pub async fn update_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateProject>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, StatusCode> {
+    if let Some(Some(template)) = &payload.branch_name_template
+        && let Err(message) = branch_name::validate_template(deployment.git(), template)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
     match deployment
         .project()
         .update_project(&deployment.db().pool, &existing_project, payload)
@@ -7,14 +7,20 @@ use axum::{
 };
 use db::models::{
     project::SearchResult,
+    quality_gate::{QualityGate, QualityGateInput},
     repo::{Repo, UpdateRepo},
+    workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
-use git::{GitBranch, GitRemote};
+use git::{GitBranch, GitRemote, SparseCone};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    file_search::SearchQuery,
-    git_host::{GitHostError, GitHostProvider, GitHostService, OpenPrInfo, ProviderKind},
+    branch_name,
+    file_search::SearchMode,
+    git_host::{
+        GitHostError, GitHostIssue, GitHostProvider, GitHostService, IssueFilter, OpenPrInfo,
+        ProviderKind,
+    },
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -143,10 +149,42 @@ pub async fn update_repo(
     Path(repo_id): Path<Uuid>,
     ResponseJson(payload): ResponseJson<UpdateRepo>,
 ) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    if let Some(Some(template)) = &payload.branch_name_template {
+        branch_name::validate_template(deployment.git(), template).map_err(ApiError::BadRequest)?;
+    }
     let repo = Repo::update(&deployment.db().pool, repo_id, &payload).await?;
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+pub async fn get_quality_gates(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<QualityGate>>>, ApiError> {
+    let gates = QualityGate::find_by_repo_id(&deployment.db().pool, repo_id).await?;
+    Ok(ResponseJson(ApiResponse::success(gates)))
+}
+
+pub async fn update_quality_gates(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    ResponseJson(payload): ResponseJson<Vec<QualityGateInput>>,
+) -> Result<ResponseJson<ApiResponse<Vec<QualityGate>>>, ApiError> {
+    if payload
+        .iter()
+        .any(|gate| gate.name.trim().is_empty() || gate.script.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest(
+            "Quality gates need a name and a script".to_string(),
+        ));
+    }
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+    let gates = QualityGate::sync_for_repo(&deployment.db().pool, repo.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(gates)))
+}
+
 pub async fn open_repo_in_editor(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -194,10 +232,19 @@ pub async fn open_repo_in_editor(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RepoSearchQuery {
+    pub q: String,
+    #[serde(default)]
+    pub mode: SearchMode,
+    /// Search the workspace's checkout, honouring its sparse-checkout override
+    pub workspace_id: Option<Uuid>,
+}
+
 pub async fn search_repo(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
-    Query(search_query): Query<SearchQuery>,
+    Query(search_query): Query<RepoSearchQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, StatusCode> {
     if search_query.q.trim().is_empty() {
         return Ok(ResponseJson(ApiResponse::error(
@@ -217,9 +264,45 @@ pub async fn search_repo(
         }
     };
 
+    let mut sparse_checkout = repo.sparse_checkout.clone();
+    if let Some(workspace_id) = search_query.workspace_id {
+        match WorkspaceRepo::find_by_workspace_and_repo_id(
+            &deployment.db().pool,
+            workspace_id,
+            repo_id,
+        )
+        .await
+        {
+            // Same precedence as worktree creation: the override wins, empty means full checkout
+            Ok(Some(workspace_repo)) => {
+                if let Some(patterns) = workspace_repo.sparse_checkout {
+                    sparse_checkout = Some(patterns);
+                }
+            }
+            Ok(None) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load repo {} for workspace {}: {}",
+                    repo_id,
+                    workspace_id,
+                    e
+                );
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
     match deployment
         .file_search_cache()
-        .search_repo(&repo.path, &search_query.q, search_query.mode)
+        .search_repo(
+            &repo.path,
+            &search_query.q,
+            search_query.mode,
+            sparse_checkout
+                .as_deref()
+                .and_then(SparseCone::parse)
+                .as_ref(),
+        )
         .await
     {
         Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
@@ -262,7 +345,8 @@ pub async fn list_open_prs(
         None => deployment.git().get_default_remote(&repo.path)?,
     };
 
-    let git_host = match GitHostService::from_url(&remote.url) {
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match GitHostService::from_url(&remote.url, &git_hosts) {
         Ok(host) => host,
         Err(GitHostError::UnsupportedProvider) => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -293,6 +377,80 @@ pub async fn list_open_prs(
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ListIssuesError {
+    CliNotInstalled { provider: ProviderKind },
+    AuthFailed { message: String },
+    UnsupportedProvider,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListIssuesQuery {
+    pub remote: Option<String>,
+    /// Comma separated labels the issues must all have
+    pub labels: Option<String>,
+    pub assignee: Option<String>,
+    pub milestone: Option<String>,
+}
+
+pub async fn list_issues(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    Query(query): Query<ListIssuesQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<GitHostIssue>, ListIssuesError>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+
+    let remote_url = match query.remote {
+        Some(name) => deployment.git().get_remote_url(&repo.path, &name)?,
+        None => deployment.git().get_default_remote(&repo.path)?.url,
+    };
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let filter = IssueFilter {
+        labels: query
+            .labels
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect(),
+        assignee: non_empty(query.assignee),
+        milestone: non_empty(query.milestone),
+    };
+
+    let git_hosts = deployment.git_hosts().await;
+    let result = match GitHostService::from_url(&remote_url, &git_hosts) {
+        Ok(git_host) => git_host.list_issues(&repo.path, &remote_url, &filter).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(issues) => Ok(ResponseJson(ApiResponse::success(issues))),
+        Err(GitHostError::CliNotInstalled { provider }) => Ok(ResponseJson(
+            ApiResponse::error_with_data(ListIssuesError::CliNotInstalled { provider }),
+        )),
+        Err(GitHostError::AuthFailed(message)) => Ok(ResponseJson(ApiResponse::error_with_data(
+            ListIssuesError::AuthFailed { message },
+        ))),
+        Err(GitHostError::UnsupportedProvider) => Ok(ResponseJson(ApiResponse::error_with_data(
+            ListIssuesError::UnsupportedProvider,
+        ))),
+        Err(e) => {
+            tracing::error!("Failed to list issues for repo {}: {}", repo_id, e);
+            Ok(ResponseJson(ApiResponse::error(&e.to_string())))
+        }
+    }
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/repos", get(get_repos).post(register_repo))
@@ -302,7 +460,12 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/repos/{repo_id}", get(get_repo).put(update_repo))
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
         .route("/repos/{repo_id}/remotes", get(get_repo_remotes))
+        .route(
+            "/repos/{repo_id}/quality-gates",
+            get(get_quality_gates).put(update_quality_gates),
+        )
         .route("/repos/{repo_id}/prs", get(list_open_prs))
+        .route("/repos/{repo_id}/issues", get(list_issues))
         .route("/repos/{repo_id}/search", get(search_repo))
         .route("/repos/{repo_id}/open-editor", post(open_repo_in_editor))
 }
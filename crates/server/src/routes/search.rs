@@ -4,7 +4,7 @@ use axum::{
     response::Json as ResponseJson,
     routing::get,
 };
-use db::models::{project::SearchResult, repo::Repo};
+use db::models::{project::SearchResult, repo::Repo, workspace_repo::WorkspaceRepo};
 use deployment::Deployment;
 use serde::Deserialize;
 use services::services::file_search::{SearchMode, SearchQuery};
@@ -19,6 +19,8 @@ pub struct MultiRepoSearchQuery {
     #[serde(default)]
     pub mode: SearchMode,
     pub repo_ids: String,
+    /// Search the workspace's checkouts, honouring their sparse-checkout overrides
+    pub workspace_id: Option<Uuid>,
 }
 
 pub async fn search_files(
@@ -45,7 +47,20 @@ pub async fn search_files(
         )));
     }
 
-    let repos = Repo::find_by_ids(&deployment.db().pool, &repo_ids).await?;
+    let mut repos = Repo::find_by_ids(&deployment.db().pool, &repo_ids).await?;
+    if let Some(workspace_id) = query.workspace_id {
+        let workspace_repos =
+            WorkspaceRepo::find_by_workspace_id(&deployment.db().pool, workspace_id).await?;
+        for repo in &mut repos {
+            let sparse_override = workspace_repos
+                .iter()
+                .find(|wr| wr.repo_id == repo.id)
+                .and_then(|wr| wr.sparse_checkout.clone());
+            if let Some(patterns) = sparse_override {
+                repo.sparse_checkout = Some(patterns);
+            }
+        }
+    }
 
     let search_query = SearchQuery {
         q: query.q,
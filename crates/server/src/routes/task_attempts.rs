@@ -1,8 +1,14 @@
+pub mod backport;
 pub mod codex_setup;
+pub mod commits;
 pub mod cursor_setup;
+pub mod disk_usage;
 pub mod gh_cli_setup;
+pub mod hunks;
 pub mod images;
 pub mod pr;
+pub mod quality_gates;
+pub mod transfer;
 pub mod workspace_summary;
 
 use std::{
@@ -13,7 +19,7 @@ use std::{
 use axum::{
     Extension, Json, Router,
     extract::{
-        Path as AxumPath, Query, State,
+        DefaultBodyLimit, Path as AxumPath, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
@@ -40,16 +46,26 @@ use executors::{
     executors::{CodingAgent, ExecutorError},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
-use git::{ConflictOp, GitCliError, GitService, GitServiceError};
+use git::{
+    CommitOptions, ConflictOp, ConflictedFile, DiffTarget, GitCliError, GitService, GitServiceError,
+};
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService, diff_stream, remote_client::RemoteClientError, remote_sync,
+    branch_archive, commit_identity,
+    commit_message::{self, CommitMessageInput},
+    container::ContainerService,
+    diff_stream,
+    quality_gates::QualityGateResult,
+    remote_client::RemoteClientError,
+    remote_sync,
     workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::{api::workspaces::CreateWorkspaceRequest, response::ApiResponse};
+use utils::{
+    api::workspaces::CreateWorkspaceRequest, diff::DiffViewOptions, response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{
@@ -74,6 +90,20 @@ pub struct ContinueRebaseRequest {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ResolveConflictsWithAgentRequest {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ResolveConflictsError {
+    NoConflicts,
+    ProcessAlreadyRunning,
+    NoAgentConfigured,
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
@@ -85,6 +115,7 @@ pub enum GitOperationError {
         target_branch: String,
     },
     RebaseInProgress,
+    ProcessAlreadyRunning,
 }
 
 #[derive(Debug, Deserialize)]
@@ -201,12 +232,22 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// `simple_id` of the remote issue the workspace will be linked to, used
+    /// for the `{issue}` branch name value
+    #[serde(default)]
+    #[ts(optional)]
+    pub linked_issue_simple_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
     pub target_branch: String,
+    /// Overrides the repo's sparse-checkout directories for this attempt.
+    /// An empty string forces a full checkout.
+    #[serde(default)]
+    #[ts(optional)]
+    pub sparse_checkout: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -217,6 +258,28 @@ pub struct RunAgentSetupRequest {
 #[derive(Debug, Serialize, TS)]
 pub struct RunAgentSetupResponse {}
 
+/// Compute agent_working_dir based on repo count:
+/// - Single repo: join repo name with default_working_dir (if set), or just repo name
+/// - Multiple repos: use None (agent runs in workspace root)
+pub(crate) async fn agent_working_dir_for_repos(
+    pool: &sqlx::SqlitePool,
+    repos: &[WorkspaceRepoInput],
+) -> Result<Option<String>, ApiError> {
+    let [workspace_repo] = repos else {
+        return Ok(None);
+    };
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    Ok(match repo.default_working_dir {
+        Some(subdir) => {
+            let path = PathBuf::from(&repo.name).join(&subdir);
+            Some(path.to_string_lossy().to_string())
+        }
+        None => Some(repo.name),
+    })
+}
+
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
@@ -230,33 +293,26 @@ pub async fn create_task_attempt(
         ));
     }
 
+    deployment.container().enforce_workspace_quota().await?;
+
     let pool = &deployment.db().pool;
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    // Compute agent_working_dir based on repo count:
-    // - Single repo: join repo name with default_working_dir (if set), or just repo name
-    // - Multiple repos: use None (agent runs in workspace root)
-    let agent_working_dir = if payload.repos.len() == 1 {
-        let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
-            .await?
-            .ok_or(RepoError::NotFound)?;
-        match repo.default_working_dir {
-            Some(subdir) => {
-                let path = PathBuf::from(&repo.name).join(&subdir);
-                Some(path.to_string_lossy().to_string())
-            }
-            None => Some(repo.name),
-        }
-    } else {
-        None
-    };
+    let agent_working_dir = agent_working_dir_for_repos(pool, &payload.repos).await?;
 
     let attempt_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = payload.repos.iter().map(|r| r.repo_id).collect();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
+        .git_branch_from_workspace(
+            &attempt_id,
+            &task,
+            &repo_ids,
+            Some(&executor_profile_id),
+            payload.linked_issue_simple_id.as_deref(),
+        )
         .await;
 
     let workspace = Workspace::create(
@@ -276,6 +332,7 @@ pub async fn create_task_attempt(
         .map(|r| CreateWorkspaceRepo {
             repo_id: r.repo_id,
             target_branch: r.target_branch.clone(),
+            sparse_checkout: r.sparse_checkout.clone(),
         })
         .collect();
 
@@ -342,6 +399,7 @@ pub async fn run_agent_setup(
 pub async fn stream_task_attempt_diff_ws(
     ws: WebSocketUpgrade,
     Query(params): Query<DiffStreamQuery>,
+    Query(view_options): Query<DiffViewOptions>,
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
@@ -349,7 +407,9 @@ pub async fn stream_task_attempt_diff_ws(
 
     let stats_only = params.stats_only;
     ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_task_attempt_diff_ws(socket, deployment, workspace, stats_only).await
+        if let Err(e) =
+            handle_task_attempt_diff_ws(socket, deployment, workspace, stats_only, view_options)
+                .await
         {
             tracing::warn!("diff WS closed: {}", e);
         }
@@ -361,13 +421,14 @@ async fn handle_task_attempt_diff_ws(
     deployment: DeploymentImpl,
     workspace: Workspace,
     stats_only: bool,
+    view_options: DiffViewOptions,
 ) -> anyhow::Result<()> {
     use futures_util::{SinkExt, StreamExt, TryStreamExt};
     use utils::log_msg::LogMsg;
 
     let stream = deployment
         .container()
-        .stream_diff(&workspace, stats_only)
+        .stream_diff(&workspace, stats_only, view_options)
         .await?;
 
     let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
@@ -460,11 +521,152 @@ async fn handle_workspaces_ws(
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Overrides the generated squash-merge commit message
+    #[serde(default)]
+    #[ts(optional)]
+    pub commit_message: Option<String>,
+    /// Merge even though required quality gates have not passed
+    #[serde(default)]
+    #[ts(optional)]
+    pub bypass_quality_gates: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum MergeError {
+    QualityGatesFailed {
+        gates: Vec<QualityGateResult>,
+        /// Whether the user may override the gates, see the
+        /// `allow_quality_gate_bypass` setting
+        bypass_allowed: bool,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeCommitMessageQuery {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct MergeCommitMessageResponse {
+    pub message: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Push even though required quality gates have not passed
+    #[serde(default)]
+    #[ts(optional)]
+    pub bypass_quality_gates: Option<bool>,
+}
+
+/// Squash-merge commit message for a workspace repo: a Conventional Commits
+/// message built from the branch diff and the latest agent summary when
+/// enabled, otherwise the task title and description.
+async fn merge_commit_message(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    task: &Task,
+    repo: &Repo,
+    workspace_repo: &WorkspaceRepo,
+) -> Result<String, ApiError> {
+    if !deployment
+        .config()
+        .read()
+        .await
+        .conventional_commits_enabled
+    {
+        let task_uuid_str = task.id.to_string();
+        let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
+
+        let mut commit_message = format!("{} (vibe-kanban {})", task.title, first_uuid_section);
+
+        // Add description on next line if it exists
+        if let Some(description) = &task.description
+            && !description.trim().is_empty()
+        {
+            commit_message.push_str("\n\n");
+            commit_message.push_str(description);
+        }
+        return Ok(commit_message);
+    }
+
+    let changed_paths: Vec<String> = deployment
+        .git()
+        .get_diffs(
+            DiffTarget::Branch {
+                repo_path: &repo.path,
+                branch_name: &workspace.branch,
+                base_branch: &workspace_repo.target_branch,
+            },
+            None,
+        )?
+        .iter()
+        .map(GitService::diff_path)
+        .collect();
+    let summary =
+        CodingAgentTurn::find_latest_summary_by_workspace_id(&deployment.db().pool, workspace.id)
+            .await?;
+
+    Ok(commit_message::repo_commit_message(
+        repo,
+        &CommitMessageInput {
+            task_title: &task.title,
+            summary: summary.as_deref(),
+            changed_paths: &changed_paths,
+        },
+    ))
+}
+
+/// Identity and signing for commits written in the workspace's repos (merges,
+/// rebases, history rewrites), crediting the executor profile of the
+/// workspace's latest session
+async fn workspace_commit_options(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo: &Repo,
+) -> Result<CommitOptions, ApiError> {
+    let pool = &deployment.db().pool;
+    let executor_profile = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+        Some(session) => {
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+        }
+        None => None,
+    };
+    let identity = deployment.config().read().await.commit_identity.clone();
+    Ok(commit_identity::commit_options(
+        &identity,
+        repo,
+        executor_profile.as_ref(),
+    ))
+}
+
+pub async fn get_merge_commit_message(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<MergeCommitMessageQuery>,
+) -> Result<ResponseJson<ApiResponse<MergeCommitMessageResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::TaskNotFound))?;
+
+    let message =
+        merge_commit_message(&deployment, &workspace, &task, &repo, &workspace_repo).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        MergeCommitMessageResponse { message },
+    )))
 }
 
 #[axum::debug_handler]
@@ -472,7 +674,7 @@ pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<MergeTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<(), MergeError>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -511,31 +713,54 @@ pub async fn merge_task_attempt(
         .ensure_container_exists(&workspace)
         .await?;
     let workspace_path = Path::new(&container_ref);
-    let worktree_path = workspace_path.join(repo.name);
+    let worktree_path = workspace_path.join(&repo.name);
+
+    let blocking_gates =
+        quality_gates::blocking_quality_gates(&deployment, workspace.id, repo.id, &worktree_path)
+            .await?;
+    if !blocking_gates.is_empty() {
+        let bypass_allowed = quality_gates::bypass_allowed(&deployment).await;
+        if !(bypass_allowed && request.bypass_quality_gates.unwrap_or(false)) {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                MergeError::QualityGatesFailed {
+                    gates: blocking_gates,
+                    bypass_allowed,
+                },
+            )));
+        }
+        quality_gates::track_quality_gates_bypassed(
+            &deployment,
+            workspace.id,
+            repo.id,
+            "merge",
+            &blocking_gates,
+        )
+        .await;
+    }
 
     let task = workspace
         .parent_task(pool)
         .await?
         .ok_or(ApiError::Workspace(WorkspaceError::TaskNotFound))?;
-    let task_uuid_str = task.id.to_string();
-    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
 
-    let mut commit_message = format!("{} (vibe-kanban {})", task.title, first_uuid_section);
-
-    // Add description on next line if it exists
-    if let Some(description) = &task.description
-        && !description.trim().is_empty()
+    let commit_message = match request
+        .commit_message
+        .filter(|message| !message.trim().is_empty())
     {
-        commit_message.push_str("\n\n");
-        commit_message.push_str(description);
-    }
+        Some(message) => message,
+        None => {
+            merge_commit_message(&deployment, &workspace, &task, &repo, &workspace_repo).await?
+        }
+    };
 
-    let merge_commit_id = deployment.git().merge_changes(
+    let commit_options = workspace_commit_options(&deployment, &workspace, &repo).await?;
+    let merge_commit_id = deployment.git().merge_changes_with_options(
         &repo.path,
         &worktree_path,
         &workspace.branch,
         &workspace_repo.target_branch,
         &commit_message,
+        &commit_options,
     )?;
 
     Merge::create_direct(
@@ -589,11 +814,20 @@ pub async fn push_task_attempt_branch(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
+    if let Some(error) =
+        check_push_quality_gates(&deployment, &workspace, &request, &worktree_path).await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(error)));
+    }
+
     match deployment
         .git()
         .push_to_remote(&worktree_path, &workspace.branch, false)
     {
-        Ok(_) => Ok(ResponseJson(ApiResponse::success(()))),
+        Ok(_) => {
+            pr::refresh_pr_description_after_push(&deployment, &workspace, request.repo_id);
+            Ok(ResponseJson(ApiResponse::success(())))
+        }
         Err(GitServiceError::GitCLI(GitCliError::PushRejected(_))) => Ok(ResponseJson(
             ApiResponse::error_with_data(PushError::ForcePushRequired),
         )),
@@ -624,9 +858,16 @@ pub async fn force_push_task_attempt_branch(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
+    if let Some(error) =
+        check_push_quality_gates(&deployment, &workspace, &request, &worktree_path).await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(error)));
+    }
+
     deployment
         .git()
         .push_to_remote(&worktree_path, &workspace.branch, true)?;
+    pr::refresh_pr_description_after_push(&deployment, &workspace, request.repo_id);
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
@@ -635,6 +876,48 @@ pub async fn force_push_task_attempt_branch(
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PushError {
     ForcePushRequired,
+    QualityGatesFailed {
+        gates: Vec<QualityGateResult>,
+        /// Whether the user may override the gates, see the
+        /// `allow_quality_gate_bypass` setting
+        bypass_allowed: bool,
+    },
+}
+
+/// The error to return when required quality gates block a push, unless the
+/// request bypasses them
+async fn check_push_quality_gates(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    request: &PushTaskAttemptRequest,
+    worktree_path: &Path,
+) -> Result<Option<PushError>, ApiError> {
+    let blocking_gates = quality_gates::blocking_quality_gates(
+        deployment,
+        workspace.id,
+        request.repo_id,
+        worktree_path,
+    )
+    .await?;
+    if blocking_gates.is_empty() {
+        return Ok(None);
+    }
+    let bypass_allowed = quality_gates::bypass_allowed(deployment).await;
+    if !(bypass_allowed && request.bypass_quality_gates.unwrap_or(false)) {
+        return Ok(Some(PushError::QualityGatesFailed {
+            gates: blocking_gates,
+            bypass_allowed,
+        }));
+    }
+    quality_gates::track_quality_gates_bypassed(
+        deployment,
+        workspace.id,
+        request.repo_id,
+        "push",
+        &blocking_gates,
+    )
+    .await;
+    Ok(None)
 }
 
 #[derive(serde::Deserialize, TS)]
@@ -739,6 +1022,8 @@ pub struct BranchStatus {
     pub conflicted_files: Vec<String>,
     /// True if the target branch is a remote branch (merging not allowed, must use PR)
     pub is_target_remote: bool,
+    /// Latest result of each quality gate configured for the repo
+    pub quality_gates: Vec<QualityGateResult>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -850,6 +1135,14 @@ pub async fn get_task_attempt_branch_status(
             }
         };
 
+        let quality_gates = quality_gates::quality_gate_results(
+            &deployment,
+            workspace.id,
+            repo.id,
+            head_oid.as_deref(),
+        )
+        .await?;
+
         let (remote_ahead, remote_behind) = if let Some(Merge::Pr(PrMerge {
             pr_info:
                 PullRequestInfo {
@@ -888,6 +1181,7 @@ pub async fn get_task_attempt_branch_status(
                 conflict_op,
                 conflicted_files,
                 is_target_remote: target_branch_type == BranchType::Remote,
+                quality_gates,
             },
         });
     }
@@ -908,6 +1202,20 @@ pub struct ChangeTargetBranchResponse {
     pub status: (usize, usize),
 }
 
+#[derive(serde::Deserialize, Debug, TS)]
+pub struct WidenSparseCheckoutRequest {
+    pub repo_id: Uuid,
+    /// Repository-relative directories to add to the sparse-checkout cone
+    pub paths: Vec<String>,
+}
+
+#[derive(serde::Serialize, Debug, TS)]
+pub struct WidenSparseCheckoutResponse {
+    pub repo_id: Uuid,
+    /// Directories now checked out, or `null` if the worktree is a full checkout
+    pub sparse_checkout: Option<String>,
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct RenameBranchRequest {
     pub new_branch_name: String,
@@ -983,6 +1291,53 @@ pub async fn change_target_branch(
     )))
 }
 
+#[axum::debug_handler]
+pub async fn widen_sparse_checkout(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<WidenSparseCheckoutRequest>,
+) -> Result<ResponseJson<ApiResponse<WidenSparseCheckoutResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    if payload.paths.iter().all(|p| p.trim().is_empty()) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "At least one directory is required",
+        )));
+    }
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let sparse_checkout = deployment
+        .git()
+        .widen_sparse_checkout(&worktree_path, &payload.paths)?
+        .map(|cone| cone.to_patterns());
+
+    // Persist as a workspace override so recreated worktrees keep the wider cone
+    if let Some(patterns) = &sparse_checkout {
+        WorkspaceRepo::update_sparse_checkout(pool, workspace.id, repo.id, Some(patterns)).await?;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        WidenSparseCheckoutResponse {
+            repo_id: repo.id,
+            sparse_checkout,
+        },
+    )))
+}
+
 #[axum::debug_handler]
 pub async fn rename_branch(
     Extension(workspace): Extension<Workspace>,
@@ -1178,12 +1533,14 @@ pub async fn rebase_task_attempt(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
+    let commit_options = workspace_commit_options(&deployment, &workspace, &repo).await?;
     let result = deployment.git().rebase_branch(
         &repo.path,
         &worktree_path,
         &new_base_branch,
         &old_base_branch,
         &workspace.branch.clone(),
+        &commit_options,
     );
     if let Err(e) = result {
         return match e {
@@ -1266,11 +1623,176 @@ pub async fn continue_rebase_task_attempt(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
-    deployment.git().continue_rebase(&worktree_path)?;
+    let commit_options = workspace_commit_options(&deployment, &workspace, &repo).await?;
+    deployment
+        .git()
+        .continue_rebase(&worktree_path, &commit_options)?;
 
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Cap on the conflict hunk text embedded in the prompt, per side
+const MAX_CONFLICT_SIDE_CHARS: usize = 4_000;
+
+fn truncate_conflict_side(text: &str) -> String {
+    if text.len() <= MAX_CONFLICT_SIDE_CHARS {
+        return text.to_string();
+    }
+    let mut end = MAX_CONFLICT_SIDE_CHARS;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n... (truncated)\n", &text[..end])
+}
+
+fn build_conflict_resolution_prompt(
+    op: &ConflictOp,
+    branch: &str,
+    target_branch: &str,
+    repo_name: &str,
+    files: &[ConflictedFile],
+) -> String {
+    let (op_name, header) = match op {
+        ConflictOp::Rebase => (
+            "rebase",
+            format!(
+                "Rebase conflicts while rebasing '{branch}' onto '{target_branch}' in repository '{repo_name}'. \
+                 During a rebase, \"ours\" is '{target_branch}' and \"theirs\" is the commit from '{branch}' being replayed."
+            ),
+        ),
+        ConflictOp::Merge => (
+            "merge",
+            format!("Merge conflicts while merging into '{branch}' in repository '{repo_name}'."),
+        ),
+        ConflictOp::CherryPick => (
+            "cherry-pick",
+            format!("Cherry-pick conflicts on '{branch}' in repository '{repo_name}'."),
+        ),
+        ConflictOp::Revert => (
+            "revert",
+            format!("Revert conflicts on '{branch}' in repository '{repo_name}'."),
+        ),
+    };
+
+    let mut prompt = header;
+    prompt.push_str("\n\nResolve the following conflicts:\n");
+
+    for file in files {
+        prompt.push_str(&format!("\n## {}\n", file.path));
+        if file.hunks.is_empty() {
+            prompt.push_str(
+                "No textual conflict markers (binary file or delete/modify conflict). \
+                 Decide which version to keep.\n",
+            );
+            continue;
+        }
+        for (idx, hunk) in file.hunks.iter().enumerate() {
+            let line = hunk.start_line;
+            prompt.push_str(&format!("\nConflict {} (line {line}):\n", idx + 1));
+            prompt.push_str(&format!(
+                "Ours ({}):\n```\n{}```\n",
+                hunk.ours_label.as_deref().unwrap_or("HEAD"),
+                truncate_conflict_side(&hunk.ours)
+            ));
+            if let Some(base) = &hunk.base {
+                prompt.push_str(&format!(
+                    "Common ancestor:\n```\n{}```\n",
+                    truncate_conflict_side(base)
+                ));
+            }
+            prompt.push_str(&format!(
+                "Theirs ({}):\n```\n{}```\n",
+                hunk.theirs_label.as_deref().unwrap_or("incoming"),
+                truncate_conflict_side(&hunk.theirs)
+            ));
+        }
+    }
+
+    prompt.push_str(&format!(
+        "\nEdit each file so that it combines the intent of both sides, and remove every \
+         conflict marker. Do not run `git {op_name} --continue`, `git commit`, or abort the \
+         {op_name}; it is continued automatically once no conflict markers remain."
+    ));
+    prompt
+}
+
+#[axum::debug_handler]
+pub async fn resolve_conflicts_with_agent(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictsWithAgentRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess, ResolveConflictsError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            ResolveConflictsError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let Some(op) = deployment.git().detect_conflict_op(&worktree_path)? else {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            ResolveConflictsError::NoConflicts,
+        )));
+    };
+    let files = deployment
+        .git()
+        .get_conflicted_file_details(&worktree_path)?;
+    if files.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            ResolveConflictsError::NoConflicts,
+        )));
+    }
+
+    let prompt = build_conflict_resolution_prompt(
+        &op,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+        &repo.name,
+        &files,
+    );
+
+    let Some(execution_process) = deployment
+        .container()
+        .start_agent_follow_up(&workspace, prompt)
+        .await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            ResolveConflictsError::NoAgentConfigured,
+        )));
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_conflicts_agent_resolution_started",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": payload.repo_id.to_string(),
+                "conflicted_files": files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
 #[axum::debug_handler]
 pub async fn start_dev_server(
     Extension(workspace): Extension<Workspace>,
@@ -1781,6 +2303,11 @@ pub async fn delete_workspace(
     let workspace_dir = workspace.container_ref.clone().map(PathBuf::from);
     let repositories = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
 
+    // Keep unmerged work reachable before the worktree and branch go away
+    let branch_archive = deployment.config().read().await.branch_archive;
+    branch_archive::archive_workspace_branches(pool, deployment.git(), &workspace, branch_archive)
+        .await;
+
     // Nullify parent_workspace_id for any child tasks before deletion
     let children_affected = Task::nullify_children_by_workspace_id(pool, workspace.id).await?;
     if children_affected > 0 {
@@ -1962,22 +2489,49 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .route("/run-setup-script", post(run_setup_script))
                 .route("/run-cleanup-script", post(run_cleanup_script))
                 .route("/run-archive-script", post(run_archive_script))
+                .route("/quality-gates/run", post(quality_gates::run_quality_gates))
+                .route(
+                    "/quality-gates/send-to-agent",
+                    post(quality_gates::send_quality_gate_failure_to_agent),
+                )
                 .route("/branch-status", get(get_task_attempt_branch_status))
                 .route("/diff/ws", get(stream_task_attempt_diff_ws))
                 .route("/merge", post(merge_task_attempt))
+                .route("/merge/commit-message", get(get_merge_commit_message))
                 .route("/push", post(push_task_attempt_branch))
                 .route("/push/force", post(force_push_task_attempt_branch))
                 .route("/rebase", post(rebase_task_attempt))
                 .route("/rebase/continue", post(continue_rebase_task_attempt))
+                .route("/backport", post(backport::backport_workspace))
+                .route("/commits", get(commits::get_workspace_commits))
+                .route("/commits/squash", post(commits::squash_commits))
+                .route("/commits/reword", post(commits::reword_commit))
+                .route("/commits/drop", post(commits::drop_commit))
+                .route("/commits/diff/ws", get(commits::stream_commit_diff_ws))
+                .route("/blame", get(commits::get_file_blame))
+                .route("/hunks", get(hunks::list_hunks))
+                .route("/hunks/accept", post(hunks::accept_hunks))
+                .route("/hunks/unstage", post(hunks::unstage_hunks))
+                .route("/hunks/reject", post(hunks::reject_hunks))
+                .route("/export", get(transfer::export_workspace))
                 .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+                .route(
+                    "/conflicts/resolve-with-agent",
+                    post(resolve_conflicts_with_agent),
+                )
                 .route("/pr", post(pr::create_pr))
                 .route("/pr/attach", post(pr::attach_existing_pr))
                 .route("/pr/comments", get(pr::get_pr_comments))
+                .route("/pr/review-threads", get(pr::get_pr_review_threads))
+                .route("/pr/review-follow-up", post(pr::address_pr_review))
+                .route("/pr/draft", post(pr::set_pr_draft))
+                .route("/pr/auto-merge", post(pr::set_pr_auto_merge))
                 .route("/open-editor", post(open_task_attempt_in_editor))
                 .route("/children", get(get_task_attempt_children))
                 .route("/stop", post(stop_task_attempt_execution))
                 .route("/change-target-branch", post(change_target_branch))
                 .route("/rename-branch", post(rename_branch))
+                .route("/sparse-checkout/widen", post(widen_sparse_checkout))
                 .route("/repos", get(get_task_attempt_repos))
                 .route("/first-message", get(get_first_user_message))
                 .route("/mark-seen", put(mark_seen))
@@ -1991,7 +2545,12 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
         .route("/from-pr", post(pr::create_workspace_from_pr))
+        .route(
+            "/import",
+            post(transfer::import_workspace).layer(DefaultBodyLimit::max(200 * 1024 * 1024)),
+        )
         .route("/count", get(get_workspace_count))
+        .route("/disk-usage", get(disk_usage::get_disk_usage))
         .route("/stream/ws", get(stream_workspaces_ws))
         .route("/summary", post(workspace_summary::get_workspace_summaries))
         .nest("/{id}", task_attempt_id_router)
@@ -0,0 +1,379 @@
+use std::path::Path;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::ExecutionProcess,
+    merge::Merge,
+    repo::{Repo, RepoError},
+    session::Session,
+    task::Task,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use git::{ConflictOp, GitServiceError};
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use super::{
+    build_conflict_resolution_prompt,
+    pr::{CreatePrApiRequest, create_pr},
+    workspace_commit_options,
+};
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct BackportRequest {
+    pub repo_id: Uuid,
+    pub target_branches: Vec<String>,
+    /// Start a coding agent in every derived workspace that stops on conflicts
+    #[serde(default)]
+    pub resolve_conflicts_with_agent: bool,
+    /// Open a pull request for every derived workspace the changes applied to
+    #[serde(default)]
+    pub create_prs: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum BackportStatus {
+    Applied,
+    /// The cherry-pick is left in progress in the derived workspace, to be
+    /// resolved through the usual conflict actions
+    Conflicts {
+        message: String,
+        conflicted_files: Vec<String>,
+        /// Coding agent started to resolve the conflicts
+        execution_process_id: Option<Uuid>,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum BackportPr {
+    Created { url: String },
+    Failed { message: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct BackportResult {
+    pub target_branch: String,
+    /// Derived workspace; `None` when it could not be created
+    pub workspace: Option<Workspace>,
+    pub status: BackportStatus,
+    /// Pull request of the derived workspace, when requested and the changes
+    /// applied
+    pub pr: Option<BackportPr>,
+}
+
+/// Replay a workspace's changes onto other target branches. Each target gets a
+/// derived workspace of the same task, branched from the target, where the
+/// squash merge commit (or, before merging, the branch's commits) is
+/// cherry-picked. With `create_prs`, a pull request is opened for each derived
+/// workspace the changes applied to.
+pub async fn backport_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BackportRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackportResult>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if payload.target_branches.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one target branch is required".to_string(),
+        ));
+    }
+    deployment.container().enforce_workspace_quota().await?;
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let merge_commit = Merge::find_by_workspace_and_repo_id(pool, workspace.id, repo.id)
+        .await?
+        .iter()
+        .find_map(Merge::merge_commit);
+    let commits = deployment.git().backport_commits(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+        merge_commit.as_deref(),
+    )?;
+    if commits.is_empty() {
+        return Err(ApiError::BadRequest(
+            "The workspace has no commits to backport".to_string(),
+        ));
+    }
+
+    let agent_profile = if payload.resolve_conflicts_with_agent {
+        Some(source_executor_profile(&deployment, &workspace).await?)
+    } else {
+        None
+    };
+
+    let mut results = Vec::with_capacity(payload.target_branches.len());
+    for target_branch in &payload.target_branches {
+        if !deployment
+            .git()
+            .check_branch_exists(&repo.path, target_branch)?
+        {
+            results.push(BackportResult {
+                target_branch: target_branch.clone(),
+                workspace: None,
+                status: BackportStatus::Failed {
+                    message: format!("Branch '{target_branch}' does not exist in the repository"),
+                },
+                pr: None,
+            });
+            continue;
+        }
+
+        let derived = create_derived_workspace(
+            &deployment,
+            &task,
+            &workspace,
+            &workspace_repo,
+            target_branch,
+        )
+        .await?;
+        let status = match apply_backport(&deployment, &derived, &repo, &commits).await {
+            Ok(()) => BackportStatus::Applied,
+            Err(ApiError::GitService(GitServiceError::MergeConflicts {
+                message,
+                conflicted_files,
+            })) => {
+                let execution_process_id = match &agent_profile {
+                    Some(profile) => start_conflict_agent(
+                        &deployment,
+                        &derived,
+                        &repo,
+                        target_branch,
+                        profile.clone(),
+                    )
+                    .await
+                    .map(|process| process.id),
+                    None => None,
+                };
+                BackportStatus::Conflicts {
+                    message,
+                    conflicted_files,
+                    execution_process_id,
+                }
+            }
+            Err(e) => BackportStatus::Failed {
+                message: e.to_string(),
+            },
+        };
+        let derived = Workspace::find_by_id(pool, derived.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let pr = if payload.create_prs && matches!(status, BackportStatus::Applied) {
+            Some(create_backport_pr(&deployment, &task, &derived, repo.id, target_branch).await)
+        } else {
+            None
+        };
+        results.push(BackportResult {
+            target_branch: target_branch.clone(),
+            workspace: Some(derived),
+            status,
+            pr,
+        });
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_backported",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+                "target_count": payload.target_branches.len(),
+                "conflict_count": results
+                    .iter()
+                    .filter(|r| matches!(r.status, BackportStatus::Conflicts { .. }))
+                    .count(),
+                "pr_count": results
+                    .iter()
+                    .filter(|r| matches!(r.pr, Some(BackportPr::Created { .. })))
+                    .count(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+/// Executor profile of the source workspace's latest coding agent run, or the
+/// configured default.
+async fn source_executor_profile(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<ExecutorProfileId, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(session) = Session::find_latest_by_workspace_id(pool, workspace.id).await?
+        && let Some(profile) =
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+    {
+        return Ok(profile);
+    }
+    Ok(deployment.config().read().await.executor_profile.clone())
+}
+
+async fn create_derived_workspace(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    source: &Workspace,
+    source_repo: &WorkspaceRepo,
+    target_branch: &str,
+) -> Result<Workspace, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_workspace(&workspace_id, task, &[source_repo.repo_id], None, None)
+        .await;
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir: source.agent_working_dir.clone(),
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+    WorkspaceRepo::create_many(
+        pool,
+        workspace.id,
+        &[CreateWorkspaceRepo {
+            repo_id: source_repo.repo_id,
+            target_branch: target_branch.to_string(),
+            sparse_checkout: source_repo.sparse_checkout.clone(),
+        }],
+    )
+    .await?;
+    let name = match &source.name {
+        Some(name) => format!("{name} → {target_branch}"),
+        None => format!("Backport to {target_branch}"),
+    };
+    Workspace::update(pool, workspace.id, None, None, Some(&name)).await?;
+    Ok(workspace)
+}
+
+async fn apply_backport(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo: &Repo,
+    commits: &[String],
+) -> Result<(), ApiError> {
+    let container_ref = deployment.container().create(workspace).await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+    let commit_options = workspace_commit_options(deployment, workspace, repo).await?;
+    deployment
+        .git()
+        .cherry_pick_commits(&worktree_path, commits, &commit_options)?;
+    Ok(())
+}
+
+/// Open the pull request of a derived workspace through the usual create-PR
+/// route, so its required quality gates are checked the same way
+async fn create_backport_pr(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    workspace: &Workspace,
+    repo_id: Uuid,
+    target_branch: &str,
+) -> BackportPr {
+    let request = CreatePrApiRequest {
+        title: format!("{} ({target_branch} backport)", task.title),
+        body: task.description.clone(),
+        target_branch: Some(target_branch.to_string()),
+        draft: None,
+        repo_id,
+        auto_generate_description: deployment.config().read().await.pr_auto_description_enabled,
+        bypass_quality_gates: None,
+    };
+    match create_pr(
+        Extension(workspace.clone()),
+        State(deployment.clone()),
+        Json(request),
+    )
+    .await
+    {
+        Ok(ResponseJson(response)) if response.is_success() => BackportPr::Created {
+            url: response.into_data().unwrap_or_default(),
+        },
+        Ok(ResponseJson(response)) => BackportPr::Failed {
+            message: response
+                .into_error_data()
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| "Failed to create the pull request".to_string()),
+        },
+        Err(e) => BackportPr::Failed {
+            message: e.to_string(),
+        },
+    }
+}
+
+async fn start_conflict_agent(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo: &Repo,
+    target_branch: &str,
+    profile: ExecutorProfileId,
+) -> Option<ExecutionProcess> {
+    let result = async {
+        let workspace = Workspace::find_by_id(&deployment.db().pool, workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let container_ref = deployment
+            .container()
+            .ensure_container_exists(&workspace)
+            .await?;
+        let worktree_path = Path::new(&container_ref).join(&repo.name);
+        let files = deployment
+            .git()
+            .get_conflicted_file_details(&worktree_path)?;
+        let prompt = build_conflict_resolution_prompt(
+            &ConflictOp::CherryPick,
+            &workspace.branch,
+            target_branch,
+            &repo.name,
+            &files,
+        );
+        Ok::<_, ApiError>(
+            deployment
+                .container()
+                .start_agent_follow_up_with_fallback(&workspace, prompt, Some(profile))
+                .await?,
+        )
+    }
+    .await;
+    match result {
+        Ok(process) => process,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to start conflict resolution agent for backport workspace {}: {}",
+                workspace.id,
+                e
+            );
+            None
+        }
+    }
+}
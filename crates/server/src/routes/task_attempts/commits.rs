@@ -0,0 +1,467 @@
+use std::{collections::HashMap, path::Path};
+
+use axum::{
+    Extension, Json,
+    extract::{
+        Query, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Json as ResponseJson},
+};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    repo::{Repo, RepoError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use git::{BlameRange, CommitInfo, ConflictOp, GitServiceError, HistoryRewrite};
+use serde::{Deserialize, Serialize};
+use services::services::{container::ContainerService, diff_stream};
+use ts_rs::TS;
+use utils::{diff::DiffViewOptions, response::ApiResponse};
+use uuid::Uuid;
+
+use super::{GitOperationError, workspace_commit_options};
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceCommitsQuery {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileBlameQuery {
+    pub repo_id: Uuid,
+    /// Path of the file relative to the repository root
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommitDiffStreamQuery {
+    pub repo_id: Uuid,
+    pub sha: String,
+    #[serde(default)]
+    pub stats_only: bool,
+}
+
+/// A commit on the workspace branch, with the execution process that created it
+#[derive(Debug, Serialize, TS)]
+pub struct WorkspaceCommit {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    /// `None` for commits made outside an execution process, or whose hash
+    /// changed after a history rewrite
+    pub execution_process_id: Option<Uuid>,
+}
+
+/// Lines of a workspace file changed since the base commit, with the coding
+/// agent turn that last touched them
+#[derive(Debug, Serialize, TS)]
+pub struct FileBlameRange {
+    #[serde(flatten)]
+    pub range: BlameRange,
+    /// `None` when no execution process can be matched to the change
+    pub execution_process_id: Option<Uuid>,
+    pub session_id: Option<Uuid>,
+    pub coding_agent_turn_id: Option<Uuid>,
+    /// The prompt of the coding agent turn that produced the change
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct SquashCommitsRequest {
+    pub repo_id: Uuid,
+    /// Oldest commit of the range to squash
+    pub from: String,
+    /// Newest commit of the range to squash
+    pub to: String,
+    /// Defaults to the messages of all squashed commits
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RewordCommitRequest {
+    pub repo_id: Uuid,
+    pub commit: String,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct DropCommitRequest {
+    pub repo_id: Uuid,
+    pub commit: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RewriteHistoryResponse {
+    pub repo_id: Uuid,
+    pub head_commit: String,
+}
+
+/// Attribute each commit to the execution process whose before/after HEAD
+/// range contains it. `commits` is newest first, as returned by git.
+fn attribute_commits(
+    commits: &[CommitInfo],
+    base_commit: &str,
+    states: &[ExecutionProcessRepoState],
+) -> HashMap<String, Uuid> {
+    let oldest_first: Vec<&str> = commits.iter().rev().map(|c| c.sha.as_str()).collect();
+    let position = |sha: &str| oldest_first.iter().position(|c| *c == sha);
+
+    let mut attribution = HashMap::new();
+    for state in states {
+        let Some(end) = state.after_head_commit.as_deref().and_then(position) else {
+            continue;
+        };
+        let start = match state.before_head_commit.as_deref() {
+            Some(before) if before == base_commit => 0,
+            Some(before) => position(before).map_or(end, |i| i + 1),
+            None => end,
+        };
+        for sha in oldest_first.iter().take(end + 1).skip(start) {
+            attribution
+                .entry(sha.to_string())
+                .or_insert(state.execution_process_id);
+        }
+    }
+    attribution
+}
+
+pub async fn get_workspace_commits(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceCommitsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceCommit>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let base_commit = deployment.git().get_base_commit(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    let commits = deployment
+        .git()
+        .list_commits(&worktree_path, &base_commit)?;
+
+    let states =
+        ExecutionProcessRepoState::find_by_workspace_and_repo(pool, workspace.id, repo.id).await?;
+    let attribution = attribute_commits(&commits, &base_commit.to_string(), &states);
+
+    let commits = commits
+        .into_iter()
+        .map(|commit| WorkspaceCommit {
+            execution_process_id: attribution.get(&commit.sha).copied(),
+            commit,
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(commits)))
+}
+
+pub async fn get_file_blame(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<FileBlameQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileBlameRange>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let base_commit = deployment.git().get_base_commit(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    let ranges = deployment
+        .git()
+        .blame_since(&worktree_path, &base_commit, &query.path)?;
+    let commits = deployment
+        .git()
+        .list_commits(&worktree_path, &base_commit)?;
+
+    let states =
+        ExecutionProcessRepoState::find_by_workspace_and_repo(pool, workspace.id, repo.id).await?;
+    let attribution = attribute_commits(&commits, &base_commit.to_string(), &states);
+
+    // Uncommitted lines belong to the coding agent that is still running, if any
+    let running_agent = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+        pool,
+        workspace.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .filter(|process| process.status == ExecutionProcessStatus::Running);
+
+    let mut processes: HashMap<Uuid, (Option<Uuid>, Option<CodingAgentTurn>)> = HashMap::new();
+    let mut blame = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let execution_process_id = match &range.commit_sha {
+            Some(sha) => attribution.get(sha).copied(),
+            None => running_agent.as_ref().map(|process| process.id),
+        };
+        let (session_id, turn) = match execution_process_id {
+            Some(id) => {
+                if !processes.contains_key(&id) {
+                    let session_id = ExecutionProcess::find_by_id(pool, id)
+                        .await?
+                        .map(|process| process.session_id);
+                    let turn = CodingAgentTurn::find_by_execution_process_id(pool, id).await?;
+                    processes.insert(id, (session_id, turn));
+                }
+                let (session_id, turn) = &processes[&id];
+                (*session_id, turn.as_ref())
+            }
+            None => (None, None),
+        };
+        blame.push(FileBlameRange {
+            execution_process_id,
+            session_id,
+            coding_agent_turn_id: turn.map(|turn| turn.id),
+            prompt: turn.and_then(|turn| turn.prompt.clone()),
+            range,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(blame)))
+}
+
+async fn rewrite_workspace_history(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+    rewrite: HistoryRewrite,
+) -> Result<ResponseJson<ApiResponse<RewriteHistoryResponse, GitOperationError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // A running agent or script would race with the rebase in the same worktree
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let base_commit = deployment.git().get_base_commit(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+
+    let commit_options = workspace_commit_options(deployment, workspace, &repo).await?;
+    let head_commit = match deployment.git().rewrite_history(
+        &worktree_path,
+        &base_commit,
+        &rewrite,
+        &commit_options,
+    ) {
+        Ok(head_commit) => head_commit,
+        Err(GitServiceError::MergeConflicts {
+            message,
+            conflicted_files,
+        }) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::MergeConflicts {
+                    message,
+                    op: ConflictOp::Rebase,
+                    conflicted_files,
+                    target_branch: workspace_repo.target_branch,
+                },
+            )));
+        }
+        Err(GitServiceError::RebaseInProgress) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::RebaseInProgress,
+            )));
+        }
+        Err(e @ (GitServiceError::InvalidRepository(_) | GitServiceError::WorktreeDirty(..))) => {
+            return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_history_rewritten",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo_id.to_string(),
+                "operation": match rewrite {
+                    HistoryRewrite::Squash { .. } => "squash",
+                    HistoryRewrite::Reword { .. } => "reword",
+                    HistoryRewrite::Drop { .. } => "drop",
+                },
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RewriteHistoryResponse {
+        repo_id,
+        head_commit,
+    })))
+}
+
+#[axum::debug_handler]
+pub async fn squash_commits(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SquashCommitsRequest>,
+) -> Result<ResponseJson<ApiResponse<RewriteHistoryResponse, GitOperationError>>, ApiError> {
+    let rewrite = HistoryRewrite::Squash {
+        from: payload.from,
+        to: payload.to,
+        message: payload.message,
+    };
+    rewrite_workspace_history(&deployment, &workspace, payload.repo_id, rewrite).await
+}
+
+#[axum::debug_handler]
+pub async fn reword_commit(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RewordCommitRequest>,
+) -> Result<ResponseJson<ApiResponse<RewriteHistoryResponse, GitOperationError>>, ApiError> {
+    let rewrite = HistoryRewrite::Reword {
+        commit: payload.commit,
+        message: payload.message,
+    };
+    rewrite_workspace_history(&deployment, &workspace, payload.repo_id, rewrite).await
+}
+
+#[axum::debug_handler]
+pub async fn drop_commit(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<DropCommitRequest>,
+) -> Result<ResponseJson<ApiResponse<RewriteHistoryResponse, GitOperationError>>, ApiError> {
+    let rewrite = HistoryRewrite::Drop {
+        commit: payload.commit,
+    };
+    rewrite_workspace_history(&deployment, &workspace, payload.repo_id, rewrite).await
+}
+
+pub async fn stream_commit_diff_ws(
+    ws: WebSocketUpgrade,
+    Query(query): Query<CommitDiffStreamQuery>,
+    Query(view_options): Query<DiffViewOptions>,
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, query.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    // Only commits on the workspace branch may be viewed, not arbitrary objects of the repo
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+    let base_commit = deployment.git().get_base_commit(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    let commits = deployment
+        .git()
+        .list_commits(&worktree_path, &base_commit)?;
+    if !commits.iter().any(|commit| commit.sha == query.sha) {
+        return Err(ApiError::BadRequest(format!(
+            "Commit {} is not on the workspace branch",
+            query.sha
+        )));
+    }
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_commit_diff_ws(socket, deployment, repo, query, view_options).await {
+            tracing::warn!("commit diff WS closed: {}", e);
+        }
+    }))
+}
+
+async fn handle_commit_diff_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    repo: Repo,
+    query: CommitDiffStreamQuery,
+    view_options: DiffViewOptions,
+) -> anyhow::Result<()> {
+    use futures_util::{SinkExt, StreamExt, TryStreamExt};
+    use utils::log_msg::LogMsg;
+
+    let stream = diff_stream::create_for_commit(
+        deployment.git().clone(),
+        repo.id,
+        repo.path,
+        query.sha,
+        query.stats_only,
+        Some(repo.name),
+        view_options,
+    )
+    .await?;
+
+    let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
+    let (mut sender, _receiver) = socket.split();
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use axum::{extract::State, response::Json as ResponseJson};
+use deployment::Deployment;
+use services::services::disk_usage::{self, DiskUsageReport};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Disk usage per workspace and repo, including git-ignored build output,
+/// together with the configured quota.
+pub async fn get_disk_usage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<DiskUsageReport>>, ApiError> {
+    let limit_bytes = deployment
+        .config()
+        .read()
+        .await
+        .workspace_quota
+        .limit_bytes();
+    let report =
+        disk_usage::disk_usage_report(&deployment.db().pool, deployment.git(), limit_bytes).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
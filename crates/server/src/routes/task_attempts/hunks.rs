@@ -0,0 +1,323 @@
+use std::path::{Path, PathBuf};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    repo::{Repo, RepoError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use git::{Commit, FileHunks, GitServiceError, HunkScope, HunkSelection};
+use serde::{Deserialize, Serialize};
+use services::services::{commit_identity, container::ContainerService};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Characters of the rejected patch included in the agent feedback
+const MAX_FEEDBACK_PATCH_CHARS: usize = 20_000;
+
+#[derive(Debug, Deserialize)]
+pub struct HunksQuery {
+    pub repo_id: Uuid,
+    pub scope: HunkScope,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct AcceptHunksRequest {
+    pub repo_id: Uuid,
+    pub files: Vec<HunkSelection>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct UnstageHunksRequest {
+    pub repo_id: Uuid,
+    pub files: Vec<HunkSelection>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RejectHunksRequest {
+    pub repo_id: Uuid,
+    pub scope: HunkScope,
+    pub files: Vec<HunkSelection>,
+    /// Sent to the coding agent together with the rejected changes
+    #[serde(default)]
+    #[ts(optional)]
+    pub feedback: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct RejectHunksResponse {
+    /// Commit reverting the rejected hunks, for the committed scope
+    pub revert_commit: Option<String>,
+    /// Coding agent follow-up started with the feedback
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum HunkReviewError {
+    ProcessAlreadyRunning,
+    /// The selected hunks no longer match the workspace's changes
+    DiffChanged {
+        message: String,
+    },
+}
+
+struct HunkTarget {
+    repo: Repo,
+    worktree_path: PathBuf,
+    base_commit: Commit,
+}
+
+async fn hunk_target(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<HunkTarget, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+    let base_commit = deployment.git().get_base_commit(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    Ok(HunkTarget {
+        repo,
+        worktree_path,
+        base_commit,
+    })
+}
+
+/// Map the git errors a user can act on to a response
+fn hunk_error<T>(
+    e: GitServiceError,
+) -> Result<ResponseJson<ApiResponse<T, HunkReviewError>>, ApiError> {
+    match e {
+        GitServiceError::PatchFailed(message) => Ok(ResponseJson(ApiResponse::error_with_data(
+            HunkReviewError::DiffChanged { message },
+        ))),
+        e @ (GitServiceError::InvalidRepository(_)
+        | GitServiceError::WorktreeDirty(..)
+        | GitServiceError::CommitHooksFailed(_)) => {
+            Ok(ResponseJson(ApiResponse::error(&e.to_string())))
+        }
+        e => Err(ApiError::GitService(e)),
+    }
+}
+
+/// Files and hunks of a repo's unstaged or staged changes, or of the changes
+/// committed since the base commit
+pub async fn list_hunks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<HunksQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileHunks>>>, ApiError> {
+    let target = hunk_target(&deployment, &workspace, query.repo_id).await?;
+    let files =
+        deployment
+            .git()
+            .list_hunks(&target.worktree_path, &target.base_commit, query.scope)?;
+    Ok(ResponseJson(ApiResponse::success(files)))
+}
+
+/// Stage the selected uncommitted hunks to mark them as accepted, so that
+/// only the hunks still to review are listed as uncommitted. Commits include
+/// all changes, accepted or not.
+#[axum::debug_handler]
+pub async fn accept_hunks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AcceptHunksRequest>,
+) -> Result<ResponseJson<ApiResponse<(), HunkReviewError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            HunkReviewError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let target = hunk_target(&deployment, &workspace, payload.repo_id).await?;
+    if let Err(e) =
+        deployment
+            .git()
+            .stage_hunks(&target.worktree_path, &target.base_commit, &payload.files)
+    {
+        return hunk_error(e);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_hunks_accepted",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": target.repo.id.to_string(),
+                "files": payload.files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Move the selected accepted hunks back to the changes still to review
+#[axum::debug_handler]
+pub async fn unstage_hunks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UnstageHunksRequest>,
+) -> Result<ResponseJson<ApiResponse<(), HunkReviewError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            HunkReviewError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let target = hunk_target(&deployment, &workspace, payload.repo_id).await?;
+    if let Err(e) =
+        deployment
+            .git()
+            .unstage_hunks(&target.worktree_path, &target.base_commit, &payload.files)
+    {
+        return hunk_error(e);
+    }
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Discard the selected uncommitted hunks, staged or not, or revert committed
+/// ones in a new commit, and optionally tell the coding agent why they were
+/// rejected
+#[axum::debug_handler]
+pub async fn reject_hunks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RejectHunksRequest>,
+) -> Result<ResponseJson<ApiResponse<RejectHunksResponse, HunkReviewError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            HunkReviewError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let target = hunk_target(&deployment, &workspace, payload.repo_id).await?;
+    let git = deployment.git();
+    let result = match payload.scope {
+        HunkScope::Uncommitted | HunkScope::Staged => git
+            .discard_hunks(
+                &target.worktree_path,
+                &target.base_commit,
+                payload.scope,
+                &payload.files,
+            )
+            .map(|patch| (None, patch)),
+        HunkScope::Committed => {
+            let options = {
+                let config = deployment.config().read().await;
+                let mut options =
+                    commit_identity::commit_options(&config.commit_identity, &target.repo, None);
+                options.hooks = commit_identity::commit_hooks(config.commit_hooks);
+                options
+            };
+            git.revert_hunks(
+                &target.worktree_path,
+                &target.base_commit,
+                &payload.files,
+                &revert_message(&payload.files),
+                &options,
+            )
+            .map(|(head, patch)| (Some(head), patch))
+        }
+    };
+    let (revert_commit, patch) = match result {
+        Ok(result) => result,
+        Err(e) => return hunk_error(e),
+    };
+
+    let feedback = payload
+        .feedback
+        .as_deref()
+        .map(str::trim)
+        .filter(|f| !f.is_empty());
+    let execution_process_id = match feedback {
+        Some(feedback) => {
+            let workspace = Workspace::find_by_id(pool, workspace.id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            let prompt = rejection_follow_up_prompt(&target.repo.name, feedback, &patch);
+            deployment
+                .container()
+                .start_agent_follow_up(&workspace, prompt)
+                .await?
+                .map(|process| process.id)
+        }
+        None => None,
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_hunks_rejected",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": target.repo.id.to_string(),
+                "scope": payload.scope,
+                "files": payload.files.len(),
+                "with_feedback": feedback.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(RejectHunksResponse {
+        revert_commit,
+        execution_process_id,
+    })))
+}
+
+fn revert_message(files: &[HunkSelection]) -> String {
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    format!("Revert rejected changes\n\nFiles: {}", paths.join(", "))
+}
+
+fn rejection_follow_up_prompt(repo_name: &str, feedback: &str, patch: &str) -> String {
+    let truncated = patch.chars().count() > MAX_FEEDBACK_PATCH_CHARS;
+    let patch: String = patch.chars().take(MAX_FEEDBACK_PATCH_CHARS).collect();
+    format!(
+        "The user reviewed your changes in the `{repo_name}` repository and rejected some of \
+         them. The rejected changes have been removed; do not reintroduce them.\n\n\
+         Feedback:\n\n{feedback}\n\n\
+         Rejected changes{truncated}:\n\n```diff\n{patch}\n```",
+        patch = patch.trim_end(),
+        truncated = if truncated { " (truncated)" } else { "" },
+    )
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use axum::{
     Extension, Json,
@@ -6,9 +6,9 @@ use axum::{
     response::Json as ResponseJson,
 };
 use db::models::{
-    coding_agent_turn::CodingAgentTurn,
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::ExecutionProcessRunReason,
     merge::{Merge, MergeStatus},
+    pr_review_thread::PrReviewThread,
     project_repo::ProjectRepo,
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
@@ -17,10 +17,6 @@ use db::models::{
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use deployment::Deployment;
-use executors::actions::{
-    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
-    coding_agent_initial::CodingAgentInitialRequest,
-};
 use git::{GitCliError, GitRemote, GitServiceError};
 use serde::{Deserialize, Serialize};
 use services::services::{
@@ -30,6 +26,9 @@ use services::services::{
         self, CreatePrRequest, GitHostError, GitHostProvider, ProviderKind, UnifiedPrComment,
         github::GhCli,
     },
+    pr_description::{self, PrDescriptionError},
+    pr_review::{self, PrReviewError, ReviewFollowUp},
+    quality_gates::QualityGateResult,
     remote_sync,
 };
 use ts_rs::TS;
@@ -39,6 +38,7 @@ use utils::{
 };
 use uuid::Uuid;
 
+use super::quality_gates;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -50,18 +50,57 @@ pub struct CreatePrApiRequest {
     pub repo_id: Uuid,
     #[serde(default)]
     pub auto_generate_description: bool,
+    /// Open the PR even though required quality gates have not passed
+    #[serde(default)]
+    #[ts(optional)]
+    pub bypass_quality_gates: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PrError {
-    CliNotInstalled { provider: ProviderKind },
-    CliNotLoggedIn { provider: ProviderKind },
+    CliNotInstalled {
+        provider: ProviderKind,
+    },
+    CliNotLoggedIn {
+        provider: ProviderKind,
+    },
     GitCliNotLoggedIn,
     GitCliNotInstalled,
-    TargetBranchNotFound { branch: String },
+    TargetBranchNotFound {
+        branch: String,
+    },
     UnsupportedProvider,
+    QualityGatesFailed {
+        gates: Vec<QualityGateResult>,
+        /// Whether the user may override the gates, see the
+        /// `allow_quality_gate_bypass` setting
+        bypass_allowed: bool,
+    },
+}
+
+impl std::fmt::Display for PrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CliNotInstalled { provider } => write!(f, "{provider} CLI is not installed"),
+            Self::CliNotLoggedIn { provider } => write!(f, "{provider} CLI is not logged in"),
+            Self::GitCliNotLoggedIn => write!(f, "Git is not authenticated with the remote"),
+            Self::GitCliNotInstalled => write!(f, "Git is not installed"),
+            Self::TargetBranchNotFound { branch } => {
+                write!(f, "Branch '{branch}' does not exist on the remote")
+            }
+            Self::UnsupportedProvider => write!(f, "The git host is not supported"),
+            Self::QualityGatesFailed { gates, .. } => {
+                let names: Vec<&str> = gates.iter().map(|gate| gate.name.as_str()).collect();
+                write!(
+                    f,
+                    "Required quality gates have not passed: {}",
+                    names.join(", ")
+                )
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -96,6 +135,60 @@ pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct AddressPrReviewRequest {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AddressPrReviewResponse {
+    /// Threads sent to the agent
+    pub threads: Vec<PrReviewThread>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum AddressPrReviewError {
+    NoPrAttached,
+    NoUnresolvedThreads,
+    NoCodingAgent,
+    AgentBusy,
+    CliNotInstalled { provider: ProviderKind },
+    CliNotLoggedIn { provider: ProviderKind },
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct SetPrDraftRequest {
+    pub repo_id: Uuid,
+    /// Convert the PR to a draft, or mark it ready for review
+    pub draft: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum SetPrDraftError {
+    NoPrAttached,
+    UnsupportedProvider,
+    CliNotInstalled { provider: ProviderKind },
+    CliNotLoggedIn { provider: ProviderKind },
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct SetPrAutoMergeRequest {
+    pub repo_id: Uuid,
+    /// Merge the PR once it is approved and its required checks pass
+    pub auto_merge: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum SetPrAutoMergeError {
+    NoPrAttached,
+}
+
 async fn trigger_pr_description_follow_up(
     deployment: &DeploymentImpl,
     workspace: &Workspace,
@@ -116,71 +209,17 @@ async fn trigger_pr_description_follow_up(
 
     drop(config); // Release the lock before async operations
 
-    // Get or create a session for this follow-up
-    let session =
-        match Session::find_latest_by_workspace_id(&deployment.db().pool, workspace.id).await? {
-            Some(s) => s,
-            None => {
-                Session::create(
-                    &deployment.db().pool,
-                    &CreateSession { executor: None },
-                    Uuid::new_v4(),
-                    workspace.id,
-                )
-                .await?
-            }
-        };
-
-    // Get executor profile from the latest coding agent process in this session
-    let Some(executor_profile_id) =
-        ExecutionProcess::latest_executor_profile_for_session(&deployment.db().pool, session.id)
-            .await?
-    else {
+    if deployment
+        .container()
+        .start_agent_follow_up(workspace, prompt)
+        .await?
+        .is_none()
+    {
         tracing::warn!(
-            "No executor profile found for session {}, skipping PR description follow-up",
-            session.id
+            "No executor profile found for workspace {}, skipping PR description follow-up",
+            workspace.id
         );
-        return Ok(());
-    };
-
-    // Get latest agent turn if one exists (for coding agent continuity)
-    let latest_session_info =
-        CodingAgentTurn::find_latest_session_info(&deployment.db().pool, session.id).await?;
-
-    let working_dir = workspace
-        .agent_working_dir
-        .as_ref()
-        .filter(|dir| !dir.is_empty())
-        .cloned();
-
-    // Build the action type (follow-up if session exists, otherwise initial)
-    let action_type = if let Some(info) = latest_session_info {
-        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-            prompt,
-            session_id: info.session_id,
-            reset_to_message_id: None,
-            executor_profile_id: executor_profile_id.clone(),
-            working_dir: working_dir.clone(),
-        })
-    } else {
-        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-            prompt,
-            executor_profile_id: executor_profile_id.clone(),
-            working_dir,
-        })
-    };
-
-    let action = ExecutorAction::new(action_type, None);
-
-    deployment
-        .container()
-        .start_execution(
-            workspace,
-            &session,
-            &action,
-            &ExecutionProcessRunReason::CodingAgent,
-        )
-        .await?;
+    }
 
     Ok(())
 }
@@ -215,6 +254,29 @@ pub async fn create_pr(
     let workspace_path = PathBuf::from(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
+    let blocking_gates =
+        quality_gates::blocking_quality_gates(&deployment, workspace.id, repo.id, &worktree_path)
+            .await?;
+    if !blocking_gates.is_empty() {
+        let bypass_allowed = quality_gates::bypass_allowed(&deployment).await;
+        if !(bypass_allowed && request.bypass_quality_gates.unwrap_or(false)) {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                PrError::QualityGatesFailed {
+                    gates: blocking_gates,
+                    bypass_allowed,
+                },
+            )));
+        }
+        quality_gates::track_quality_gates_bypassed(
+            &deployment,
+            workspace.id,
+            repo.id,
+            "pr",
+            &blocking_gates,
+        )
+        .await;
+    }
+
     let git = deployment.git();
     let push_remote = git.resolve_remote_for_branch(&repo_path, &workspace.branch)?;
 
@@ -270,7 +332,8 @@ pub async fn create_pr(
         }
     }
 
-    let git_host = match git_host::GitHostService::from_url(&target_remote.url) {
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match git_host::GitHostService::from_url(&target_remote.url, &git_hosts) {
         Ok(host) => host,
         Err(GitHostError::UnsupportedProvider) => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -310,6 +373,7 @@ pub async fn create_pr(
                 &base_branch,
                 pr_info.number,
                 &pr_info.url,
+                pr_info.is_draft,
             )
             .await
             {
@@ -422,7 +486,8 @@ pub async fn attach_existing_pr(
     let git = deployment.git();
     let remote = git.resolve_remote_for_branch(&repo.path, &workspace_repo.target_branch)?;
 
-    let git_host = match git_host::GitHostService::from_url(&remote.url) {
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match git_host::GitHostService::from_url(&remote.url, &git_hosts) {
         Ok(host) => host,
         Err(GitHostError::UnsupportedProvider) => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -468,6 +533,7 @@ pub async fn attach_existing_pr(
             &workspace_repo.target_branch,
             pr_info.number,
             &pr_info.url,
+            pr_info.is_draft,
         )
         .await?;
 
@@ -562,7 +628,8 @@ pub async fn get_pr_comments(
     let git = deployment.git();
     let remote = git.resolve_remote_for_branch(&repo.path, &workspace_repo.target_branch)?;
 
-    let git_host = match git_host::GitHostService::from_url(&remote.url) {
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match git_host::GitHostService::from_url(&remote.url, &git_hosts) {
         Ok(host) => host,
         Err(GitHostError::CliNotInstalled { provider }) => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -603,6 +670,264 @@ pub async fn get_pr_comments(
     }
 }
 
+/// Review threads of the repo's PR that were sent to the agent, with what
+/// came of each
+pub async fn get_pr_review_threads(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<GetPrCommentsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewThread>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id).await?;
+    let threads = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) => PrReviewThread::find_by_merge_id(pool, pr_merge.id).await?,
+        _ => Vec::new(),
+    };
+    Ok(ResponseJson(ApiResponse::success(threads)))
+}
+
+/// Send the unresolved review threads of the repo's PR to the agent. The PR
+/// monitor pushes the result and replies to the threads once the agent is done.
+pub async fn address_pr_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<AddressPrReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<AddressPrReviewResponse, AddressPrReviewError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) if matches!(pr_merge.pr_info.status, MergeStatus::Open) => {
+            pr_merge
+        }
+        _ => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                AddressPrReviewError::NoPrAttached,
+            )));
+        }
+    };
+
+    let remote = deployment
+        .git()
+        .resolve_remote_for_branch(&repo.path, &workspace_repo.target_branch)?;
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match git_host::GitHostService::from_url(&remote.url, &git_hosts) {
+        Ok(host) => host,
+        Err(GitHostError::CliNotInstalled { provider }) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                AddressPrReviewError::CliNotInstalled { provider },
+            )));
+        }
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+    let provider = git_host.provider_kind();
+
+    let result = pr_review::start_review_follow_up(
+        deployment.container(),
+        &git_host,
+        &workspace,
+        &repo,
+        &remote.url,
+        &pr_merge,
+    )
+    .await;
+    let error = match result {
+        Ok(ReviewFollowUp::Started(threads)) => {
+            deployment
+                .track_if_analytics_allowed(
+                    "pr_review_follow_up_started",
+                    serde_json::json!({
+                        "workspace_id": workspace.id.to_string(),
+                        "pr_number": pr_merge.pr_info.number,
+                        "threads": threads.len(),
+                    }),
+                )
+                .await;
+            return Ok(ResponseJson(ApiResponse::success(
+                AddressPrReviewResponse { threads },
+            )));
+        }
+        Ok(ReviewFollowUp::NoUnresolvedThreads) => AddressPrReviewError::NoUnresolvedThreads,
+        Ok(ReviewFollowUp::NoCodingAgent) => AddressPrReviewError::NoCodingAgent,
+        Ok(ReviewFollowUp::AgentBusy) => AddressPrReviewError::AgentBusy,
+        Err(PrReviewError::GitHost(GitHostError::CliNotInstalled { provider })) => {
+            AddressPrReviewError::CliNotInstalled { provider }
+        }
+        Err(PrReviewError::GitHost(GitHostError::AuthFailed(_))) => {
+            AddressPrReviewError::CliNotLoggedIn { provider }
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(ResponseJson(ApiResponse::error_with_data(error)))
+}
+
+/// Mark the repo's open PR as ready for review, or convert it back to a draft
+pub async fn set_pr_draft(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<SetPrDraftRequest>,
+) -> Result<ResponseJson<ApiResponse<(), SetPrDraftError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) if matches!(pr_merge.pr_info.status, MergeStatus::Open) => {
+            pr_merge
+        }
+        _ => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                SetPrDraftError::NoPrAttached,
+            )));
+        }
+    };
+
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = match git_host::GitHostService::from_url(&pr_merge.pr_info.url, &git_hosts) {
+        Ok(host) => host,
+        Err(GitHostError::CliNotInstalled { provider }) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                SetPrDraftError::CliNotInstalled { provider },
+            )));
+        }
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+    let provider = git_host.provider_kind();
+
+    let error = match git_host
+        .set_pr_draft(&pr_merge.pr_info.url, request.draft)
+        .await
+    {
+        Ok(()) => {
+            Merge::set_draft(pool, pr_merge.id, request.draft).await?;
+            deployment
+                .track_if_analytics_allowed(
+                    if request.draft {
+                        "pr_converted_to_draft"
+                    } else {
+                        "pr_marked_ready"
+                    },
+                    serde_json::json!({
+                        "workspace_id": workspace.id.to_string(),
+                        "pr_number": pr_merge.pr_info.number,
+                        "automatic": false,
+                    }),
+                )
+                .await;
+            return Ok(ResponseJson(ApiResponse::success(())));
+        }
+        Err(GitHostError::UnsupportedProvider) => SetPrDraftError::UnsupportedProvider,
+        Err(GitHostError::CliNotInstalled { provider }) => {
+            SetPrDraftError::CliNotInstalled { provider }
+        }
+        Err(GitHostError::AuthFailed(_)) => SetPrDraftError::CliNotLoggedIn { provider },
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+    Ok(ResponseJson(ApiResponse::error_with_data(error)))
+}
+
+/// Turn auto-merge of the repo's open PR on or off. The PR monitor merges it
+/// through the git host once it is approved with passing checks.
+pub async fn set_pr_auto_merge(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<SetPrAutoMergeRequest>,
+) -> Result<ResponseJson<ApiResponse<(), SetPrAutoMergeError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, request.repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) if matches!(pr_merge.pr_info.status, MergeStatus::Open) => {
+            pr_merge
+        }
+        _ => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                SetPrAutoMergeError::NoPrAttached,
+            )));
+        }
+    };
+
+    Merge::set_auto_merge(pool, pr_merge.id, request.auto_merge).await?;
+    deployment
+        .track_if_analytics_allowed(
+            if request.auto_merge {
+                "pr_auto_merge_enabled"
+            } else {
+                "pr_auto_merge_disabled"
+            },
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "pr_number": pr_merge.pr_info.number,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Have the agent rewrite the description of the repo's open PR in the
+/// background, now that new commits were pushed to it. Does nothing unless
+/// description refreshes are enabled.
+pub fn refresh_pr_description_after_push(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) {
+    let deployment = deployment.clone();
+    let workspace = workspace.clone();
+    tokio::spawn(async move {
+        if let Err(e) = start_pr_description_refresh(&deployment, &workspace, repo_id).await {
+            tracing::error!(
+                "Failed to refresh the PR description for workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+    });
+}
+
+async fn start_pr_description_refresh(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<(), PrDescriptionError> {
+    let Some(template) = pr_description::refresh_prompt_template(&deployment.config().read().await)
+    else {
+        return Ok(());
+    };
+    let pool = &deployment.db().pool;
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) if matches!(pr_merge.pr_info.status, MergeStatus::Open) => {
+            pr_merge
+        }
+        _ => return Ok(()),
+    };
+
+    let git_hosts = deployment.git_hosts().await;
+    let git_host = git_host::GitHostService::from_url(&pr_merge.pr_info.url, &git_hosts)?;
+    let refresh = pr_description::start_description_refresh(
+        deployment.container(),
+        &git_host,
+        workspace,
+        &pr_merge,
+        &template,
+    )
+    .await?;
+    tracing::debug!(
+        "Description refresh of PR #{} after a push: {:?}",
+        pr_merge.pr_info.number,
+        refresh
+    );
+    Ok(())
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateWorkspaceFromPrBody {
     pub repo_id: Uuid,
@@ -638,6 +963,7 @@ pub async fn create_workspace_from_pr(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateWorkspaceFromPrBody>,
 ) -> Result<ResponseJson<ApiResponse<CreateWorkspaceFromPrResponse, CreateFromPrError>>, ApiError> {
+    deployment.container().enforce_workspace_quota().await?;
     let pool = &deployment.db().pool;
 
     let repo = Repo::find_by_id(pool, payload.repo_id)
@@ -704,6 +1030,7 @@ pub async fn create_workspace_from_pr(
         &[CreateWorkspaceRepo {
             repo_id: payload.repo_id,
             target_branch: target_branch_ref.clone(),
+            sparse_checkout: None,
         }],
     )
     .await?;
@@ -716,39 +1043,30 @@ pub async fn create_workspace_from_pr(
     // Update workspace with container_ref so start_execution can find it
     workspace.container_ref = Some(container_ref.clone());
 
-    // Use gh pr checkout to fetch and switch to the PR branch
-    // This handles SSH/HTTPS auth correctly regardless of fork URL format
     let worktree_path = PathBuf::from(&container_ref).join(&repo.name);
-    match GhCli::new().get_repo_info(&remote.url, &worktree_path) {
-        Ok(repo_info) => {
-            if let Err(e) = GhCli::new().pr_checkout(
-                &worktree_path,
-                &repo_info.owner,
-                &repo_info.repo_name,
-                payload.pr_number,
-            ) {
+    let git_hosts = deployment.git_hosts().await;
+    let pr_head_ref = git_host::GitHostService::from_url(&remote.url, &git_hosts)
+        .ok()
+        .and_then(|git_host| git_host.pr_head_ref(payload.pr_number));
+    let checkout = match pr_head_ref {
+        // Providers that expose a pull request ref are fetched with plain git
+        Some(pr_ref) => deployment
+            .git()
+            .checkout_pull_request(&worktree_path, &remote.url, &pr_ref, &payload.head_branch)
+            .map_err(|e| {
                 tracing::error!("Failed to checkout PR branch: {e}");
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    CreateFromPrError::BranchFetchFailed {
-                        message: e.to_string(),
-                    },
-                )));
-            }
-            // Update workspace branch to the actual PR branch
-            Workspace::update_branch_name(pool, workspace.id, &payload.head_branch).await?;
-            workspace.branch = payload.head_branch.clone();
-        }
-        Err(e) => {
-            tracing::error!(
-                "Failed to get repo info for PR checkout (gh CLI may not be installed): {e}"
-            );
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                CreateFromPrError::BranchFetchFailed {
-                    message: format!("Failed to get repository info: {e}"),
-                },
-            )));
-        }
+                e.to_string()
+            }),
+        None => checkout_pr_with_gh(&remote.url, &worktree_path, payload.pr_number),
+    };
+    if let Err(message) = checkout {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            CreateFromPrError::BranchFetchFailed { message },
+        )));
     }
+    // Update workspace branch to the actual PR branch
+    Workspace::update_branch_name(pool, workspace.id, &payload.head_branch).await?;
+    workspace.branch = payload.head_branch.clone();
 
     Merge::create_pr(
         pool,
@@ -757,6 +1075,8 @@ pub async fn create_workspace_from_pr(
         &format!("{}/{}", remote.name, payload.base_branch),
         payload.pr_number,
         &payload.pr_url,
+        // Synced by the PR monitor on its next poll
+        false,
     )
     .await?;
 
@@ -814,3 +1134,31 @@ pub async fn create_workspace_from_pr(
         CreateWorkspaceFromPrResponse { workspace, task },
     )))
 }
+
+/// Use gh pr checkout to fetch and switch to the PR branch
+/// This handles SSH/HTTPS auth correctly regardless of fork URL format
+fn checkout_pr_with_gh(
+    remote_url: &str,
+    worktree_path: &Path,
+    pr_number: i64,
+) -> Result<(), String> {
+    let repo_info = GhCli::new()
+        .get_repo_info(remote_url, worktree_path)
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to get repo info for PR checkout (gh CLI may not be installed): {e}"
+            );
+            format!("Failed to get repository info: {e}")
+        })?;
+    GhCli::new()
+        .pr_checkout(
+            worktree_path,
+            &repo_info.owner,
+            &repo_info.repo_name,
+            pr_number,
+        )
+        .map_err(|e| {
+            tracing::error!("Failed to checkout PR branch: {e}");
+            e.to_string()
+        })
+}
@@ -0,0 +1,231 @@
+use std::path::Path;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    quality_gate::{QualityGate, QualityGateRun},
+    repo::{Repo, RepoError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    quality_gates::{self, QualityGateResult},
+};
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, response::ApiResponse};
+use uuid::Uuid;
+
+use super::RunScriptError;
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct RunQualityGatesRequest {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct SendQualityGateFailureRequest {
+    /// Execution process of the failed gate run
+    pub execution_process_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum QualityGateFollowUpError {
+    GateNotFailed,
+    ProcessAlreadyRunning,
+    NoAgentConfigured,
+}
+
+/// Gate results for a repo in the workspace, relative to the worktree's HEAD
+pub async fn quality_gate_results(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    repo_id: Uuid,
+    head_commit: Option<&str>,
+) -> Result<Vec<QualityGateResult>, ApiError> {
+    let pool = &deployment.db().pool;
+    let gates = QualityGate::find_by_repo_id(pool, repo_id).await?;
+    if gates.is_empty() {
+        return Ok(Vec::new());
+    }
+    let latest_runs = QualityGateRun::find_latest_by_workspace_id(pool, workspace_id).await?;
+    Ok(quality_gates::gate_results(
+        gates,
+        &latest_runs,
+        head_commit,
+    ))
+}
+
+/// Required gates that have not passed at the worktree's HEAD
+pub async fn blocking_quality_gates(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    repo_id: Uuid,
+    worktree_path: &Path,
+) -> Result<Vec<QualityGateResult>, ApiError> {
+    let head_commit = deployment.git().get_head_info(worktree_path)?.oid;
+    let results =
+        quality_gate_results(deployment, workspace_id, repo_id, Some(&head_commit)).await?;
+    Ok(results.into_iter().filter(|r| r.is_blocking()).collect())
+}
+
+/// Whether the settings let users override required gates that have not passed
+pub async fn bypass_allowed(deployment: &DeploymentImpl) -> bool {
+    deployment.config().read().await.allow_quality_gate_bypass
+}
+
+/// Record that required gates were overridden for a merge, push or PR
+pub async fn track_quality_gates_bypassed(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    repo_id: Uuid,
+    operation: &str,
+    blocking: &[QualityGateResult],
+) {
+    tracing::info!(
+        "Bypassing {} blocking quality gate(s) to {} workspace {}",
+        blocking.len(),
+        operation,
+        workspace_id
+    );
+    deployment
+        .track_if_analytics_allowed(
+            "quality_gates_bypassed",
+            serde_json::json!({
+                "workspace_id": workspace_id.to_string(),
+                "repo_id": repo_id.to_string(),
+                "operation": operation,
+                "blocking_gates": blocking.len(),
+            }),
+        )
+        .await;
+}
+
+#[axum::debug_handler]
+pub async fn run_quality_gates(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RunQualityGatesRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess, RunScriptError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            RunScriptError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let Some(process) = deployment
+        .container()
+        .start_quality_gates(&workspace, &repo)
+        .await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            RunScriptError::NoScriptConfigured,
+        )));
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "quality_gates_started",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(process)))
+}
+
+/// Send a failed gate's output to the coding agent as a follow-up
+#[axum::debug_handler]
+pub async fn send_quality_gate_failure_to_agent(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendQualityGateFailureRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess, QualityGateFollowUpError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let run = QualityGateRun::find_by_execution_process_id(pool, payload.execution_process_id)
+        .await?
+        .filter(|run| run.workspace_id == workspace.id)
+        .ok_or_else(|| ApiError::BadRequest("Quality gate run not found".to_string()))?;
+    let gate = QualityGate::find_by_id(pool, run.quality_gate_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Quality gate not found".to_string()))?;
+    let repo = Repo::find_by_id(pool, gate.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let process = ExecutionProcess::find_by_id(pool, run.execution_process_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Execution process not found".to_string()))?;
+    let failed = match process.status {
+        ExecutionProcessStatus::Failed => true,
+        ExecutionProcessStatus::Completed => process.exit_code != Some(0),
+        ExecutionProcessStatus::Running | ExecutionProcessStatus::Killed => false,
+    };
+    if !failed {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            QualityGateFollowUpError::GateNotFailed,
+        )));
+    }
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            QualityGateFollowUpError::ProcessAlreadyRunning,
+        )));
+    }
+
+    let records = ExecutionProcessLogs::find_by_execution_id(pool, process.id).await?;
+    let output: String = ExecutionProcessLogs::parse_logs(&records)
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read gate output: {e}")))?
+        .into_iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stdout(line) | LogMsg::Stderr(line) => Some(line),
+            _ => None,
+        })
+        .collect();
+
+    let prompt = quality_gates::failure_follow_up_prompt(&gate, &repo.name, &output);
+    let Some(execution_process) = deployment
+        .container()
+        .start_agent_follow_up(&workspace, prompt)
+        .await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            QualityGateFollowUpError::NoAgentConfigured,
+        )));
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "quality_gate_failure_sent_to_agent",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
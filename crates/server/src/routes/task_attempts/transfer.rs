@@ -0,0 +1,345 @@
+use std::{io::Read, path::Path};
+
+use axum::{
+    Extension,
+    body::Body,
+    extract::{Multipart, Query, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+};
+use db::models::{
+    repo::Repo,
+    task::Task,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use git::{ExportFormat, GitServiceError};
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use super::{WorkspaceRepoInput, agent_working_dir_for_repos, workspace_commit_options};
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct ExportWorkspaceQuery {
+    pub format: ExportFormat,
+    /// Export a single repo; without it every repo with changes is archived
+    /// into one tar file
+    pub repo_id: Option<Uuid>,
+}
+
+/// The `metadata` part of a workspace import upload; the export itself is
+/// sent as the `file` part
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ImportWorkspaceRequest {
+    pub task_id: Uuid,
+    pub repos: Vec<WorkspaceRepoInput>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ImportWorkspaceError {
+    /// The archive has no `<repo name>.<format>` entry for any selected repo
+    NothingToImport,
+    ApplyFailed {
+        repo_name: String,
+        message: String,
+    },
+}
+
+fn file_name_safe(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | ' ' => '-',
+            c => c,
+        })
+        .collect()
+}
+
+fn is_tar(contents: &[u8]) -> bool {
+    contents.get(257..262) == Some(b"ustar".as_slice())
+}
+
+/// Download the workspace changes as a `format-patch` mailbox, a unified diff
+/// or a git bundle, for one repo or for all repos as a tar archive.
+pub async fn export_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportWorkspaceQuery>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut repos =
+        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?;
+    if let Some(repo_id) = query.repo_id {
+        repos.retain(|repo| repo.repo.id == repo_id);
+    }
+    if repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Repository is not part of this workspace".to_string(),
+        ));
+    }
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let mut exports = Vec::new();
+    for repo in &repos {
+        let worktree_path = Path::new(&container_ref).join(&repo.repo.name);
+        let base_commit = deployment.git().get_base_commit(
+            &repo.repo.path,
+            &workspace.branch,
+            &repo.target_branch,
+        )?;
+        if deployment
+            .git()
+            .list_commits(&worktree_path, &base_commit)?
+            .is_empty()
+        {
+            continue;
+        }
+        let contents =
+            deployment
+                .git()
+                .export_changes(&worktree_path, &base_commit, query.format)?;
+        exports.push((&repo.repo.name, contents));
+    }
+    if exports.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No commits to export. Commit the workspace changes first.".to_string(),
+        ));
+    }
+
+    let branch = file_name_safe(&workspace.branch);
+    let extension = query.format.extension();
+    let (file_name, content_type, body) = if query.repo_id.is_some() {
+        let (repo_name, contents) = exports.remove(0);
+        (
+            format!("{branch}-{}.{extension}", file_name_safe(repo_name)),
+            "application/octet-stream",
+            contents,
+        )
+    } else {
+        let mut archive = tar::Builder::new(Vec::new());
+        for (repo_name, contents) in &exports {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            archive.append_data(
+                &mut header,
+                format!("{repo_name}.{extension}"),
+                contents.as_slice(),
+            )?;
+        }
+        (
+            format!("{branch}.tar"),
+            "application/x-tar",
+            archive.into_inner()?,
+        )
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        )
+        .body(Body::from(body))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+/// Create a workspace for a task and apply an uploaded export to it: a single
+/// mailbox, diff or bundle for a one-repo workspace, or a tar archive from
+/// [`export_workspace`] with one entry per repo.
+pub async fn import_workspace(
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<Workspace, ImportWorkspaceError>>, ApiError> {
+    let mut request: Option<ImportWorkspaceRequest> = None;
+    let mut upload: Option<(String, Vec<u8>)> = None;
+    while let Some(field) = multipart.next_field().await? {
+        match field.name() {
+            Some("metadata") => {
+                let text = field.text().await?;
+                request =
+                    Some(serde_json::from_str(&text).map_err(|e| {
+                        ApiError::BadRequest(format!("Invalid import metadata: {e}"))
+                    })?);
+            }
+            Some("file") => {
+                let file_name = field.file_name().unwrap_or("export").to_string();
+                upload = Some((file_name, field.bytes().await?.to_vec()));
+            }
+            _ => {}
+        }
+    }
+    let (Some(request), Some((file_name, contents))) = (request, upload) else {
+        return Err(ApiError::BadRequest(
+            "Upload needs a `metadata` part and a `file` part".to_string(),
+        ));
+    };
+    if request.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+
+    deployment.container().enforce_workspace_quota().await?;
+
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, request.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let workspace_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = request.repos.iter().map(|r| r.repo_id).collect();
+    let branch = deployment
+        .container()
+        .git_branch_from_workspace(&workspace_id, &task, &repo_ids, None, None)
+        .await;
+    let agent_working_dir = agent_working_dir_for_repos(pool, &request.repos).await?;
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir,
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+    let workspace_repos: Vec<CreateWorkspaceRepo> = request
+        .repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+            sparse_checkout: r.sparse_checkout.clone(),
+        })
+        .collect();
+    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+
+    let result = apply_import(&deployment, &workspace, &file_name, &contents).await;
+    let workspace = Workspace::find_by_id(pool, workspace.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    match result {
+        Ok(None) => {
+            deployment
+                .track_if_analytics_allowed(
+                    "workspace_imported",
+                    serde_json::json!({
+                        "task_id": task.id.to_string(),
+                        "workspace_id": workspace.id.to_string(),
+                        "repository_count": request.repos.len(),
+                    }),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse::success(workspace)))
+        }
+        Ok(Some(error)) => {
+            discard_workspace(&deployment, &workspace).await;
+            Ok(ResponseJson(ApiResponse::error_with_data(error)))
+        }
+        Err(e) => {
+            discard_workspace(&deployment, &workspace).await;
+            Err(e)
+        }
+    }
+}
+
+async fn apply_import(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    file_name: &str,
+    contents: &[u8],
+) -> Result<Option<ImportWorkspaceError>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+
+    // Pair every repo with its export: archive entries are named `<repo name>.<ext>`
+    let mut imports: Vec<(&Repo, Vec<u8>)> = Vec::new();
+    if is_tar(contents) {
+        let mut archive = tar::Archive::new(contents);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Some(repo) = repos.iter().find(|repo| repo.name == stem) {
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data)?;
+                imports.push((repo, data));
+            }
+        }
+        if imports.is_empty() {
+            return Ok(Some(ImportWorkspaceError::NothingToImport));
+        }
+    } else {
+        let [repo] = repos.as_slice() else {
+            return Err(ApiError::BadRequest(
+                "Importing into several repos needs a tar archive with one export per repo"
+                    .to_string(),
+            ));
+        };
+        imports.push((repo, contents.to_vec()));
+    }
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    let message = format!("Import changes from {file_name}");
+    for (repo, data) in imports {
+        let worktree_path = Path::new(&container_ref).join(&repo.name);
+        let commit_options = workspace_commit_options(deployment, workspace, repo).await?;
+        match deployment
+            .git()
+            .import_changes(&worktree_path, &data, &message, &commit_options)
+        {
+            Ok(format) => {
+                tracing::info!(
+                    "Imported {} into {} for workspace {}",
+                    format.extension(),
+                    repo.name,
+                    workspace.id
+                );
+            }
+            Err(e @ (GitServiceError::GitCLI(_) | GitServiceError::WorktreeDirty(..))) => {
+                return Ok(Some(ImportWorkspaceError::ApplyFailed {
+                    repo_name: repo.name.clone(),
+                    message: e.to_string(),
+                }));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(None)
+}
+
+async fn discard_workspace(deployment: &DeploymentImpl, workspace: &Workspace) {
+    if let Err(e) = deployment.container().delete(workspace).await {
+        tracing::error!(
+            "Failed to clean up imported workspace {}: {}",
+            workspace.id,
+            e
+        );
+    }
+    if let Err(e) = Workspace::delete(&deployment.db().pool, workspace.id).await {
+        tracing::error!(
+            "Failed to delete imported workspace {}: {}",
+            workspace.id,
+            e
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashSet, path::PathBuf};
 
 use anyhow;
 use axum::{
@@ -14,8 +14,10 @@ use axum::{
 };
 use db::models::{
     image::TaskImage,
+    project_repo::ProjectRepo,
     repo::{Repo, RepoError},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task_issue::TaskIssue,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -23,7 +25,10 @@ use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use services::services::{container::ContainerService, workspace_manager::WorkspaceManager};
+use services::services::{
+    branch_archive, container::ContainerService, events::task_patch, git_host::GitHostIssue,
+    workspace_manager::WorkspaceManager,
+};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -137,11 +142,111 @@ pub async fn create_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+/// Git host issues the tasks of a project were imported from
+pub async fn get_task_issues(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskIssue>>>, ApiError> {
+    let links = TaskIssue::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportIssuesRequest {
+    pub project_id: Uuid,
+    /// Repo whose git host the issues are on
+    pub repo_id: Uuid,
+    pub issues: Vec<GitHostIssue>,
+}
+
+/// Create a task for each issue, linked to the issue so that their status is
+/// kept in sync. Issues the project already has a task for are skipped.
+pub async fn import_issues(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportIssuesRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if ProjectRepo::find_by_project_and_repo(pool, payload.project_id, payload.repo_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(
+            "Repository does not belong to the project".to_string(),
+        ));
+    }
+
+    let imported: HashSet<String> = TaskIssue::find_by_project_id(pool, payload.project_id)
+        .await?
+        .into_iter()
+        .map(|link| link.issue_url)
+        .collect();
+
+    // Import all issues or none, so a retry doesn't leave tasks without links
+    let mut tx = pool.begin().await?;
+    let mut tasks = Vec::new();
+    for issue in payload
+        .issues
+        .iter()
+        .filter(|issue| !imported.contains(&issue.url))
+    {
+        let description = match issue.body.as_deref().map(str::trim) {
+            Some(body) if !body.is_empty() => format!("{body}\n\nIssue: {}", issue.url),
+            _ => format!("Issue: {}", issue.url),
+        };
+        let data = CreateTask::from_title_description(
+            payload.project_id,
+            issue.title.clone(),
+            Some(description),
+        );
+        let task = Task::create(&mut *tx, &data, Uuid::new_v4()).await?;
+        TaskIssue::create(&mut *tx, task.id, payload.repo_id, &issue.url, issue.number).await?;
+        tasks.push(task);
+    }
+    tx.commit().await?;
+
+    // The event hook can't see the tasks before the commit, so announce them
+    // to task streams here
+    let task_ids: HashSet<Uuid> = tasks.iter().map(|task| task.id).collect();
+    for task in Task::find_by_project_id_with_attempt_status(pool, payload.project_id)
+        .await?
+        .iter()
+        .filter(|task| task_ids.contains(&task.id))
+    {
+        deployment
+            .events()
+            .msg_store()
+            .push_patch(task_patch::add(task));
+    }
+
+    tracing::info!(
+        "Imported {} issues as tasks in project {}",
+        tasks.len(),
+        payload.project_id
+    );
+    deployment
+        .track_if_analytics_allowed(
+            "issues_imported",
+            serde_json::json!({
+                "project_id": payload.project_id.to_string(),
+                "repo_id": payload.repo_id.to_string(),
+                "issue_count": tasks.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// `simple_id` of the remote issue the workspace will be linked to, used
+    /// for the `{issue}` branch name value
+    #[serde(default)]
+    #[ts(optional)]
+    pub linked_issue_simple_id: Option<String>,
 }
 
 pub async fn create_task_and_start(
@@ -154,6 +259,8 @@ pub async fn create_task_and_start(
         ));
     }
 
+    deployment.container().enforce_workspace_quota().await?;
+
     let pool = &deployment.db().pool;
 
     let task_id = Uuid::new_v4();
@@ -176,9 +283,16 @@ pub async fn create_task_and_start(
         .await;
 
     let attempt_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = payload.repos.iter().map(|r| r.repo_id).collect();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
+        .git_branch_from_workspace(
+            &attempt_id,
+            &task,
+            &repo_ids,
+            Some(&payload.executor_profile_id),
+            payload.linked_issue_simple_id.as_deref(),
+        )
         .await;
 
     // Compute agent_working_dir based on repo count:
@@ -216,6 +330,7 @@ pub async fn create_task_and_start(
         .map(|r| CreateWorkspaceRepo {
             repo_id: r.repo_id,
             target_branch: r.target_branch.clone(),
+            sparse_checkout: r.sparse_checkout.clone(),
         })
         .collect();
     WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
@@ -307,6 +422,18 @@ pub async fn delete_task(
         deployment.container().try_stop(workspace, true).await;
     }
 
+    // Keep unmerged work reachable before the worktrees go away
+    let branch_archive = deployment.config().read().await.branch_archive;
+    for workspace in &attempts {
+        branch_archive::archive_workspace_branches(
+            pool,
+            deployment.git(),
+            workspace,
+            branch_archive,
+        )
+        .await;
+    }
+
     let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
 
     // Collect workspace directories that need cleanup
@@ -409,6 +536,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
+        .route("/issues", get(get_task_issues))
+        .route("/import-issues", post(import_issues))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
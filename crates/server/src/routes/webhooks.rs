@@ -0,0 +1,58 @@
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use deployment::Deployment;
+use services::services::git_host::webhook::{parse_webhook_event, verify_webhook_signature};
+
+use crate::DeploymentImpl;
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/webhooks/git-host", post(handle_git_host_webhook))
+}
+
+/// POST /api/webhooks/git-host
+/// Receives pull request events from git hosts, through a tunnel the user set
+/// up, and has the PR monitor check the PRs they are about right away
+async fn handle_git_host_webhook(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let pr_webhooks = deployment.config().read().await.pr_webhooks.clone();
+    let Some(secret) = deployment.pr_webhook_secret().active(&pr_webhooks).await else {
+        tracing::warn!("Received git host webhook but webhooks are disabled");
+        return StatusCode::NOT_IMPLEMENTED;
+    };
+
+    if !verify_webhook_signature(&secret, &headers, &body) {
+        tracing::warn!("Invalid git host webhook signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!("Failed to parse git host webhook payload: {}", e);
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+
+    let Some(event) = parse_webhook_event(&headers, &payload) else {
+        tracing::debug!("Ignoring git host webhook event without pull requests");
+        return StatusCode::OK;
+    };
+
+    tracing::info!(
+        "Received git host webhook '{}' for {} pull request(s)",
+        event.name,
+        event.pull_requests.len()
+    );
+    deployment
+        .pr_monitor()
+        .check_pull_requests(event.pull_requests);
+    StatusCode::ACCEPTED
+}
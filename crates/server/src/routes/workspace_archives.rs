@@ -0,0 +1,161 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::{
+    repo::Repo,
+    task::Task,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_branch_archive::WorkspaceBranchArchive,
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use deployment::Deployment;
+use services::services::{branch_archive, container::ContainerService};
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{WorkspaceRepoInput, agent_working_dir_for_repos},
+};
+
+pub async fn get_workspace_archives(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorkspaceBranchArchive>>>, ApiError> {
+    let archives = WorkspaceBranchArchive::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(archives)))
+}
+
+/// Recreate the archived branch, together with the other repos archived from
+/// the same workspace, and check it out in a fresh worktree. The original
+/// workspace is reused when it still exists; otherwise a new workspace is
+/// created for the task.
+pub async fn restore_workspace_archive(
+    State(deployment): State<DeploymentImpl>,
+    Path(archive_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    deployment.container().enforce_workspace_quota().await?;
+    let pool = &deployment.db().pool;
+    let archive = WorkspaceBranchArchive::find_by_id(pool, archive_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let archives: Vec<WorkspaceBranchArchive> = WorkspaceBranchArchive::find_all(pool)
+        .await?
+        .into_iter()
+        .filter(|a| a.workspace_id == archive.workspace_id)
+        .collect();
+
+    let mut repos = Vec::with_capacity(archives.len());
+    for archive in &archives {
+        let repo = Repo::find_by_id(pool, archive.repo_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        branch_archive::restore_archived_branch(deployment.git(), archive, &repo.path)?;
+        repos.push(repo);
+    }
+
+    let workspace = match Workspace::find_by_id(pool, archive.workspace_id).await? {
+        Some(workspace) => workspace,
+        None => {
+            let task = Task::find_by_id(pool, archive.task_id)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::BadRequest(
+                        "The task of this workspace was deleted. The branch was restored in the \
+                         repository."
+                            .to_string(),
+                    )
+                })?;
+            let inputs: Vec<WorkspaceRepoInput> = archives
+                .iter()
+                .map(|a| WorkspaceRepoInput {
+                    repo_id: a.repo_id,
+                    target_branch: a.target_branch.clone(),
+                    sparse_checkout: None,
+                })
+                .collect();
+            let agent_working_dir = agent_working_dir_for_repos(pool, &inputs).await?;
+            let workspace = Workspace::create(
+                pool,
+                &CreateWorkspace {
+                    branch: archive.branch.clone(),
+                    agent_working_dir,
+                },
+                Uuid::new_v4(),
+                task.id,
+            )
+            .await?;
+            let workspace_repos: Vec<CreateWorkspaceRepo> = inputs
+                .iter()
+                .map(|r| CreateWorkspaceRepo {
+                    repo_id: r.repo_id,
+                    target_branch: r.target_branch.clone(),
+                    sparse_checkout: None,
+                })
+                .collect();
+            WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+            workspace
+        }
+    };
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let workspace = Workspace::find_by_id(pool, workspace.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // The branch is back in the repository, so the archive is no longer needed
+    for (archive, repo) in archives.iter().zip(&repos) {
+        if let Err(e) =
+            branch_archive::discard_archived_branch(deployment.git(), archive, &repo.path)
+        {
+            tracing::warn!("Failed to remove branch archive {}: {}", archive.id, e);
+        }
+        WorkspaceBranchArchive::delete(pool, archive.id).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_archive_restored",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "task_id": workspace.task_id.to_string(),
+                "repository_count": archives.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
+
+/// Drop an archive, removing its ref or bundle file.
+pub async fn delete_workspace_archive(
+    State(deployment): State<DeploymentImpl>,
+    Path(archive_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let archive = WorkspaceBranchArchive::find_by_id(pool, archive_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let repo = Repo::find_by_id(pool, archive.repo_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    branch_archive::discard_archived_branch(deployment.git(), &archive, &repo.path)?;
+    WorkspaceBranchArchive::delete(pool, archive.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_workspace_archives))
+        .route("/{archive_id}", delete(delete_workspace_archive))
+        .route("/{archive_id}/restore", post(restore_workspace_archive));
+
+    Router::new().nest("/workspace-archives", inner)
+}
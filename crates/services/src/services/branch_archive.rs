@@ -0,0 +1,168 @@
+//! Keeps unmerged workspace branches reachable before their worktrees are
+//! cleaned up, so the work can be restored later.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    workspace::Workspace,
+    workspace_branch_archive::{
+        BranchArchiveStorage, CreateWorkspaceBranchArchive, WorkspaceBranchArchive,
+    },
+    workspace_repo::{RepoWithTargetBranch, WorkspaceRepo},
+};
+use git::{ARCHIVE_REF_PREFIX, GitService, GitServiceError};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::config::BranchArchiveMode;
+
+#[derive(Debug, Error)]
+pub enum BranchArchiveError {
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Directory holding bundle archives
+pub fn bundle_dir() -> PathBuf {
+    utils::assets::asset_dir().join("branch-archives")
+}
+
+pub fn archive_ref_name(workspace_id: Uuid) -> String {
+    format!("{ARCHIVE_REF_PREFIX}{workspace_id}")
+}
+
+/// Archive every workspace branch that has commits not on its target branch.
+/// Failures are logged and never stop the cleanup that follows.
+pub async fn archive_workspace_branches(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &Workspace,
+    mode: BranchArchiveMode,
+) {
+    if mode == BranchArchiveMode::Off {
+        return;
+    }
+    let repos = match WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+        .await
+    {
+        Ok(repos) => repos,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load repos to archive for workspace {}: {}",
+                workspace.id,
+                e
+            );
+            return;
+        }
+    };
+    for repo in &repos {
+        if let Err(e) = archive_repo_branch(pool, git, workspace, repo, mode).await {
+            tracing::warn!(
+                "Failed to archive branch '{}' of repo {} for workspace {}: {}",
+                workspace.branch,
+                repo.repo.name,
+                workspace.id,
+                e
+            );
+        }
+    }
+}
+
+async fn archive_repo_branch(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &Workspace,
+    repo: &RepoWithTargetBranch,
+    mode: BranchArchiveMode,
+) -> Result<(), BranchArchiveError> {
+    let Some(commit_sha) =
+        git.unmerged_branch_tip(&repo.repo.path, &workspace.branch, &repo.target_branch)?
+    else {
+        return Ok(());
+    };
+
+    let (storage, location) = match mode {
+        BranchArchiveMode::Off => return Ok(()),
+        BranchArchiveMode::Refs => {
+            let refname = archive_ref_name(workspace.id);
+            git.write_ref(&repo.repo.path, &refname, &commit_sha)?;
+            (BranchArchiveStorage::Ref, refname)
+        }
+        BranchArchiveMode::Bundle => {
+            let dir = bundle_dir();
+            std::fs::create_dir_all(&dir)?;
+            let bundle_path = dir.join(format!("{}-{}.bundle", workspace.id, repo.repo.id));
+            git.write_branch_bundle(
+                &repo.repo.path,
+                &workspace.branch,
+                &repo.target_branch,
+                &bundle_path,
+            )?;
+            (
+                BranchArchiveStorage::Bundle,
+                bundle_path.to_string_lossy().to_string(),
+            )
+        }
+    };
+
+    WorkspaceBranchArchive::upsert(
+        pool,
+        &CreateWorkspaceBranchArchive {
+            workspace_id: workspace.id,
+            task_id: workspace.task_id,
+            repo_id: repo.repo.id,
+            workspace_name: workspace.name.clone(),
+            branch: workspace.branch.clone(),
+            target_branch: repo.target_branch.clone(),
+            commit_sha: commit_sha.clone(),
+            storage,
+            location,
+        },
+    )
+    .await?;
+    tracing::info!(
+        "Archived branch '{}' of repo {} at {} before cleaning up workspace {}",
+        workspace.branch,
+        repo.repo.name,
+        commit_sha,
+        workspace.id
+    );
+    Ok(())
+}
+
+/// Recreate the archived branch in its repository.
+pub fn restore_archived_branch(
+    git: &GitService,
+    archive: &WorkspaceBranchArchive,
+    repo_path: &Path,
+) -> Result<(), BranchArchiveError> {
+    let bundle_path = match archive.storage {
+        BranchArchiveStorage::Ref => None,
+        BranchArchiveStorage::Bundle => Some(Path::new(&archive.location)),
+    };
+    git.restore_branch(repo_path, &archive.branch, &archive.commit_sha, bundle_path)?;
+    Ok(())
+}
+
+/// Remove the archive ref or bundle file. The database row is left to the
+/// caller.
+pub fn discard_archived_branch(
+    git: &GitService,
+    archive: &WorkspaceBranchArchive,
+    repo_path: &Path,
+) -> Result<(), BranchArchiveError> {
+    match archive.storage {
+        BranchArchiveStorage::Ref => git.delete_ref(repo_path, &archive.location)?,
+        BranchArchiveStorage::Bundle => match std::fs::remove_file(&archive.location) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! Branch names for new workspaces, rendered from a naming template set
+//! globally, per project or per repo.
+
+use chrono::{DateTime, Utc};
+use db::models::{project::Project, repo::Repo};
+use git::GitService;
+use utils::text::{git_branch_id, short_uuid};
+use uuid::Uuid;
+
+/// Reproduces the historical `prefix/abcd-task-title` names
+pub const DEFAULT_BRANCH_NAME_TEMPLATE: &str = "{prefix}/{short_id}-{slug}";
+
+/// Placeholders a branch naming template may use
+const PLACEHOLDERS: &[&str] = &[
+    "prefix",
+    "slug",
+    "short_id",
+    "id",
+    "task_id",
+    "task_short_id",
+    "executor",
+    "user",
+    "issue",
+    "date",
+];
+
+/// Suffixed names tried before the directories of a nested name are folded in
+const NESTED_CANDIDATE_TRIES: usize = 20;
+
+/// Values available to a branch naming template
+pub struct BranchNameInput<'a> {
+    pub prefix: &'a str,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: &'a str,
+    /// Executor of the coding agent, e.g. `CLAUDE_CODE`
+    pub executor: Option<&'a str>,
+    /// GitHub username of the signed-in user
+    pub user: Option<&'a str>,
+    /// `simple_id` of the linked remote issue, e.g. `VK-42`
+    pub issue: Option<&'a str>,
+    pub now: DateTime<Utc>,
+}
+
+/// The first repo template that is set, then the project's, then the global
+/// one.
+pub fn resolve_template<'a>(
+    global: Option<&'a str>,
+    project: Option<&'a Project>,
+    repos: &'a [Repo],
+) -> &'a str {
+    repos
+        .iter()
+        .filter_map(|r| r.branch_name_template.as_deref())
+        .chain(project.and_then(|p| p.branch_name_template.as_deref()))
+        .chain(global)
+        .map(str::trim)
+        .find(|t| !t.is_empty())
+        .unwrap_or(DEFAULT_BRANCH_NAME_TEMPLATE)
+}
+
+/// Render `template`. Supported placeholders are `{prefix}`, `{slug}`,
+/// `{short_id}` and `{id}` (of the workspace), `{task_id}`,
+/// `{task_short_id}`, `{executor}`, `{user}`, `{issue}` and `{date}`
+/// (`YYYY-MM-DD`). Separators and path segments left empty by unset values
+/// are dropped, so `{prefix}/{issue}-{slug}` renders as `slug` without a
+/// prefix or linked issue.
+pub fn render_branch_name(template: &str, input: &BranchNameInput<'_>) -> String {
+    let optional = |value: Option<&str>| value.map(sanitize).unwrap_or_default();
+    let rendered = template
+        .replace("{prefix}", input.prefix)
+        .replace("{slug}", &git_branch_id(input.task_title))
+        .replace("{short_id}", &short_uuid(&input.workspace_id))
+        .replace("{id}", &input.workspace_id.to_string())
+        .replace("{task_short_id}", &short_uuid(&input.task_id))
+        .replace("{task_id}", &input.task_id.to_string())
+        .replace(
+            "{executor}",
+            &optional(input.executor).to_lowercase().replace('_', "-"),
+        )
+        .replace("{user}", &optional(input.user))
+        .replace("{issue}", &optional(input.issue))
+        .replace("{date}", &input.now.format("%Y-%m-%d").to_string());
+
+    rendered
+        .split('/')
+        .map(tidy_segment)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Check that `template` only uses known placeholders and renders to a valid
+/// branch name both with and without the optional values.
+pub fn validate_template(git: &GitService, template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Ok(());
+    }
+    if let Some(unknown) = unknown_placeholder(template) {
+        let valid = PLACEHOLDERS
+            .iter()
+            .map(|p| format!("{{{p}}}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!(
+            "Unknown placeholder '{{{unknown}}}' in branch name template. Valid placeholders are {valid}"
+        ));
+    }
+    let id = Uuid::nil();
+    let full = BranchNameInput {
+        prefix: "vk",
+        workspace_id: id,
+        task_id: id,
+        task_title: "Example task",
+        executor: Some("CLAUDE_CODE"),
+        user: Some("octocat"),
+        issue: Some("VK-1"),
+        now: Utc::now(),
+    };
+    let minimal = BranchNameInput {
+        prefix: "",
+        executor: None,
+        user: None,
+        issue: None,
+        ..full
+    };
+    for input in [&full, &minimal] {
+        let name = render_branch_name(template, input);
+        if name.is_empty() || !git.is_branch_name_valid(&name) {
+            return Err(format!(
+                "Branch name template '{template}' renders to the invalid branch name '{name}'"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `base`, then `base-2`, `base-3`, … for resolving collisions in order.
+/// Suffixes cannot get around a branch named like one of the directories in
+/// `base`, such as `vk` for `vk/fix`, so after a few tries the directories are
+/// folded into the name: `vk-fix`, `vk-fix-2`, …
+pub fn candidates(base: &str) -> impl Iterator<Item = String> + '_ {
+    let flat = base.replace('/', "-");
+    let tries = if flat == base {
+        usize::MAX
+    } else {
+        NESTED_CANDIDATE_TRIES
+    };
+    suffixed(base.to_string()).take(tries).chain(suffixed(flat))
+}
+
+fn suffixed(base: String) -> impl Iterator<Item = String> {
+    std::iter::once(base.clone()).chain((2..).map(move |n| format!("{base}-{n}")))
+}
+
+fn unknown_placeholder(template: &str) -> Option<&str> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after.find('}')?;
+        let name = &after[..end];
+        if !PLACEHOLDERS.contains(&name) {
+            return Some(name);
+        }
+        rest = &after[end + 1..];
+    }
+    None
+}
+
+/// Keep characters that are safe in a ref name
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn tidy_segment(segment: &str) -> String {
+    let mut tidy = String::with_capacity(segment.len());
+    for c in segment.chars() {
+        if c == '-' && tidy.ends_with('-') {
+            continue;
+        }
+        tidy.push(c);
+    }
+    tidy.trim_matches(|c| matches!(c, '-' | '_' | '.'))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(prefix: &'a str, issue: Option<&'a str>) -> BranchNameInput<'a> {
+        BranchNameInput {
+            prefix,
+            workspace_id: Uuid::parse_str("abcd1234-0000-0000-0000-000000000000").unwrap(),
+            task_id: Uuid::parse_str("ef561234-0000-0000-0000-000000000000").unwrap(),
+            task_title: "Fix login bug",
+            executor: Some("CLAUDE_CODE"),
+            user: Some("octo cat"),
+            issue,
+            now: DateTime::parse_from_rfc3339("2026-02-16T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn default_template_matches_historical_names() {
+        assert_eq!(
+            render_branch_name(DEFAULT_BRANCH_NAME_TEMPLATE, &input("vk", None)),
+            "vk/abcd-fix-login-bug"
+        );
+        assert_eq!(
+            render_branch_name(DEFAULT_BRANCH_NAME_TEMPLATE, &input("", None)),
+            "abcd-fix-login-bug"
+        );
+    }
+
+    #[test]
+    fn renders_variables_and_drops_empty_parts() {
+        assert_eq!(
+            render_branch_name("feature/{user}/{issue}-{slug}", &input("vk", Some("VK-42"))),
+            "feature/octo-cat/VK-42-fix-login-bug"
+        );
+        assert_eq!(
+            render_branch_name("{prefix}/{issue}-{slug}", &input("", None)),
+            "fix-login-bug"
+        );
+        assert_eq!(
+            render_branch_name("{executor}/{date}-{task_short_id}", &input("vk", None)),
+            "claude-code/2026-02-16-ef56"
+        );
+    }
+
+    #[test]
+    fn validates_templates_and_resolves_collisions() {
+        let git = GitService::new();
+        assert!(validate_template(&git, "{prefix}/{issue}-{slug}").is_ok());
+        assert!(validate_template(&git, "feature..{slug}").is_err());
+        let unknown = validate_template(&git, "{prefix}/{task_number}-{slug}").unwrap_err();
+        assert!(unknown.contains("{task_number}") && unknown.contains("{task_short_id}"));
+
+        let taken = ["vk/fix", "vk/fix-2"];
+        let free = |base| candidates(base).find(|n| !taken.contains(&n.as_str()));
+        assert_eq!(free("vk/fix").as_deref(), Some("vk/fix-3"));
+        assert_eq!(free("vk/other").as_deref(), Some("vk/other"));
+
+        let nested = candidates("vk/fix").find(|n| !n.starts_with("vk/"));
+        assert_eq!(nested.as_deref(), Some("vk-fix"));
+    }
+}
@@ -0,0 +1,96 @@
+//! Resolves the global and per-repository commit identity settings into the
+//! options the git service applies to agent commits and merges.
+
+use db::models::repo::{CommitIdentityMode, CommitSigningFormat, Repo};
+use executors::profile::ExecutorProfileId;
+use git::{
+    CommitHooks, CommitIdentity, CommitOptions, CommitSigning, FALLBACK_COMMIT_EMAIL,
+    FALLBACK_COMMIT_NAME, SigningFormat,
+};
+
+use crate::services::config::{CommitHookMode, CommitIdentityConfig};
+
+/// Commit options for `repo`, with repository overrides taking precedence over
+/// `config`. `executor_profile` is credited as co-author when trailers are enabled.
+pub fn commit_options(
+    config: &CommitIdentityConfig,
+    repo: &Repo,
+    executor_profile: Option<&ExecutorProfileId>,
+) -> CommitOptions {
+    let identity = match repo.commit_identity_mode.unwrap_or(config.mode) {
+        CommitIdentityMode::User => None,
+        CommitIdentityMode::Bot => Some(CommitIdentity::new(
+            non_empty(&config.bot_name).unwrap_or(FALLBACK_COMMIT_NAME),
+            non_empty(&config.bot_email).unwrap_or(FALLBACK_COMMIT_EMAIL),
+        )),
+    };
+
+    let co_authors = executor_profile
+        .filter(|_| config.co_author_trailers)
+        .map(|profile| {
+            vec![CommitIdentity::new(
+                executor_display_name(profile),
+                FALLBACK_COMMIT_EMAIL,
+            )]
+        })
+        .unwrap_or_default();
+
+    let key = repo
+        .commit_signing_key
+        .as_deref()
+        .and_then(non_empty)
+        .or_else(|| config.signing_key.as_deref().and_then(non_empty))
+        .map(str::to_string);
+    let signing = match repo.commit_signing_format.unwrap_or(config.signing_format) {
+        CommitSigningFormat::None => None,
+        CommitSigningFormat::Gpg => Some(CommitSigning {
+            format: SigningFormat::Gpg,
+            key,
+        }),
+        CommitSigningFormat::Ssh => Some(CommitSigning {
+            format: SigningFormat::Ssh,
+            key,
+        }),
+    };
+
+    CommitOptions {
+        identity,
+        co_authors,
+        signing,
+        ..Default::default()
+    }
+}
+
+/// How the git service treats the repository's commit hooks for `mode`
+pub fn commit_hooks(mode: CommitHookMode) -> CommitHooks {
+    match mode {
+        CommitHookMode::Run => CommitHooks::Run,
+        CommitHookMode::Skip => CommitHooks::Skip,
+        CommitHookMode::RunAndRetry => CommitHooks::RunAndRetry,
+    }
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    Some(value.trim()).filter(|v| !v.is_empty())
+}
+
+/// Human-readable executor name, e.g. `Claude Code (PLAN)` for `CLAUDE_CODE:PLAN`
+fn executor_display_name(profile: &ExecutorProfileId) -> String {
+    let executor = profile
+        .executor
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_string() + &chars.as_str().to_lowercase())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    match &profile.variant {
+        Some(variant) => format!("{executor} ({variant})"),
+        None => executor,
+    }
+}
@@ -0,0 +1,460 @@
+//! Conventional Commits messages for auto-commits and merges. Coding agents
+//! are asked to end their final message with one; when they don't, a message
+//! is built from the task title, the agent summary and the staged paths.
+
+use db::models::repo::Repo;
+use executors::actions::{ExecutorAction, ExecutorActionType};
+
+pub const DEFAULT_COMMIT_MESSAGE_TEMPLATE: &str = "{type}{scope}: {description}\n\n{body}";
+pub const DEFAULT_COMMIT_SUBJECT_MAX_LENGTH: usize = 72;
+
+/// Appended to coding agent prompts while Conventional Commits are enabled
+pub const COMMIT_MESSAGE_REQUEST: &str = "\n\nWhen you are done, end your final message with a \
+     commit message for your changes in the Conventional Commits format, inside a ```commit \
+     fenced block: a subject line such as `feat(parser): support nested lists` in the \
+     imperative mood, a blank line, then a short body saying what changed and why.";
+
+const BODY_WRAP_WIDTH: usize = 72;
+const MIN_SUBJECT_LENGTH: usize = 20;
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+/// Directory names that say nothing about what changed
+const GENERIC_DIRS: &[&str] = &[
+    "src", "lib", "crates", "packages", "apps", "app", "internal",
+];
+
+/// Inputs for a generated commit message
+pub struct CommitMessageInput<'a> {
+    pub task_title: &'a str,
+    /// Final message of the coding agent, if any
+    pub summary: Option<&'a str>,
+    /// Repository-relative paths of the staged changes
+    pub changed_paths: &'a [String],
+}
+
+/// Render a Conventional Commits message. The commit message block the agent
+/// was asked for is used when the summary has one; otherwise a summary that
+/// already starts with a Conventional Commits subject, and failing that the
+/// task title with the summary as body. `template` supports the `{type}`,
+/// `{scope}` (rendered as `(scope)` or nothing), `{description}`, `{body}`
+/// and `{task_title}` placeholders; the subject is shortened to fit
+/// `subject_max_length` and the body is wrapped at 72 columns.
+pub fn conventional_commit_message(
+    input: &CommitMessageInput<'_>,
+    template: Option<&str>,
+    subject_max_length: Option<usize>,
+) -> String {
+    let template = template
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(DEFAULT_COMMIT_MESSAGE_TEMPLATE);
+    let max_len = subject_max_length
+        .unwrap_or(DEFAULT_COMMIT_SUBJECT_MAX_LENGTH)
+        .max(MIN_SUBJECT_LENGTH);
+
+    let summary = input.summary.map(str::trim).filter(|s| !s.is_empty());
+    let (headline, body) = match summary.and_then(agent_commit_message) {
+        Some(message) => split_subject(message),
+        None => match summary.map(split_subject) {
+            Some((first, rest)) if parse_conventional(first).is_some() => (first, rest),
+            _ => (input.task_title, summary.unwrap_or("")),
+        },
+    };
+
+    let (commit_type, scope, description) = match parse_conventional(headline) {
+        Some(parsed) => parsed,
+        None => (
+            infer_type(headline, input.changed_paths).to_string(),
+            infer_scope(input.changed_paths),
+            normalize_description(headline),
+        ),
+    };
+    let body = format_body(body);
+
+    let render = |description: &str| {
+        template
+            .replace("{type}", &commit_type)
+            .replace(
+                "{scope}",
+                &scope.as_ref().map(|s| format!("({s})")).unwrap_or_default(),
+            )
+            .replace("{description}", description)
+            .replace("{body}", &body)
+            .replace("{task_title}", input.task_title.trim())
+    };
+
+    let mut message = render(&description);
+    let subject_len = message.lines().next().unwrap_or("").chars().count();
+    if subject_len > max_len {
+        let overflow = subject_len - max_len;
+        let keep = description.chars().count().saturating_sub(overflow + 1);
+        message = render(&truncate_words(&description, keep));
+    }
+    tidy(&message)
+}
+
+/// Generated message using the repository's template and subject length limit
+pub fn repo_commit_message(repo: &Repo, input: &CommitMessageInput<'_>) -> String {
+    conventional_commit_message(
+        input,
+        repo.commit_message_template.as_deref(),
+        repo.commit_subject_max_length
+            .and_then(|len| usize::try_from(len).ok()),
+    )
+}
+
+/// Ask coding agents for a commit message at the end of their final message
+pub fn with_commit_message_request(action: &ExecutorAction) -> ExecutorAction {
+    let mut action = action.clone();
+    match &mut action.typ {
+        ExecutorActionType::CodingAgentInitialRequest(request) => {
+            request.prompt.push_str(COMMIT_MESSAGE_REQUEST)
+        }
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+            request.prompt.push_str(COMMIT_MESSAGE_REQUEST)
+        }
+        ExecutorActionType::ScriptRequest(_) | ExecutorActionType::ReviewRequest(_) => {}
+    }
+    action
+}
+
+/// Contents of the last ```commit block in an agent summary
+fn agent_commit_message(summary: &str) -> Option<&str> {
+    let start = summary.rfind("```commit")?;
+    let rest = &summary[start + "```commit".len()..];
+    let rest = rest
+        .strip_prefix('\n')
+        .or_else(|| rest.strip_prefix("\r\n"))?;
+    let message = rest.split("```").next()?.trim();
+    (!message.is_empty()).then_some(message)
+}
+
+/// First meaningful line of `text`, and everything after it
+fn split_subject(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.split_once('\n') {
+        Some((first, rest)) => (strip_markdown(first), rest),
+        None => (strip_markdown(text), ""),
+    }
+}
+
+fn parse_conventional(line: &str) -> Option<(String, Option<String>, String)> {
+    let (head, description) = line.split_once(": ")?;
+    let head = head.trim_end_matches('!');
+    let (commit_type, scope) = match head.split_once('(') {
+        Some((t, rest)) => (t, Some(rest.strip_suffix(')')?.to_string())),
+        None => (head, None),
+    };
+    let commit_type = commit_type.to_ascii_lowercase();
+    CONVENTIONAL_TYPES
+        .contains(&commit_type.as_str())
+        .then(|| (commit_type, scope, normalize_description(description)))
+}
+
+fn infer_type(headline: &str, changed_paths: &[String]) -> &'static str {
+    if !changed_paths.is_empty() && changed_paths.iter().all(|p| is_docs_path(p)) {
+        return "docs";
+    }
+    if !changed_paths.is_empty() && changed_paths.iter().all(|p| is_test_path(p)) {
+        return "test";
+    }
+    let first_word = headline
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match first_word.as_str() {
+        "fix" | "fixes" | "fixed" | "resolve" | "resolves" | "resolved" | "correct" => "fix",
+        "refactor" | "refactors" | "refactored" | "simplify" | "rename" | "move" | "extract"
+        | "clean" => "refactor",
+        "add" | "adds" | "added" | "implement" | "implements" | "implemented" | "introduce"
+        | "support" | "create" | "allow" | "enable" => "feat",
+        "document" | "documents" | "documented" => "docs",
+        "test" | "tests" => "test",
+        "optimize" | "optimise" | "speed" => "perf",
+        _ => "chore",
+    }
+}
+
+fn is_docs_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".md")
+        || lower.ends_with(".mdx")
+        || lower.starts_with("docs/")
+        || lower.contains("/docs/")
+}
+
+fn is_test_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.starts_with("tests/")
+        || lower.contains("/tests/")
+        || lower.contains("/__tests__/")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+        || lower.contains("_test.")
+}
+
+/// The last meaningful directory shared by every changed path
+fn infer_scope(changed_paths: &[String]) -> Option<String> {
+    let mut dirs = changed_paths
+        .iter()
+        .map(|p| p.rsplit_once('/').map_or("", |(dir, _)| dir));
+    let first: Vec<&str> = dirs.next()?.split('/').collect();
+    let common = dirs.fold(first.len(), |len, dir| {
+        first
+            .iter()
+            .zip(dir.split('/'))
+            .take(len)
+            .take_while(|(a, b)| *a == b)
+            .count()
+    });
+    first[..common]
+        .iter()
+        .rev()
+        .find(|c| !c.is_empty() && !GENERIC_DIRS.contains(c))
+        .map(|c| c.to_string())
+}
+
+fn strip_markdown(line: &str) -> &str {
+    line.trim()
+        .trim_start_matches(['#', '*', '-', '>', '`'])
+        .trim_end_matches(['*', '`'])
+        .trim()
+}
+
+/// Subject description: lowercase first letter (unless it starts an acronym),
+/// no trailing period
+fn normalize_description(text: &str) -> String {
+    let text = text.trim().trim_end_matches('.').trim();
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), Some(second)) if !second.is_uppercase() => {
+            first.to_lowercase().chain(text.chars().skip(1)).collect()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Cut `text` to at most `max_chars`, breaking on a word boundary where possible
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(i) if i > max_chars / 2 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end_matches([' ', ',', ';', ':']))
+}
+
+/// Body wrapped at 72 columns, keeping paragraphs and list items apart
+fn format_body(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut paragraph = String::new();
+    for line in text.lines().map(str::trim) {
+        let item = list_item(line);
+        let fence = line.starts_with("```");
+        if line.is_empty() || fence || item.is_some() {
+            push_wrapped(&mut lines, "", &std::mem::take(&mut paragraph));
+        }
+        if let Some((marker, rest)) = item {
+            push_wrapped(&mut lines, marker, rest);
+        } else if line.is_empty() {
+            lines.push(String::new());
+        } else if !fence {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(line.trim_start_matches('#').trim());
+        }
+    }
+    push_wrapped(&mut lines, "", &paragraph);
+    lines.join("\n")
+}
+
+/// List marker, normalized for bullets, and the item text
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(("- ", rest.trim()));
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let after = &line[digits..];
+    (digits > 0 && (after.starts_with(". ") || after.starts_with(") ")))
+        .then(|| (&line[..digits + 2], line[digits + 2..].trim()))
+}
+
+/// Wrap `text` behind `marker`, indenting continuation lines to match
+fn push_wrapped(lines: &mut Vec<String>, marker: &str, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let indent = " ".repeat(marker.len());
+    let wrapped = wrap(text, BODY_WRAP_WIDTH - marker.len());
+    for (i, line) in wrapped.lines().enumerate() {
+        let prefix = if i == 0 { marker } else { indent.as_str() };
+        lines.push(format!("{prefix}{line}"));
+    }
+}
+
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
+/// Drop empty placeholder sections left by the template
+fn tidy(message: &str) -> String {
+    let mut out = String::new();
+    let mut blank_run = 0;
+    for line in message.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 0 { "\n\n" } else { "\n" });
+        }
+        blank_run = 0;
+        out.push_str(line);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(p: &[&str]) -> Vec<String> {
+        p.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn uses_commit_message_block_from_agent_summary() {
+        let changed = paths(&["crates/git/src/lib.rs", "crates/git/src/cli.rs"]);
+        let input = CommitMessageInput {
+            task_title: "Rebase fixes",
+            summary: Some(
+                "I've implemented the changes and all tests pass.\n\n```commit\n\
+                 fix(git): reject merge commits in rebase todos\n\n\
+                 Merge commits are now rejected before rewriting, so the todo list\n\
+                 can't drop them silently.\n\n\
+                 - prefixes shorter than seven characters no longer match any commit in the todo\n\
+                 - the parser keeps exec lines\n```",
+            ),
+            changed_paths: &changed,
+        };
+        assert_eq!(
+            conventional_commit_message(&input, None, None),
+            "fix(git): reject merge commits in rebase todos\n\nMerge commits are now \
+             rejected before rewriting, so the todo list can't\ndrop them silently.\n\n\
+             - prefixes shorter than seven characters no longer match any commit in\n  the todo\n\
+             - the parser keeps exec lines"
+        );
+    }
+
+    #[test]
+    fn free_form_summary_becomes_the_body_of_the_task_title() {
+        let changed = paths(&["crates/git/src/lib.rs"]);
+        let input = CommitMessageInput {
+            task_title: "Fix the rebase todo parser",
+            summary: Some(
+                "## Summary\nI've implemented the fix.\n\n1. Merge commits are rejected\n\
+                 2. Short prefixes no longer match",
+            ),
+            changed_paths: &changed,
+        };
+        assert_eq!(
+            conventional_commit_message(&input, None, None),
+            "fix(git): fix the rebase todo parser\n\nSummary I've implemented the fix.\n\n\
+             1. Merge commits are rejected\n2. Short prefixes no longer match"
+        );
+    }
+
+    #[test]
+    fn only_coding_agent_prompts_ask_for_a_commit_message() {
+        use executors::{
+            actions::{
+                coding_agent_initial::CodingAgentInitialRequest,
+                script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+            },
+            executors::BaseCodingAgent,
+            profile::ExecutorProfileId,
+        };
+
+        let request = CodingAgentInitialRequest {
+            prompt: "Add a parser".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            working_dir: None,
+        };
+        let initial = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(request.clone()),
+            None,
+        );
+        assert_eq!(
+            with_commit_message_request(&initial).typ,
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: format!("Add a parser{COMMIT_MESSAGE_REQUEST}"),
+                ..request
+            })
+        );
+
+        let script = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "cargo fmt".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::CleanupScript,
+                working_dir: None,
+            }),
+            None,
+        );
+        assert_eq!(with_commit_message_request(&script).typ, script.typ);
+    }
+
+    #[test]
+    fn keeps_existing_prefix_and_applies_template_and_limit() {
+        let changed = paths(&["README.md"]);
+        let input = CommitMessageInput {
+            task_title: "Docs",
+            summary: Some("docs(readme): Describe every configuration option in great detail"),
+            changed_paths: &changed,
+        };
+        let message = conventional_commit_message(
+            &input,
+            Some("{type}{scope}: {description} [vk]"),
+            Some(40),
+        );
+        assert_eq!(message, "docs(readme): describe every… [vk]");
+        assert!(message.chars().count() <= 40);
+    }
+
+    #[test]
+    fn falls_back_to_task_title() {
+        let changed = paths(&["frontend/src/App.tsx", "frontend/src/lib/api.ts"]);
+        let input = CommitMessageInput {
+            task_title: "Add dark mode toggle",
+            summary: None,
+            changed_paths: &changed,
+        };
+        assert_eq!(
+            conventional_commit_message(&input, None, None),
+            "feat(frontend): add dark mode toggle"
+        );
+    }
+}
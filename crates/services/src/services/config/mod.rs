@@ -42,6 +42,15 @@ pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
 pub type SendMessageShortcut = versions::v8::SendMessageShortcut;
+pub type CommitIdentityConfig = versions::v8::CommitIdentityConfig;
+pub type BranchArchiveMode = versions::v8::BranchArchiveMode;
+pub type QuotaExceededAction = versions::v8::QuotaExceededAction;
+pub type WorkspaceQuotaConfig = versions::v8::WorkspaceQuotaConfig;
+pub type CommitHookMode = versions::v8::CommitHookMode;
+pub type CiAutoFixConfig = versions::v8::CiAutoFixConfig;
+pub type PrWebhookConfig = versions::v8::PrWebhookConfig;
+pub type PrLifecycleConfig = versions::v8::PrLifecycleConfig;
+pub type IssueDoneAction = versions::v8::IssueDoneAction;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
@@ -1,4 +1,5 @@
 use anyhow::Error;
+use db::models::repo::{CommitIdentityMode, CommitSigningFormat};
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -7,7 +8,10 @@ pub use v7::{
     ThemeMode, UiLanguage,
 };
 
-use crate::services::config::versions::v7;
+use crate::services::{
+    config::versions::v7,
+    git_host::{GitHostMapping, MergeMethod},
+};
 
 fn default_git_branch_prefix() -> String {
     "vk".to_string()
@@ -28,6 +32,154 @@ pub enum SendMessageShortcut {
     Enter,
 }
 
+/// What happens to an unmerged workspace branch before its worktree is
+/// cleaned up or its workspace deleted
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum BranchArchiveMode {
+    Off,
+    /// Keep the branch tip under `refs/vk-archive/` in the repository
+    #[default]
+    Refs,
+    /// Write the unmerged commits to a bundle file
+    Bundle,
+}
+
+/// What happens when workspaces use more disk space than the quota allows
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum QuotaExceededAction {
+    /// Log a warning and carry on
+    #[default]
+    Warn,
+    /// Refuse to create new workspaces
+    Refuse,
+    /// Remove the worktrees of the least recently used archived workspaces
+    CleanupArchived,
+}
+
+/// How the repository's `pre-commit` and `commit-msg` hooks are treated when
+/// agent changes are committed
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum CommitHookMode {
+    #[default]
+    Run,
+    Skip,
+    /// Re-stage files the hooks rewrote, such as formatter output, and commit
+    /// once more before giving up
+    RunAndRetry,
+}
+
+/// Disk quota for everything under the workspace base directory
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct WorkspaceQuotaConfig {
+    /// No quota when unset
+    pub limit_mb: Option<u64>,
+    pub action: QuotaExceededAction,
+}
+
+impl WorkspaceQuotaConfig {
+    pub fn limit_bytes(&self) -> Option<u64> {
+        self.limit_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+}
+
+/// Identity and signing for commits made by agents and merges. Repositories
+/// can override the mode, signing format and key.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct CommitIdentityConfig {
+    pub mode: CommitIdentityMode,
+    pub bot_name: String,
+    pub bot_email: String,
+    /// Credit the executor profile with a `Co-authored-by` trailer
+    pub co_author_trailers: bool,
+    pub signing_format: CommitSigningFormat,
+    /// GPG key id or SSH key path; git's `user.signingkey` when unset
+    pub signing_key: Option<String>,
+}
+
+impl Default for CommitIdentityConfig {
+    fn default() -> Self {
+        Self {
+            mode: CommitIdentityMode::User,
+            bot_name: git::FALLBACK_COMMIT_NAME.to_string(),
+            bot_email: git::FALLBACK_COMMIT_EMAIL.to_string(),
+            co_author_trailers: false,
+            signing_format: CommitSigningFormat::None,
+            signing_key: None,
+        }
+    }
+}
+
+/// Follow-ups sent to a workspace's agent when required CI checks fail on its PR
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct CiAutoFixConfig {
+    pub enabled: bool,
+    /// Follow-ups per PR until its checks pass again
+    pub max_attempts: u32,
+    /// Include the failing job's log in the follow-up, where the git host
+    /// provides it
+    pub include_logs: bool,
+}
+
+impl Default for CiAutoFixConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 3,
+            include_logs: true,
+        }
+    }
+}
+
+/// Local endpoint receiving pull request events from git hosts, reached
+/// through a tunnel the user sets up
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct PrWebhookConfig {
+    pub enabled: bool,
+    /// Shared secret the git host signs deliveries with. Stored apart from
+    /// the config in [`PrWebhookSecret`](crate::services::pr_webhook_secret::PrWebhookSecret);
+    /// still read from older config files so it can be moved there.
+    #[serde(skip_serializing)]
+    #[ts(skip)]
+    pub secret: Option<String>,
+}
+
+/// What happens to a workspace PR after it was opened
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct PrLifecycleConfig {
+    /// Have the agent rewrite the PR description after follow-ups are pushed
+    pub refresh_description: bool,
+    /// Mark draft PRs ready for review once the required quality gates pass
+    /// on the pushed branch
+    pub auto_ready: bool,
+    /// How PRs with auto-merge turned on are merged
+    pub merge_method: MergeMethod,
+}
+
+/// What happens to the git host issue a task was imported from once the task
+/// is done
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum IssueDoneAction {
+    Nothing,
+    /// Comment that the task is done and leave the issue open
+    Comment,
+    /// Comment that the task is done and close the issue
+    #[default]
+    Close,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -60,8 +212,41 @@ pub struct Config {
     pub commit_reminder_enabled: bool,
     #[serde(default)]
     pub commit_reminder_prompt: Option<String>,
+    /// Generate Conventional Commits messages for auto-commits and merges
+    #[serde(default)]
+    pub conventional_commits_enabled: bool,
+    #[serde(default)]
+    pub commit_identity: CommitIdentityConfig,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    #[serde(default)]
+    pub branch_archive: BranchArchiveMode,
+    #[serde(default)]
+    pub workspace_quota: WorkspaceQuotaConfig,
+    /// Branch naming template, e.g. `{prefix}/{short_id}-{slug}`. Projects and
+    /// repos can override it.
+    #[serde(default)]
+    pub git_branch_template: Option<String>,
+    /// Hook failures are logged to the process and sent to the agent as a
+    /// follow-up
+    #[serde(default)]
+    pub commit_hooks: CommitHookMode,
+    /// Let merges, pushes and PRs go ahead when the user overrides required
+    /// quality gates that have not passed
+    #[serde(default)]
+    pub allow_quality_gate_bypass: bool,
+    /// Self-hosted git hosts and their providers; API tokens are kept
+    /// separately in the git host credential store
+    #[serde(default)]
+    pub git_hosts: Vec<GitHostMapping>,
+    #[serde(default)]
+    pub ci_auto_fix: CiAutoFixConfig,
+    #[serde(default)]
+    pub pr_webhooks: PrWebhookConfig,
+    #[serde(default)]
+    pub pr_lifecycle: PrLifecycleConfig,
+    #[serde(default)]
+    pub issue_done_action: IssueDoneAction,
 }
 
 impl Config {
@@ -91,7 +276,19 @@ impl Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
+            conventional_commits_enabled: false,
+            commit_identity: CommitIdentityConfig::default(),
             send_message_shortcut: SendMessageShortcut::default(),
+            branch_archive: BranchArchiveMode::default(),
+            workspace_quota: WorkspaceQuotaConfig::default(),
+            git_branch_template: None,
+            commit_hooks: CommitHookMode::default(),
+            allow_quality_gate_bypass: false,
+            git_hosts: Vec::new(),
+            ci_auto_fix: CiAutoFixConfig::default(),
+            pr_webhooks: PrWebhookConfig::default(),
+            pr_lifecycle: PrLifecycleConfig::default(),
+            issue_done_action: IssueDoneAction::default(),
         }
     }
 
@@ -146,7 +343,19 @@ impl Default for Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
+            conventional_commits_enabled: false,
+            commit_identity: CommitIdentityConfig::default(),
             send_message_shortcut: SendMessageShortcut::default(),
+            branch_archive: BranchArchiveMode::default(),
+            workspace_quota: WorkspaceQuotaConfig::default(),
+            git_branch_template: None,
+            commit_hooks: CommitHookMode::default(),
+            allow_quality_gate_bypass: false,
+            git_hosts: Vec::new(),
+            ci_auto_fix: CiAutoFixConfig::default(),
+            pr_webhooks: PrWebhookConfig::default(),
+            pr_lifecycle: PrLifecycleConfig::default(),
+            issue_done_action: IssueDoneAction::default(),
         }
     }
 }
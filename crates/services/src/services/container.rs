@@ -6,6 +6,7 @@ use std::{
 
 use anyhow::{Error as AnyhowError, anyhow};
 use async_trait::async_trait;
+use chrono::Utc;
 use db::{
     DBService,
     models::{
@@ -18,6 +19,8 @@ use db::{
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
+        project::Project,
+        quality_gate::{QualityGate, QualityGateRun},
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
@@ -32,6 +35,7 @@ use executors::profile::ExecutorConfigs;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
@@ -45,15 +49,13 @@ use json_patch::Patch;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
-use utils::{
-    log_msg::LogMsg,
-    msg_store::MsgStore,
-    text::{git_branch_id, short_uuid},
-};
+use utils::{diff::DiffViewOptions, log_msg::LogMsg, msg_store::MsgStore};
 use uuid::Uuid;
 
 use crate::services::{
-    notification::NotificationService, workspace_manager::WorkspaceError as WorkspaceManagerError,
+    branch_name::{self, BranchNameInput, DEFAULT_BRANCH_NAME_TEMPLATE},
+    notification::NotificationService,
+    workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
@@ -80,6 +82,8 @@ pub enum ContainerError {
     Io(#[from] std::io::Error),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
+    #[error("{0}")]
+    QuotaExceeded(String),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -159,6 +163,10 @@ pub trait ContainerService {
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError>;
 
+    /// Apply the workspace disk quota before a new workspace is created. Fails
+    /// with [`ContainerError::QuotaExceeded`] when new workspaces are refused.
+    async fn enforce_workspace_quota(&self) -> Result<(), ContainerError>;
+
     /// Check if a task has any running execution processes
     async fn has_running_processes(&self, task_id: Uuid) -> Result<bool, ContainerError> {
         let workspaces = Workspace::fetch_all(&self.db().pool, Some(task_id)).await?;
@@ -183,14 +191,15 @@ pub trait ContainerService {
 
     /// A context is finalized when
     /// - Always when the execution process has failed or been killed
-    /// - Never when the run reason is DevServer
+    /// - Never when the run reason is DevServer or QualityGate
     /// - Never when a setup script has no next_action (parallel mode)
     /// - The next action is None (no follow-up actions)
     fn should_finalize(&self, ctx: &ExecutionContext) -> bool {
-        // Never finalize DevServer processes
+        // Never finalize DevServer processes or quality gates, which run
+        // alongside the task rather than as part of it
         if matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::DevServer
+            ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::QualityGate
         ) {
             return false;
         }
@@ -523,6 +532,204 @@ pub trait ContainerService {
         Ok(())
     }
 
+    /// Run the quality gates configured for `repo` in its worktree, one at a
+    /// time in gate order, each as its own execution process recorded against
+    /// the current branch head. Returns the process of the first gate; the
+    /// rest are started by `start_next_quality_gate` as each one exits.
+    async fn start_quality_gates(
+        &self,
+        workspace: &Workspace,
+        repo: &Repo,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        let pool = &self.db().pool;
+        let Some(gate) = QualityGate::find_by_repo_id(pool, repo.id)
+            .await?
+            .into_iter()
+            .next()
+        else {
+            return Ok(None);
+        };
+
+        let container_ref = self.ensure_container_exists(workspace).await?;
+        let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+        let head_commit = self.git().get_head_info(&worktree_path)?.oid;
+
+        let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(s) => s,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    workspace.id,
+                )
+                .await?
+            }
+        };
+
+        let process = self
+            .start_quality_gate(workspace, &session, repo, gate, &head_commit)
+            .await?;
+        Ok(Some(process))
+    }
+
+    /// After a quality gate process exits, start the gate that follows it for
+    /// the same repo and commit. Gates share the worktree, so they never run
+    /// side by side. Stopping a gate stops the rest of the run.
+    async fn start_next_quality_gate(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        if !matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::QualityGate
+        ) || matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed)
+        {
+            return Ok(None);
+        }
+
+        let pool = &self.db().pool;
+        let Some(run) =
+            QualityGateRun::find_by_execution_process_id(pool, ctx.execution_process.id).await?
+        else {
+            return Ok(None);
+        };
+        let Some(finished) = QualityGate::find_by_id(pool, run.quality_gate_id).await? else {
+            return Ok(None);
+        };
+        let Some(repo) = ctx.repos.iter().find(|r| r.id == finished.repo_id) else {
+            return Ok(None);
+        };
+
+        let Some(next) = QualityGate::find_by_repo_id(pool, repo.id)
+            .await?
+            .into_iter()
+            .skip_while(|gate| gate.id != finished.id)
+            .nth(1)
+        else {
+            return Ok(None);
+        };
+
+        let process = self
+            .start_quality_gate(&ctx.workspace, &ctx.session, repo, next, &run.head_commit)
+            .await?;
+        Ok(Some(process))
+    }
+
+    async fn start_quality_gate(
+        &self,
+        workspace: &Workspace,
+        session: &Session,
+        repo: &Repo,
+        gate: QualityGate,
+        head_commit: &str,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: gate.script,
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::QualityGate,
+                working_dir: Some(repo.name.clone()),
+            }),
+            None,
+        );
+        let process = self
+            .start_execution(
+                workspace,
+                session,
+                &action,
+                &ExecutionProcessRunReason::QualityGate,
+            )
+            .await?;
+        QualityGateRun::create(
+            &self.db().pool,
+            gate.id,
+            workspace.id,
+            process.id,
+            head_commit,
+        )
+        .await?;
+        Ok(process)
+    }
+
+    /// Start a coding agent follow-up with `prompt` in the workspace's latest session,
+    /// reusing the executor profile of its most recent coding agent run.
+    /// Returns None if the session has never run a coding agent.
+    async fn start_agent_follow_up(
+        &self,
+        workspace: &Workspace,
+        prompt: String,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        self.start_agent_follow_up_with_fallback(workspace, prompt, None)
+            .await
+    }
+
+    /// Like [`Self::start_agent_follow_up`], but starts a new agent with
+    /// `fallback_profile` when the session has never run a coding agent.
+    async fn start_agent_follow_up_with_fallback(
+        &self,
+        workspace: &Workspace,
+        prompt: String,
+        fallback_profile: Option<ExecutorProfileId>,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        let pool = &self.db().pool;
+        let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(s) => s,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    workspace.id,
+                )
+                .await?
+            }
+        };
+
+        let Some(executor_profile_id) =
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+                .await?
+                .or(fallback_profile)
+        else {
+            return Ok(None);
+        };
+
+        let latest_session_info =
+            CodingAgentTurn::find_latest_session_info(pool, session.id).await?;
+
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action_type = if let Some(info) = latest_session_info {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id: info.session_id,
+                reset_to_message_id: None,
+                executor_profile_id,
+                working_dir,
+            })
+        } else {
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir,
+            })
+        };
+
+        let action = ExecutorAction::new(action_type, None);
+        self.start_execution(
+            workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+        .map(Some)
+    }
+
     /// Archive a workspace: set archived flag, stop running dev servers, and run archive script.
     async fn archive_workspace(&self, workspace_id: Uuid) -> Result<(), ContainerError> {
         let pool = &self.db().pool;
@@ -773,6 +980,7 @@ pub trait ContainerService {
         &self,
         workspace: &Workspace,
         stats_only: bool,
+        view_options: DiffViewOptions,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
@@ -783,15 +991,76 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
+    async fn git_branch_template(&self) -> Option<String>;
+
+    /// GitHub username of the signed-in user, for the `{user}` template value
+    async fn git_branch_user(&self) -> Option<String>;
+
+    /// Branch for a new workspace of `task`, rendered from the naming template
+    /// of its repos, project or the global settings. Collisions with existing
+    /// workspaces or branches in the repos, including `vk` against `vk/…`, get
+    /// a `-2`, `-3`, … suffix.
+    async fn git_branch_from_workspace(
+        &self,
+        workspace_id: &Uuid,
+        task: &Task,
+        repo_ids: &[Uuid],
+        executor: Option<&ExecutorProfileId>,
+        issue: Option<&str>,
+    ) -> String {
+        let pool = &self.db().pool;
+        let mut repos = Vec::with_capacity(repo_ids.len());
+        for repo_id in repo_ids {
+            if let Ok(Some(repo)) = Repo::find_by_id(pool, *repo_id).await {
+                repos.push(repo);
+            }
+        }
+        let project = Project::find_by_id(pool, task.project_id)
+            .await
+            .ok()
+            .flatten();
+        let global_template = self.git_branch_template().await;
         let prefix = self.git_branch_prefix().await;
+        let user = self.git_branch_user().await;
+        let executor = executor.map(|profile| profile.executor.to_string());
+
+        let input = BranchNameInput {
+            prefix: &prefix,
+            workspace_id: *workspace_id,
+            task_id: task.id,
+            task_title: &task.title,
+            executor: executor.as_deref(),
+            user: user.as_deref(),
+            issue,
+            now: Utc::now(),
+        };
+        let template =
+            branch_name::resolve_template(global_template.as_deref(), project.as_ref(), &repos);
+        let mut base = branch_name::render_branch_name(template, &input);
+        if base.is_empty() || !self.git().is_branch_name_valid(&base) {
+            tracing::warn!(
+                "Branch name template '{}' rendered the invalid branch name '{}', using the default template",
+                template,
+                base
+            );
+            base = branch_name::render_branch_name(DEFAULT_BRANCH_NAME_TEMPLATE, &input);
+        }
 
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(workspace_id), task_title_id)
-        } else {
-            format!("{}/{}-{}", prefix, short_uuid(workspace_id), task_title_id)
+        for candidate in branch_name::candidates(&base) {
+            let in_repos = repos.iter().any(|repo| {
+                self.git()
+                    .branch_name_conflicts(&repo.path, &candidate)
+                    .unwrap_or(false)
+            });
+            if !in_repos
+                && !Workspace::branch_conflicts(pool, &candidate)
+                    .await
+                    .unwrap_or(false)
+            {
+                return candidate;
+            }
         }
+        base
     }
 
     async fn stream_raw_logs(
@@ -1212,7 +1481,10 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
         if task.status != TaskStatus::InProgress
-            && run_reason != &ExecutionProcessRunReason::DevServer
+            && !matches!(
+                run_reason,
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::QualityGate
+            )
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
         }
@@ -0,0 +1,51 @@
+//! JSON files holding credentials, readable only by the current user and
+//! replaced atomically on every write.
+
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Read `path`, returning `None` when it doesn't exist. A file that no longer
+/// parses is renamed to `.bad` and treated as missing.
+pub fn load<T: DeserializeOwned>(path: &Path) -> std::io::Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let bytes = std::fs::read(path)?;
+    match serde_json::from_slice(&bytes) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => {
+            tracing::warn!(?e, path = %path.display(), "failed to parse credentials file, renaming to .bad");
+            let bad = path.with_extension("bad");
+            let _ = std::fs::rename(path, bad);
+            Ok(None)
+        }
+    }
+}
+
+/// Write `value` to a mode 0600 temp file next to `path`, sync it and move it
+/// into place
+pub fn save<T: Serialize + ?Sized>(path: &Path, value: &T) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+
+    let file = {
+        let mut opts = std::fs::OpenOptions::new();
+        opts.create(true).truncate(true).write(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+
+        opts.open(&tmp)?
+    };
+
+    serde_json::to_writer_pretty(&file, value)?;
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
@@ -25,7 +25,7 @@ use thiserror::Error;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::{IntervalStream, ReceiverStream};
 use utils::{
-    diff::{self, Diff},
+    diff::{self, Diff, DiffViewOptions},
     log_msg::LogMsg,
 };
 use uuid::Uuid;
@@ -172,6 +172,7 @@ pub struct DiffStreamArgs {
     pub base_commit: Commit,
     pub stats_only: bool,
     pub path_prefix: Option<String>,
+    pub view_options: DiffViewOptions,
 }
 
 struct DiffStreamManager {
@@ -180,6 +181,8 @@ struct DiffStreamManager {
     cumulative: Arc<AtomicUsize>,
     known_paths: Arc<std::sync::RwLock<HashSet<String>>>,
     full_sent: Arc<std::sync::RwLock<HashSet<String>>>,
+    /// Submodule paths, refreshed only when `.gitmodules` changes
+    submodule_paths: Arc<Vec<String>>,
     current_base_commit: Commit,
     current_target_branch: String,
 }
@@ -192,7 +195,8 @@ enum DiffEvent {
 
 pub async fn create(args: DiffStreamArgs) -> Result<DiffStreamHandle, DiffStreamError> {
     let (tx, rx) = mpsc::channel::<Result<LogMsg, io::Error>>(DIFF_STREAM_CHANNEL_CAPACITY);
-    let manager_args = args.clone();
+    let mut manager_args = args.clone();
+    manager_args.view_options = stream_view_options(manager_args.view_options, args.stats_only);
 
     let watcher_task = tokio::spawn(async move {
         let mut manager = DiffStreamManager::new(manager_args, tx);
@@ -208,6 +212,58 @@ pub async fn create(args: DiffStreamArgs) -> Result<DiffStreamHandle, DiffStream
     ))
 }
 
+/// Stream the changes introduced by a single commit. Commits are immutable,
+/// so every file is sent once, followed by `Ready` and `Finished`.
+pub async fn create_for_commit(
+    git_service: GitService,
+    repo_id: Uuid,
+    repo_path: PathBuf,
+    commit_sha: String,
+    stats_only: bool,
+    path_prefix: Option<String>,
+    view_options: DiffViewOptions,
+) -> Result<DiffStreamHandle, DiffStreamError> {
+    let view_options = stream_view_options(view_options, stats_only);
+    let diffs = tokio::task::spawn_blocking(move || {
+        git_service.get_diffs_with_options(
+            DiffTarget::Commit {
+                repo_path: &repo_path,
+                commit_sha: &commit_sha,
+            },
+            None,
+            &view_options,
+        )
+    })
+    .await??;
+
+    let cumulative = Arc::new(AtomicUsize::new(0));
+    let messages = diffs
+        .into_iter()
+        .map(|mut diff| {
+            apply_stream_omit_policy(&mut diff, &cumulative, stats_only);
+            let entry = prefix_path(GitService::diff_path(&diff), path_prefix.as_deref());
+            diff.old_path = diff
+                .old_path
+                .map(|old| prefix_path(old, path_prefix.as_deref()));
+            diff.new_path = diff
+                .new_path
+                .map(|new| prefix_path(new, path_prefix.as_deref()));
+            diff.repo_id = Some(repo_id);
+            LogMsg::JsonPatch(ConversationPatch::add_diff(
+                escape_json_pointer_segment(&entry),
+                diff,
+            ))
+        })
+        .chain([LogMsg::Ready, LogMsg::Finished])
+        .map(Ok::<_, io::Error>)
+        .collect::<Vec<_>>();
+
+    Ok(DiffStreamHandle::new(
+        futures::stream::iter(messages).boxed(),
+        None,
+    ))
+}
+
 impl DiffStreamManager {
     fn new(args: DiffStreamArgs, tx: mpsc::Sender<Result<LogMsg, io::Error>>) -> Self {
         Self {
@@ -218,10 +274,12 @@ impl DiffStreamManager {
             cumulative: Arc::new(AtomicUsize::new(0)),
             known_paths: Arc::new(std::sync::RwLock::new(HashSet::new())),
             full_sent: Arc::new(std::sync::RwLock::new(HashSet::new())),
+            submodule_paths: Arc::new(Vec::new()),
         }
     }
 
     async fn run(&mut self) -> Result<(), DiffStreamError> {
+        self.refresh_submodule_paths().await?;
         self.reset_stream().await?;
 
         // Send Ready message to indicate initial data has been sent
@@ -304,15 +362,17 @@ impl DiffStreamManager {
         let worktree = self.args.worktree_path.clone();
         let base = self.current_base_commit.clone();
         let stats_only = self.args.stats_only;
+        let view_options = self.args.view_options.clone();
         let cumulative = self.cumulative.clone();
 
         tokio::task::spawn_blocking(move || {
-            let diffs = git.get_diffs(
+            let diffs = git.get_diffs_with_options(
                 DiffTarget::Worktree {
                     worktree_path: &worktree,
                     base_commit: &base,
                 },
                 None,
+                &view_options,
             )?;
 
             let mut processed_diffs = Vec::with_capacity(diffs.len());
@@ -357,8 +417,16 @@ impl DiffStreamManager {
         Ok(())
     }
 
+    async fn refresh_submodule_paths(&mut self) -> Result<(), DiffStreamError> {
+        let git = self.args.git_service.clone();
+        let worktree = self.args.worktree_path.clone();
+        let paths = tokio::task::spawn_blocking(move || git.get_submodule_paths(&worktree)).await?;
+        self.submodule_paths = Arc::new(paths);
+        Ok(())
+    }
+
     async fn handle_fs_events(
-        &self,
+        &mut self,
         events: Vec<DebouncedEvent>,
         canonical_worktree: &Path,
     ) -> Result<(), DiffStreamError> {
@@ -369,13 +437,19 @@ impl DiffStreamManager {
             return Ok(());
         }
 
+        if changed_paths.iter().any(|p| p == ".gitmodules") {
+            self.refresh_submodule_paths().await?;
+        }
+
         let git = self.args.git_service.clone();
         let worktree = self.args.worktree_path.clone();
         let base = self.current_base_commit.clone();
         let cumulative = self.cumulative.clone();
         let full_sent = self.full_sent.clone();
         let known_paths = self.known_paths.clone();
+        let submodule_paths = self.submodule_paths.clone();
         let stats_only = self.args.stats_only;
+        let view_options = self.args.view_options.clone();
         let prefix = self.args.path_prefix.clone();
         let repo_id = self.args.repo_id;
 
@@ -385,10 +459,12 @@ impl DiffStreamManager {
                 &worktree,
                 &base,
                 &changed_paths,
+                &submodule_paths,
                 &cumulative,
                 &full_sent,
                 &known_paths,
                 stats_only,
+                &view_options,
                 prefix.as_deref(),
                 repo_id,
             )
@@ -472,6 +548,9 @@ pub fn apply_stream_omit_policy(diff: &mut Diff, sent_bytes: &Arc<AtomicUsize>,
     if let Some(ref s) = diff.new_content {
         size += s.len();
     }
+    if let Some(ref hunks) = diff.hunks {
+        size += hunks.iter().map(String::len).sum::<usize>();
+    }
 
     if size == 0 {
         return;
@@ -485,6 +564,15 @@ pub fn apply_stream_omit_policy(diff: &mut Diff, sent_bytes: &Arc<AtomicUsize>,
     }
 }
 
+/// Stats-only streams drop file contents, so word highlights would only be
+/// computed to be thrown away
+fn stream_view_options(view_options: DiffViewOptions, stats_only: bool) -> DiffViewOptions {
+    DiffViewOptions {
+        word_diff: view_options.word_diff && !stats_only,
+        ..view_options
+    }
+}
+
 fn omit_diff_contents(diff: &mut Diff) {
     if diff.additions.is_none()
         && diff.deletions.is_none()
@@ -499,6 +587,8 @@ fn omit_diff_contents(diff: &mut Diff) {
 
     diff.old_content = None;
     diff.new_content = None;
+    diff.hunks = None;
+    diff.word_highlights = None;
     diff.content_omitted = true;
 }
 
@@ -520,27 +610,49 @@ fn extract_changed_paths(
         .collect()
 }
 
+fn collapse_submodule_paths(changed_paths: &[String], submodule_paths: &[String]) -> Vec<String> {
+    let mut collapsed: Vec<String> = Vec::with_capacity(changed_paths.len());
+    for path in changed_paths {
+        let path = submodule_paths
+            .iter()
+            .find(|sub| {
+                path.strip_prefix(sub.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .unwrap_or(path);
+        if !collapsed.contains(path) {
+            collapsed.push(path.clone());
+        }
+    }
+    collapsed
+}
+
 #[allow(clippy::too_many_arguments)]
 fn process_file_changes(
     git_service: &GitService,
     worktree_path: &Path,
     base_commit: &Commit,
     changed_paths: &[String],
+    submodule_paths: &[String],
     cumulative_bytes: &Arc<AtomicUsize>,
     full_sent_paths: &Arc<std::sync::RwLock<HashSet<String>>>,
     known_paths: &Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    view_options: &DiffViewOptions,
     path_prefix: Option<&str>,
     repo_id: Uuid,
 ) -> Result<Vec<LogMsg>, DiffStreamError> {
+    // Edits inside a submodule only move its pointer, so diff the submodule itself
+    let changed_paths = collapse_submodule_paths(changed_paths, submodule_paths);
     let path_filter: Vec<&str> = changed_paths.iter().map(|s| s.as_str()).collect();
 
-    let current_diffs = git_service.get_diffs(
+    let current_diffs = git_service.get_diffs_with_options(
         DiffTarget::Worktree {
             worktree_path,
             base_commit,
         },
         Some(&path_filter),
+        view_options,
     )?;
 
     let mut msgs = Vec::new();
@@ -579,7 +691,7 @@ fn process_file_changes(
         msgs.push(LogMsg::JsonPatch(patch));
     }
 
-    for changed_path in changed_paths {
+    for changed_path in &changed_paths {
         if !files_with_diffs.contains(changed_path) {
             let prefixed_path = prefix_path(changed_path.clone(), path_prefix);
             let patch = ConversationPatch::remove_diff(escape_json_pointer_segment(&prefixed_path));
@@ -0,0 +1,175 @@
+//! Disk usage of workspace worktrees, including git-ignored build output such
+//! as `node_modules` and `target`.
+
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    workspace::{Workspace, WorkspaceError},
+    workspace_repo::WorkspaceRepo,
+};
+use git::GitService;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::workspace_manager::WorkspaceManager;
+
+/// How long a measured size of the workspace base directory is reused before
+/// the directory is walked again
+const TOTAL_BYTES_MAX_AGE: Duration = Duration::from_secs(300);
+
+static TOTAL_BYTES_CACHE: Mutex<Option<(Instant, u64)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RepoDiskUsage {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub total_bytes: u64,
+    /// Bytes in git-ignored paths, such as dependency and build directories
+    pub ignored_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WorkspaceDiskUsage {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub name: Option<String>,
+    pub branch: String,
+    pub archived: bool,
+    /// Last activity in the workspace; the least recently used archived
+    /// workspaces are cleaned up first when the quota is exceeded
+    pub last_used_at: DateTime<Utc>,
+    pub total_bytes: u64,
+    pub repos: Vec<RepoDiskUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DiskUsageReport {
+    /// Size of the whole workspace directory, including worktrees that no
+    /// longer belong to a workspace
+    pub total_bytes: u64,
+    pub limit_bytes: Option<u64>,
+    pub workspaces: Vec<WorkspaceDiskUsage>,
+}
+
+/// Total size of the files under `path`, without following symlinks.
+/// Entries that cannot be read are skipped.
+pub fn dir_size(path: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(metadata) = std::fs::symlink_metadata(&current) else {
+            continue;
+        };
+        if !metadata.is_dir() {
+            total += metadata.len();
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        pending.extend(entries.flatten().map(|entry| entry.path()));
+    }
+    total
+}
+
+/// Size of the workspace base directory
+pub async fn total_workspace_bytes() -> u64 {
+    let total =
+        tokio::task::spawn_blocking(|| dir_size(&WorkspaceManager::get_workspace_base_dir()))
+            .await
+            .unwrap_or_default();
+    *TOTAL_BYTES_CACHE.lock().unwrap() = Some((Instant::now(), total));
+    total
+}
+
+/// Size of the workspace base directory, reusing the last measurement while
+/// it is recent. Walking the directory is expensive with large worktrees.
+pub async fn cached_total_workspace_bytes() -> u64 {
+    let cached = *TOTAL_BYTES_CACHE.lock().unwrap();
+    match cached {
+        Some((measured_at, total)) if measured_at.elapsed() < TOTAL_BYTES_MAX_AGE => total,
+        _ => total_workspace_bytes().await,
+    }
+}
+
+/// Drop the cached size after workspaces were removed
+pub fn invalidate_total_workspace_bytes() {
+    *TOTAL_BYTES_CACHE.lock().unwrap() = None;
+}
+
+/// Disk usage of one workspace's worktrees. Workspaces without a worktree on
+/// disk report zero.
+pub async fn workspace_disk_usage(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &Workspace,
+) -> Result<WorkspaceDiskUsage, WorkspaceError> {
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let container_ref = workspace.container_ref.clone();
+    let git = git.clone();
+    let repos = tokio::task::spawn_blocking(move || {
+        let Some(container_ref) = container_ref else {
+            return Vec::new();
+        };
+        let workspace_dir = Path::new(&container_ref);
+        repos
+            .iter()
+            .map(|repo| {
+                let worktree_path = workspace_dir.join(&repo.name);
+                let ignored_bytes = git
+                    .ignored_paths(&worktree_path)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|p| dir_size(&worktree_path.join(p)))
+                    .sum();
+                RepoDiskUsage {
+                    repo_id: repo.id,
+                    repo_name: repo.name.clone(),
+                    total_bytes: dir_size(&worktree_path),
+                    ignored_bytes,
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    Ok(WorkspaceDiskUsage {
+        workspace_id: workspace.id,
+        task_id: workspace.task_id,
+        name: workspace.name.clone(),
+        branch: workspace.branch.clone(),
+        archived: workspace.archived,
+        last_used_at: workspace.updated_at,
+        total_bytes: repos.iter().map(|r| r.total_bytes).sum(),
+        repos,
+    })
+}
+
+/// Disk usage of every workspace that has a worktree on disk, largest first.
+pub async fn disk_usage_report(
+    pool: &SqlitePool,
+    git: &GitService,
+    limit_bytes: Option<u64>,
+) -> Result<DiskUsageReport, WorkspaceError> {
+    let mut workspaces = Vec::new();
+    for workspace in Workspace::fetch_all(pool, None).await? {
+        if workspace.container_ref.is_some() {
+            workspaces.push(workspace_disk_usage(pool, git, &workspace).await?);
+        }
+    }
+    workspaces.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+
+    Ok(DiskUsageReport {
+        total_bytes: total_workspace_bytes().await,
+        limit_bytes,
+        workspaces,
+    })
+}
@@ -10,7 +10,7 @@ use db::models::{
     project_repo::ProjectRepo,
 };
 use fst::{Map, MapBuilder};
-use git::GitService;
+use git::{GitService, SparseCone};
 use ignore::WalkBuilder;
 use moka::future::Cache;
 use notify::{RecommendedWatcher, RecursiveMode};
@@ -140,6 +140,7 @@ impl FileSearchCache {
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<Vec<SearchResult>, CacheError> {
         let repo_path_buf = repo_path.to_path_buf();
 
@@ -149,7 +150,9 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
-            return Ok(self.search_in_cache(&cached, query, mode).await);
+            return Ok(self
+                .search_in_cache(&cached, query, mode, sparse_cone)
+                .await);
         }
 
         // Cache miss - trigger background refresh and return error
@@ -231,13 +234,16 @@ impl FileSearchCache {
         cached: &CachedRepo,
         query: &str,
         mode: SearchMode,
+        sparse_cone: Option<&SparseCone>,
     ) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
         let mut results = Vec::new();
 
         // Search through indexed files with mode-based filtering
         for indexed_file in &cached.indexed_files {
-            if indexed_file.path_lowercase.contains(&query_lower) {
+            if indexed_file.path_lowercase.contains(&query_lower)
+                && in_sparse_cone(sparse_cone, &indexed_file.path, indexed_file.is_file)
+            {
                 // Apply mode-based filtering
                 match mode {
                     SearchMode::TaskForm => {
@@ -274,12 +280,14 @@ impl FileSearchCache {
         results
     }
 
-    /// Search files in a single repository with cache + fallback
+    /// Search files in a single repository with cache + fallback.
+    /// Results outside `sparse_cone` are dropped, since sparse worktrees won't contain them.
     pub async fn search_repo(
         &self,
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<Vec<SearchResult>, String> {
         let query = query.trim();
         if query.is_empty() {
@@ -287,11 +295,15 @@ impl FileSearchCache {
         }
 
         // Try cache first
-        match self.search(repo_path, query, mode.clone()).await {
+        match self
+            .search(repo_path, query, mode.clone(), sparse_cone)
+            .await
+        {
             Ok(results) => Ok(results),
             Err(CacheError::Miss) | Err(CacheError::BuildError(_)) => {
                 // Fall back to filesystem search
-                self.search_files_no_cache(repo_path, query, mode).await
+                self.search_files_no_cache(repo_path, query, mode, sparse_cone)
+                    .await
             }
         }
     }
@@ -302,6 +314,7 @@ impl FileSearchCache {
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<Vec<SearchResult>, String> {
         if !repo_path.exists() {
             return Err(format!("Path not found: {:?}", repo_path));
@@ -356,6 +369,13 @@ impl FileSearchCache {
                 Ok(p) => p,
                 Err(_) => continue,
             };
+            if !in_sparse_cone(
+                sparse_cone,
+                &relative_path.to_string_lossy().replace('\\', "/"),
+                path.is_file(),
+            ) {
+                continue;
+            }
             let relative_path_str = relative_path.to_string_lossy().to_lowercase();
 
             let file_name = path
@@ -667,3 +687,11 @@ impl Default for FileSearchCache {
         Self::new()
     }
 }
+
+fn in_sparse_cone(sparse_cone: Option<&SparseCone>, path: &str, is_file: bool) -> bool {
+    match sparse_cone {
+        None => true,
+        Some(cone) if is_file => cone.contains_file(path),
+        Some(cone) => cone.contains_dir(path),
+    }
+}
@@ -1,7 +1,7 @@
-//! Minimal helpers around the Azure CLI (`az repos`).
+//! Minimal helpers around the Azure CLI (`az repos`, `az boards`).
 //!
 //! This module provides low-level access to the Azure CLI for Azure DevOps
-//! repository and pull request operations.
+//! repository, pull request and work item operations.
 
 use std::{
     ffi::{OsStr, OsString},
@@ -15,7 +15,12 @@ use serde::Deserialize;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::git_host::types::{CreatePrRequest, UnifiedPrComment};
+use crate::services::git_host::types::{
+    CreatePrRequest, GitHostIssue, IssueFilter, MergeMethod, UnifiedPrComment,
+};
+
+/// Work item states that count as closed, across the built-in processes
+const CLOSED_WORK_ITEM_STATES: [&str; 3] = ["Closed", "Done", "Removed"];
 
 #[derive(Debug, Clone)]
 pub struct AzureRepoInfo {
@@ -31,9 +36,26 @@ pub struct AzureRepoInfo {
 struct AzPrResponse {
     pull_request_id: i64,
     status: Option<String>,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    description: Option<String>,
     closed_date: Option<String>,
     repository: Option<AzRepository>,
     last_merge_commit: Option<AzCommit>,
+    #[serde(default)]
+    reviewers: Vec<AzReviewer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzReviewer {
+    /// 10 approved, 5 approved with suggestions, 0 no vote, -5 waiting for
+    /// the author, -10 rejected
+    #[serde(default)]
+    vote: i64,
+    #[serde(default)]
+    is_required: bool,
 }
 
 #[derive(Deserialize)]
@@ -88,6 +110,39 @@ struct AzAuthor {
     display_name: Option<String>,
 }
 
+/// Work item from `az boards query` or `az boards work-item show`
+#[derive(Deserialize)]
+struct AzWorkItem {
+    id: i64,
+    fields: AzWorkItemFields,
+}
+
+#[derive(Deserialize)]
+struct AzWorkItemFields {
+    #[serde(rename = "System.Title", default)]
+    title: String,
+    #[serde(rename = "System.State", default)]
+    state: String,
+    #[serde(rename = "System.Description")]
+    description: Option<String>,
+    /// Tags separated by `; `
+    #[serde(rename = "System.Tags")]
+    tags: Option<String>,
+    #[serde(rename = "System.AssignedTo")]
+    assigned_to: Option<AzIdentity>,
+    #[serde(rename = "System.IterationPath")]
+    iteration_path: Option<String>,
+    #[serde(rename = "System.TeamProject")]
+    team_project: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzIdentity {
+    unique_name: Option<String>,
+    display_name: Option<String>,
+}
+
 /// Response item from `az repos list`
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -309,28 +364,79 @@ impl AzCli {
     }
 
     pub fn view_pr(&self, pr_url: &str) -> Result<PullRequestInfo, AzCliError> {
-        let (organization, pr_id) = Self::parse_pr_url(pr_url).ok_or_else(|| {
-            AzCliError::UnexpectedOutput(format!("Could not parse Azure DevOps PR URL: {pr_url}"))
+        let raw = self.run_for_pr(pr_url, "show", &[])?;
+        Self::parse_pr_response(&raw)
+    }
+
+    /// Description of a PR, as markdown
+    pub fn pr_description(&self, pr_url: &str) -> Result<String, AzCliError> {
+        let raw = self.run_for_pr(pr_url, "show", &[])?;
+        let pr: AzPrResponse = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!("Failed to parse PR response: {e}; raw: {raw}"))
         })?;
+        Ok(pr.description.unwrap_or_default())
+    }
 
-        let org_url = format!("https://dev.azure.com/{}", organization);
+    pub fn update_pr_description(&self, pr_url: &str, description: &str) -> Result<(), AzCliError> {
+        self.run_for_pr(pr_url, "update", &["--description", description])?;
+        Ok(())
+    }
 
-        let raw = self.run(
-            [
-                "repos",
-                "pr",
-                "show",
-                "--id",
-                &pr_id.to_string(),
-                "--organization",
-                &org_url,
-                "--output",
-                "json",
-            ],
-            None,
+    pub fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), AzCliError> {
+        let draft = if draft { "true" } else { "false" };
+        self.run_for_pr(pr_url, "update", &["--draft", draft])?;
+        Ok(())
+    }
+
+    /// Whether a PR is approved by someone and every required reviewer, and
+    /// no one waits for the author or rejected it
+    pub fn is_pr_approved(&self, pr_url: &str) -> Result<bool, AzCliError> {
+        let raw = self.run_for_pr(pr_url, "show", &[])?;
+        Self::parse_pr_approved(&raw)
+    }
+
+    /// Set the PR to complete once its branch policies pass. The CLI can't
+    /// rebase, so rebases complete with a merge commit.
+    pub fn complete_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), AzCliError> {
+        let squash = if method == MergeMethod::Squash {
+            "true"
+        } else {
+            "false"
+        };
+        self.run_for_pr(
+            pr_url,
+            "update",
+            &["--status", "completed", "--squash", squash],
         )?;
+        Ok(())
+    }
 
-        Self::parse_pr_response(&raw)
+    /// Run `az repos pr <command>` for the PR at `pr_url`
+    fn run_for_pr(
+        &self,
+        pr_url: &str,
+        command: &str,
+        extra: &[&str],
+    ) -> Result<String, AzCliError> {
+        let (organization, pr_id) = Self::parse_pr_url(pr_url).ok_or_else(|| {
+            AzCliError::UnexpectedOutput(format!("Could not parse Azure DevOps PR URL: {pr_url}"))
+        })?;
+
+        let org_url = format!("https://dev.azure.com/{}", organization);
+        let pr_id = pr_id.to_string();
+
+        let mut args = vec![
+            "repos",
+            "pr",
+            command,
+            "--id",
+            &pr_id,
+            "--organization",
+            &org_url,
+        ];
+        args.extend_from_slice(extra);
+        args.extend(["--output", "json"]);
+        self.run(args, None)
     }
 
     pub fn list_prs_for_branch(
@@ -393,6 +499,166 @@ impl AzCli {
         Self::parse_pr_threads(&raw)
     }
 
+    /// Open work items of a project that match `filter`. Labels are matched
+    /// against tags and the milestone against the iteration path.
+    pub fn list_work_items(
+        &self,
+        organization_url: &str,
+        project: &str,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, AzCliError> {
+        let wiql = Self::work_items_query(project, filter);
+        let raw = self.run(
+            [
+                "boards",
+                "query",
+                "--wiql",
+                &wiql,
+                "--organization",
+                organization_url,
+                "--project",
+                project,
+                "--output",
+                "json",
+            ],
+            None,
+        )?;
+        let items: Vec<AzWorkItem> = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!(
+                "Failed to parse work item query response: {e}; raw: {raw}"
+            ))
+        })?;
+        Ok(items
+            .into_iter()
+            .map(|item| Self::work_item_to_issue(item, organization_url, project))
+            .collect())
+    }
+
+    pub fn view_work_item(&self, work_item_url: &str) -> Result<GitHostIssue, AzCliError> {
+        let raw = self.run_for_work_item(work_item_url, "show", &[])?;
+        let item: AzWorkItem = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!(
+                "Failed to parse work item response: {e}; raw: {raw}"
+            ))
+        })?;
+        let (organization_url, _) = Self::parse_work_item_url(work_item_url).ok_or_else(|| {
+            AzCliError::UnexpectedOutput(format!("Could not parse work item URL: {work_item_url}"))
+        })?;
+        let project = item.fields.team_project.clone().unwrap_or_default();
+        Ok(Self::work_item_to_issue(item, &organization_url, &project))
+    }
+
+    pub fn comment_on_work_item(&self, work_item_url: &str, body: &str) -> Result<(), AzCliError> {
+        self.run_for_work_item(work_item_url, "update", &["--discussion", body])?;
+        Ok(())
+    }
+
+    /// Move a work item to `Closed`, or to `Done` for processes that have no
+    /// `Closed` state
+    pub fn close_work_item(&self, work_item_url: &str) -> Result<(), AzCliError> {
+        match self.run_for_work_item(work_item_url, "update", &["--state", "Closed"]) {
+            Ok(_) => Ok(()),
+            Err(AzCliError::CommandFailed(_)) => {
+                self.run_for_work_item(work_item_url, "update", &["--state", "Done"])?;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Run `az boards work-item <command>` for the work item at `work_item_url`
+    fn run_for_work_item(
+        &self,
+        work_item_url: &str,
+        command: &str,
+        extra: &[&str],
+    ) -> Result<String, AzCliError> {
+        let (organization_url, id) = Self::parse_work_item_url(work_item_url).ok_or_else(|| {
+            AzCliError::UnexpectedOutput(format!("Could not parse work item URL: {work_item_url}"))
+        })?;
+        let id = id.to_string();
+        let mut args = vec![
+            "boards",
+            "work-item",
+            command,
+            "--id",
+            &id,
+            "--organization",
+            &organization_url,
+        ];
+        args.extend_from_slice(extra);
+        args.extend(["--output", "json"]);
+        self.run(args, None)
+    }
+
+    fn work_items_query(project: &str, filter: &IssueFilter) -> String {
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let closed = CLOSED_WORK_ITEM_STATES
+            .iter()
+            .map(|state| quote(state))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut wiql = format!(
+            "SELECT [System.Id], [System.Title], [System.State], [System.Description], \
+             [System.Tags], [System.AssignedTo], [System.IterationPath] FROM WorkItems \
+             WHERE [System.TeamProject] = {} AND [System.State] NOT IN ({closed})",
+            quote(project)
+        );
+        for label in &filter.labels {
+            wiql.push_str(&format!(" AND [System.Tags] CONTAINS {}", quote(label)));
+        }
+        if let Some(assignee) = &filter.assignee {
+            wiql.push_str(&format!(" AND [System.AssignedTo] = {}", quote(assignee)));
+        }
+        if let Some(milestone) = &filter.milestone {
+            wiql.push_str(&format!(
+                " AND [System.IterationPath] UNDER {}",
+                quote(milestone)
+            ));
+        }
+        wiql.push_str(" ORDER BY [System.ChangedDate] DESC");
+        wiql
+    }
+
+    fn work_item_to_issue(item: AzWorkItem, organization_url: &str, project: &str) -> GitHostIssue {
+        let fields = item.fields;
+        GitHostIssue {
+            number: item.id,
+            url: format!(
+                "{}/{}/_workitems/edit/{}",
+                organization_url.trim_end_matches('/'),
+                project.replace(' ', "%20"),
+                item.id
+            ),
+            title: fields.title,
+            body: fields.description.filter(|d| !d.is_empty()),
+            is_open: !CLOSED_WORK_ITEM_STATES.contains(&fields.state.as_str()),
+            labels: fields
+                .tags
+                .as_deref()
+                .unwrap_or_default()
+                .split(';')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+            assignees: fields
+                .assigned_to
+                .and_then(|a| a.unique_name.or(a.display_name))
+                .into_iter()
+                .collect(),
+            milestone: fields.iteration_path,
+        }
+    }
+
+    /// Parse a work item URL to extract the organization URL and work item ID.
+    /// Format: `https://dev.azure.com/{org}/{project}/_workitems/edit/{id}`
+    pub fn parse_work_item_url(url: &str) -> Option<(String, i64)> {
+        let (_, tail) = url.split_once("/_workitems/edit/")?;
+        let id = tail.split(['/', '#', '?']).next()?.parse().ok()?;
+        Some((Self::extract_organization_url(url)?, id))
+    }
+
     /// Parse PR URL to extract organization and PR ID.
     ///
     /// Only extracts the minimal info needed for `az repos pr show`.
@@ -443,6 +709,16 @@ impl AzCli {
         Ok(Self::az_pr_to_info(pr))
     }
 
+    fn parse_pr_approved(raw: &str) -> Result<bool, AzCliError> {
+        let pr: AzPrResponse = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!("Failed to parse PR response: {e}; raw: {raw}"))
+        })?;
+        let approved = |r: &AzReviewer| r.vote >= 5;
+        Ok(pr.reviewers.iter().any(approved)
+            && pr.reviewers.iter().all(|r| r.vote >= 0)
+            && pr.reviewers.iter().filter(|r| r.is_required).all(approved))
+    }
+
     fn parse_pr_list_response(raw: &str) -> Result<Vec<PullRequestInfo>, AzCliError> {
         let prs: Vec<AzPrResponse> = serde_json::from_str(raw.trim()).map_err(|e| {
             AzCliError::UnexpectedOutput(format!("Failed to parse PR list: {e}; raw: {raw}"))
@@ -471,6 +747,7 @@ impl AzCli {
             status: Self::map_azure_status(status),
             merged_at,
             merge_commit_sha,
+            is_draft: pr.is_draft,
         }
     }
 
@@ -581,6 +858,34 @@ mod tests {
         assert_eq!(id, 456);
     }
 
+    #[test]
+    fn test_parse_work_item_url() {
+        let (org_url, id) = AzCli::parse_work_item_url(
+            "https://dev.azure.com/myorg/my%20project/_workitems/edit/42",
+        )
+        .unwrap();
+        assert_eq!(org_url, "https://dev.azure.com/myorg");
+        assert_eq!(id, 42);
+        assert!(
+            AzCli::parse_work_item_url("https://dev.azure.com/myorg/myproject/_workitems")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_approved() {
+        let pr = |reviewers: &str| {
+            format!(r#"{{"pullRequestId": 1, "status": "active", "reviewers": {reviewers}}}"#)
+        };
+        assert!(!AzCli::parse_pr_approved(&pr("[]")).unwrap());
+        assert!(AzCli::parse_pr_approved(&pr(r#"[{"vote": 5}, {"vote": 0}]"#)).unwrap());
+        assert!(!AzCli::parse_pr_approved(&pr(r#"[{"vote": 10}, {"vote": -5}]"#)).unwrap());
+        assert!(
+            !AzCli::parse_pr_approved(&pr(r#"[{"vote": 10}, {"vote": 0, "isRequired": true}]"#))
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_parse_pr_url_invalid() {
         // GitHub URL should return None
@@ -14,7 +14,10 @@ use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, OpenPrInfo, ProviderKind, UnifiedPrComment},
+    types::{
+        CreatePrRequest, GitHostError, GitHostIssue, IssueFilter, MergeMethod, OpenPrInfo,
+        ProviderKind, UnifiedPrComment,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -255,6 +258,136 @@ impl GitHostProvider for AzureDevOpsProvider {
         Err(GitHostError::UnsupportedProvider)
     }
 
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.set_pr_draft(&url, draft))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute Azure CLI for updating draft state: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.is_pr_approved(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute Azure CLI for fetching PR reviewers: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    /// Completion waits for the branch policies, so the PR may still be open
+    /// when this returns
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.complete_pr(&url, method))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute Azure CLI for completing PR: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.pr_description(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute Azure CLI for fetching PR description: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = pr_url.to_string();
+        let body = body.to_string();
+        task::spawn_blocking(move || cli.update_pr_description(&url, &body))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute Azure CLI for updating PR description: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    /// Issues are the work items of the repository's project
+    async fn list_issues(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, GitHostError> {
+        let repo_info = self.get_repo_info(repo_path, remote_url).await?;
+        let cli = self.az_cli.clone();
+        let filter = filter.clone();
+        task::spawn_blocking(move || {
+            cli.list_work_items(&repo_info.organization_url, &repo_info.project, &filter)
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::Issue(format!(
+                "Failed to execute Azure CLI for listing work items: {err}"
+            ))
+        })?
+        .map_err(Into::into)
+    }
+
+    async fn get_issue(&self, issue_url: &str) -> Result<GitHostIssue, GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = issue_url.to_string();
+        task::spawn_blocking(move || cli.view_work_item(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute Azure CLI for fetching work item: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn comment_on_issue(&self, issue_url: &str, body: &str) -> Result<(), GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = issue_url.to_string();
+        let body = body.to_string();
+        task::spawn_blocking(move || cli.comment_on_work_item(&url, &body))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute Azure CLI for commenting on work item: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn close_issue(&self, issue_url: &str) -> Result<(), GitHostError> {
+        let cli = self.az_cli.clone();
+        let url = issue_url.to_string();
+        task::spawn_blocking(move || cli.close_work_item(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute Azure CLI for closing work item: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::AzureDevOps
     }
@@ -0,0 +1,716 @@
+//! Bitbucket Cloud provider, on the 2.0 REST API.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PrCheck, PullRequestInfo};
+use reqwest::Method;
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::json;
+use tracing::info;
+
+use super::{
+    BitbucketApiError, BitbucketClient, BuildStatus, pull_request_number, split_url,
+    strip_git_suffix, with_retry,
+};
+use crate::services::git_host::{
+    GitHostProvider,
+    types::{
+        CreatePrRequest, GitHostError, GitHostMapping, MergeMethod, OpenPrInfo, ProviderKind,
+        UnifiedPrComment,
+    },
+};
+
+const DEFAULT_API_URL: &str = "https://api.bitbucket.org/2.0";
+const PAGE_LEN: &str = "50";
+
+/// A repository on bitbucket.org
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CloudRepo {
+    workspace: String,
+    slug: String,
+}
+
+impl CloudRepo {
+    /// Parse `https://bitbucket.org/workspace/repo(.git)`,
+    /// `git@bitbucket.org:workspace/repo.git` or a URL below the repository
+    /// such as `https://bitbucket.org/workspace/repo/pull-requests/1`.
+    fn from_url(url: &str) -> Option<Self> {
+        let (_, segments) = split_url(url)?;
+        match segments.as_slice() {
+            [workspace, slug, ..] => Some(Self {
+                workspace: workspace.to_string(),
+                slug: strip_git_suffix(slug).to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn full_name(&self) -> String {
+        format!("{}/{}", self.workspace, self.slug)
+    }
+
+    fn api_path(&self) -> String {
+        format!("/repositories/{}/{}", self.workspace, self.slug)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Endpoint {
+    branch: Branch,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestLinks {
+    html: Link,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestResponse {
+    id: i64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    draft: bool,
+    state: String,
+    links: PullRequestLinks,
+    merge_commit: Option<Commit>,
+    source: Endpoint,
+    destination: Endpoint,
+    updated_on: DateTime<Utc>,
+    #[serde(default)]
+    participants: Vec<Participant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Participant {
+    #[serde(default)]
+    approved: bool,
+    /// `approved`, `changes_requested` or null
+    state: Option<String>,
+}
+
+impl PullRequestResponse {
+    fn into_pr_info(self) -> PullRequestInfo {
+        let status = match self.state.as_str() {
+            "OPEN" => MergeStatus::Open,
+            "MERGED" => MergeStatus::Merged,
+            "DECLINED" | "SUPERSEDED" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: self.id,
+            url: self.links.html.href,
+            // Merged pull requests are not updated any more
+            merged_at: matches!(status, MergeStatus::Merged).then_some(self.updated_on),
+            status,
+            merge_commit_sha: self.merge_commit.map(|c| c.hash),
+            is_draft: self.draft,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentContent {
+    #[serde(default)]
+    raw: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentUser {
+    display_name: String,
+    nickname: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentLinks {
+    html: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentInline {
+    path: String,
+    /// Line in the old file
+    from: Option<i64>,
+    /// Line in the new file
+    to: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentResponse {
+    id: i64,
+    content: CommentContent,
+    user: Option<CommentUser>,
+    created_on: DateTime<Utc>,
+    links: CommentLinks,
+    inline: Option<CommentInline>,
+    #[serde(default)]
+    deleted: bool,
+}
+
+impl CommentResponse {
+    fn into_comment(self) -> UnifiedPrComment {
+        let author = self
+            .user
+            .map(|u| u.nickname.unwrap_or(u.display_name))
+            .unwrap_or_default();
+        let url = self.links.html.map(|l| l.href);
+        match self.inline {
+            Some(inline) => UnifiedPrComment::Review {
+                id: self.id,
+                author,
+                author_association: None,
+                body: self.content.raw,
+                created_at: self.created_on,
+                url,
+                path: inline.path,
+                line: inline.to.or(inline.from),
+                side: match (inline.to, inline.from) {
+                    (Some(_), _) => Some("RIGHT".to_string()),
+                    (None, Some(_)) => Some("LEFT".to_string()),
+                    (None, None) => None,
+                },
+                diff_hunk: None,
+            },
+            None => UnifiedPrComment::General {
+                id: self.id.to_string(),
+                author,
+                author_association: None,
+                body: self.content.raw,
+                created_at: self.created_on,
+                url,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitbucketCloudProvider {
+    client: BitbucketClient,
+}
+
+impl BitbucketCloudProvider {
+    pub fn new(url: &str, hosts: &[GitHostMapping]) -> Result<Self, GitHostError> {
+        Ok(Self {
+            client: BitbucketClient::new(url, hosts, DEFAULT_API_URL.to_string())?,
+        })
+    }
+
+    fn repo(url: &str) -> Result<CloudRepo, GitHostError> {
+        CloudRepo::from_url(url).ok_or_else(|| {
+            GitHostError::Repository(format!("Not a Bitbucket repository URL: {url}"))
+        })
+    }
+
+    /// Repository and number of a pull request URL
+    fn pull_request(pr_url: &str) -> Result<(CloudRepo, i64), GitHostError> {
+        let number = split_url(pr_url)
+            .and_then(|(_, segments)| pull_request_number(&segments))
+            .ok_or_else(|| {
+                GitHostError::PullRequest(format!("Not a Bitbucket pull request URL: {pr_url}"))
+            })?;
+        Ok((Self::repo(pr_url)?, number))
+    }
+
+    /// All pages of a list endpoint, following the `next` links
+    async fn get_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, BitbucketApiError> {
+        let first = self
+            .client
+            .request(Method::GET, path)
+            .query(query)
+            .query(&[("pagelen", PAGE_LEN)]);
+        let mut page: Page<T> = self.client.send(first).await?;
+        let mut items = std::mem::take(&mut page.values);
+        while let Some(next) = page.next.take() {
+            page = self
+                .client
+                .send(self.client.request_url(Method::GET, &next))
+                .await?;
+            items.append(&mut page.values);
+        }
+        Ok(items)
+    }
+
+    async fn pull_requests(
+        &self,
+        repo: &CloudRepo,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<PullRequestResponse>, GitHostError> {
+        let path = format!("{}/pullrequests", repo.api_path());
+        with_retry(|| async { Ok(self.get_all(&path, query).await?) }).await
+    }
+
+    async fn get_pull_request(&self, pr_url: &str) -> Result<PullRequestResponse, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let path = format!("{}/pullrequests/{number}", repo.api_path());
+        with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::GET, &path))
+                .await?)
+        })
+        .await
+    }
+
+    /// Update fields of a pull request. The title is always sent, as
+    /// Bitbucket rejects updates without one.
+    async fn update_pull_request(
+        &self,
+        pr_url: &str,
+        mut body: serde_json::Value,
+    ) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        body["title"] = json!(self.get_pull_request(pr_url).await?.title);
+        let path = format!("{}/pullrequests/{number}", repo.api_path());
+        let _: PullRequestResponse = with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::PUT, &path).json(&body))
+                .await?)
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for BitbucketCloudProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let target = Self::repo(remote_url)?;
+        let source = match &request.head_repo_url {
+            Some(head_url) => Self::repo(head_url)?,
+            None => target.clone(),
+        };
+        let body = json!({
+            "title": request.title,
+            "description": request.body.as_deref().unwrap_or(""),
+            "draft": request.draft.unwrap_or(false),
+            "source": {
+                "branch": { "name": request.head_branch },
+                "repository": { "full_name": source.full_name() },
+            },
+            "destination": {
+                "branch": { "name": request.base_branch },
+            },
+        });
+        let path = format!("{}/pullrequests", target.api_path());
+        let pr: PullRequestResponse = with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::POST, &path).json(&body))
+                .await?)
+        })
+        .await?;
+        info!(
+            "Created Bitbucket PR #{} for branch {}",
+            pr.id, request.head_branch
+        );
+        Ok(pr.into_pr_info())
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        Ok(self.get_pull_request(pr_url).await?.into_pr_info())
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let filter = format!(
+            "source.branch.name = \"{}\"",
+            branch_name.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let prs = self
+            .pull_requests(
+                &repo,
+                &[
+                    ("q", &filter),
+                    ("state", "OPEN"),
+                    ("state", "MERGED"),
+                    ("state", "DECLINED"),
+                    ("state", "SUPERSEDED"),
+                ],
+            )
+            .await?;
+        Ok(prs
+            .into_iter()
+            .map(PullRequestResponse::into_pr_info)
+            .collect())
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let path = format!("{}/pullrequests/{pr_number}/comments", repo.api_path());
+        let comments: Vec<CommentResponse> =
+            with_retry(|| async { Ok(self.get_all(&path, &[]).await?) }).await?;
+        let mut comments: Vec<UnifiedPrComment> = comments
+            .into_iter()
+            .filter(|c| !c.deleted)
+            .map(CommentResponse::into_comment)
+            .collect();
+        comments.sort_by_key(UnifiedPrComment::created_at);
+        Ok(comments)
+    }
+
+    async fn list_open_prs(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<OpenPrInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let prs = self.pull_requests(&repo, &[("state", "OPEN")]).await?;
+        Ok(prs
+            .into_iter()
+            .map(|pr| OpenPrInfo {
+                number: pr.id,
+                url: pr.links.html.href,
+                title: pr.title,
+                head_branch: pr.source.branch.name,
+                base_branch: pr.destination.branch.name,
+            })
+            .collect())
+    }
+
+    async fn get_pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let path = format!("{}/pullrequests/{number}/statuses", repo.api_path());
+        let statuses: Vec<BuildStatus> =
+            with_retry(|| async { Ok(self.get_all(&path, &[]).await?) }).await?;
+        Ok(statuses.into_iter().map(BuildStatus::into_check).collect())
+    }
+
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        self.update_pull_request(pr_url, json!({ "draft": draft }))
+            .await
+    }
+
+    /// Merge checks configured on the branch are enforced when merging
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let participants = self.get_pull_request(pr_url).await?.participants;
+        Ok(participants.iter().any(|p| p.approved)
+            && !participants
+                .iter()
+                .any(|p| p.state.as_deref() == Some("changes_requested")))
+    }
+
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let path = format!("{}/pullrequests/{number}/merge", repo.api_path());
+        let strategy = match method {
+            MergeMethod::Merge => "merge_commit",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "fast_forward",
+        };
+        // Long merges are answered with a task to poll instead of the
+        // pull request, which the PR monitor does anyway
+        let _: serde_json::Value = self
+            .client
+            .send(
+                self.client
+                    .request(Method::POST, &path)
+                    .json(&json!({ "merge_strategy": strategy })),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        Ok(self.get_pull_request(pr_url).await?.description)
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        self.update_pull_request(pr_url, json!({ "description": body }))
+            .await
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Bitbucket
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Json, Router,
+        extract::Query,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+    };
+    use db::models::merge::PrCheckStatus;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::services::git_host::GitHostService;
+
+    const REMOTE: &str = "git@bitbucket.org:team/app.git";
+    const PR_URL: &str = "https://bitbucket.org/team/app/pull-requests/9";
+
+    #[test]
+    fn parses_repo_urls() {
+        let expected = CloudRepo {
+            workspace: "team".to_string(),
+            slug: "app".to_string(),
+        };
+        for url in [
+            "https://bitbucket.org/team/app.git",
+            "https://someone@bitbucket.org/team/app.git",
+            "git@bitbucket.org:team/app.git",
+            PR_URL,
+        ] {
+            assert_eq!(CloudRepo::from_url(url).as_ref(), Some(&expected), "{url}");
+        }
+    }
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("Authorization").and_then(|v| v.to_str().ok()) {
+            Some("Bearer secret") => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn pull_request(id: i64, state: &str) -> Value {
+        json!({
+            "id": id,
+            "title": "Add feature",
+            "state": state,
+            "links": { "html": { "href": format!("https://bitbucket.org/team/app/pull-requests/{id}") } },
+            "merge_commit": if state == "MERGED" { json!({ "hash": "abc123" }) } else { json!(null) },
+            "source": { "branch": { "name": "vk/feature" } },
+            "destination": { "branch": { "name": "main" } },
+            "updated_on": "2026-01-02T10:00:00+00:00",
+        })
+    }
+
+    /// Stub of the Bitbucket Cloud endpoints used by the provider
+    async fn stub_bitbucket() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let api_url = format!("http://{addr}/2.0");
+        let next_page = format!("{api_url}/repositories/team/app/pullrequests/9/comments?page=2");
+        let app = Router::new()
+            .route(
+                "/2.0/repositories/team/app/pullrequests",
+                get(
+                    |headers: HeaderMap, Query(query): Query<Vec<(String, String)>>| async move {
+                        authorized(&headers)?;
+                        let values = if query.iter().any(|(k, _)| k == "q") {
+                            assert!(query.contains(&(
+                                "q".to_string(),
+                                "source.branch.name = \"vk/feature\"".to_string()
+                            )));
+                            vec![pull_request(9, "MERGED")]
+                        } else {
+                            vec![pull_request(9, "OPEN")]
+                        };
+                        Ok::<_, StatusCode>(Json(json!({ "values": values })))
+                    },
+                )
+                .post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["draft"], true);
+                    assert_eq!(body["source"]["branch"]["name"], "vk/feature");
+                    assert_eq!(body["source"]["repository"]["full_name"], "team/app");
+                    assert_eq!(body["destination"]["branch"]["name"], "main");
+                    Ok::<_, StatusCode>((StatusCode::CREATED, Json(pull_request(9, "OPEN"))))
+                }),
+            )
+            .route(
+                "/2.0/repositories/team/app/pullrequests/9",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    let mut pr = pull_request(9, "MERGED");
+                    pr["participants"] = json!([
+                        { "role": "REVIEWER", "approved": true, "state": "approved" },
+                        { "role": "PARTICIPANT", "approved": false, "state": null },
+                    ]);
+                    Ok::<_, StatusCode>(Json(pr))
+                }),
+            )
+            .route(
+                "/2.0/repositories/team/app/pullrequests/9/merge",
+                post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["merge_strategy"], "squash");
+                    Ok::<_, StatusCode>(Json(pull_request(9, "MERGED")))
+                }),
+            )
+            .route(
+                "/2.0/repositories/team/app/pullrequests/9/comments",
+                get(
+                    move |headers: HeaderMap, Query(query): Query<HashMap<String, String>>| async move {
+                        authorized(&headers)?;
+                        let page = if query.get("page").map(String::as_str) == Some("2") {
+                            json!({ "values": [{
+                                "id": 2,
+                                "content": { "raw": "Rename this" },
+                                "user": { "display_name": "Bob B", "nickname": "bob" },
+                                "created_on": "2026-01-01T11:00:00+00:00",
+                                "links": { "html": { "href": format!("{PR_URL}#comment-2") } },
+                                "inline": { "path": "src/lib.rs", "from": null, "to": 12 },
+                            }] })
+                        } else {
+                            json!({
+                                "values": [
+                                    {
+                                        "id": 1,
+                                        "content": { "raw": "Looks good" },
+                                        "user": { "display_name": "Alice A" },
+                                        "created_on": "2026-01-01T10:00:00+00:00",
+                                        "links": {},
+                                    },
+                                    {
+                                        "id": 3,
+                                        "content": { "raw": "" },
+                                        "created_on": "2026-01-01T12:00:00+00:00",
+                                        "links": {},
+                                        "deleted": true,
+                                    },
+                                ],
+                                "next": next_page,
+                            })
+                        };
+                        Ok::<_, StatusCode>(Json(page))
+                    },
+                ),
+            )
+            .route(
+                "/2.0/repositories/team/app/pullrequests/9/statuses",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!({ "values": [
+                        {
+                            "key": "pipelines-build",
+                            "name": "Build #42",
+                            "state": "FAILED",
+                            "url": "https://bitbucket.org/team/app/pipelines/results/42",
+                        },
+                        { "key": "deploy", "name": null, "state": "INPROGRESS" },
+                    ] })))
+                }),
+            );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        api_url
+    }
+
+    fn hosts(api_url: &str) -> Vec<GitHostMapping> {
+        vec![GitHostMapping {
+            host: "bitbucket.org".to_string(),
+            provider: ProviderKind::Bitbucket,
+            api_url: Some(api_url.to_string()),
+            token: Some("secret".to_string()),
+        }]
+    }
+
+    #[tokio::test]
+    async fn pull_requests_through_cloud_api() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_bitbucket().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url)).unwrap();
+        assert_eq!(host.provider_kind(), ProviderKind::Bitbucket);
+        let repo_path = Path::new(".");
+
+        let created = host
+            .create_pr(
+                repo_path,
+                REMOTE,
+                &CreatePrRequest {
+                    title: "Add feature".to_string(),
+                    body: None,
+                    head_branch: "vk/feature".to_string(),
+                    base_branch: "main".to_string(),
+                    draft: Some(true),
+                    head_repo_url: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.number, 9);
+        assert_eq!(created.url, PR_URL);
+
+        let status = host.get_pr_status(PR_URL).await.unwrap();
+        assert!(matches!(status.status, MergeStatus::Merged));
+        assert_eq!(status.merge_commit_sha.as_deref(), Some("abc123"));
+        assert!(status.merged_at.is_some());
+
+        assert!(host.is_pr_approved(PR_URL).await.unwrap());
+        host.merge_pr(PR_URL, MergeMethod::Squash).await.unwrap();
+
+        let for_branch = host
+            .list_prs_for_branch(repo_path, REMOTE, "vk/feature")
+            .await
+            .unwrap();
+        assert_eq!(for_branch.len(), 1);
+
+        let open = host.list_open_prs(repo_path, REMOTE).await.unwrap();
+        assert_eq!(open[0].head_branch, "vk/feature");
+        assert_eq!(open[0].base_branch, "main");
+
+        let comments = host.get_pr_comments(repo_path, REMOTE, 9).await.unwrap();
+        assert_eq!(comments.len(), 2);
+        match &comments[1] {
+            UnifiedPrComment::Review {
+                author,
+                path,
+                line,
+                side,
+                ..
+            } => {
+                assert_eq!(author, "bob");
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(12));
+                assert_eq!(side.as_deref(), Some("RIGHT"));
+            }
+            other => panic!("expected an inline comment, got {other:?}"),
+        }
+
+        let checks = host.get_pr_checks(PR_URL).await.unwrap();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].name, "Build #42");
+        assert_eq!(checks[0].status, PrCheckStatus::Failed);
+        assert_eq!(checks[1].name, "deploy");
+        assert_eq!(checks[1].status, PrCheckStatus::Pending);
+    }
+}
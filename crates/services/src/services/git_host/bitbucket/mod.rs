@@ -0,0 +1,242 @@
+//! Bitbucket hosting service implementations: Bitbucket Cloud (bitbucket.org)
+//! on the 2.0 REST API, and Bitbucket Server / Data Center on the 1.0 REST API.
+
+mod cloud;
+mod server;
+
+use std::{future::Future, time::Duration};
+
+use backon::{ExponentialBuilder, Retryable};
+pub use cloud::BitbucketCloudProvider;
+use db::models::merge::{PrCheck, PrCheckStatus};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::{Deserialize, de::DeserializeOwned};
+pub use server::BitbucketServerProvider;
+use thiserror::Error;
+
+use super::{
+    detection::find_host_mapping,
+    types::{GitHostError, GitHostMapping},
+};
+
+/// Token used when the host has none stored
+const TOKEN_ENV_VAR: &str = "BITBUCKET_TOKEN";
+
+#[derive(Debug, Error)]
+enum BitbucketApiError {
+    #[error("Bitbucket API returned {status}: {message}")]
+    Http { status: u16, message: String },
+    #[error("Bitbucket API request failed: {0}")]
+    Request(String),
+    #[error("Bitbucket API returned unexpected data: {0}")]
+    UnexpectedOutput(String),
+}
+
+impl From<BitbucketApiError> for GitHostError {
+    fn from(error: BitbucketApiError) -> Self {
+        match error {
+            BitbucketApiError::Http { status, message } => match status {
+                401 => GitHostError::AuthFailed(message),
+                403 => GitHostError::InsufficientPermissions(message),
+                404 => GitHostError::RepoNotFoundOrNoAccess(message),
+                400..=499 if !matches!(status, 408 | 429) => {
+                    GitHostError::Rejected(format!("Bitbucket API returned {status}: {message}"))
+                }
+                _ => {
+                    GitHostError::PullRequest(format!("Bitbucket API returned {status}: {message}"))
+                }
+            },
+            BitbucketApiError::Request(msg) => GitHostError::PullRequest(msg),
+            BitbucketApiError::UnexpectedOutput(msg) => GitHostError::UnexpectedOutput(msg),
+        }
+    }
+}
+
+/// Build status reported on a commit, in the shape both APIs share
+#[derive(Debug, Deserialize)]
+struct BuildStatus {
+    key: String,
+    name: Option<String>,
+    state: String,
+    url: Option<String>,
+    description: Option<String>,
+}
+
+impl BuildStatus {
+    fn into_check(self) -> PrCheck {
+        let status = match self.state.as_str() {
+            "SUCCESSFUL" => PrCheckStatus::Passed,
+            "FAILED" | "STOPPED" | "CANCELLED" => PrCheckStatus::Failed,
+            _ => PrCheckStatus::Pending,
+        };
+        PrCheck {
+            name: self.name.filter(|n| !n.is_empty()).unwrap_or(self.key),
+            status,
+            // Which builds a merge needs is part of the merge checks, which
+            // aren't exposed to regular users
+            required: true,
+            url: self.url.filter(|u| !u.is_empty()),
+            description: self.description.filter(|d| !d.is_empty()),
+        }
+    }
+}
+
+/// HTTP client shared by both Bitbucket flavours
+#[derive(Debug, Clone)]
+struct BitbucketClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl BitbucketClient {
+    /// Client for the Bitbucket instance hosting `url`. The API URL and token
+    /// come from the host mapping when there is one, falling back to
+    /// `default_api_url` and the `BITBUCKET_TOKEN` environment variable.
+    fn new(
+        url: &str,
+        hosts: &[GitHostMapping],
+        default_api_url: String,
+    ) -> Result<Self, BitbucketApiError> {
+        let mapping = find_host_mapping(url, hosts);
+        let api_url = mapping
+            .and_then(|m| m.api_url.as_deref())
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(str::to_string)
+            .unwrap_or(default_api_url);
+        let token = mapping
+            .and_then(|m| m.token.as_deref())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .or_else(|| std::env::var(TOKEN_ENV_VAR).ok());
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| BitbucketApiError::Request(e.to_string()))?;
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.request_url(method, &format!("{}{path}", self.api_url))
+    }
+
+    /// Request to an absolute URL, such as a pagination link. Tokens of the
+    /// form `username:app_password` are sent as basic auth, others as bearer
+    /// tokens.
+    fn request_url(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match self.token.as_deref() {
+            Some(token) => match token.split_once(':') {
+                Some((username, password)) => request.basic_auth(username, Some(password)),
+                None => request.bearer_auth(token),
+            },
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, BitbucketApiError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| BitbucketApiError::Request(e.to_string()))?;
+        let response = Self::check_status(response).await?;
+        let text = response
+            .text()
+            .await
+            .map_err(|e| BitbucketApiError::Request(e.to_string()))?;
+        serde_json::from_str(&text)
+            .map_err(|e| BitbucketApiError::UnexpectedOutput(format!("{e}; raw: {text}")))
+    }
+
+    async fn check_status(response: Response) -> Result<Response, BitbucketApiError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let text = response.text().await.unwrap_or_default();
+        // Cloud errors are `{"error": {"message": ...}}`, Server errors are
+        // `{"errors": [{"message": ...}]}`
+        let message = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|v| {
+                v.pointer("/error/message")
+                    .or_else(|| v.pointer("/errors/0/message"))
+                    .and_then(|m| m.as_str())
+                    .map(str::to_string)
+            })
+            .unwrap_or(text);
+        Err(BitbucketApiError::Http {
+            status: status.as_u16(),
+            message,
+        })
+    }
+}
+
+async fn with_retry<T, F, Fut>(operation: F) -> Result<T, GitHostError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, GitHostError>>,
+{
+    operation
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Bitbucket API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+}
+
+/// Web base URL and path segments of an `https://`, `ssh://` or scp-like
+/// `user@host:path` URL. The web UI of a host reached over SSH is assumed to
+/// be served over HTTPS on the default port.
+fn split_url(url: &str) -> Option<(String, Vec<&str>)> {
+    let url = url.trim();
+    let (scheme, rest) = match url.split_once("://") {
+        Some(parts) => parts,
+        None => ("ssh", url.split_once('@').map_or(url, |(_, rest)| rest)),
+    };
+    let (authority, path) = if url.contains("://") {
+        rest.split_once('/')?
+    } else {
+        rest.split_once(':')?
+    };
+    let host_port = authority.rsplit('@').next()?;
+    let base_url = match scheme {
+        "http" | "https" => format!("{scheme}://{host_port}"),
+        _ => format!("https://{}", host_port.split(':').next()?),
+    };
+    let path = path.split(['?', '#']).next()?;
+    Some((
+        base_url,
+        path.split('/').filter(|s| !s.is_empty()).collect(),
+    ))
+}
+
+/// Number following `pull-requests` in a pull request URL
+fn pull_request_number(segments: &[&str]) -> Option<i64> {
+    let index = segments.iter().position(|s| *s == "pull-requests")?;
+    segments.get(index + 1)?.parse().ok()
+}
+
+fn strip_git_suffix(slug: &str) -> &str {
+    slug.strip_suffix(".git").unwrap_or(slug)
+}
@@ -0,0 +1,825 @@
+//! Bitbucket Server and Data Center provider, on the 1.0 REST API.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PrCheck, PullRequestInfo};
+use reqwest::Method;
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use tracing::info;
+
+use super::{
+    BitbucketApiError, BitbucketClient, BuildStatus, pull_request_number, split_url,
+    strip_git_suffix, with_retry,
+};
+use crate::services::git_host::{
+    GitHostProvider,
+    types::{
+        CreatePrRequest, GitHostError, GitHostMapping, MergeMethod, OpenPrInfo, ProviderKind,
+        UnifiedPrComment,
+    },
+};
+
+const PAGE_LIMIT: &str = "100";
+
+/// A repository on a Bitbucket Server instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ServerRepo {
+    /// Web URL of the instance, including its context path
+    base_url: String,
+    /// Project key, or `~user` for personal repositories
+    project: String,
+    slug: String,
+}
+
+impl ServerRepo {
+    /// Parse clone URLs (`https://host/scm/PROJ/repo.git`,
+    /// `ssh://git@host:7999/proj/repo.git`) and web URLs
+    /// (`https://host/projects/PROJ/repos/repo/...`,
+    /// `https://host/users/name/repos/repo/...`).
+    fn from_url(url: &str) -> Option<Self> {
+        let (mut base_url, segments) = split_url(url)?;
+        let marker = segments
+            .iter()
+            .position(|s| matches!(*s, "scm" | "projects" | "users"));
+        let (context, project, slug) = match marker {
+            Some(i) => {
+                let (project, slug) = match segments[i..] {
+                    ["scm", project, slug, ..] => (project.to_string(), slug),
+                    ["projects", project, "repos", slug, ..] => (project.to_string(), slug),
+                    ["users", user, "repos", slug, ..] => (format!("~{user}"), slug),
+                    _ => return None,
+                };
+                (&segments[..i], project, slug)
+            }
+            // SSH clone URLs have the project and repository only
+            None => match segments.as_slice() {
+                [project, slug] if !url.starts_with("http") => {
+                    (&[][..], project.to_string(), *slug)
+                }
+                _ => return None,
+            },
+        };
+        for segment in context {
+            base_url.push('/');
+            base_url.push_str(segment);
+        }
+        Some(Self {
+            base_url,
+            project,
+            slug: strip_git_suffix(slug).to_string(),
+        })
+    }
+
+    fn api_path(&self) -> String {
+        format!("/projects/{}/repos/{}", self.project, self.slug)
+    }
+
+    fn pull_request_url(&self, number: i64) -> String {
+        let owner = match self.project.strip_prefix('~') {
+            Some(user) => format!("users/{user}"),
+            None => format!("projects/{}", self.project),
+        };
+        format!(
+            "{}/{owner}/repos/{}/pull-requests/{number}",
+            self.base_url, self.slug
+        )
+    }
+
+    fn repository_ref(&self) -> Value {
+        json!({ "slug": self.slug, "project": { "key": self.project } })
+    }
+}
+
+fn from_millis(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ref {
+    display_id: String,
+    #[serde(default)]
+    latest_commit: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PullRequestLinks {
+    #[serde(rename = "self", default)]
+    web: Vec<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeCommit {
+    id: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestProperties {
+    merge_commit: Option<MergeCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestResponse {
+    id: i64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    /// Only reported by Data Center 8.18+
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    version: i64,
+    state: String,
+    closed_date: Option<i64>,
+    from_ref: Ref,
+    to_ref: Ref,
+    #[serde(default)]
+    links: PullRequestLinks,
+    #[serde(default)]
+    properties: PullRequestProperties,
+    #[serde(default)]
+    reviewers: Vec<Reviewer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reviewer {
+    /// `APPROVED`, `NEEDS_WORK` or `UNAPPROVED`
+    status: String,
+}
+
+impl PullRequestResponse {
+    fn url(&mut self, repo: &ServerRepo) -> String {
+        match self.links.web.pop() {
+            Some(link) => link.href,
+            None => repo.pull_request_url(self.id),
+        }
+    }
+
+    fn into_pr_info(mut self, repo: &ServerRepo) -> PullRequestInfo {
+        let status = match self.state.as_str() {
+            "OPEN" => MergeStatus::Open,
+            "MERGED" => MergeStatus::Merged,
+            "DECLINED" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: self.id,
+            url: self.url(repo),
+            merged_at: match status {
+                MergeStatus::Merged => self.closed_date.map(from_millis),
+                _ => None,
+            },
+            status,
+            merge_commit_sha: self.properties.merge_commit.map(|c| c.id),
+            is_draft: self.draft,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    values: Vec<T>,
+    #[serde(default = "default_true")]
+    is_last_page: bool,
+    next_page_start: Option<i64>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentAuthor {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Comment {
+    id: i64,
+    #[serde(default)]
+    text: String,
+    author: CommentAuthor,
+    created_date: i64,
+    /// Replies
+    #[serde(default)]
+    comments: Vec<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentAnchor {
+    path: Option<String>,
+    line: Option<i64>,
+    /// `ADDED`, `REMOVED` or `CONTEXT`
+    line_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActivityResponse {
+    action: String,
+    comment: Option<Comment>,
+    comment_anchor: Option<CommentAnchor>,
+}
+
+/// A comment and its replies, which share the comment's anchor
+fn push_comments(
+    comments: &mut Vec<UnifiedPrComment>,
+    comment: Comment,
+    anchor: Option<&CommentAnchor>,
+    pr_url: &str,
+) {
+    let url = Some(format!("{pr_url}/overview?commentId={}", comment.id));
+    let created_at = from_millis(comment.created_date);
+    comments.push(match anchor {
+        Some(CommentAnchor {
+            path: Some(path),
+            line,
+            line_type,
+        }) => UnifiedPrComment::Review {
+            id: comment.id,
+            author: comment.author.name,
+            author_association: None,
+            body: comment.text,
+            created_at,
+            url,
+            path: path.clone(),
+            line: *line,
+            side: line.map(|_| match line_type.as_deref() {
+                Some("REMOVED") => "LEFT".to_string(),
+                _ => "RIGHT".to_string(),
+            }),
+            diff_hunk: None,
+        },
+        _ => UnifiedPrComment::General {
+            id: comment.id.to_string(),
+            author: comment.author.name,
+            author_association: None,
+            body: comment.text,
+            created_at,
+            url,
+        },
+    });
+    for reply in comment.comments {
+        push_comments(comments, reply, anchor, pr_url);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitbucketServerProvider {
+    client: BitbucketClient,
+}
+
+impl BitbucketServerProvider {
+    /// Provider for the Bitbucket Server instance hosting `url`, whose API is
+    /// expected at `/rest/api/1.0` unless the host mapping has an API URL
+    pub fn new(url: &str, hosts: &[GitHostMapping]) -> Result<Self, GitHostError> {
+        let repo = Self::repo(url)?;
+        let default_api_url = format!("{}/rest/api/1.0", repo.base_url);
+        Ok(Self {
+            client: BitbucketClient::new(url, hosts, default_api_url)?,
+        })
+    }
+
+    fn repo(url: &str) -> Result<ServerRepo, GitHostError> {
+        ServerRepo::from_url(url).ok_or_else(|| {
+            GitHostError::Repository(format!("Not a Bitbucket Server repository URL: {url}"))
+        })
+    }
+
+    /// Repository and number of a pull request URL
+    fn pull_request(pr_url: &str) -> Result<(ServerRepo, i64), GitHostError> {
+        let number = split_url(pr_url)
+            .and_then(|(_, segments)| pull_request_number(&segments))
+            .ok_or_else(|| {
+                GitHostError::PullRequest(format!(
+                    "Not a Bitbucket Server pull request URL: {pr_url}"
+                ))
+            })?;
+        Ok((Self::repo(pr_url)?, number))
+    }
+
+    /// All pages of a list endpoint, following `nextPageStart`
+    async fn get_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, BitbucketApiError> {
+        let mut items = Vec::new();
+        let mut start = "0".to_string();
+        loop {
+            let request = self
+                .client
+                .request(Method::GET, path)
+                .query(query)
+                .query(&[("limit", PAGE_LIMIT), ("start", start.as_str())]);
+            let mut page: Page<T> = self.client.send(request).await?;
+            items.append(&mut page.values);
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next.to_string(),
+                _ => return Ok(items),
+            }
+        }
+    }
+
+    async fn pull_requests(
+        &self,
+        repo: &ServerRepo,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<PullRequestResponse>, GitHostError> {
+        let path = format!("{}/pull-requests", repo.api_path());
+        with_retry(|| async { Ok(self.get_all(&path, query).await?) }).await
+    }
+
+    async fn get_pull_request(
+        &self,
+        repo: &ServerRepo,
+        number: i64,
+    ) -> Result<PullRequestResponse, GitHostError> {
+        let path = format!("{}/pull-requests/{number}", repo.api_path());
+        with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::GET, &path))
+                .await?)
+        })
+        .await
+    }
+
+    /// Update fields of a pull request. Updates must carry the current
+    /// version and title, so the pull request is fetched first.
+    async fn update_pull_request(&self, pr_url: &str, mut body: Value) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let path = format!("{}/pull-requests/{number}", repo.api_path());
+        let current = self.get_pull_request(&repo, number).await?;
+        body["version"] = json!(current.version);
+        body["title"] = json!(current.title);
+        with_retry(|| async {
+            let _: PullRequestResponse = self
+                .client
+                .send(self.client.request(Method::PUT, &path).json(&body))
+                .await?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for BitbucketServerProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let target = Self::repo(remote_url)?;
+        let source = match &request.head_repo_url {
+            Some(head_url) => Self::repo(head_url)?,
+            None => target.clone(),
+        };
+        let mut body = json!({
+            "title": request.title,
+            "description": request.body.as_deref().unwrap_or(""),
+            "fromRef": {
+                "id": format!("refs/heads/{}", request.head_branch),
+                "repository": source.repository_ref(),
+            },
+            "toRef": {
+                "id": format!("refs/heads/{}", request.base_branch),
+                "repository": target.repository_ref(),
+            },
+        });
+        // Only sent when set, as draft pull requests need Data Center 8.18+
+        if request.draft.unwrap_or(false) {
+            body["draft"] = json!(true);
+        }
+        let path = format!("{}/pull-requests", target.api_path());
+        let pr: PullRequestResponse = with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::POST, &path).json(&body))
+                .await?)
+        })
+        .await?;
+        info!(
+            "Created Bitbucket Server PR #{} for branch {}",
+            pr.id, request.head_branch
+        );
+        Ok(pr.into_pr_info(&target))
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let pr = self.get_pull_request(&repo, number).await?;
+        Ok(pr.into_pr_info(&repo))
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let at = format!("refs/heads/{branch_name}");
+        let prs = self
+            .pull_requests(
+                &repo,
+                &[("state", "ALL"), ("direction", "OUTGOING"), ("at", &at)],
+            )
+            .await?;
+        Ok(prs.into_iter().map(|pr| pr.into_pr_info(&repo)).collect())
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let path = format!("{}/pull-requests/{pr_number}/activities", repo.api_path());
+        let activities: Vec<ActivityResponse> =
+            with_retry(|| async { Ok(self.get_all(&path, &[]).await?) }).await?;
+        let pr_url = repo.pull_request_url(pr_number);
+        let mut comments = Vec::new();
+        for activity in activities {
+            if activity.action != "COMMENTED" {
+                continue;
+            }
+            if let Some(comment) = activity.comment {
+                push_comments(
+                    &mut comments,
+                    comment,
+                    activity.comment_anchor.as_ref(),
+                    &pr_url,
+                );
+            }
+        }
+        comments.sort_by_key(UnifiedPrComment::created_at);
+        Ok(comments)
+    }
+
+    async fn list_open_prs(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<OpenPrInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        let prs = self.pull_requests(&repo, &[("state", "OPEN")]).await?;
+        Ok(prs
+            .into_iter()
+            .map(|mut pr| OpenPrInfo {
+                number: pr.id,
+                url: pr.url(&repo),
+                title: pr.title,
+                head_branch: pr.from_ref.display_id,
+                base_branch: pr.to_ref.display_id,
+            })
+            .collect())
+    }
+
+    async fn get_pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let path = format!("{}/pull-requests/{number}", repo.api_path());
+        let pr: PullRequestResponse = with_retry(|| async {
+            Ok(self
+                .client
+                .send(self.client.request(Method::GET, &path))
+                .await?)
+        })
+        .await?;
+        let path = format!(
+            "{}/commits/{}/builds",
+            repo.api_path(),
+            pr.from_ref.latest_commit
+        );
+        let builds: Vec<BuildStatus> =
+            with_retry(|| async { Ok(self.get_all(&path, &[]).await?) }).await?;
+        Ok(builds.into_iter().map(BuildStatus::into_check).collect())
+    }
+
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        self.update_pull_request(pr_url, json!({ "draft": draft }))
+            .await
+    }
+
+    /// Required approver counts are merge checks, enforced when merging
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let reviewers = self.get_pull_request(&repo, number).await?.reviewers;
+        Ok(reviewers.iter().any(|r| r.status == "APPROVED")
+            && !reviewers.iter().any(|r| r.status == "NEEDS_WORK"))
+    }
+
+    /// The strategy has to be enabled in the repository's merge settings
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        let version = self.get_pull_request(&repo, number).await?.version;
+        let path = format!("{}/pull-requests/{number}/merge", repo.api_path());
+        let strategy = match method {
+            MergeMethod::Merge => "no-ff",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase-ff-only",
+        };
+        let _: PullRequestResponse = self
+            .client
+            .send(
+                self.client
+                    .request(Method::POST, &path)
+                    .query(&[("version", version)])
+                    .json(&json!({ "strategyId": strategy })),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Ok(self.get_pull_request(&repo, number).await?.description)
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        self.update_pull_request(pr_url, json!({ "description": body }))
+            .await
+    }
+
+    fn pr_head_ref(&self, pr_number: i64) -> Option<String> {
+        Some(format!("refs/pull-requests/{pr_number}/from"))
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::BitbucketServer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Json, Router,
+        extract::Query,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+    };
+
+    use super::*;
+    use crate::services::git_host::GitHostService;
+
+    const REMOTE: &str = "https://bitbucket.example.com/scm/PROJ/app.git";
+    const PR_URL: &str = "https://bitbucket.example.com/projects/PROJ/repos/app/pull-requests/4";
+
+    #[test]
+    fn parses_repo_urls() {
+        let expected = ServerRepo {
+            base_url: "https://bitbucket.example.com".to_string(),
+            project: "PROJ".to_string(),
+            slug: "app".to_string(),
+        };
+        for url in [
+            REMOTE,
+            "https://someone@bitbucket.example.com/scm/PROJ/app.git",
+            "ssh://git@bitbucket.example.com:7999/PROJ/app.git",
+            "https://bitbucket.example.com/projects/PROJ/repos/app/browse",
+            PR_URL,
+        ] {
+            assert_eq!(ServerRepo::from_url(url).as_ref(), Some(&expected), "{url}");
+        }
+
+        let personal =
+            ServerRepo::from_url("https://git.example.com/bitbucket/scm/~alice/app.git").unwrap();
+        assert_eq!(personal.base_url, "https://git.example.com/bitbucket");
+        assert_eq!(personal.project, "~alice");
+        assert_eq!(
+            personal.pull_request_url(1),
+            "https://git.example.com/bitbucket/users/alice/repos/app/pull-requests/1"
+        );
+        assert_eq!(
+            ServerRepo::from_url("https://git.example.com/bitbucket/users/alice/repos/app/browse"),
+            Some(personal)
+        );
+    }
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("Authorization").and_then(|v| v.to_str().ok()) {
+            Some("Bearer secret") => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn pull_request(state: &str) -> Value {
+        json!({
+            "id": 4,
+            "title": "Add feature",
+            "state": state,
+            "closedDate": if state == "MERGED" { json!(1767261600000_i64) } else { json!(null) },
+            "fromRef": { "id": "refs/heads/vk/feature", "displayId": "vk/feature" },
+            "toRef": { "id": "refs/heads/main", "displayId": "main" },
+            "links": { "self": [{ "href": PR_URL }] },
+            "properties": if state == "MERGED" {
+                json!({ "mergeCommit": { "id": "abc123" } })
+            } else {
+                json!({})
+            },
+        })
+    }
+
+    /// Stub of the Bitbucket Server endpoints used by the provider
+    async fn stub_bitbucket_server() -> String {
+        let app = Router::new()
+            .route(
+                "/rest/api/1.0/projects/PROJ/repos/app/pull-requests",
+                get(
+                    |headers: HeaderMap, Query(query): Query<HashMap<String, String>>| async move {
+                        authorized(&headers)?;
+                        // Two pages of one pull request each
+                        let page = match query.get("start").map(String::as_str) {
+                            Some("0") => json!({
+                                "values": [pull_request("OPEN")],
+                                "isLastPage": false,
+                                "nextPageStart": 1,
+                            }),
+                            _ => json!({
+                                "values": [pull_request("DECLINED")],
+                                "isLastPage": true,
+                            }),
+                        };
+                        Ok::<_, StatusCode>(Json(page))
+                    },
+                )
+                .post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["fromRef"]["id"], "refs/heads/vk/feature");
+                    assert_eq!(body["toRef"]["repository"]["project"]["key"], "PROJ");
+                    assert!(body.get("draft").is_none());
+                    Ok::<_, StatusCode>((StatusCode::CREATED, Json(pull_request("OPEN"))))
+                }),
+            )
+            .route(
+                "/rest/api/1.0/projects/PROJ/repos/app/pull-requests/4",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    let mut pr = pull_request("MERGED");
+                    pr["version"] = json!(2);
+                    pr["reviewers"] = json!([
+                        { "user": { "name": "bob" }, "approved": true, "status": "APPROVED" },
+                        { "user": { "name": "carol" }, "approved": false, "status": "NEEDS_WORK" },
+                    ]);
+                    Ok::<_, StatusCode>(Json(pr))
+                }),
+            )
+            .route(
+                "/rest/api/1.0/projects/PROJ/repos/app/pull-requests/4/merge",
+                post(
+                    |headers: HeaderMap,
+                     Query(query): Query<HashMap<String, String>>,
+                     Json(body): Json<Value>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(query.get("version").map(String::as_str), Some("2"));
+                        assert_eq!(body["strategyId"], "no-ff");
+                        Ok::<_, StatusCode>(Json(pull_request("MERGED")))
+                    },
+                ),
+            )
+            .route(
+                "/rest/api/1.0/projects/PROJ/repos/app/pull-requests/4/activities",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!({
+                        "isLastPage": true,
+                        "values": [
+                            { "action": "APPROVED" },
+                            {
+                                "action": "COMMENTED",
+                                "comment": {
+                                    "id": 11,
+                                    "text": "Why remove this?",
+                                    "author": { "name": "bob" },
+                                    "createdDate": 1767261600000_i64,
+                                    "comments": [{
+                                        "id": 12,
+                                        "text": "It was unused",
+                                        "author": { "name": "alice" },
+                                        "createdDate": 1767265200000_i64,
+                                    }],
+                                },
+                                "commentAnchor": {
+                                    "path": "src/lib.rs",
+                                    "line": 3,
+                                    "lineType": "REMOVED",
+                                },
+                            },
+                            {
+                                "action": "COMMENTED",
+                                "comment": {
+                                    "id": 10,
+                                    "text": "Thanks!",
+                                    "author": { "name": "carol" },
+                                    "createdDate": 1767258000000_i64,
+                                },
+                            },
+                        ],
+                    })))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/rest/api/1.0")
+    }
+
+    fn hosts(api_url: &str) -> Vec<GitHostMapping> {
+        vec![GitHostMapping {
+            host: "bitbucket.example.com".to_string(),
+            provider: ProviderKind::BitbucketServer,
+            api_url: Some(api_url.to_string()),
+            token: Some("secret".to_string()),
+        }]
+    }
+
+    #[tokio::test]
+    async fn pull_requests_through_server_api() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_bitbucket_server().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url)).unwrap();
+        assert_eq!(host.provider_kind(), ProviderKind::BitbucketServer);
+        let repo_path = Path::new(".");
+
+        let created = host
+            .create_pr(
+                repo_path,
+                REMOTE,
+                &CreatePrRequest {
+                    title: "Add feature".to_string(),
+                    body: None,
+                    head_branch: "vk/feature".to_string(),
+                    base_branch: "main".to_string(),
+                    draft: None,
+                    head_repo_url: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.url, PR_URL);
+
+        let status = host.get_pr_status(PR_URL).await.unwrap();
+        assert!(matches!(status.status, MergeStatus::Merged));
+        assert_eq!(status.merge_commit_sha.as_deref(), Some("abc123"));
+        assert_eq!(status.merged_at, Some(from_millis(1767261600000)));
+
+        // Carol still needs work done
+        assert!(!host.is_pr_approved(PR_URL).await.unwrap());
+        host.merge_pr(PR_URL, MergeMethod::Merge).await.unwrap();
+
+        let for_branch = host
+            .list_prs_for_branch(repo_path, REMOTE, "vk/feature")
+            .await
+            .unwrap();
+        assert_eq!(for_branch.len(), 2);
+        assert!(matches!(for_branch[1].status, MergeStatus::Closed));
+
+        let comments = host.get_pr_comments(repo_path, REMOTE, 4).await.unwrap();
+        let ids: Vec<String> = comments
+            .iter()
+            .map(|c| match c {
+                UnifiedPrComment::General { id, .. } => id.clone(),
+                UnifiedPrComment::Review { id, .. } => id.to_string(),
+            })
+            .collect();
+        assert_eq!(ids, ["10", "11", "12"]);
+        match &comments[2] {
+            UnifiedPrComment::Review {
+                path,
+                line,
+                side,
+                url,
+                ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(3));
+                assert_eq!(side.as_deref(), Some("LEFT"));
+                assert_eq!(
+                    url.as_deref(),
+                    Some(&*format!("{PR_URL}/overview?commentId=12"))
+                );
+            }
+            other => panic!("expected an inline reply, got {other:?}"),
+        }
+    }
+}
@@ -1,6 +1,34 @@
 //! Git hosting provider detection from repository URLs.
 
-use super::types::ProviderKind;
+use super::types::{GitHostMapping, ProviderKind};
+
+/// Detect the git hosting provider from a remote or PR URL, preferring the
+/// configured host mappings over [`detect_provider_from_url`].
+pub fn detect_provider(url: &str, hosts: &[GitHostMapping]) -> ProviderKind {
+    find_host_mapping(url, hosts)
+        .map(|mapping| mapping.provider)
+        .unwrap_or_else(|| detect_provider_from_url(url))
+}
+
+/// The mapping configured for the host of `url`
+pub fn find_host_mapping<'a>(url: &str, hosts: &'a [GitHostMapping]) -> Option<&'a GitHostMapping> {
+    let host = url_host(url)?;
+    hosts
+        .iter()
+        .find(|mapping| mapping.host.trim().eq_ignore_ascii_case(&host))
+}
+
+/// Host name of an `https://`, `ssh://` or scp-like `user@host:path` URL
+pub fn url_host(url: &str) -> Option<String> {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => url.split_once(':')?.0,
+    };
+    let authority = rest.split('/').next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
 
 /// Detect the git hosting provider from a remote URL.
 ///
@@ -8,6 +36,14 @@ use super::types::ProviderKind;
 /// - GitHub.com: `https://github.com/owner/repo` or `git@github.com:owner/repo.git`
 /// - GitHub Enterprise: URLs containing `github.` (e.g., `https://github.company.com/owner/repo`)
 /// - Azure DevOps: `https://dev.azure.com/org/project/_git/repo` or legacy `https://org.visualstudio.com/...`
+/// - GitLab.com and hosts named `gitlab.*`: `https://gitlab.com/group/subgroup/project`
+/// - Codeberg (Forgejo): `https://codeberg.org/owner/repo`
+/// - Bitbucket Cloud: `https://bitbucket.org/workspace/repo`
+/// - Bitbucket Server / Data Center: `https://host/scm/PROJ/repo.git`,
+///   `https://host/projects/PROJ/repos/repo/...` or hosts named `bitbucket.*`
+///
+/// Other Gitea and Forgejo instances can only be recognised through a host
+/// mapping.
 pub fn detect_provider_from_url(url: &str) -> ProviderKind {
     let url_lower = url.to_lowercase();
 
@@ -33,6 +69,28 @@ pub fn detect_provider_from_url(url: &str) -> ProviderKind {
         return ProviderKind::GitHub;
     }
 
+    // /-/merge_requests/ is unique to GitLab
+    if url_lower.contains("gitlab.") || url_lower.contains("/-/merge_requests/") {
+        return ProviderKind::GitLab;
+    }
+
+    if url_lower.contains("codeberg.org") {
+        return ProviderKind::Gitea;
+    }
+
+    if url_lower.contains("bitbucket.org") {
+        return ProviderKind::Bitbucket;
+    }
+
+    // Clone and web URL layouts of Bitbucket Server
+    if url_lower.contains("bitbucket.")
+        || url_lower.contains("/scm/")
+        || ((url_lower.contains("/projects/") || url_lower.contains("/users/"))
+            && url_lower.contains("/repos/"))
+    {
+        return ProviderKind::BitbucketServer;
+    }
+
     ProviderKind::Unknown
 }
 
@@ -137,13 +195,95 @@ mod tests {
     }
 
     #[test]
-    fn test_unknown_provider() {
+    fn test_gitlab() {
+        assert_eq!(
+            detect_provider_from_url("https://gitlab.com/group/subgroup/repo.git"),
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            detect_provider_from_url("git@gitlab.example.com:group/repo.git"),
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            detect_provider_from_url("https://git.example.com/group/repo/-/merge_requests/7"),
+            ProviderKind::GitLab
+        );
+    }
+
+    #[test]
+    fn test_host_mapping() {
+        let hosts = [GitHostMapping {
+            host: "git.example.com".to_string(),
+            provider: ProviderKind::GitLab,
+            api_url: None,
+            token: None,
+        }];
+        assert_eq!(
+            detect_provider("ssh://git@git.example.com:2222/group/repo.git", &hosts),
+            ProviderKind::GitLab
+        );
         assert_eq!(
-            detect_provider_from_url("https://gitlab.com/owner/repo"),
+            detect_provider("git@git.example.com:group/repo.git", &hosts),
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            detect_provider("https://git.other.com/group/repo", &hosts),
             ProviderKind::Unknown
         );
+        assert_eq!(
+            detect_provider("https://github.com/owner/repo", &hosts),
+            ProviderKind::GitHub
+        );
+    }
+
+    #[test]
+    fn test_gitea() {
+        assert_eq!(
+            detect_provider_from_url("https://codeberg.org/owner/repo.git"),
+            ProviderKind::Gitea
+        );
+        // Self-hosted Forgejo has no recognisable URL pattern
+        let url = "https://forge.example.com/owner/repo/pulls/3";
+        assert_eq!(detect_provider(url, &[]), ProviderKind::Unknown);
+        let hosts = [GitHostMapping {
+            host: "forge.example.com".to_string(),
+            provider: ProviderKind::Gitea,
+            api_url: None,
+            token: None,
+        }];
+        assert_eq!(detect_provider(url, &hosts), ProviderKind::Gitea);
+    }
+
+    #[test]
+    fn test_bitbucket() {
         assert_eq!(
             detect_provider_from_url("https://bitbucket.org/owner/repo"),
+            ProviderKind::Bitbucket
+        );
+        assert_eq!(
+            detect_provider_from_url("git@bitbucket.org:owner/repo.git"),
+            ProviderKind::Bitbucket
+        );
+        assert_eq!(
+            detect_provider_from_url("https://git.example.com/scm/PROJ/repo.git"),
+            ProviderKind::BitbucketServer
+        );
+        assert_eq!(
+            detect_provider_from_url("ssh://git@bitbucket.example.com:7999/proj/repo.git"),
+            ProviderKind::BitbucketServer
+        );
+        assert_eq!(
+            detect_provider_from_url(
+                "https://git.example.com/projects/PROJ/repos/repo/pull-requests/4/overview"
+            ),
+            ProviderKind::BitbucketServer
+        );
+    }
+
+    #[test]
+    fn test_unknown_provider() {
+        assert_eq!(
+            detect_provider_from_url("https://git.example.com/owner/repo"),
             ProviderKind::Unknown
         );
     }
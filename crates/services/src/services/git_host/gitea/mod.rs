@@ -0,0 +1,499 @@
+//! Gitea hosting service implementation, for Gitea and Forgejo instances
+//! (including Codeberg), on top of the REST API.
+
+mod api;
+
+use std::{future::Future, path::Path, time::Duration};
+
+use api::{GiteaApi, GiteaApiError, GiteaRepo, parse_pull_request_url};
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use db::models::merge::{PrCheck, PullRequestInfo};
+use tracing::info;
+
+use super::{
+    GitHostProvider,
+    detection::find_host_mapping,
+    types::{
+        CreatePrRequest, GitHostError, GitHostMapping, MergeMethod, OpenPrInfo, ProviderKind,
+        UnifiedPrComment,
+    },
+};
+
+/// Token used when the host has none stored
+const TOKEN_ENV_VAR: &str = "GITEA_TOKEN";
+
+#[derive(Debug, Clone)]
+pub struct GiteaProvider {
+    api: GiteaApi,
+}
+
+impl GiteaProvider {
+    /// Provider for the Gitea or Forgejo instance hosting `url`. The API URL
+    /// and token come from the host mapping when there is one; otherwise the
+    /// API is expected at `/api/v1` on the host and the token in `GITEA_TOKEN`.
+    pub fn new(url: &str, hosts: &[GitHostMapping]) -> Result<Self, GitHostError> {
+        let repo = Self::repo(url)?;
+        let mapping = find_host_mapping(url, hosts);
+        let api_url = mapping
+            .and_then(|m| m.api_url.as_deref())
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}/api/v1", repo.base_url));
+        let token = mapping
+            .and_then(|m| m.token.as_deref())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .or_else(|| std::env::var(TOKEN_ENV_VAR).ok());
+        Ok(Self {
+            api: GiteaApi::new(&api_url, token)?,
+        })
+    }
+
+    fn repo(url: &str) -> Result<GiteaRepo, GitHostError> {
+        GiteaRepo::from_url(url)
+            .ok_or_else(|| GitHostError::Repository(format!("Not a Gitea repository URL: {url}")))
+    }
+
+    fn pull_request(pr_url: &str) -> Result<(GiteaRepo, i64), GitHostError> {
+        parse_pull_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a Gitea pull request URL: {pr_url}"))
+        })
+    }
+
+    async fn with_retry<T, F, Fut>(operation: F) -> Result<T, GitHostError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, GitHostError>>,
+    {
+        operation
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &GitHostError| e.should_retry())
+            .notify(|err: &GitHostError, dur: Duration| {
+                tracing::warn!(
+                    "Gitea API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+}
+
+impl From<GiteaApiError> for GitHostError {
+    fn from(error: GiteaApiError) -> Self {
+        match error {
+            GiteaApiError::Http { status, message } => match status {
+                401 => GitHostError::AuthFailed(message),
+                403 => GitHostError::InsufficientPermissions(message),
+                404 => GitHostError::RepoNotFoundOrNoAccess(message),
+                400..=499 if !matches!(status, 408 | 429) => {
+                    GitHostError::Rejected(format!("Gitea API returned {status}: {message}"))
+                }
+                _ => GitHostError::PullRequest(format!("Gitea API returned {status}: {message}")),
+            },
+            GiteaApiError::Request(msg) => GitHostError::PullRequest(msg),
+            GiteaApiError::UnexpectedOutput(msg) => GitHostError::UnexpectedOutput(msg),
+        }
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for GiteaProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let target = Self::repo(remote_url)?;
+        let source = match &request.head_repo_url {
+            Some(head_url) => Self::repo(head_url)?,
+            None => target.clone(),
+        };
+
+        let pr_info = Self::with_retry(|| async {
+            Ok(self
+                .api
+                .create_pull_request(&target, &source, request)
+                .await?)
+        })
+        .await?;
+        info!(
+            "Created Gitea PR #{} for branch {}",
+            pr_info.number, request.head_branch
+        );
+        Ok(pr_info)
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        let (repo, number) = parse_pull_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a Gitea pull request URL: {pr_url}"))
+        })?;
+        Self::with_retry(|| async { Ok(self.api.pull_request(&repo, number).await?) }).await
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .pull_requests_for_branch(&repo, branch_name)
+                .await?)
+        })
+        .await
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.pull_request_comments(&repo, pr_number).await?) })
+            .await
+    }
+
+    async fn list_open_prs(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<OpenPrInfo>, GitHostError> {
+        let repo = Self::repo(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.open_pull_requests(&repo).await?) }).await
+    }
+
+    async fn get_pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        let (repo, number) = parse_pull_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a Gitea pull request URL: {pr_url}"))
+        })?;
+        Self::with_retry(|| async { Ok(self.api.pull_request_statuses(&repo, number).await?) })
+            .await
+    }
+
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .set_pull_request_draft(&repo, number, draft)
+                .await?)
+        })
+        .await
+    }
+
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Self::with_retry(|| async { Ok(self.api.pull_request_approved(&repo, number).await?) })
+            .await
+    }
+
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Ok(self.api.merge_pull_request(&repo, number, method).await?)
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Self::with_retry(|| async { Ok(self.api.pull_request_body(&repo, number).await?) }).await
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        let (repo, number) = Self::pull_request(pr_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .update_pull_request_body(&repo, number, body)
+                .await?)
+        })
+        .await
+    }
+
+    fn pr_head_ref(&self, pr_number: i64) -> Option<String> {
+        Some(format!("refs/pull/{pr_number}/head"))
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Gitea
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Json, Router,
+        extract::Query,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+    };
+    use db::models::merge::{MergeStatus, PrCheckStatus};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::services::git_host::GitHostService;
+
+    const REMOTE: &str = "git@forge.example.com:owner/repo.git";
+    const FORK_REMOTE: &str = "git@forge.example.com:contributor/repo.git";
+    const PR_URL: &str = "https://forge.example.com/owner/repo/pulls/3";
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("Authorization").and_then(|v| v.to_str().ok()) {
+            Some("token secret") => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn pull(number: i64, head: &str, state: &str, merged: bool) -> Value {
+        json!({
+            "number": number,
+            "html_url": format!("https://forge.example.com/owner/repo/pulls/{number}"),
+            "state": state,
+            "merged": merged,
+            "merged_at": if merged { json!("2026-01-02T10:00:00Z") } else { json!(null) },
+            "merge_commit_sha": if merged { json!("abc123") } else { json!(null) },
+            "title": "Add feature",
+            "head": { "ref": head, "sha": "def456" },
+            "base": { "ref": "main" },
+        })
+    }
+
+    fn user(login: &str) -> Value {
+        json!({ "login": login })
+    }
+
+    /// Stub of the Gitea endpoints used by the provider
+    async fn stub_gitea() -> String {
+        let app = Router::new()
+            .route(
+                "/api/v1/repos/owner/repo/pulls",
+                get(
+                    |headers: HeaderMap, Query(query): Query<HashMap<String, String>>| async move {
+                        authorized(&headers)?;
+                        let pulls = match query.get("state").map(String::as_str) {
+                            Some("open") => vec![pull(3, "vk/feature", "open", false)],
+                            _ => vec![
+                                pull(3, "vk/feature", "closed", true),
+                                pull(4, "vk/other", "open", false),
+                            ],
+                        };
+                        Ok::<_, StatusCode>(Json(json!(pulls)))
+                    },
+                )
+                .post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["title"], "WIP: Add feature");
+                    assert_eq!(body["head"], "contributor:vk/feature");
+                    assert_eq!(body["base"], "main");
+                    Ok::<_, StatusCode>((
+                        StatusCode::CREATED,
+                        Json(pull(3, "vk/feature", "open", false)),
+                    ))
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/pulls/3",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(pull(3, "vk/feature", "closed", true)))
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/pulls/3/merge",
+                post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["Do"], "rebase");
+                    Ok::<_, StatusCode>(StatusCode::OK)
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/commits/def456/status",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!({
+                        "state": "failure",
+                        "statuses": [
+                            {
+                                "context": "ci / test",
+                                "status": "failure",
+                                "target_url": "https://forge.example.com/owner/repo/actions/runs/1",
+                                "description": "Failing after 2m",
+                            },
+                            {
+                                "context": "ci / lint",
+                                "status": "pending",
+                                "target_url": "",
+                                "description": null,
+                            },
+                        ],
+                    })))
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/issues/3/comments",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!([{
+                        "id": 10,
+                        "html_url": "https://forge.example.com/owner/repo/pulls/3#issuecomment-10",
+                        "user": user("alice"),
+                        "body": "Thanks!",
+                        "created_at": "2026-01-01T12:00:00Z",
+                    }])))
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/pulls/3/reviews",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!([
+                        {
+                            "id": 5,
+                            "user": user("bob"),
+                            "body": "A couple of nits",
+                            "state": "REQUEST_CHANGES",
+                            "submitted_at": "2026-01-01T11:00:00Z",
+                            "html_url": null,
+                            "comments_count": 1,
+                        },
+                        {
+                            "id": 6,
+                            "user": user("bob"),
+                            "body": "",
+                            "state": "PENDING",
+                            "submitted_at": null,
+                            "comments_count": 1,
+                        },
+                    ])))
+                }),
+            )
+            .route(
+                "/api/v1/repos/owner/repo/pulls/3/reviews/5/comments",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!([{
+                        "id": 20,
+                        "body": "Rename this",
+                        "user": user("bob"),
+                        "created_at": "2026-01-01T10:00:00Z",
+                        "html_url": "https://forge.example.com/owner/repo/pulls/3/files#issuecomment-20",
+                        "path": "src/lib.rs",
+                        "diff_hunk": "@@ -1,3 +1,4 @@",
+                        "position": 0,
+                        "original_position": 7,
+                    }])))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/api/v1")
+    }
+
+    fn hosts(api_url: &str, token: &str) -> Vec<GitHostMapping> {
+        vec![GitHostMapping {
+            host: "forge.example.com".to_string(),
+            provider: ProviderKind::Gitea,
+            api_url: Some(api_url.to_string()),
+            token: Some(token.to_string()),
+        }]
+    }
+
+    #[tokio::test]
+    async fn pull_requests_through_mapped_forgejo_instance() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_gitea().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url, "secret")).unwrap();
+        assert_eq!(host.provider_kind(), ProviderKind::Gitea);
+        assert_eq!(host.pr_head_ref(3).as_deref(), Some("refs/pull/3/head"));
+        let repo_path = Path::new(".");
+
+        let created = host
+            .create_pr(
+                repo_path,
+                REMOTE,
+                &CreatePrRequest {
+                    title: "Add feature".to_string(),
+                    body: None,
+                    head_branch: "vk/feature".to_string(),
+                    base_branch: "main".to_string(),
+                    draft: Some(true),
+                    head_repo_url: Some(FORK_REMOTE.to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.number, 3);
+        assert!(matches!(created.status, MergeStatus::Open));
+
+        let status = host.get_pr_status(PR_URL).await.unwrap();
+        assert!(matches!(status.status, MergeStatus::Merged));
+        assert_eq!(status.merge_commit_sha.as_deref(), Some("abc123"));
+
+        // Bob's latest review requests changes
+        assert!(!host.is_pr_approved(PR_URL).await.unwrap());
+        host.merge_pr(PR_URL, MergeMethod::Rebase).await.unwrap();
+
+        let for_branch = host
+            .list_prs_for_branch(repo_path, REMOTE, "vk/feature")
+            .await
+            .unwrap();
+        assert_eq!(for_branch.len(), 1);
+        assert_eq!(for_branch[0].number, 3);
+
+        let open = host.list_open_prs(repo_path, REMOTE).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].head_branch, "vk/feature");
+
+        let comments = host.get_pr_comments(repo_path, REMOTE, 3).await.unwrap();
+        let ids: Vec<String> = comments
+            .iter()
+            .map(|c| match c {
+                UnifiedPrComment::General { id, .. } => id.clone(),
+                UnifiedPrComment::Review { id, .. } => id.to_string(),
+            })
+            .collect();
+        assert_eq!(ids, ["20", "review-5", "10"]);
+        match &comments[0] {
+            UnifiedPrComment::Review {
+                path, line, side, ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(7));
+                assert_eq!(side.as_deref(), Some("LEFT"));
+            }
+            other => panic!("expected a review comment, got {other:?}"),
+        }
+
+        let checks = host.get_pr_checks(PR_URL).await.unwrap();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].status, PrCheckStatus::Failed);
+        assert_eq!(checks[1].status, PrCheckStatus::Pending);
+        assert_eq!(checks[1].url, None);
+        assert!(checks.iter().all(|c| c.required));
+    }
+
+    #[tokio::test]
+    async fn rejected_token_is_an_auth_failure() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_gitea().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url, "wrong")).unwrap();
+        let err = host.get_pr_status(PR_URL).await.unwrap_err();
+        assert!(matches!(err, GitHostError::AuthFailed(_)));
+    }
+}
@@ -11,7 +11,7 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
-use db::models::merge::{MergeStatus, PullRequestInfo};
+use db::models::merge::{MergeStatus, PrCheck, PrCheckStatus, PullRequestInfo};
 use serde::Deserialize;
 use tempfile::NamedTempFile;
 use thiserror::Error;
@@ -19,7 +19,8 @@ use url::Url;
 use utils::shell::resolve_executable_path_blocking;
 
 use crate::services::git_host::types::{
-    CreatePrRequest, OpenPrInfo, PrComment, PrCommentAuthor, PrReviewComment, ReviewCommentUser,
+    CreatePrRequest, GitHostIssue, IssueFilter, MergeMethod, OpenPrInfo, PrComment,
+    PrCommentAuthor, PrReviewComment, ReviewCommentUser, ReviewThread, ReviewThreadComment,
 };
 
 #[derive(Debug, Clone)]
@@ -106,10 +107,33 @@ struct GhPrResponse {
     url: String,
     #[serde(default)]
     state: String,
+    #[serde(default)]
+    is_draft: bool,
     merged_at: Option<DateTime<Utc>>,
     merge_commit: Option<GhMergeCommit>,
 }
 
+#[derive(Deserialize)]
+struct GhPrBodyResponse {
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPrReviewResponse {
+    /// Empty when branch protection doesn't require reviews
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    latest_reviews: Vec<GhReview>,
+}
+
+#[derive(Deserialize)]
+struct GhReview {
+    state: String,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GhPrListExtendedResponse {
@@ -121,6 +145,107 @@ struct GhPrListExtendedResponse {
     base_ref_name: String,
 }
 
+#[derive(Deserialize)]
+struct GhIssueResponse {
+    number: i64,
+    url: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    labels: Vec<GhLabel>,
+    #[serde(default)]
+    assignees: Vec<GhUserLogin>,
+    milestone: Option<GhMilestone>,
+}
+
+#[derive(Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GhMilestone {
+    title: String,
+}
+
+const ISSUE_JSON_FIELDS: &str = "number,url,title,body,state,labels,assignees,milestone";
+
+#[derive(Deserialize)]
+struct GhCheckResponse {
+    name: String,
+    #[serde(default)]
+    bucket: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    description: String,
+}
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          id
+          isResolved
+          path
+          line
+          comments(first: 50) {
+            nodes { author { login } body url diffHunk }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+const REPLY_TO_THREAD_MUTATION: &str = r#"
+mutation($thread: ID!, $body: String!) {
+  addPullRequestReviewThreadReply(input: {pullRequestReviewThreadId: $thread, body: $body}) {
+    comment { id }
+  }
+}"#;
+
+const RESOLVE_THREAD_MUTATION: &str = r#"
+mutation($thread: ID!) {
+  resolveReviewThread(input: {threadId: $thread}) {
+    thread { isResolved }
+  }
+}"#;
+
+#[derive(Deserialize)]
+struct GhGraphQlNodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhReviewThreadResponse {
+    id: String,
+    is_resolved: bool,
+    #[serde(default)]
+    path: String,
+    line: Option<i64>,
+    comments: GhGraphQlNodes<GhThreadCommentResponse>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhThreadCommentResponse {
+    author: Option<GhUserLogin>,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    diff_hunk: String,
+}
+
 #[derive(Debug, Error)]
 pub enum GhCliError {
     #[error("GitHub CLI (`gh`) executable not found or not runnable")]
@@ -258,7 +383,92 @@ impl GhCli {
         }
 
         let raw = self.run(args, Some(repo_path))?;
-        Self::parse_pr_create_text(&raw)
+        let mut pr_info = Self::parse_pr_create_text(&raw)?;
+        pr_info.is_draft = request.draft.unwrap_or(false);
+        Ok(pr_info)
+    }
+
+    /// Mark a pull request as a draft, or as ready for review.
+    pub fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GhCliError> {
+        let mut args = vec!["pr", "ready", pr_url];
+        if draft {
+            args.push("--undo");
+        }
+        self.run(args, None)?;
+        Ok(())
+    }
+
+    /// Whether a pull request has the reviews branch protection requires, or
+    /// an approval and no requested changes when it requires none.
+    pub fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GhCliError> {
+        let raw = self.run(
+            [
+                "pr",
+                "view",
+                pr_url,
+                "--json",
+                "reviewDecision,latestReviews",
+            ],
+            None,
+        )?;
+        let pr: GhPrReviewResponse = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh pr view --json reviewDecision response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(match pr.review_decision.as_deref() {
+            Some("APPROVED") => true,
+            None | Some("") => {
+                pr.latest_reviews.iter().any(|r| r.state == "APPROVED")
+                    && !pr
+                        .latest_reviews
+                        .iter()
+                        .any(|r| r.state == "CHANGES_REQUESTED")
+            }
+            Some(_) => false,
+        })
+    }
+
+    /// Merge a pull request right away.
+    pub fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GhCliError> {
+        let method_flag = match method {
+            MergeMethod::Merge => "--merge",
+            MergeMethod::Squash => "--squash",
+            MergeMethod::Rebase => "--rebase",
+        };
+        self.run(["pr", "merge", pr_url, method_flag], None)?;
+        Ok(())
+    }
+
+    /// Retrieve the description of a pull request.
+    pub fn pr_body(&self, pr_url: &str) -> Result<String, GhCliError> {
+        let raw = self.run(["pr", "view", pr_url, "--json", "body"], None)?;
+        let pr: GhPrBodyResponse = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh pr view --json body response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(pr.body)
+    }
+
+    /// Replace the description of a pull request.
+    pub fn edit_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GhCliError> {
+        let mut body_file = NamedTempFile::new()
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to create temp file: {e}")))?;
+        body_file
+            .write_all(body.as_bytes())
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to write body: {e}")))?;
+        self.run(
+            [
+                OsString::from("pr"),
+                OsString::from("edit"),
+                OsString::from(pr_url),
+                OsString::from("--body-file"),
+                body_file.path().as_os_str().to_os_string(),
+            ],
+            None,
+        )?;
+        Ok(())
     }
 
     /// Retrieve details for a pull request by URL.
@@ -269,7 +479,7 @@ impl GhCli {
                 "view",
                 pr_url,
                 "--json",
-                "number,url,state,mergedAt,mergeCommit",
+                "number,url,state,isDraft,mergedAt,mergeCommit",
             ],
             None,
         )?;
@@ -294,7 +504,7 @@ impl GhCli {
                 "--head",
                 branch,
                 "--json",
-                "number,url,state,mergedAt,mergeCommit",
+                "number,url,state,isDraft,mergedAt,mergeCommit",
             ],
             None,
         )?;
@@ -318,6 +528,85 @@ impl GhCli {
         Self::parse_open_pr_list(&raw)
     }
 
+    /// List the open issues of a repository that match `filter`.
+    pub fn list_issues(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, GhCliError> {
+        let mut args = vec![
+            "issue".to_string(),
+            "list".to_string(),
+            "--repo".to_string(),
+            repo_info.repo_spec(),
+            "--state".to_string(),
+            "open".to_string(),
+            "--limit".to_string(),
+            "200".to_string(),
+            "--json".to_string(),
+            ISSUE_JSON_FIELDS.to_string(),
+        ];
+        for label in &filter.labels {
+            args.extend(["--label".to_string(), label.clone()]);
+        }
+        if let Some(assignee) = &filter.assignee {
+            args.extend(["--assignee".to_string(), assignee.clone()]);
+        }
+        if let Some(milestone) = &filter.milestone {
+            args.extend(["--milestone".to_string(), milestone.clone()]);
+        }
+        let raw = self.run(args, None)?;
+        let issues: Vec<GhIssueResponse> = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue list response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(issues
+            .into_iter()
+            .map(Self::issue_response_to_issue)
+            .collect())
+    }
+
+    /// Retrieve an issue by URL.
+    pub fn view_issue(&self, issue_url: &str) -> Result<GitHostIssue, GhCliError> {
+        let raw = self.run(
+            ["issue", "view", issue_url, "--json", ISSUE_JSON_FIELDS],
+            None,
+        )?;
+        let issue: GhIssueResponse = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue view response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(Self::issue_response_to_issue(issue))
+    }
+
+    /// Add a comment to an issue.
+    pub fn comment_on_issue(&self, issue_url: &str, body: &str) -> Result<(), GhCliError> {
+        let mut body_file = NamedTempFile::new()
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to create temp file: {e}")))?;
+        body_file
+            .write_all(body.as_bytes())
+            .map_err(|e| GhCliError::CommandFailed(format!("Failed to write body: {e}")))?;
+        self.run(
+            [
+                OsString::from("issue"),
+                OsString::from("comment"),
+                OsString::from(issue_url),
+                OsString::from("--body-file"),
+                body_file.path().as_os_str().to_os_string(),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Close an issue as completed.
+    pub fn close_issue(&self, issue_url: &str) -> Result<(), GhCliError> {
+        self.run(["issue", "close", issue_url, "--reason", "completed"], None)?;
+        Ok(())
+    }
+
     /// Fetch comments for a pull request.
     pub fn get_pr_comments(
         &self,
@@ -361,6 +650,139 @@ impl GhCli {
         Self::parse_pr_review_comments(&raw)
     }
 
+    /// Unresolved review threads of a pull request, via the GraphQL API
+    pub fn get_review_threads(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewThread>, GhCliError> {
+        let raw = self.graphql(
+            repo_info,
+            REVIEW_THREADS_QUERY,
+            &[
+                ("owner", repo_info.owner.clone()),
+                ("repo", repo_info.repo_name.clone()),
+            ],
+            &[("number", pr_number.to_string())],
+        )?;
+        Self::parse_review_threads(&raw)
+    }
+
+    /// Reply to a review thread, resolving it when `resolve` is set
+    pub fn reply_to_review_thread(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        thread_id: &str,
+        body: &str,
+        resolve: bool,
+    ) -> Result<(), GhCliError> {
+        self.graphql(
+            repo_info,
+            REPLY_TO_THREAD_MUTATION,
+            &[
+                ("thread", thread_id.to_string()),
+                ("body", body.to_string()),
+            ],
+            &[],
+        )?;
+        if resolve {
+            self.graphql(
+                repo_info,
+                RESOLVE_THREAD_MUTATION,
+                &[("thread", thread_id.to_string())],
+                &[],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Run a GraphQL query. `fields` are passed as strings, `typed_fields`
+    /// as JSON values such as numbers.
+    fn graphql(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        query: &str,
+        fields: &[(&str, String)],
+        typed_fields: &[(&str, String)],
+    ) -> Result<String, GhCliError> {
+        let mut args = vec![
+            "api".to_string(),
+            "graphql".to_string(),
+            "-f".to_string(),
+            format!("query={query}"),
+        ];
+        for (name, value) in fields {
+            args.push("-f".to_string());
+            args.push(format!("{name}={value}"));
+        }
+        for (name, value) in typed_fields {
+            args.push("-F".to_string());
+            args.push(format!("{name}={value}"));
+        }
+        if let Some(ref host) = repo_info.hostname {
+            args.push("--hostname".to_string());
+            args.push(host.clone());
+        }
+        self.run(args, None)
+    }
+
+    /// CI checks of a pull request. Checks the base branch requires are
+    /// marked as required, or every check when it requires none.
+    pub fn pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GhCliError> {
+        let mut checks = Self::parse_pr_checks(&self.pr_checks_json(pr_url, false)?)?;
+        // `--required` is unknown to older gh versions
+        let required = match self.pr_checks_json(pr_url, true) {
+            Ok(raw) => Self::parse_pr_checks(&raw)?,
+            Err(GhCliError::CommandFailed(_)) => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        for check in &mut checks {
+            check.required = required.is_empty() || required.iter().any(|r| r.name == check.name);
+        }
+        Ok(checks)
+    }
+
+    fn pr_checks_json(&self, pr_url: &str, required: bool) -> Result<String, GhCliError> {
+        let mut args = vec![
+            "pr",
+            "checks",
+            pr_url,
+            "--json",
+            "name,bucket,link,description",
+        ];
+        if required {
+            args.push("--required");
+        }
+        match self.run(args, None) {
+            // gh fails instead of printing an empty list
+            Err(GhCliError::CommandFailed(msg)) if msg.contains("checks reported") => {
+                Ok("[]".to_string())
+            }
+            result => result,
+        }
+    }
+
+    /// Log of the failed steps of a GitHub Actions job, given the job's URL.
+    /// Returns None for checks that don't link to an Actions job.
+    pub fn failed_job_log(&self, job_url: &str) -> Result<Option<String>, GhCliError> {
+        let Some((repo_spec, job_id)) = Self::parse_actions_job_url(job_url) else {
+            return Ok(None);
+        };
+        let raw = self.run(
+            [
+                "run",
+                "view",
+                "--job",
+                &job_id,
+                "--log-failed",
+                "--repo",
+                &repo_spec,
+            ],
+            None,
+        )?;
+        Ok(Some(raw))
+    }
+
     pub fn pr_checkout(
         &self,
         repo_path: &Path,
@@ -421,6 +843,7 @@ impl GhCli {
             status: MergeStatus::Open,
             merged_at: None,
             merge_commit_sha: None,
+            is_draft: false,
         })
     }
 
@@ -461,6 +884,23 @@ impl GhCli {
             .collect())
     }
 
+    fn issue_response_to_issue(issue: GhIssueResponse) -> GitHostIssue {
+        GitHostIssue {
+            number: issue.number,
+            url: issue.url,
+            title: issue.title,
+            body: Some(issue.body).filter(|b| !b.is_empty()),
+            is_open: !issue.state.eq_ignore_ascii_case("closed"),
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            assignees: issue
+                .assignees
+                .into_iter()
+                .filter_map(|a| a.login)
+                .collect(),
+            milestone: issue.milestone.map(|m| m.title),
+        }
+    }
+
     fn pr_response_to_info(pr: GhPrResponse) -> PullRequestInfo {
         let state = if pr.state.is_empty() {
             "OPEN"
@@ -478,6 +918,44 @@ impl GhCli {
             },
             merged_at: pr.merged_at,
             merge_commit_sha: pr.merge_commit.and_then(|c| c.oid),
+            is_draft: pr.is_draft,
+        }
+    }
+
+    fn parse_pr_checks(raw: &str) -> Result<Vec<PrCheck>, GhCliError> {
+        let checks: Vec<GhCheckResponse> = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh pr checks response: {err}; raw: {raw}"
+            ))
+        })?;
+        Ok(checks
+            .into_iter()
+            .map(|c| PrCheck {
+                name: c.name,
+                status: match c.bucket.as_str() {
+                    "pass" => PrCheckStatus::Passed,
+                    "fail" | "cancel" => PrCheckStatus::Failed,
+                    "skipping" => PrCheckStatus::Skipped,
+                    _ => PrCheckStatus::Pending,
+                },
+                required: false,
+                url: Some(c.link).filter(|l| !l.is_empty()),
+                description: Some(c.description).filter(|d| !d.is_empty()),
+            })
+            .collect())
+    }
+
+    /// `HOST/OWNER/REPO` and job id of
+    /// `https://HOST/OWNER/REPO/actions/runs/RUN/job/JOB`
+    fn parse_actions_job_url(url: &str) -> Option<(String, String)> {
+        let url = Url::parse(url).ok()?;
+        let segments: Vec<&str> = url.path_segments()?.collect();
+        match segments.as_slice() {
+            [owner, repo, "actions", "runs", _, "job", job, ..] => Some((
+                format!("{}/{owner}/{repo}", url.host_str()?),
+                job.to_string(),
+            )),
+            _ => None,
         }
     }
 
@@ -507,6 +985,53 @@ impl GhCli {
             .collect())
     }
 
+    fn parse_review_threads(raw: &str) -> Result<Vec<ReviewThread>, GhCliError> {
+        let value: serde_json::Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse review threads response: {err}; raw: {raw}"
+            ))
+        })?;
+        let threads = value
+            .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+            .cloned()
+            .ok_or_else(|| {
+                GhCliError::UnexpectedOutput(format!("Review threads missing from response: {raw}"))
+            })?;
+        let threads: Vec<GhReviewThreadResponse> =
+            serde_json::from_value(threads).map_err(|err| {
+                GhCliError::UnexpectedOutput(format!(
+                    "Failed to parse review threads response: {err}; raw: {raw}"
+                ))
+            })?;
+
+        Ok(threads
+            .into_iter()
+            .filter(|t| !t.is_resolved)
+            .map(|t| {
+                let first = t.comments.nodes.first();
+                ReviewThread {
+                    diff_hunk: first.map(|c| c.diff_hunk.clone()).filter(|h| !h.is_empty()),
+                    url: first.map(|c| c.url.clone()).filter(|u| !u.is_empty()),
+                    id: t.id,
+                    path: t.path,
+                    line: t.line,
+                    comments: t
+                        .comments
+                        .nodes
+                        .into_iter()
+                        .map(|c| ReviewThreadComment {
+                            author: c
+                                .author
+                                .and_then(|a| a.login)
+                                .unwrap_or_else(|| "unknown".to_string()),
+                            body: c.body,
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
     fn parse_pr_review_comments(raw: &str) -> Result<Vec<PrReviewComment>, GhCliError> {
         let items: Vec<GhReviewCommentResponse> =
             serde_json::from_str(raw.trim()).map_err(|err| {
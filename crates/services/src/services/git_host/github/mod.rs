@@ -8,13 +8,16 @@ use async_trait::async_trait;
 use backon::{ExponentialBuilder, Retryable};
 pub use cli::GhCli;
 use cli::{GhCliError, GitHubRepoInfo};
-use db::models::merge::PullRequestInfo;
+use db::models::merge::{PrCheck, PullRequestInfo};
 use tokio::task;
 use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, OpenPrInfo, ProviderKind, UnifiedPrComment},
+    types::{
+        CreatePrRequest, GitHostError, GitHostIssue, IssueFilter, MergeMethod, OpenPrInfo,
+        ProviderKind, ReviewThread, UnifiedPrComment,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -140,6 +143,12 @@ impl From<GhCliError> for GitHostError {
                     GitHostError::InsufficientPermissions(msg.clone())
                 } else if lower.contains("404") || lower.contains("not found") {
                     GitHostError::RepoNotFoundOrNoAccess(msg.clone())
+                } else if lower.contains("not mergeable")
+                    || ["http 405", "http 409", "http 422"]
+                        .iter()
+                        .any(|code| lower.contains(code))
+                {
+                    GitHostError::Rejected(msg.clone())
                 } else {
                     GitHostError::PullRequest(msg.clone())
                 }
@@ -389,6 +398,249 @@ impl GitHostProvider for GitHubProvider {
         .await
     }
 
+    async fn get_review_threads(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewThread>, GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+
+        let cli = self.gh_cli.clone();
+
+        (|| async {
+            let cli = cli.clone();
+            let repo_info = repo_info.clone();
+
+            let threads =
+                task::spawn_blocking(move || cli.get_review_threads(&repo_info, pr_number))
+                    .await
+                    .map_err(|err| {
+                        GitHostError::PullRequest(format!(
+                            "Failed to execute GitHub CLI for fetching review threads: {err}"
+                        ))
+                    })?;
+            threads.map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn reply_to_review_thread(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        _pr_number: i64,
+        thread_id: &str,
+        body: &str,
+        resolve: bool,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let cli = self.gh_cli.clone();
+        let thread_id = thread_id.to_string();
+        let body = body.to_string();
+
+        // Not retried, so that a reply is never posted twice
+        task::spawn_blocking(move || {
+            cli.reply_to_review_thread(&repo_info, &thread_id, &body, resolve)
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute GitHub CLI for replying to a review thread: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)
+    }
+
+    async fn get_pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+
+        (|| async {
+            let cli = cli.clone();
+            let url = url.clone();
+            let checks = task::spawn_blocking(move || cli.pr_checks(&url))
+                .await
+                .map_err(|err| {
+                    GitHostError::PullRequest(format!(
+                        "Failed to execute GitHub CLI for fetching PR checks: {err}"
+                    ))
+                })?;
+            checks.map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn get_check_log(&self, check: &PrCheck) -> Result<Option<String>, GitHostError> {
+        let Some(url) = check.url.clone() else {
+            return Ok(None);
+        };
+        let cli = self.gh_cli.clone();
+        task::spawn_blocking(move || cli.failed_job_log(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for fetching job log: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.set_pr_draft(&url, draft))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for updating draft state: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.is_pr_approved(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for fetching PR reviews: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.merge_pr(&url, method))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for merging PR: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        task::spawn_blocking(move || cli.pr_body(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for fetching PR description: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = pr_url.to_string();
+        let body = body.to_string();
+        task::spawn_blocking(move || cli.edit_pr_body(&url, &body))
+            .await
+            .map_err(|err| {
+                GitHostError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for updating PR description: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn list_issues(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let cli = self.gh_cli.clone();
+        let filter = filter.clone();
+        task::spawn_blocking(move || cli.list_issues(&repo_info, &filter))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute GitHub CLI for listing issues: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn get_issue(&self, issue_url: &str) -> Result<GitHostIssue, GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = issue_url.to_string();
+        task::spawn_blocking(move || cli.view_issue(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute GitHub CLI for viewing issue: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn comment_on_issue(&self, issue_url: &str, body: &str) -> Result<(), GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = issue_url.to_string();
+        let body = body.to_string();
+        task::spawn_blocking(move || cli.comment_on_issue(&url, &body))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute GitHub CLI for commenting on issue: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
+    async fn close_issue(&self, issue_url: &str) -> Result<(), GitHostError> {
+        let cli = self.gh_cli.clone();
+        let url = issue_url.to_string();
+        task::spawn_blocking(move || cli.close_issue(&url))
+            .await
+            .map_err(|err| {
+                GitHostError::Issue(format!(
+                    "Failed to execute GitHub CLI for closing issue: {err}"
+                ))
+            })?
+            .map_err(Into::into)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::GitHub
     }
@@ -0,0 +1,664 @@
+//! GitLab hosting service implementation, for gitlab.com and self-hosted
+//! instances, on top of the REST API.
+
+mod api;
+
+use std::{future::Future, path::Path, time::Duration};
+
+use api::{
+    GitLabApi, GitLabApiError, GitLabProject, parse_issue_url, parse_job_url,
+    parse_merge_request_url,
+};
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use db::models::merge::{PrCheck, PullRequestInfo};
+use tracing::info;
+
+use super::{
+    GitHostProvider,
+    detection::find_host_mapping,
+    types::{
+        CreatePrRequest, GitHostError, GitHostIssue, GitHostMapping, IssueFilter, MergeMethod,
+        OpenPrInfo, ProviderKind, ReviewThread, UnifiedPrComment,
+    },
+};
+
+/// Token used when the host mapping has none
+const TOKEN_ENV_VAR: &str = "GITLAB_TOKEN";
+
+#[derive(Debug, Clone)]
+pub struct GitLabProvider {
+    api: GitLabApi,
+}
+
+impl GitLabProvider {
+    /// Provider for the GitLab instance hosting `url`. The API URL and token
+    /// come from the host mapping when there is one; otherwise the API is
+    /// expected at `/api/v4` on the host and the token in `GITLAB_TOKEN`.
+    pub fn new(url: &str, hosts: &[GitHostMapping]) -> Result<Self, GitHostError> {
+        let project = Self::project(url)?;
+        let mapping = find_host_mapping(url, hosts);
+        let api_url = mapping
+            .and_then(|m| m.api_url.as_deref())
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}/api/v4", project.base_url));
+        let token = mapping
+            .and_then(|m| m.token.as_deref())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .or_else(|| std::env::var(TOKEN_ENV_VAR).ok());
+        Ok(Self {
+            api: GitLabApi::new(&api_url, token)?,
+        })
+    }
+
+    fn project(url: &str) -> Result<GitLabProject, GitHostError> {
+        GitLabProject::from_url(url)
+            .ok_or_else(|| GitHostError::Repository(format!("Not a GitLab project URL: {url}")))
+    }
+
+    fn merge_request(pr_url: &str) -> Result<(GitLabProject, i64), GitHostError> {
+        parse_merge_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a GitLab merge request URL: {pr_url}"))
+        })
+    }
+
+    fn issue(issue_url: &str) -> Result<(GitLabProject, i64), GitHostError> {
+        parse_issue_url(issue_url)
+            .ok_or_else(|| GitHostError::Issue(format!("Not a GitLab issue URL: {issue_url}")))
+    }
+
+    async fn with_retry<T, F, Fut>(operation: F) -> Result<T, GitHostError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, GitHostError>>,
+    {
+        operation
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &GitHostError| e.should_retry())
+            .notify(|err: &GitHostError, dur: Duration| {
+                tracing::warn!(
+                    "GitLab API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+}
+
+impl From<GitLabApiError> for GitHostError {
+    fn from(error: GitLabApiError) -> Self {
+        match error {
+            GitLabApiError::Http { status, message } => match status {
+                401 => GitHostError::AuthFailed(message),
+                403 => GitHostError::InsufficientPermissions(message),
+                404 => GitHostError::RepoNotFoundOrNoAccess(message),
+                400..=499 if !matches!(status, 408 | 429) => {
+                    GitHostError::Rejected(format!("GitLab API returned {status}: {message}"))
+                }
+                _ => GitHostError::PullRequest(format!("GitLab API returned {status}: {message}")),
+            },
+            GitLabApiError::Request(msg) => GitHostError::PullRequest(msg),
+            GitLabApiError::UnexpectedOutput(msg) => GitHostError::UnexpectedOutput(msg),
+        }
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for GitLabProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let target = Self::project(remote_url)?;
+        // Merge requests from a fork are opened on the fork, targeting the
+        // upstream project by id
+        let source = match &request.head_repo_url {
+            Some(head_url) => Self::project(head_url)?,
+            None => target.clone(),
+        };
+        let target_project_id = if source != target {
+            Some(
+                Self::with_retry(|| async { Ok(self.api.project(&target).await?) })
+                    .await?
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let pr_info = Self::with_retry(|| async {
+            Ok(self
+                .api
+                .create_merge_request(&source, target_project_id, request)
+                .await?)
+        })
+        .await?;
+        info!(
+            "Created GitLab MR !{} for branch {}",
+            pr_info.number, request.head_branch
+        );
+        Ok(pr_info)
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        let (project, iid) = parse_merge_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a GitLab merge request URL: {pr_url}"))
+        })?;
+        Self::with_retry(|| async { Ok(self.api.merge_request(&project, iid).await?) }).await
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let project = Self::project(remote_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .merge_requests_for_branch(&project, branch_name)
+                .await?)
+        })
+        .await
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let project = Self::project(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.merge_request_notes(&project, pr_number).await?) })
+            .await
+    }
+
+    async fn list_open_prs(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<OpenPrInfo>, GitHostError> {
+        let project = Self::project(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.open_merge_requests(&project).await?) }).await
+    }
+
+    async fn get_review_threads(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewThread>, GitHostError> {
+        let project = Self::project(remote_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .merge_request_review_threads(&project, pr_number)
+                .await?)
+        })
+        .await
+    }
+
+    async fn reply_to_review_thread(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        thread_id: &str,
+        body: &str,
+        resolve: bool,
+    ) -> Result<(), GitHostError> {
+        let project = Self::project(remote_url)?;
+        // Not retried, so that a reply is never posted twice
+        Ok(self
+            .api
+            .reply_to_discussion(&project, pr_number, thread_id, body, resolve)
+            .await?)
+    }
+
+    async fn get_pr_checks(&self, pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        let (project, iid) = parse_merge_request_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a GitLab merge request URL: {pr_url}"))
+        })?;
+        Self::with_retry(|| async { Ok(self.api.merge_request_jobs(&project, iid).await?) }).await
+    }
+
+    async fn get_check_log(&self, check: &PrCheck) -> Result<Option<String>, GitHostError> {
+        let Some((project, job_id)) = check.url.as_deref().and_then(parse_job_url) else {
+            return Ok(None);
+        };
+        let trace =
+            Self::with_retry(|| async { Ok(self.api.job_trace(&project, job_id).await?) }).await?;
+        Ok(Some(trace))
+    }
+
+    async fn set_pr_draft(&self, pr_url: &str, draft: bool) -> Result<(), GitHostError> {
+        let (project, iid) = Self::merge_request(pr_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .set_merge_request_draft(&project, iid, draft)
+                .await?)
+        })
+        .await
+    }
+
+    async fn is_pr_approved(&self, pr_url: &str) -> Result<bool, GitHostError> {
+        let (project, iid) = Self::merge_request(pr_url)?;
+        Self::with_retry(|| async { Ok(self.api.merge_request_approved(&project, iid).await?) })
+            .await
+    }
+
+    async fn merge_pr(&self, pr_url: &str, method: MergeMethod) -> Result<(), GitHostError> {
+        let (project, iid) = Self::merge_request(pr_url)?;
+        Ok(self.api.accept_merge_request(&project, iid, method).await?)
+    }
+
+    async fn get_pr_body(&self, pr_url: &str) -> Result<String, GitHostError> {
+        let (project, iid) = Self::merge_request(pr_url)?;
+        Self::with_retry(|| async { Ok(self.api.merge_request_description(&project, iid).await?) })
+            .await
+    }
+
+    async fn update_pr_body(&self, pr_url: &str, body: &str) -> Result<(), GitHostError> {
+        let (project, iid) = Self::merge_request(pr_url)?;
+        Self::with_retry(|| async {
+            Ok(self
+                .api
+                .update_merge_request_description(&project, iid, body)
+                .await?)
+        })
+        .await
+    }
+
+    async fn list_issues(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, GitHostError> {
+        let project = Self::project(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.open_issues(&project, filter).await?) }).await
+    }
+
+    async fn get_issue(&self, issue_url: &str) -> Result<GitHostIssue, GitHostError> {
+        let (project, iid) = Self::issue(issue_url)?;
+        Self::with_retry(|| async { Ok(self.api.issue(&project, iid).await?) }).await
+    }
+
+    async fn comment_on_issue(&self, issue_url: &str, body: &str) -> Result<(), GitHostError> {
+        let (project, iid) = Self::issue(issue_url)?;
+        // Not retried, so that a comment is never posted twice
+        Ok(self.api.create_issue_note(&project, iid, body).await?)
+    }
+
+    async fn close_issue(&self, issue_url: &str) -> Result<(), GitHostError> {
+        let (project, iid) = Self::issue(issue_url)?;
+        Self::with_retry(|| async { Ok(self.api.close_issue(&project, iid).await?) }).await
+    }
+
+    fn pr_head_ref(&self, pr_number: i64) -> Option<String> {
+        Some(format!("refs/merge-requests/{pr_number}/head"))
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::GitLab
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Json, Router,
+        extract::{Path as AxumPath, Query},
+        http::{HeaderMap, StatusCode},
+        routing::{get, post, put},
+    };
+    use db::models::merge::{MergeStatus, PrCheckStatus};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::services::git_host::GitHostService;
+
+    const REMOTE: &str = "git@git.example.com:group/project.git";
+    const PROJECT_ID: &str = "group/project";
+    const MR_URL: &str = "https://git.example.com/group/project/-/merge_requests/7";
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("PRIVATE-TOKEN").and_then(|v| v.to_str().ok()) {
+            Some("secret") => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn merge_request(state: &str) -> Value {
+        json!({
+            "iid": 7,
+            "web_url": MR_URL,
+            "state": state,
+            "merged_at": null,
+            "merge_commit_sha": null,
+            "squash_commit_sha": null,
+            "title": "Draft: Add feature",
+            "source_branch": "vk/feature",
+            "target_branch": "main",
+        })
+    }
+
+    /// Stub of the GitLab endpoints used by the provider
+    async fn stub_gitlab() -> String {
+        let app = Router::new()
+            .route(
+                "/api/v4/projects/{id}/merge_requests",
+                get(
+                    |headers: HeaderMap,
+                     AxumPath(id): AxumPath<String>,
+                     Query(query): Query<HashMap<String, String>>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(id, PROJECT_ID);
+                        let state = match query.get("state").map(String::as_str) {
+                            Some("opened") => "opened",
+                            _ => "merged",
+                        };
+                        Ok::<_, StatusCode>(Json(json!([merge_request(state)])))
+                    },
+                )
+                .post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["title"], "Draft: Add feature");
+                    assert_eq!(body["source_branch"], "vk/feature");
+                    assert!(body.get("target_project_id").is_none());
+                    Ok::<_, StatusCode>((StatusCode::CREATED, Json(merge_request("opened"))))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    let mut mr = merge_request("merged");
+                    mr["merge_commit_sha"] = json!("abc123");
+                    Ok::<_, StatusCode>(Json(mr))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/approvals",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!({
+                        "approved": true,
+                        "approvals_left": 0,
+                        "approved_by": [{ "user": { "username": "alice" } }],
+                    })))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/merge",
+                put(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                    authorized(&headers)?;
+                    assert_eq!(body["squash"], true);
+                    Ok::<_, StatusCode>(Json(merge_request("merged")))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/notes",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!([
+                        {
+                            "id": 1,
+                            "body": "Looks good overall",
+                            "author": { "username": "alice" },
+                            "created_at": "2026-01-01T10:00:00Z",
+                            "system": false,
+                        },
+                        {
+                            "id": 2,
+                            "body": "Rename this",
+                            "author": { "username": "bob" },
+                            "created_at": "2026-01-01T11:00:00Z",
+                            "system": false,
+                            "type": "DiffNote",
+                            "position": {
+                                "new_path": "src/lib.rs",
+                                "old_path": "src/lib.rs",
+                                "new_line": 12,
+                                "old_line": null,
+                            },
+                        },
+                        {
+                            "id": 3,
+                            "body": "added 1 commit",
+                            "author": { "username": "bob" },
+                            "created_at": "2026-01-01T12:00:00Z",
+                            "system": true,
+                        },
+                    ])))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/discussions",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    let note = |id: i64, body: &str, resolved: bool| {
+                        json!({
+                            "id": id,
+                            "body": body,
+                            "author": { "username": "bob" },
+                            "resolvable": true,
+                            "resolved": resolved,
+                            "position": {
+                                "new_path": "src/lib.rs",
+                                "old_path": "src/lib.rs",
+                                "new_line": 12,
+                                "old_line": null,
+                            },
+                        })
+                    };
+                    Ok::<_, StatusCode>(Json(json!([
+                        {
+                            "id": "general",
+                            "notes": [{
+                                "id": 1,
+                                "body": "Looks good overall",
+                                "author": { "username": "alice" },
+                                "resolvable": false,
+                            }],
+                        },
+                        { "id": "open", "notes": [note(2, "Rename this", false)] },
+                        { "id": "done", "notes": [note(4, "Typo", true)] },
+                    ])))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}/notes",
+                post(
+                    |headers: HeaderMap,
+                     AxumPath((_, _, discussion_id)): AxumPath<(String, i64, String)>,
+                     Json(body): Json<Value>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(discussion_id, "open");
+                        assert_eq!(body["body"], "Renamed");
+                        Ok::<_, StatusCode>((StatusCode::CREATED, Json(json!({ "id": 5 }))))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/discussions/{discussion_id}",
+                put(
+                    |headers: HeaderMap, Json(body): Json<Value>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(body["resolved"], true);
+                        Ok::<_, StatusCode>(Json(json!({ "id": "open" })))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/{id}/merge_requests/{iid}/pipelines",
+                get(|headers: HeaderMap| async move {
+                    authorized(&headers)?;
+                    Ok::<_, StatusCode>(Json(json!([{ "id": 99 }])))
+                }),
+            )
+            .route(
+                "/api/v4/projects/{id}/pipelines/{pipeline_id}/jobs",
+                get(
+                    |headers: HeaderMap,
+                     AxumPath((_, pipeline_id)): AxumPath<(String, i64)>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(pipeline_id, 99);
+                        Ok::<_, StatusCode>(Json(json!([
+                            {
+                                "name": "test",
+                                "stage": "test",
+                                "status": "failed",
+                                "allow_failure": false,
+                                "web_url": "https://git.example.com/group/project/-/jobs/5",
+                            },
+                            {
+                                "name": "lint",
+                                "stage": "test",
+                                "status": "failed",
+                                "allow_failure": true,
+                                "web_url": "https://git.example.com/group/project/-/jobs/6",
+                            },
+                        ])))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/{id}/jobs/{job_id}/trace",
+                get(
+                    |headers: HeaderMap, AxumPath((_, job_id)): AxumPath<(String, i64)>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(job_id, 5);
+                        Ok::<_, StatusCode>("error: test failed\n")
+                    },
+                ),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/api/v4")
+    }
+
+    fn hosts(api_url: &str, token: &str) -> Vec<GitHostMapping> {
+        vec![GitHostMapping {
+            host: "git.example.com".to_string(),
+            provider: ProviderKind::GitLab,
+            api_url: Some(api_url.to_string()),
+            token: Some(token.to_string()),
+        }]
+    }
+
+    #[tokio::test]
+    async fn merge_requests_through_mapped_self_hosted_instance() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_gitlab().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url, "secret")).unwrap();
+        assert_eq!(host.provider_kind(), ProviderKind::GitLab);
+        let repo_path = Path::new(".");
+
+        let created = host
+            .create_pr(
+                repo_path,
+                REMOTE,
+                &CreatePrRequest {
+                    title: "Add feature".to_string(),
+                    body: None,
+                    head_branch: "vk/feature".to_string(),
+                    base_branch: "main".to_string(),
+                    draft: Some(true),
+                    head_repo_url: Some(REMOTE.to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.number, 7);
+        assert!(matches!(created.status, MergeStatus::Open));
+
+        assert_eq!(
+            host.pr_head_ref(7).as_deref(),
+            Some("refs/merge-requests/7/head")
+        );
+
+        let status = host.get_pr_status(MR_URL).await.unwrap();
+        assert!(matches!(status.status, MergeStatus::Merged));
+        assert_eq!(status.merge_commit_sha.as_deref(), Some("abc123"));
+
+        assert!(host.is_pr_approved(MR_URL).await.unwrap());
+        host.merge_pr(MR_URL, MergeMethod::Squash).await.unwrap();
+
+        let for_branch = host
+            .list_prs_for_branch(repo_path, REMOTE, "vk/feature")
+            .await
+            .unwrap();
+        assert_eq!(for_branch.len(), 1);
+
+        let open = host.list_open_prs(repo_path, REMOTE).await.unwrap();
+        assert_eq!(open[0].head_branch, "vk/feature");
+        assert_eq!(open[0].base_branch, "main");
+
+        let comments = host.get_pr_comments(repo_path, REMOTE, 7).await.unwrap();
+        assert_eq!(comments.len(), 2);
+        match &comments[1] {
+            UnifiedPrComment::Review {
+                path,
+                line,
+                side,
+                url,
+                ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(12));
+                assert_eq!(side.as_deref(), Some("RIGHT"));
+                assert_eq!(url.as_deref(), Some(&*format!("{MR_URL}#note_2")));
+            }
+            other => panic!("expected a diff note, got {other:?}"),
+        }
+
+        let threads = host.get_review_threads(repo_path, REMOTE, 7).await.unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "open");
+        assert_eq!(threads[0].path, "src/lib.rs");
+        assert_eq!(threads[0].line, Some(12));
+        assert_eq!(threads[0].comments[0].body, "Rename this");
+        host.reply_to_review_thread(repo_path, REMOTE, 7, "open", "Renamed", true)
+            .await
+            .unwrap();
+
+        let checks = host.get_pr_checks(MR_URL).await.unwrap();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].status, PrCheckStatus::Failed);
+        assert!(checks[0].required);
+        assert!(!checks[1].required);
+        let log = host.get_check_log(&checks[0]).await.unwrap();
+        assert_eq!(log.as_deref(), Some("error: test failed\n"));
+    }
+
+    #[tokio::test]
+    async fn rejected_token_is_an_auth_failure() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let api_url = stub_gitlab().await;
+        let host = GitHostService::from_url(REMOTE, &hosts(&api_url, "wrong")).unwrap();
+        let err = host
+            .list_open_prs(Path::new("."), REMOTE)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GitHostError::AuthFailed(_)));
+    }
+}
@@ -2,20 +2,31 @@ mod detection;
 mod types;
 
 pub mod azure;
+pub mod bitbucket;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
+pub mod webhook;
 
 use std::path::Path;
 
 use async_trait::async_trait;
-use db::models::merge::PullRequestInfo;
-use detection::detect_provider_from_url;
+use db::models::merge::{PrCheck, PullRequestInfo};
+use detection::detect_provider;
 use enum_dispatch::enum_dispatch;
 pub use types::{
-    CreatePrRequest, GitHostError, OpenPrInfo, PrComment, PrCommentAuthor, PrReviewComment,
-    ProviderKind, ReviewCommentUser, UnifiedPrComment,
+    CreatePrRequest, GitHostError, GitHostIssue, GitHostMapping, IssueFilter, MergeMethod,
+    OpenPrInfo, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind, ReviewCommentUser,
+    ReviewThread, ReviewThreadComment, UnifiedPrComment,
 };
 
-use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
+use self::{
+    azure::AzureDevOpsProvider,
+    bitbucket::{BitbucketCloudProvider, BitbucketServerProvider},
+    gitea::GiteaProvider,
+    github::GitHubProvider,
+    gitlab::GitLabProvider,
+};
 
 #[async_trait]
 #[enum_dispatch(GitHostService)]
@@ -49,6 +60,101 @@ pub trait GitHostProvider: Send + Sync {
         remote_url: &str,
     ) -> Result<Vec<OpenPrInfo>, GitHostError>;
 
+    /// Unresolved review threads on a pull request. Providers without
+    /// resolvable threads report every review comment as its own thread.
+    async fn get_review_threads(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewThread>, GitHostError> {
+        let comments = self
+            .get_pr_comments(repo_path, remote_url, pr_number)
+            .await?;
+        Ok(comments
+            .into_iter()
+            .filter_map(ReviewThread::from_comment)
+            .collect())
+    }
+
+    /// Reply to a review thread, and mark it resolved when `resolve` is set
+    async fn reply_to_review_thread(
+        &self,
+        _repo_path: &Path,
+        _remote_url: &str,
+        _pr_number: i64,
+        _thread_id: &str,
+        _body: &str,
+        _resolve: bool,
+    ) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// CI checks on a pull request's head commit. When the provider can't
+    /// tell which checks the target branch requires, every check is required.
+    async fn get_pr_checks(&self, _pr_url: &str) -> Result<Vec<PrCheck>, GitHostError> {
+        Ok(Vec::new())
+    }
+
+    /// Log output of a failed check, for checks whose jobs run on the provider
+    async fn get_check_log(&self, _check: &PrCheck) -> Result<Option<String>, GitHostError> {
+        Ok(None)
+    }
+
+    /// Mark a pull request as a draft, or as ready for review
+    async fn set_pr_draft(&self, _pr_url: &str, _draft: bool) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// Whether a pull request has the approvals its target branch requires,
+    /// or at least one when the provider can't tell
+    async fn is_pr_approved(&self, _pr_url: &str) -> Result<bool, GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// Merge an open pull request. Providers that complete merges in the
+    /// background may return before the PR reports as merged.
+    async fn merge_pr(&self, _pr_url: &str, _method: MergeMethod) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// Description of a pull request, as markdown
+    async fn get_pr_body(&self, _pr_url: &str) -> Result<String, GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    async fn update_pr_body(&self, _pr_url: &str, _body: &str) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// Open issues of a repository that match `filter`, pull requests excluded
+    async fn list_issues(
+        &self,
+        _repo_path: &Path,
+        _remote_url: &str,
+        _filter: &IssueFilter,
+    ) -> Result<Vec<GitHostIssue>, GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    async fn get_issue(&self, _issue_url: &str) -> Result<GitHostIssue, GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    async fn comment_on_issue(&self, _issue_url: &str, _body: &str) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    async fn close_issue(&self, _issue_url: &str) -> Result<(), GitHostError> {
+        Err(GitHostError::UnsupportedProvider)
+    }
+
+    /// Ref a pull request's head can be fetched from with plain git, for
+    /// providers whose pull requests are not checked out through a CLI
+    fn pr_head_ref(&self, _pr_number: i64) -> Option<String> {
+        None
+    }
+
     fn provider_kind(&self) -> ProviderKind;
 }
 
@@ -56,13 +162,27 @@ pub trait GitHostProvider: Send + Sync {
 pub enum GitHostService {
     GitHub(GitHubProvider),
     AzureDevOps(AzureDevOpsProvider),
+    GitLab(GitLabProvider),
+    Gitea(GiteaProvider),
+    Bitbucket(BitbucketCloudProvider),
+    BitbucketServer(BitbucketServerProvider),
 }
 
 impl GitHostService {
-    pub fn from_url(url: &str) -> Result<Self, GitHostError> {
-        match detect_provider_from_url(url) {
+    /// Provider for a remote or PR URL. `hosts` maps self-hosted instances
+    /// whose provider can't be told from the URL.
+    pub fn from_url(url: &str, hosts: &[GitHostMapping]) -> Result<Self, GitHostError> {
+        match detect_provider(url, hosts) {
             ProviderKind::GitHub => Ok(Self::GitHub(GitHubProvider::new()?)),
             ProviderKind::AzureDevOps => Ok(Self::AzureDevOps(AzureDevOpsProvider::new()?)),
+            ProviderKind::GitLab => Ok(Self::GitLab(GitLabProvider::new(url, hosts)?)),
+            ProviderKind::Gitea => Ok(Self::Gitea(GiteaProvider::new(url, hosts)?)),
+            ProviderKind::Bitbucket => {
+                Ok(Self::Bitbucket(BitbucketCloudProvider::new(url, hosts)?))
+            }
+            ProviderKind::BitbucketServer => Ok(Self::BitbucketServer(
+                BitbucketServerProvider::new(url, hosts)?,
+            )),
             ProviderKind::Unknown => Err(GitHostError::UnsupportedProvider),
         }
     }
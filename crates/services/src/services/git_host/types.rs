@@ -8,6 +8,10 @@ use ts_rs::TS;
 pub enum ProviderKind {
     GitHub,
     AzureDevOps,
+    GitLab,
+    Gitea,
+    Bitbucket,
+    BitbucketServer,
     Unknown,
 }
 
@@ -16,11 +20,46 @@ impl std::fmt::Display for ProviderKind {
         match self {
             ProviderKind::GitHub => write!(f, "GitHub"),
             ProviderKind::AzureDevOps => write!(f, "Azure DevOps"),
+            ProviderKind::GitLab => write!(f, "GitLab"),
+            ProviderKind::Gitea => write!(f, "Gitea"),
+            ProviderKind::Bitbucket => write!(f, "Bitbucket"),
+            ProviderKind::BitbucketServer => write!(f, "Bitbucket Server"),
             ProviderKind::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// Git host whose provider can't be told from its URL, e.g. a self-hosted
+/// GitLab or Forgejo instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct GitHostMapping {
+    /// Host name as it appears in remote URLs, e.g. `git.example.com`
+    pub host: String,
+    pub provider: ProviderKind,
+    /// API base URL; defaults to the provider's API on `host`
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Access token for REST API based providers. Stored apart from the
+    /// config in [`GitHostCredentials`](crate::services::git_host_credentials::GitHostCredentials);
+    /// still read from older config files so it can be moved there.
+    #[serde(default, skip_serializing)]
+    #[ts(skip)]
+    pub token: Option<String>,
+}
+
+/// How a pull request's commits land on its target branch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeMethod {
+    /// Merge commit
+    Merge,
+    #[default]
+    Squash,
+    /// Rebase onto the target branch, or fast-forward where rebasing isn't
+    /// offered
+    Rebase,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreatePrRequest {
     pub title: String,
@@ -38,12 +77,18 @@ pub enum GitHostError {
     Repository(String),
     #[error("Pull request error: {0}")]
     PullRequest(String),
+    #[error("Issue error: {0}")]
+    Issue(String),
     #[error("Authentication failed: {0}")]
     AuthFailed(String),
     #[error("Insufficient permissions: {0}")]
     InsufficientPermissions(String),
     #[error("Repository not found or no access: {0}")]
     RepoNotFoundOrNoAccess(String),
+    /// The host refused the request as made, e.g. a merge blocked by
+    /// conflicts or branch rules
+    #[error("Request rejected: {0}")]
+    Rejected(String),
     #[error("{provider} CLI is not installed or not available in PATH")]
     CliNotInstalled { provider: ProviderKind },
     #[error("Unsupported git hosting provider")]
@@ -59,6 +104,7 @@ impl GitHostError {
             GitHostError::AuthFailed(_)
                 | GitHostError::InsufficientPermissions(_)
                 | GitHostError::RepoNotFoundOrNoAccess(_)
+                | GitHostError::Rejected(_)
                 | GitHostError::CliNotInstalled { .. }
                 | GitHostError::UnsupportedProvider
         )
@@ -135,6 +181,52 @@ impl UnifiedPrComment {
     }
 }
 
+/// Review thread nobody has resolved yet
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    /// Provider id used to reply to and resolve the thread
+    pub id: String,
+    pub path: String,
+    pub line: Option<i64>,
+    pub diff_hunk: Option<String>,
+    pub url: Option<String>,
+    /// Comments in the thread, oldest first
+    pub comments: Vec<ReviewThreadComment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewThreadComment {
+    pub author: String,
+    pub body: String,
+}
+
+impl ReviewThread {
+    /// Thread made of a single review comment, for providers that have no
+    /// notion of threads
+    pub fn from_comment(comment: UnifiedPrComment) -> Option<Self> {
+        match comment {
+            UnifiedPrComment::Review {
+                id,
+                author,
+                body,
+                url,
+                path,
+                line,
+                diff_hunk,
+                ..
+            } => Some(Self {
+                id: id.to_string(),
+                path,
+                line,
+                diff_hunk,
+                url,
+                comments: vec![ReviewThreadComment { author, body }],
+            }),
+            UnifiedPrComment::General { .. } => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct OpenPrInfo {
     pub number: i64,
@@ -143,3 +235,28 @@ pub struct OpenPrInfo {
     pub head_branch: String,
     pub base_branch: String,
 }
+
+/// Issue on a repository's git host
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitHostIssue {
+    pub number: i64,
+    pub url: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub is_open: bool,
+    pub labels: Vec<String>,
+    /// Logins of the assigned users
+    pub assignees: Vec<String>,
+    pub milestone: Option<String>,
+}
+
+/// Filters for listing the open issues of a repository. Unset filters match
+/// every issue; `labels` match issues that have all of them.
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    pub labels: Vec<String>,
+    /// Login of the assigned user
+    pub assignee: Option<String>,
+    /// Milestone title
+    pub milestone: Option<String>,
+}
@@ -0,0 +1,301 @@
+//! Webhook deliveries from git hosts: verifying that a delivery was signed
+//! with the configured secret, and finding the pull requests an event is about.
+
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Pull request a webhook event is about, by repository web URL and number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookPullRequest {
+    pub repo_url: String,
+    pub number: i64,
+}
+
+impl WebhookPullRequest {
+    /// Whether this is the pull request at `pr_url`
+    pub fn matches(&self, pr_url: &str, pr_number: i64) -> bool {
+        let repo_url = normalize_url(&self.repo_url);
+        self.number == pr_number
+            && normalize_url(pr_url)
+                .strip_prefix(&repo_url)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// Webhook event that concerns pull requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEvent {
+    pub name: String,
+    pub pull_requests: Vec<WebhookPullRequest>,
+}
+
+/// Whether a delivery was signed with `secret`. GitHub, Bitbucket and recent
+/// Gitea versions send an HMAC-SHA256 as `sha256=<hex>`, older Gitea and
+/// Forgejo send the bare hex digest, and GitLab sends the secret itself.
+pub fn verify_webhook_signature(secret: &str, headers: &HeaderMap, payload: &[u8]) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(signature) = header("X-Hub-Signature-256").or_else(|| header("X-Hub-Signature")) {
+        return signature
+            .strip_prefix("sha256=")
+            .is_some_and(|hex_signature| hmac_matches(secret, hex_signature, payload));
+    }
+    if let Some(signature) = header("X-Gitea-Signature").or_else(|| header("X-Forgejo-Signature")) {
+        return hmac_matches(secret, signature, payload);
+    }
+    if let Some(token) = header("X-Gitlab-Token") {
+        return token.as_bytes().ct_eq(secret.as_bytes()).into();
+    }
+    false
+}
+
+fn hmac_matches(secret: &str, hex_signature: &str, payload: &[u8]) -> bool {
+    let Ok(expected_signature) = hex::decode(hex_signature.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(payload);
+    let computed_signature = mac.finalize().into_bytes();
+    computed_signature[..].ct_eq(&expected_signature).into()
+}
+
+/// The event a delivery carries, when it is a pull request, review, comment
+/// or CI event on pull requests. Other events, such as pings, give `None`.
+pub fn parse_webhook_event(headers: &HeaderMap, payload: &Value) -> Option<WebhookEvent> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    let (name, pull_requests) =
+        if let Some(name) = header("X-GitHub-Event").or_else(|| header("X-Gitea-Event")) {
+            (name, github_pull_requests(name, payload))
+        } else if let Some(name) = header("X-Gitlab-Event") {
+            (name, gitlab_pull_requests(name, payload))
+        } else if let Some(name) = header("X-Event-Key") {
+            (name, bitbucket_pull_requests(name, payload))
+        } else {
+            return None;
+        };
+
+    (!pull_requests.is_empty()).then(|| WebhookEvent {
+        name: name.to_string(),
+        pull_requests,
+    })
+}
+
+/// GitHub events, which Gitea and Forgejo mirror for pull requests
+fn github_pull_requests(event: &str, payload: &Value) -> Vec<WebhookPullRequest> {
+    let Some(repo_url) = string_at(payload, "/repository/html_url") else {
+        return Vec::new();
+    };
+    let numbers: Vec<i64> = match event {
+        // pull_request, pull_request_review, pull_request_review_comment,
+        // pull_request_review_thread and Gitea's pull_request_review_*
+        e if e.starts_with("pull_request") => payload
+            .pointer("/pull_request/number")
+            .and_then(Value::as_i64)
+            .into_iter()
+            .collect(),
+        "issue_comment" => payload
+            .pointer("/issue/pull_request")
+            .filter(|pr| !pr.is_null())
+            .and_then(|_| payload.pointer("/issue/number"))
+            .and_then(Value::as_i64)
+            .into_iter()
+            .collect(),
+        "check_suite" | "check_run" => payload
+            .pointer(&format!("/{event}/pull_requests"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|pr| pr.get("number").and_then(Value::as_i64))
+            .collect(),
+        _ => Vec::new(),
+    };
+    with_repo(repo_url, numbers)
+}
+
+fn gitlab_pull_requests(event: &str, payload: &Value) -> Vec<WebhookPullRequest> {
+    let Some(repo_url) = string_at(payload, "/project/web_url") else {
+        return Vec::new();
+    };
+    let iid = match event {
+        "Merge Request Hook" => payload.pointer("/object_attributes/iid"),
+        "Note Hook" | "Pipeline Hook" => payload.pointer("/merge_request/iid"),
+        _ => None,
+    };
+    with_repo(repo_url, iid.and_then(Value::as_i64).into_iter().collect())
+}
+
+fn bitbucket_pull_requests(event: &str, payload: &Value) -> Vec<WebhookPullRequest> {
+    let (repo_url, id) = if event.starts_with("pullrequest:") {
+        // Bitbucket Cloud
+        (
+            string_at(payload, "/repository/links/html/href"),
+            payload.pointer("/pullrequest/id"),
+        )
+    } else if event.starts_with("pr:") {
+        // Bitbucket Server links the repository's browse page
+        (
+            string_at(payload, "/pullRequest/toRef/repository/links/self/0/href")
+                .map(|url| url.trim_end_matches("/browse")),
+            payload.pointer("/pullRequest/id"),
+        )
+    } else {
+        (None, None)
+    };
+    match repo_url {
+        Some(repo_url) => with_repo(repo_url, id.and_then(Value::as_i64).into_iter().collect()),
+        None => Vec::new(),
+    }
+}
+
+fn with_repo(repo_url: &str, numbers: Vec<i64>) -> Vec<WebhookPullRequest> {
+    numbers
+        .into_iter()
+        .map(|number| WebhookPullRequest {
+            repo_url: repo_url.to_string(),
+            number,
+        })
+        .collect()
+}
+
+fn string_at<'a>(payload: &'a Value, pointer: &str) -> Option<&'a str> {
+    payload
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+fn normalize_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn sign(secret: &str, payload: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn signatures_are_verified_per_host() {
+        let payload = br#"{"action":"opened"}"#;
+        let signature = sign("s3cret", payload);
+
+        let github = headers(&[("X-Hub-Signature-256", &format!("sha256={signature}"))]);
+        assert!(verify_webhook_signature("s3cret", &github, payload));
+        assert!(!verify_webhook_signature("other", &github, payload));
+        assert!(!verify_webhook_signature("s3cret", &github, b"tampered"));
+
+        let gitea = headers(&[("X-Gitea-Signature", &signature)]);
+        assert!(verify_webhook_signature("s3cret", &gitea, payload));
+
+        let gitlab = headers(&[("X-Gitlab-Token", "s3cret")]);
+        assert!(verify_webhook_signature("s3cret", &gitlab, payload));
+        assert!(!verify_webhook_signature("s3cre", &gitlab, payload));
+
+        assert!(!verify_webhook_signature(
+            "s3cret",
+            &HeaderMap::new(),
+            payload
+        ));
+    }
+
+    #[test]
+    fn github_events_name_their_pull_requests() {
+        let review = json!({
+            "action": "submitted",
+            "pull_request": { "number": 12 },
+            "repository": { "html_url": "https://github.com/acme/app" },
+        });
+        let event = parse_webhook_event(
+            &headers(&[("X-GitHub-Event", "pull_request_review")]),
+            &review,
+        )
+        .unwrap();
+        assert_eq!(event.name, "pull_request_review");
+        assert_eq!(
+            event.pull_requests,
+            vec![WebhookPullRequest {
+                repo_url: "https://github.com/acme/app".to_string(),
+                number: 12,
+            }]
+        );
+        assert!(event.pull_requests[0].matches("https://github.com/Acme/app/pull/12", 12));
+        assert!(!event.pull_requests[0].matches("https://github.com/acme/app-2/pull/12", 12));
+
+        let check_suite = json!({
+            "check_suite": { "pull_requests": [{ "number": 3 }, { "number": 4 }] },
+            "repository": { "html_url": "https://github.com/acme/app" },
+        });
+        let event =
+            parse_webhook_event(&headers(&[("X-GitHub-Event", "check_suite")]), &check_suite)
+                .unwrap();
+        assert_eq!(event.pull_requests.len(), 2);
+
+        let issue_comment = json!({
+            "issue": { "number": 7, "pull_request": null },
+            "repository": { "html_url": "https://github.com/acme/app" },
+        });
+        assert!(
+            parse_webhook_event(
+                &headers(&[("X-GitHub-Event", "issue_comment")]),
+                &issue_comment
+            )
+            .is_none()
+        );
+        assert!(parse_webhook_event(&headers(&[("X-GitHub-Event", "ping")]), &json!({})).is_none());
+    }
+
+    #[test]
+    fn gitlab_and_bitbucket_events_name_their_pull_requests() {
+        let note = json!({
+            "project": { "web_url": "https://gitlab.example.com/group/app" },
+            "merge_request": { "iid": 5 },
+        });
+        let event = parse_webhook_event(&headers(&[("X-Gitlab-Event", "Note Hook")]), &note)
+            .unwrap()
+            .pull_requests;
+        assert!(event[0].matches("https://gitlab.example.com/group/app/-/merge_requests/5", 5));
+
+        let server = json!({
+            "pullRequest": {
+                "id": 9,
+                "toRef": { "repository": { "links": { "self": [
+                    { "href": "https://bitbucket.example.com/projects/P/repos/app/browse" }
+                ] } } },
+            },
+        });
+        let event = parse_webhook_event(
+            &headers(&[("X-Event-Key", "pr:reviewer:approved")]),
+            &server,
+        )
+        .unwrap()
+        .pull_requests;
+        assert!(event[0].matches(
+            "https://bitbucket.example.com/projects/P/repos/app/pull-requests/9",
+            9
+        ));
+    }
+}
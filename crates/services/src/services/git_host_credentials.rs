@@ -0,0 +1,95 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use tokio::sync::RwLock;
+
+use super::{credential_file, git_host::GitHostMapping};
+
+/// Service for managing the API tokens of self-hosted git hosts, kept out of
+/// the config file. Tokens are keyed by lowercase host name, loaded into
+/// memory on startup and persisted to disk on every change.
+pub struct GitHostCredentials {
+    path: PathBuf,
+    inner: RwLock<BTreeMap<String, String>>,
+}
+
+impl GitHostCredentials {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            inner: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    pub async fn load(&self) -> std::io::Result<()> {
+        let tokens = credential_file::load(&self.path)?.unwrap_or_default();
+        *self.inner.write().await = tokens;
+        Ok(())
+    }
+
+    pub async fn get(&self, host: &str) -> Option<String> {
+        self.inner.read().await.get(&normalize(host)).cloned()
+    }
+
+    /// Hosts that have a stored token
+    pub async fn hosts(&self) -> Vec<String> {
+        self.inner.read().await.keys().cloned().collect()
+    }
+
+    pub async fn set(&self, host: &str, token: &str) -> std::io::Result<()> {
+        let mut inner = self.inner.write().await;
+        let mut tokens = inner.clone();
+        tokens.insert(normalize(host), token.to_string());
+        credential_file::save(&self.path, &tokens)?;
+        *inner = tokens;
+        Ok(())
+    }
+
+    pub async fn remove(&self, host: &str) -> std::io::Result<()> {
+        let mut inner = self.inner.write().await;
+        let mut tokens = inner.clone();
+        if tokens.remove(&normalize(host)).is_none() {
+            return Ok(());
+        }
+        credential_file::save(&self.path, &tokens)?;
+        *inner = tokens;
+        Ok(())
+    }
+
+    /// Move tokens read from an older config file into the store, keeping
+    /// any token already stored for the same host
+    pub async fn import_legacy_tokens(&self, hosts: &mut [GitHostMapping]) -> std::io::Result<()> {
+        let mut inner = self.inner.write().await;
+        let mut tokens = inner.clone();
+        let mut imported = false;
+        for mapping in hosts.iter_mut() {
+            if let Some(token) = mapping.token.take() {
+                tokens.entry(normalize(&mapping.host)).or_insert_with(|| {
+                    imported = true;
+                    token
+                });
+            }
+        }
+        if !imported {
+            return Ok(());
+        }
+        credential_file::save(&self.path, &tokens)?;
+        *inner = tokens;
+        Ok(())
+    }
+
+    /// The configured host mappings with their stored tokens filled in
+    pub async fn with_tokens(&self, hosts: &[GitHostMapping]) -> Vec<GitHostMapping> {
+        let tokens = self.inner.read().await;
+        hosts
+            .iter()
+            .map(|mapping| GitHostMapping {
+                token: tokens.get(&normalize(&mapping.host)).cloned(),
+                ..mapping.clone()
+            })
+            .collect()
+    }
+}
+
+fn normalize(host: &str) -> String {
+    host.trim().to_ascii_lowercase()
+}
@@ -0,0 +1,199 @@
+//! Keeping tasks imported from git host issues in step with their issues.
+//! Issues of tasks that are done get a comment and are closed, depending on
+//! [`IssueDoneAction`]; tasks whose issue was closed on the git host are
+//! cancelled.
+
+use std::{sync::Arc, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        merge::{Merge, MergeStatus},
+        task::{Task, TaskStatus},
+        task_issue::{TaskIssue, TaskIssueState},
+        workspace::{Workspace, WorkspaceError},
+    },
+};
+use sqlx::error::Error as SqlxError;
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{debug, error, info};
+
+use crate::services::{
+    config::{Config, IssueDoneAction},
+    git_host::{GitHostError, GitHostMapping, GitHostProvider, GitHostService},
+    git_host_credentials::GitHostCredentials,
+};
+
+#[derive(Debug, Error)]
+enum IssueSyncError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+}
+
+/// Service that syncs the status of imported issues and their tasks
+pub struct IssueSyncService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    git_host_credentials: Arc<GitHostCredentials>,
+    poll_interval: Duration,
+}
+
+impl IssueSyncService {
+    pub async fn spawn(
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        git_host_credentials: Arc<GitHostCredentials>,
+    ) {
+        let service = Self {
+            db,
+            config,
+            git_host_credentials,
+            poll_interval: Duration::from_secs(5 * 60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        });
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting issue sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sync_open_issues().await {
+                error!("Error syncing imported issues: {}", e);
+            }
+        }
+    }
+
+    async fn sync_open_issues(&self) -> Result<(), IssueSyncError> {
+        let links = TaskIssue::find_open(&self.db.pool).await?;
+        if links.is_empty() {
+            debug!("No open imported issues to sync");
+            return Ok(());
+        }
+
+        let (git_hosts, done_action) = {
+            let config = self.config.read().await;
+            (config.git_hosts.clone(), config.issue_done_action)
+        };
+        let git_hosts = self.git_host_credentials.with_tokens(&git_hosts).await;
+
+        debug!("Syncing {} imported issues", links.len());
+        for link in links {
+            if let Err(e) = self.sync_issue(&link, &git_hosts, done_action).await {
+                error!(
+                    "Error syncing issue {} with task {}: {}",
+                    link.issue_url, link.task_id, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn sync_issue(
+        &self,
+        link: &TaskIssue,
+        git_hosts: &[GitHostMapping],
+        done_action: IssueDoneAction,
+    ) -> Result<(), IssueSyncError> {
+        let pool = &self.db.pool;
+        let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+            return Ok(());
+        };
+        let git_host = GitHostService::from_url(&link.issue_url, git_hosts)?;
+
+        if task.status == TaskStatus::Done {
+            // Comment before closing, so a failed comment is retried on the
+            // next sync instead of being skipped for an already closed issue
+            let (comment, close) = done_steps(done_action, link.done_commented);
+            if comment {
+                let comment = self.done_comment(&task).await?;
+                git_host.comment_on_issue(&link.issue_url, &comment).await?;
+                TaskIssue::set_done_commented(pool, link.id).await?;
+            }
+            if close {
+                git_host.close_issue(&link.issue_url).await?;
+            }
+            info!(
+                "Task {} is done, synced issue #{} ({:?})",
+                task.id, link.issue_number, done_action
+            );
+            TaskIssue::set_state(pool, link.id, TaskIssueState::Closed).await?;
+            return Ok(());
+        }
+
+        let issue = git_host.get_issue(&link.issue_url).await?;
+        if !issue.is_open {
+            info!(
+                "Issue #{} was closed on the git host, cancelling task {}",
+                link.issue_number, task.id
+            );
+            if task.status != TaskStatus::Cancelled {
+                Task::update_status(pool, task.id, TaskStatus::Cancelled).await?;
+            }
+            TaskIssue::set_state(pool, link.id, TaskIssueState::Closed).await?;
+        }
+        Ok(())
+    }
+
+    /// Comment left on the issue of a done task, linking the merged PRs
+    async fn done_comment(&self, task: &Task) -> Result<String, IssueSyncError> {
+        let pool = &self.db.pool;
+        let mut pr_urls = Vec::new();
+        for workspace in Workspace::fetch_all(pool, Some(task.id)).await? {
+            for merge in Merge::find_by_workspace_id(pool, workspace.id).await? {
+                if let Merge::Pr(pr) = merge
+                    && matches!(pr.pr_info.status, MergeStatus::Merged)
+                {
+                    pr_urls.push(pr.pr_info.url);
+                }
+            }
+        }
+
+        let mut comment = format!("Task \"{}\" is done in Vibe Kanban.", task.title);
+        match pr_urls.as_slice() {
+            [] => {}
+            [url] => comment.push_str(&format!(" Merged in {url}.")),
+            urls => {
+                comment.push_str(" Merged in:\n");
+                for url in urls {
+                    comment.push_str(&format!("\n- {url}"));
+                }
+            }
+        }
+        Ok(comment)
+    }
+}
+
+/// Whether the issue of a done task still needs its comment, and whether it
+/// is closed. A close that failed after the comment was posted is retried
+/// without commenting again.
+fn done_steps(done_action: IssueDoneAction, commented: bool) -> (bool, bool) {
+    let comment = done_action != IssueDoneAction::Nothing && !commented;
+    let close = done_action == IssueDoneAction::Close;
+    (comment, close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_close_is_retried_without_a_second_comment() {
+        assert_eq!(done_steps(IssueDoneAction::Close, false), (true, true));
+        // The comment went through but closing the issue failed
+        assert_eq!(done_steps(IssueDoneAction::Close, true), (false, true));
+        assert_eq!(done_steps(IssueDoneAction::Comment, true), (false, false));
+        assert_eq!(done_steps(IssueDoneAction::Nothing, false), (false, false));
+    }
+}
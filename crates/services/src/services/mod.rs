@@ -1,21 +1,33 @@
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
+pub mod branch_archive;
+pub mod branch_name;
+pub mod commit_identity;
+pub mod commit_message;
 pub mod config;
 pub mod container;
+pub mod credential_file;
 pub mod diff_stream;
+pub mod disk_usage;
 pub mod events;
 pub mod file_ranker;
 pub mod file_search;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git_host;
+pub mod git_host_credentials;
 pub mod image;
+pub mod issue_sync;
 pub mod migration;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod pr_description;
 pub mod pr_monitor;
+pub mod pr_review;
+pub mod pr_webhook_secret;
 pub mod project;
+pub mod quality_gates;
 #[cfg(feature = "qa-mode")]
 pub mod qa_repos;
 pub mod queued_message;
@@ -4,6 +4,8 @@ use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use super::credential_file;
+
 /// OAuth credentials containing the JWT tokens issued by the remote OAuth service.
 /// The `access_token` is short-lived; `refresh_token` allows minting a new pair.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,7 +55,7 @@ impl OAuthCredentials {
     }
 
     pub async fn load(&self) -> std::io::Result<()> {
-        let creds = self.load_from_file().await?.map(Credentials::from);
+        let creds = credential_file::load::<StoredCredentials>(&self.path)?.map(Credentials::from);
         *self.inner.write().await = creds;
         Ok(())
     }
@@ -62,7 +64,7 @@ impl OAuthCredentials {
         let stored = StoredCredentials {
             refresh_token: creds.refresh_token.clone(),
         };
-        self.save_to_file(&stored).await?;
+        credential_file::save(&self.path, &stored)?;
         *self.inner.write().await = Some(creds.clone());
         Ok(())
     }
@@ -76,45 +78,4 @@ impl OAuthCredentials {
     pub async fn get(&self) -> Option<Credentials> {
         self.inner.read().await.clone()
     }
-
-    async fn load_from_file(&self) -> std::io::Result<Option<StoredCredentials>> {
-        if !self.path.exists() {
-            return Ok(None);
-        }
-
-        let bytes = std::fs::read(&self.path)?;
-        match serde_json::from_slice::<StoredCredentials>(&bytes) {
-            Ok(creds) => Ok(Some(creds)),
-            Err(e) => {
-                tracing::warn!(?e, "failed to parse credentials file, renaming to .bad");
-                let bad = self.path.with_extension("bad");
-                let _ = std::fs::rename(&self.path, bad);
-                Ok(None)
-            }
-        }
-    }
-
-    async fn save_to_file(&self, creds: &StoredCredentials) -> std::io::Result<()> {
-        let tmp = self.path.with_extension("tmp");
-
-        let file = {
-            let mut opts = std::fs::OpenOptions::new();
-            opts.create(true).truncate(true).write(true);
-
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::OpenOptionsExt;
-                opts.mode(0o600);
-            }
-
-            opts.open(&tmp)?
-        };
-
-        serde_json::to_writer_pretty(&file, creds)?;
-        file.sync_all()?;
-        drop(file);
-
-        std::fs::rename(&tmp, &self.path)?;
-        Ok(())
-    }
 }
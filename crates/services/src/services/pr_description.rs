@@ -0,0 +1,202 @@
+//! Regenerating a workspace PR's description after follow-ups are pushed.
+//! The agent rewrites the description with the PR description prompt; a
+//! section the user marked to keep is restored if the agent dropped it.
+
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    merge::{Merge, PrMerge},
+    workspace::Workspace,
+};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::services::{
+    config::{Config, DEFAULT_PR_DESCRIPTION_PROMPT},
+    container::{ContainerError, ContainerService},
+    git_host::{GitHostError, GitHostProvider, GitHostService},
+};
+
+/// Start of the description section the user maintains
+pub const USER_SECTION_START: &str = "<!-- vibe-kanban:keep -->";
+/// End of the description section the user maintains
+pub const USER_SECTION_END: &str = "<!-- /vibe-kanban:keep -->";
+
+#[derive(Debug, Error)]
+pub enum PrDescriptionError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+#[derive(Debug)]
+pub enum DescriptionRefresh {
+    Started,
+    /// The previous refresh of the description is still running
+    AlreadyRunning,
+    /// An agent or script is still running in the workspace
+    AgentBusy,
+    /// The workspace has no coding agent session to follow up on
+    NoCodingAgent,
+}
+
+/// Prompt template for description refreshes, when they are enabled
+pub fn refresh_prompt_template(config: &Config) -> Option<String> {
+    config.pr_lifecycle.refresh_description.then(|| {
+        config
+            .pr_auto_description_prompt
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PR_DESCRIPTION_PROMPT.to_string())
+    })
+}
+
+/// Ask the workspace's agent to rewrite the PR description now that new
+/// commits were pushed. The user-maintained section is recorded first, so it
+/// can be restored once the run finishes.
+pub async fn start_description_refresh<C: ContainerService + Sync>(
+    container: &C,
+    git_host: &GitHostService,
+    workspace: &Workspace,
+    pr_merge: &PrMerge,
+    prompt_template: &str,
+) -> Result<DescriptionRefresh, PrDescriptionError> {
+    let pool = &container.db().pool;
+    if let Some(process_id) = pr_merge.description_process_id {
+        let running = ExecutionProcess::find_by_id(pool, process_id)
+            .await?
+            .is_some_and(|p| p.status == ExecutionProcessStatus::Running);
+        if running {
+            return Ok(DescriptionRefresh::AlreadyRunning);
+        }
+        // Restore the section the previous run may have dropped before
+        // taking a new snapshot of it
+        complete_description_refresh(container, git_host, pr_merge).await?;
+    }
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(DescriptionRefresh::AgentBusy);
+    }
+
+    let body = git_host.get_pr_body(&pr_merge.pr_info.url).await?;
+    let user_section = user_section(&body);
+    let prompt = refresh_prompt(prompt_template, pr_merge, user_section.is_some());
+    let Some(process) = container.start_agent_follow_up(workspace, prompt).await? else {
+        return Ok(DescriptionRefresh::NoCodingAgent);
+    };
+    Merge::start_description_refresh(pool, pr_merge.id, process.id, user_section).await?;
+    info!(
+        "Asked the agent in workspace {} to refresh the description of PR #{}",
+        workspace.id, pr_merge.pr_info.number
+    );
+    Ok(DescriptionRefresh::Started)
+}
+
+/// Once a description refresh has finished, put back the user-maintained
+/// section if the agent dropped or changed it. Runs still in progress are left
+/// for a later call.
+pub async fn complete_description_refresh<C: ContainerService + Sync>(
+    container: &C,
+    git_host: &GitHostService,
+    pr_merge: &PrMerge,
+) -> Result<(), PrDescriptionError> {
+    let Some(process_id) = pr_merge.description_process_id else {
+        return Ok(());
+    };
+    let pool = &container.db().pool;
+    match ExecutionProcess::find_by_id(pool, process_id).await? {
+        Some(process) if process.status == ExecutionProcessStatus::Running => return Ok(()),
+        Some(process) if process.status != ExecutionProcessStatus::Completed => {
+            warn!(
+                "Description refresh {} of PR #{} did not complete",
+                process.id, pr_merge.pr_info.number
+            );
+        }
+        _ => {}
+    }
+
+    if let Some(section) = &pr_merge.description_user_section {
+        let body = git_host.get_pr_body(&pr_merge.pr_info.url).await?;
+        if let Some(restored) = restore_user_section(&body, section) {
+            debug!(
+                "Restoring the user section of the description of PR #{}",
+                pr_merge.pr_info.number
+            );
+            git_host
+                .update_pr_body(&pr_merge.pr_info.url, &restored)
+                .await?;
+        }
+    }
+    Merge::clear_description_refresh(pool, pr_merge.id).await?;
+    Ok(())
+}
+
+fn refresh_prompt(template: &str, pr_merge: &PrMerge, keep_user_section: bool) -> String {
+    let mut prompt = format!(
+        "New commits were pushed to the PR since its description was written, so the title and \
+         description need to be brought up to date with the branch as it is now.\n\n{}",
+        template
+            .replace("{pr_number}", &pr_merge.pr_info.number.to_string())
+            .replace("{pr_url}", &pr_merge.pr_info.url)
+    );
+    if keep_user_section {
+        prompt.push_str(&format!(
+            "\n\nThe description has a section between `{USER_SECTION_START}` and \
+             `{USER_SECTION_END}` that the user maintains. Keep that section, markers included, \
+             exactly as it is."
+        ));
+    }
+    prompt
+}
+
+/// The user-maintained section of a description, markers included
+pub fn user_section(body: &str) -> Option<&str> {
+    let start = body.find(USER_SECTION_START)?;
+    let end = body[start..].find(USER_SECTION_END)? + start + USER_SECTION_END.len();
+    Some(&body[start..end])
+}
+
+/// The description with `section` put back in place of its current
+/// user-maintained section, or appended when it has none. `None` when the
+/// description already has the section as it was.
+pub fn restore_user_section(body: &str, section: &str) -> Option<String> {
+    match user_section(body) {
+        Some(current) if current == section => None,
+        Some(current) => Some(body.replacen(current, section, 1)),
+        None if body.trim().is_empty() => Some(section.to_string()),
+        None => Some(format!("{}\n\n{section}", body.trim_end())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_section_is_restored() {
+        let section = format!("{USER_SECTION_START}\nDeploy after 5pm\n{USER_SECTION_END}");
+        let body = format!("## Summary\nAdds caching\n\n{section}\n");
+        assert_eq!(user_section(&body), Some(section.as_str()));
+        assert_eq!(restore_user_section(&body, &section), None);
+
+        let rewritten = "## Summary\nAdds caching and metrics";
+        assert_eq!(
+            restore_user_section(rewritten, &section).unwrap(),
+            format!("{rewritten}\n\n{section}")
+        );
+
+        let edited =
+            format!("## Summary\n{USER_SECTION_START}\nDeploy any time\n{USER_SECTION_END}\nEnd");
+        assert_eq!(
+            restore_user_section(&edited, &section).unwrap(),
+            format!("## Summary\n{section}\nEnd")
+        );
+
+        assert_eq!(
+            user_section(&format!("{USER_SECTION_START} unterminated")),
+            None
+        );
+    }
+}
@@ -1,25 +1,40 @@
-use std::time::Duration;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use chrono::Utc;
 use db::{
     DBService,
     models::{
-        merge::{Merge, MergeStatus, PrMerge},
+        execution_process::ExecutionProcess,
+        merge::{Merge, MergeStatus, PrCheck, PrCheckStatus, PrMerge, PullRequestInfo},
+        quality_gate::{QualityGate, QualityGateRun},
+        repo::Repo,
         task::{Task, TaskStatus},
         workspace::{Workspace, WorkspaceError},
     },
 };
-use serde_json::json;
+use git::GitServiceError;
+use serde_json::{Value, json};
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
-use tokio::time::interval;
-use tracing::{debug, error, info};
+use tokio::{
+    sync::{RwLock, mpsc},
+    time::interval,
+};
+use tracing::{debug, error, info, warn};
 use utils::api::pull_requests::{PullRequestStatus, UpsertPullRequestRequest};
 
 use crate::services::{
     analytics::AnalyticsContext,
-    container::ContainerService,
-    git_host::{self, GitHostError, GitHostProvider},
+    config::Config,
+    container::{ContainerError, ContainerService},
+    git_host::{self, GitHostError, GitHostProvider, GitHostService, webhook::WebhookPullRequest},
+    git_host_credentials::GitHostCredentials,
+    pr_description, pr_review,
+    pr_webhook_secret::PrWebhookSecret,
+    quality_gates,
     remote_client::RemoteClient,
     remote_sync,
 };
@@ -32,11 +47,39 @@ enum PrMonitorError {
     WorkspaceError(#[from] WorkspaceError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+}
+
+/// Maximum job log included in a CI fix follow-up, per failed check
+const MAX_LOG_CHARS: usize = 6_000;
+
+/// Open PRs are only polled this often while webhooks deliver their events
+const WEBHOOK_FALLBACK_POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Handle for having the PR monitor check PRs as soon as their git host
+/// reports an event on them
+#[derive(Clone)]
+pub struct PrMonitorHandle {
+    events: mpsc::UnboundedSender<Vec<WebhookPullRequest>>,
+}
+
+impl PrMonitorHandle {
+    pub fn check_pull_requests(&self, pull_requests: Vec<WebhookPullRequest>) {
+        if self.events.send(pull_requests).is_err() {
+            warn!("PR monitor is not running, webhook event dropped");
+        }
+    }
 }
 
 /// Service to monitor PRs and update task status when they are merged
 pub struct PrMonitorService<C: ContainerService> {
     db: DBService,
+    config: Arc<RwLock<Config>>,
+    git_host_credentials: Arc<GitHostCredentials>,
+    pr_webhook_secret: Arc<PrWebhookSecret>,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     container: C,
@@ -46,34 +89,65 @@ pub struct PrMonitorService<C: ContainerService> {
 impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
     pub async fn spawn(
         db: DBService,
+        config: Arc<RwLock<Config>>,
+        git_host_credentials: Arc<GitHostCredentials>,
+        pr_webhook_secret: Arc<PrWebhookSecret>,
         analytics: Option<AnalyticsContext>,
         container: C,
         remote_client: Option<RemoteClient>,
-    ) -> tokio::task::JoinHandle<()> {
+    ) -> PrMonitorHandle {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
         let service = Self {
             db,
+            config,
+            git_host_credentials,
+            pr_webhook_secret,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             container,
             remote_client,
         };
         tokio::spawn(async move {
-            service.start().await;
-        })
+            service.start(events_rx).await;
+        });
+        PrMonitorHandle { events: events_tx }
     }
 
-    async fn start(&self) {
+    async fn start(&self, mut events: mpsc::UnboundedReceiver<Vec<WebhookPullRequest>>) {
         info!(
             "Starting PR monitoring service with interval {:?}",
             self.poll_interval
         );
 
         let mut interval = interval(self.poll_interval);
+        let mut last_poll: Option<Instant> = None;
 
         loop {
-            interval.tick().await;
-            if let Err(e) = self.check_all_open_prs().await {
-                error!("Error checking open PRs: {}", e);
+            tokio::select! {
+                _ = interval.tick() => {
+                    // Polling is only a fallback while webhooks are enabled
+                    let pr_webhooks = self.config.read().await.pr_webhooks.clone();
+                    let webhooks = self.pr_webhook_secret.active(&pr_webhooks).await.is_some();
+                    if webhooks
+                        && last_poll.is_some_and(|t| t.elapsed() < WEBHOOK_FALLBACK_POLL_INTERVAL)
+                    {
+                        continue;
+                    }
+                    last_poll = Some(Instant::now());
+                    if let Err(e) = self.check_all_open_prs().await {
+                        error!("Error checking open PRs: {}", e);
+                    }
+                }
+                Some(mut pull_requests) = events.recv() => {
+                    // Hosts send bursts of events for one change, such as a
+                    // check suite per CI app
+                    while let Ok(more) = events.try_recv() {
+                        pull_requests.extend(more);
+                    }
+                    if let Err(e) = self.check_webhook_prs(&pull_requests).await {
+                        error!("Error checking PRs from webhook event: {}", e);
+                    }
+                }
             }
         }
     }
@@ -100,9 +174,36 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
         Ok(())
     }
 
+    /// Check the open PRs a webhook event was about
+    async fn check_webhook_prs(
+        &self,
+        pull_requests: &[WebhookPullRequest],
+    ) -> Result<(), PrMonitorError> {
+        let open_prs = Merge::get_open_prs(&self.db.pool).await?;
+        for pr_merge in open_prs.iter().filter(|m| {
+            pull_requests
+                .iter()
+                .any(|pr| pr.matches(&m.pr_info.url, m.pr_info.number))
+        }) {
+            debug!(
+                "Checking PR #{} after a webhook event",
+                pr_merge.pr_info.number
+            );
+            if let Err(e) = self.check_pr_status(pr_merge).await {
+                error!(
+                    "Error checking PR #{} for workspace {}: {}",
+                    pr_merge.pr_info.number, pr_merge.workspace_id, e
+                );
+            }
+        }
+        Ok(())
+    }
+
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        let git_host = git_host::GitHostService::from_url(&pr_merge.pr_info.url)?;
+        let git_hosts = self.config.read().await.git_hosts.clone();
+        let git_hosts = self.git_host_credentials.with_tokens(&git_hosts).await;
+        let git_host = git_host::GitHostService::from_url(&pr_merge.pr_info.url, &git_hosts)?;
         let pr_status = git_host.get_pr_status(&pr_merge.pr_info.url).await?;
 
         debug!(
@@ -112,54 +213,403 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
 
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from git host
-            Merge::update_status(
-                &self.db.pool,
-                pr_merge.id,
-                pr_status.status.clone(),
-                pr_status.merge_commit_sha.clone(),
-            )
-            .await?;
+            self.update_closed_pr(pr_merge, pr_status).await?;
+        } else {
+            if pr_status.is_draft != pr_merge.pr_info.is_draft {
+                Merge::set_draft(&self.db.pool, pr_merge.id, pr_status.is_draft).await?;
+            }
+            let pushed =
+                pr_review::complete_review_follow_ups(&self.container, &git_host, pr_merge)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!(
+                            "Failed to complete review follow-ups on PR #{}: {}",
+                            pr_merge.pr_info.number, e
+                        );
+                        false
+                    });
+            self.update_description(&git_host, pr_merge, pushed).await?;
+            if pr_status.is_draft
+                && let Err(e) = self.mark_ready_if_gates_pass(&git_host, pr_merge).await
+            {
+                error!(
+                    "Failed to mark PR #{} ready for review: {}",
+                    pr_merge.pr_info.number, e
+                );
+            }
+            let checks = self.check_pr_checks(&git_host, pr_merge).await?;
+            if pr_merge.auto_merge && !pr_status.is_draft {
+                self.auto_merge_if_ready(&git_host, pr_merge, &checks)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store the final status of a PR that was merged or closed, and finish
+    /// the task of merged PRs
+    async fn update_closed_pr(
+        &self,
+        pr_merge: &PrMerge,
+        pr_status: PullRequestInfo,
+    ) -> Result<(), PrMonitorError> {
+        // Update merge status with the latest information from git host
+        Merge::update_status(
+            &self.db.pool,
+            pr_merge.id,
+            pr_status.status.clone(),
+            pr_status.merge_commit_sha.clone(),
+        )
+        .await?;
+
+        self.sync_pr_to_remote(pr_merge, &pr_status.status, pr_status.merge_commit_sha)
+            .await;
 
-            self.sync_pr_to_remote(pr_merge, &pr_status.status, pr_status.merge_commit_sha)
-                .await;
+        // If the PR was merged, update the task status to done
+        if matches!(&pr_status.status, MergeStatus::Merged)
+            && let Some(workspace) =
+                Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
+        {
+            info!(
+                "PR #{} was merged, updating task {} to done and archiving workspace",
+                pr_merge.pr_info.number, workspace.task_id
+            );
+            Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
+            if !workspace.pinned
+                && let Err(e) = self.container.archive_workspace(workspace.id).await
+            {
+                error!("Failed to archive workspace {}: {}", workspace.id, e);
+            }
 
-            // If the PR was merged, update the task status to done
-            if matches!(&pr_status.status, MergeStatus::Merged)
-                && let Some(workspace) =
-                    Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
+            // Track analytics event
+            if let Some(analytics) = &self.analytics
+                && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
             {
-                info!(
-                    "PR #{} was merged, updating task {} to done and archiving workspace",
-                    pr_merge.pr_info.number, workspace.task_id
+                analytics.analytics_service.track_event(
+                    &analytics.user_id,
+                    "pr_merged",
+                    Some(json!({
+                        "task_id": workspace.task_id.to_string(),
+                        "workspace_id": workspace.id.to_string(),
+                        "project_id": task.project_id.to_string(),
+                    })),
                 );
-                Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
-                if !workspace.pinned
-                    && let Err(e) = self.container.archive_workspace(workspace.id).await
-                {
-                    error!("Failed to archive workspace {}: {}", workspace.id, e);
-                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish the PR's description refresh once its agent run is done, and
+    /// start a new one when commits were just pushed and refreshes are enabled
+    async fn update_description(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+        pushed: bool,
+    ) -> Result<(), PrMonitorError> {
+        let template = if pushed {
+            pr_description::refresh_prompt_template(&self.config.read().await)
+        } else {
+            None
+        };
+        let workspace = match template {
+            Some(_) => Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?,
+            None => None,
+        };
+        let result = match (template, workspace) {
+            (Some(template), Some(workspace)) => pr_description::start_description_refresh(
+                &self.container,
+                git_host,
+                &workspace,
+                pr_merge,
+                &template,
+            )
+            .await
+            .map(|refresh| {
+                debug!(
+                    "Description refresh of PR #{}: {:?}",
+                    pr_merge.pr_info.number, refresh
+                );
+            }),
+            _ => {
+                pr_description::complete_description_refresh(&self.container, git_host, pr_merge)
+                    .await
+            }
+        };
+        if let Err(e) = result {
+            error!(
+                "Failed to refresh the description of PR #{}: {}",
+                pr_merge.pr_info.number, e
+            );
+        }
+        Ok(())
+    }
+
+    /// Mark a draft PR ready for review once the repo's required quality gates
+    /// passed at the commit the branch was last pushed with
+    async fn mark_ready_if_gates_pass(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+    ) -> Result<(), PrMonitorError> {
+        if !self.config.read().await.pr_lifecycle.auto_ready {
+            return Ok(());
+        }
+        if self.required_gates_pass(pr_merge).await? != Some(true) {
+            return Ok(());
+        }
+
+        git_host.set_pr_draft(&pr_merge.pr_info.url, false).await?;
+        Merge::set_draft(&self.db.pool, pr_merge.id, false).await?;
+        info!(
+            "Marked PR #{} ready for review after its quality gates passed",
+            pr_merge.pr_info.number
+        );
+        self.track_pr_event(pr_merge, "pr_marked_ready", json!({ "automatic": true }));
+        Ok(())
+    }
+
+    /// Whether the repo's required quality gates passed at the commit the PR's
+    /// branch was last pushed with, or `None` when the repo has none
+    async fn required_gates_pass(
+        &self,
+        pr_merge: &PrMerge,
+    ) -> Result<Option<bool>, PrMonitorError> {
+        let pool = &self.db.pool;
+        let (Some(workspace), Some(repo)) = (
+            Workspace::find_by_id(pool, pr_merge.workspace_id).await?,
+            Repo::find_by_id(pool, pr_merge.repo_id).await?,
+        ) else {
+            return Ok(None);
+        };
+        let gates = QualityGate::find_by_repo_id(pool, repo.id).await?;
+        if !gates.iter().any(|gate| gate.required) {
+            return Ok(None);
+        }
+
+        // Gates that passed on commits the PR doesn't have yet say nothing
+        // about it
+        let git = self.container.git();
+        let (ahead, _) = git.get_remote_branch_status(&repo.path, &workspace.branch, None)?;
+        if ahead > 0 {
+            return Ok(Some(false));
+        }
+        let head_commit = git.get_branch_oid(&repo.path, &workspace.branch)?;
+        let latest_runs = QualityGateRun::find_latest_by_workspace_id(pool, workspace.id).await?;
+        Ok(Some(
+            !quality_gates::gate_results(gates, &latest_runs, Some(&head_commit))
+                .iter()
+                .any(|result| result.is_blocking()),
+        ))
+    }
 
-                // Track analytics event
-                if let Some(analytics) = &self.analytics
-                    && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
+    /// Store the CI checks of an open PR, and ask the workspace's agent to fix
+    /// required checks that started failing. Returns the current checks.
+    async fn check_pr_checks(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+    ) -> Result<Vec<PrCheck>, PrMonitorError> {
+        let checks = git_host.get_pr_checks(&pr_merge.pr_info.url).await?;
+        if checks == pr_merge.checks {
+            return Ok(checks);
+        }
+
+        let status = PrCheckStatus::of_required(&checks);
+        let newly_failed = status == Some(PrCheckStatus::Failed)
+            && PrCheckStatus::of_required(&pr_merge.checks) != Some(PrCheckStatus::Failed);
+        if newly_failed {
+            let auto_fix = self.config.read().await.ci_auto_fix.clone();
+            if auto_fix.enabled {
+                if pr_merge.ci_fix_attempts >= i64::from(auto_fix.max_attempts) {
+                    info!(
+                        "PR #{} has failing checks but used all {} CI fix attempts",
+                        pr_merge.pr_info.number, auto_fix.max_attempts
+                    );
+                } else if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                    &self.db.pool,
+                    pr_merge.workspace_id,
+                )
+                .await?
                 {
-                    analytics.analytics_service.track_event(
-                        &analytics.user_id,
-                        "pr_merged",
-                        Some(json!({
-                            "task_id": workspace.task_id.to_string(),
-                            "workspace_id": workspace.id.to_string(),
-                            "project_id": task.project_id.to_string(),
-                        })),
+                    // Leave the stored checks as they were so the failure is
+                    // picked up again once the agent is idle
+                    debug!(
+                        "Deferring CI fix for PR #{} while workspace {} is busy",
+                        pr_merge.pr_info.number, pr_merge.workspace_id
                     );
+                    return Ok(checks);
+                } else {
+                    self.request_ci_fix(git_host, pr_merge, &checks, auto_fix.include_logs)
+                        .await?;
                 }
             }
+            self.track_pr_event(
+                pr_merge,
+                "pr_checks_failed",
+                json!({
+                    "failed_checks": checks
+                        .iter()
+                        .filter(|c| c.required && c.status == PrCheckStatus::Failed)
+                        .count(),
+                }),
+            );
+        } else if status == Some(PrCheckStatus::Passed) && pr_merge.ci_fix_attempts > 0 {
+            Merge::set_ci_fix_attempts(&self.db.pool, pr_merge.id, 0).await?;
+            self.track_pr_event(
+                pr_merge,
+                "pr_checks_fixed",
+                json!({ "ci_fix_attempts": pr_merge.ci_fix_attempts }),
+            );
+        }
+
+        Merge::update_checks(&self.db.pool, pr_merge.id, &checks).await?;
+        Ok(checks)
+    }
+
+    /// Merge a PR with auto-merge turned on once it is approved and its
+    /// required checks and quality gates passed, then finish it like any
+    /// merged PR
+    async fn auto_merge_if_ready(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+        checks: &[PrCheck],
+    ) -> Result<(), PrMonitorError> {
+        if !checks_passed(checks) {
+            return Ok(());
+        }
+        let pool = &self.db.pool;
+        // The agent may be about to push more commits
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            pool,
+            pr_merge.workspace_id,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        if !git_host.is_pr_approved(&pr_merge.pr_info.url).await? {
+            return Ok(());
+        }
+        if self.required_gates_pass(pr_merge).await? == Some(false) {
+            debug!(
+                "Holding auto-merge of PR #{} until its quality gates pass",
+                pr_merge.pr_info.number
+            );
+            return Ok(());
+        }
+
+        let method = self.config.read().await.pr_lifecycle.merge_method;
+        if let Err(e) = git_host.merge_pr(&pr_merge.pr_info.url, method).await {
+            // Network and server errors are retried on the next check
+            if e.should_retry() {
+                warn!(
+                    "Auto-merge of PR #{} failed and will be retried: {}",
+                    pr_merge.pr_info.number, e
+                );
+                return Ok(());
+            }
+            // Conflicts and branch rules the PR doesn't meet won't go away on
+            // their own, so auto-merge is turned off instead of retried
+            Merge::set_auto_merge(pool, pr_merge.id, false).await?;
+            warn!(
+                "Auto-merge of PR #{} failed and was turned off: {}",
+                pr_merge.pr_info.number, e
+            );
+            self.track_pr_event(pr_merge, "pr_auto_merge_failed", json!({}));
+            return Ok(());
         }
+        info!(
+            "Auto-merged PR #{} after it was approved with passing checks",
+            pr_merge.pr_info.number
+        );
+        self.track_pr_event(
+            pr_merge,
+            "pr_auto_merged",
+            json!({ "merge_method": method }),
+        );
 
+        // Hosts that merge in the background are picked up on a later poll
+        let pr_status = git_host.get_pr_status(&pr_merge.pr_info.url).await?;
+        if !matches!(pr_status.status, MergeStatus::Open) {
+            self.update_closed_pr(pr_merge, pr_status).await?;
+        }
         Ok(())
     }
 
+    /// Send the failed required checks, with their logs, to the workspace's agent
+    async fn request_ci_fix(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+        checks: &[PrCheck],
+        include_logs: bool,
+    ) -> Result<(), PrMonitorError> {
+        let Some(workspace) = Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
+        else {
+            return Ok(());
+        };
+        if workspace.archived {
+            return Ok(());
+        }
+
+        let mut failed = Vec::new();
+        for check in checks
+            .iter()
+            .filter(|c| c.required && c.status == PrCheckStatus::Failed)
+        {
+            let log = if include_logs {
+                git_host.get_check_log(check).await.unwrap_or_else(|e| {
+                    warn!("Failed to fetch log of check '{}': {}", check.name, e);
+                    None
+                })
+            } else {
+                None
+            };
+            failed.push((check, log));
+        }
+
+        let prompt = ci_failure_follow_up_prompt(pr_merge, &failed);
+        if self
+            .container
+            .start_agent_follow_up(&workspace, prompt)
+            .await?
+            .is_none()
+        {
+            debug!(
+                "Workspace {} has no coding agent to fix PR #{}",
+                workspace.id, pr_merge.pr_info.number
+            );
+            return Ok(());
+        }
+
+        let attempts = pr_merge.ci_fix_attempts + 1;
+        Merge::set_ci_fix_attempts(&self.db.pool, pr_merge.id, attempts).await?;
+        info!(
+            "Asked the agent in workspace {} to fix failing checks on PR #{} (attempt {})",
+            workspace.id, pr_merge.pr_info.number, attempts
+        );
+        self.track_pr_event(
+            pr_merge,
+            "pr_ci_fix_requested",
+            json!({ "attempt": attempts }),
+        );
+        Ok(())
+    }
+
+    fn track_pr_event(&self, pr_merge: &PrMerge, event_name: &str, mut properties: Value) {
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        properties["workspace_id"] = json!(pr_merge.workspace_id.to_string());
+        properties["pr_number"] = json!(pr_merge.pr_info.number);
+        analytics
+            .analytics_service
+            .track_event(&analytics.user_id, event_name, Some(properties));
+    }
+
     /// Sync PR status to remote server
     async fn sync_pr_to_remote(
         &self,
@@ -199,3 +649,164 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
         });
     }
 }
+
+/// Follow-up prompt asking the coding agent to fix the failed checks of a PR
+fn ci_failure_follow_up_prompt(
+    pr_merge: &PrMerge,
+    failed: &[(&PrCheck, Option<String>)],
+) -> String {
+    let mut prompt = format!(
+        "Required CI checks failed on pull request #{number} ({url}), which targets `{target}`:\n",
+        number = pr_merge.pr_info.number,
+        url = pr_merge.pr_info.url,
+        target = pr_merge.target_branch_name,
+    );
+    for (check, _) in failed {
+        prompt.push_str(&format!("\n- {}", check.name));
+        if let Some(description) = &check.description {
+            prompt.push_str(&format!(": {description}"));
+        }
+        if let Some(url) = &check.url {
+            prompt.push_str(&format!(" ({url})"));
+        }
+    }
+    for (check, log) in failed {
+        let Some(log) = log.as_deref().map(str::trim).filter(|l| !l.is_empty()) else {
+            continue;
+        };
+        let skip = log.chars().count().saturating_sub(MAX_LOG_CHARS);
+        let tail: String = log.chars().skip(skip).collect();
+        prompt.push_str(&format!(
+            "\n\nLog of {name}{truncated}:\n\n```\n{tail}\n```",
+            name = check.name,
+            truncated = if skip > 0 { " (truncated)" } else { "" },
+        ));
+    }
+    prompt.push_str(
+        "\n\nFind the cause of each failure, fix it, then commit and push the branch so the \
+         checks run again. Do not weaken, skip or disable the checks.",
+    );
+    prompt
+}
+
+/// Whether CI passed on a PR: its required checks, or all of its checks when
+/// the target branch requires none. A PR without any checks hasn't passed.
+fn checks_passed(checks: &[PrCheck]) -> bool {
+    match PrCheckStatus::of_required(checks) {
+        Some(status) => status == PrCheckStatus::Passed,
+        None => {
+            !checks.is_empty()
+                && checks
+                    .iter()
+                    .all(|c| matches!(c.status, PrCheckStatus::Passed | PrCheckStatus::Skipped))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn check(name: &str, required: bool, status: PrCheckStatus) -> PrCheck {
+        PrCheck {
+            name: name.to_string(),
+            status,
+            required,
+            url: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn required_checks_decide_the_combined_status() {
+        use PrCheckStatus::*;
+
+        assert_eq!(PrCheckStatus::of_required(&[]), None);
+        assert_eq!(
+            PrCheckStatus::of_required(&[check("lint", false, Failed)]),
+            None
+        );
+        assert_eq!(
+            PrCheckStatus::of_required(&[
+                check("test", true, Passed),
+                check("docs", true, Skipped),
+                check("lint", false, Failed),
+            ]),
+            Some(Passed)
+        );
+        assert_eq!(
+            PrCheckStatus::of_required(&[
+                check("test", true, Pending),
+                check("build", true, Passed)
+            ]),
+            Some(Pending)
+        );
+        assert_eq!(
+            PrCheckStatus::of_required(&[
+                check("test", true, Pending),
+                check("build", true, Failed)
+            ]),
+            Some(Failed)
+        );
+    }
+
+    #[test]
+    fn auto_merge_waits_for_checks_to_pass() {
+        use PrCheckStatus::*;
+
+        assert!(!checks_passed(&[]));
+        assert!(checks_passed(&[
+            check("test", true, Passed),
+            check("lint", false, Failed),
+        ]));
+        assert!(!checks_passed(&[check("test", true, Pending)]));
+        assert!(checks_passed(&[
+            check("test", false, Passed),
+            check("docs", false, Skipped),
+        ]));
+        assert!(!checks_passed(&[
+            check("test", false, Passed),
+            check("lint", false, Failed),
+        ]));
+    }
+
+    #[test]
+    fn follow_up_prompt_lists_failures_with_log_tails() {
+        let pr_merge = PrMerge {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            repo_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            target_branch_name: "main".to_string(),
+            pr_info: PullRequestInfo {
+                number: 12,
+                url: "https://github.com/acme/app/pull/12".to_string(),
+                status: MergeStatus::Open,
+                merged_at: None,
+                merge_commit_sha: None,
+                is_draft: false,
+            },
+            checks: Vec::new(),
+            ci_fix_attempts: 0,
+            description_process_id: None,
+            description_user_section: None,
+            auto_merge: false,
+        };
+        let mut test = check("test", true, PrCheckStatus::Failed);
+        test.description = Some("2 tests failed".to_string());
+        let build = check("build", true, PrCheckStatus::Failed);
+        let log = format!("{}assertion failed", "x".repeat(MAX_LOG_CHARS));
+
+        let prompt = ci_failure_follow_up_prompt(&pr_merge, &[(&test, Some(log)), (&build, None)]);
+
+        assert!(prompt.contains("pull request #12 (https://github.com/acme/app/pull/12)"));
+        assert!(prompt.contains("- test: 2 tests failed"));
+        assert!(prompt.contains("- build"));
+        assert!(prompt.contains("Log of test (truncated)"));
+        assert!(prompt.contains("assertion failed"));
+        assert!(!prompt.contains("Log of build"));
+    }
+}
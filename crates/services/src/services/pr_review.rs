@@ -0,0 +1,444 @@
+//! Review comments on a workspace PR sent to the coding agent as a follow-up.
+//! Once the agent run finishes and the repo's required quality gates pass, its
+//! commits are pushed and each thread gets a reply: addressed threads are
+//! resolved, skipped ones explain why.
+
+use std::{collections::HashMap, path::Path};
+
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    merge::PrMerge,
+    pr_review_thread::{PrReviewThread, PrReviewThreadStatus, StartPrReviewThread},
+    quality_gate::{QualityGate, QualityGateRun},
+    repo::Repo,
+    workspace::Workspace,
+};
+use git::GitServiceError;
+use thiserror::Error;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    git_host::{GitHostError, GitHostProvider, GitHostService, ReviewThread},
+    quality_gates::{self, QualityGateResult, QualityGateStatus},
+};
+
+#[derive(Debug, Error)]
+pub enum PrReviewError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+pub enum ReviewFollowUp {
+    Started(Vec<PrReviewThread>),
+    /// Every unresolved thread was already sent to the agent
+    NoUnresolvedThreads,
+    /// The workspace has no coding agent session to follow up on
+    NoCodingAgent,
+    /// An agent or script is still running in the workspace
+    AgentBusy,
+}
+
+/// Ask the workspace's agent to address the PR's unresolved review threads.
+/// Threads that were addressed or skipped before are left out; failed ones
+/// are retried.
+pub async fn start_review_follow_up<C: ContainerService + Sync>(
+    container: &C,
+    git_host: &GitHostService,
+    workspace: &Workspace,
+    repo: &Repo,
+    remote_url: &str,
+    pr_merge: &PrMerge,
+) -> Result<ReviewFollowUp, PrReviewError> {
+    let pool = &container.db().pool;
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ReviewFollowUp::AgentBusy);
+    }
+
+    let tracked = PrReviewThread::find_by_merge_id(pool, pr_merge.id).await?;
+    let mut threads: Vec<ReviewThread> = git_host
+        .get_review_threads(&repo.path, remote_url, pr_merge.pr_info.number)
+        .await?
+        .into_iter()
+        .filter(|thread| {
+            !tracked
+                .iter()
+                .any(|t| t.thread_id == thread.id && t.status != PrReviewThreadStatus::Failed)
+        })
+        .collect();
+    if threads.is_empty() {
+        return Ok(ReviewFollowUp::NoUnresolvedThreads);
+    }
+    threads.sort_by(|a, b| (&a.path, a.line, &a.id).cmp(&(&b.path, b.line, &b.id)));
+
+    let prompt = review_follow_up_prompt(pr_merge, &threads);
+    let Some(process) = container.start_agent_follow_up(workspace, prompt).await? else {
+        return Ok(ReviewFollowUp::NoCodingAgent);
+    };
+
+    let mut started = Vec::with_capacity(threads.len());
+    for thread in threads {
+        let start = StartPrReviewThread {
+            thread_id: thread.id,
+            path: thread.path,
+            line: thread.line,
+            url: thread.url,
+        };
+        started.push(PrReviewThread::start(pool, pr_merge.id, &start, process.id).await?);
+    }
+    info!(
+        "Asked the agent in workspace {} to address {} review threads on PR #{}",
+        workspace.id,
+        started.len(),
+        pr_merge.pr_info.number
+    );
+    Ok(ReviewFollowUp::Started(started))
+}
+
+/// Push the work of finished review follow-ups on a PR and reply to their
+/// threads. Runs still in progress, or waiting on quality gates, are left for a
+/// later call. Returns whether new commits were pushed.
+pub async fn complete_review_follow_ups<C: ContainerService + Sync>(
+    container: &C,
+    git_host: &GitHostService,
+    pr_merge: &PrMerge,
+) -> Result<bool, PrReviewError> {
+    let pool = &container.db().pool;
+    let in_progress = PrReviewThread::find_in_progress_by_merge_id(pool, pr_merge.id).await?;
+    if in_progress.is_empty() {
+        return Ok(false);
+    }
+
+    let mut by_process: HashMap<Option<Uuid>, Vec<PrReviewThread>> = HashMap::new();
+    for thread in in_progress {
+        by_process
+            .entry(thread.execution_process_id)
+            .or_default()
+            .push(thread);
+    }
+
+    let mut pushed = false;
+    for (process_id, mut threads) in by_process {
+        let process = match process_id {
+            Some(id) => ExecutionProcess::find_by_id(pool, id).await?,
+            None => None,
+        };
+        let Some(process) = process else {
+            fail_threads(pool, &threads, "The agent run was deleted").await?;
+            continue;
+        };
+        match process.status {
+            ExecutionProcessStatus::Running => continue,
+            ExecutionProcessStatus::Failed => {
+                fail_threads(pool, &threads, "The agent run failed").await?;
+                continue;
+            }
+            ExecutionProcessStatus::Killed => {
+                fail_threads(pool, &threads, "The agent run was stopped").await?;
+                continue;
+            }
+            ExecutionProcessStatus::Completed => {}
+        }
+
+        // The head is recorded once the run's changes have been committed
+        let Some(head_commit) =
+            ExecutionProcessRepoState::find_by_execution_process_id(pool, process.id)
+                .await?
+                .into_iter()
+                .find(|state| state.repo_id == pr_merge.repo_id)
+                .and_then(|state| state.after_head_commit)
+        else {
+            debug!(
+                "Waiting for the changes of execution {} to be committed",
+                process.id
+            );
+            continue;
+        };
+
+        threads
+            .sort_by(|a, b| (&a.path, a.line, &a.thread_id).cmp(&(&b.path, b.line, &b.thread_id)));
+        let summary = CodingAgentTurn::find_by_execution_process_id(pool, process.id)
+            .await?
+            .and_then(|turn| turn.summary)
+            .unwrap_or_default();
+        let outcomes = parse_thread_outcomes(&summary);
+
+        let (Some(workspace), Some(repo)) = (
+            Workspace::find_by_id(pool, pr_merge.workspace_id).await?,
+            Repo::find_by_id(pool, pr_merge.repo_id).await?,
+        ) else {
+            fail_threads(pool, &threads, "The workspace or repository was deleted").await?;
+            continue;
+        };
+
+        let addressed_any = outcomes
+            .values()
+            .any(|(status, _)| *status == PrReviewThreadStatus::Addressed);
+        if addressed_any {
+            let container_ref = container.ensure_container_exists(&workspace).await?;
+            let worktree_path = Path::new(&container_ref).join(&repo.name);
+            let gates = QualityGate::find_by_repo_id(pool, repo.id).await?;
+            if gates.iter().any(|gate| gate.required) {
+                // Pushes from the UI are held back by failing required gates,
+                // so these are too
+                let head = container.git().get_head_info(&worktree_path)?.oid;
+                let latest_runs =
+                    QualityGateRun::find_latest_by_workspace_id(pool, workspace.id).await?;
+                let blocking: Vec<QualityGateResult> =
+                    quality_gates::gate_results(gates, &latest_runs, Some(&head))
+                        .into_iter()
+                        .filter(|result| result.is_blocking())
+                        .collect();
+                let failed: Vec<&str> = blocking
+                    .iter()
+                    .filter(|result| result.status == QualityGateStatus::Failed)
+                    .map(|result| result.name.as_str())
+                    .collect();
+                if !failed.is_empty() {
+                    let note = format!("Quality gates failed: {}", failed.join(", "));
+                    fail_threads(pool, &threads, &note).await?;
+                    continue;
+                }
+                if blocking
+                    .iter()
+                    .any(|result| result.status == QualityGateStatus::Running)
+                {
+                    continue;
+                }
+                // Gates that haven't checked the head yet are run, and the
+                // push waits for them on a later call
+                if !blocking.is_empty() {
+                    debug!(
+                        "Running quality gates before pushing review follow-up {}",
+                        process.id
+                    );
+                    container.start_quality_gates(&workspace, &repo).await?;
+                    continue;
+                }
+            }
+            if let Err(e) = container
+                .git()
+                .push_to_remote(&worktree_path, &workspace.branch, false)
+            {
+                fail_threads(pool, &threads, &format!("Failed to push the branch: {e}")).await?;
+                continue;
+            }
+            pushed = true;
+        }
+
+        let remote = container
+            .git()
+            .resolve_remote_for_branch(&repo.path, &pr_merge.target_branch_name)?;
+        let short_commit = &head_commit[..head_commit.len().min(7)];
+        for (index, thread) in threads.iter().enumerate() {
+            let Some((status, text)) = outcomes.get(&(index + 1)) else {
+                PrReviewThread::update_status(
+                    pool,
+                    thread.id,
+                    PrReviewThreadStatus::Skipped,
+                    Some("The agent did not report on this thread"),
+                )
+                .await?;
+                continue;
+            };
+            let (reply, note) = match status {
+                PrReviewThreadStatus::Addressed => {
+                    (format!("Addressed in {short_commit}: {text}"), None)
+                }
+                _ => (text.clone(), Some(text.as_str())),
+            };
+            let resolve = *status == PrReviewThreadStatus::Addressed;
+            match git_host
+                .reply_to_review_thread(
+                    &repo.path,
+                    &remote.url,
+                    pr_merge.pr_info.number,
+                    &thread.thread_id,
+                    &reply,
+                    resolve,
+                )
+                .await
+            {
+                // The outcome still stands where the provider can't take replies
+                Ok(()) | Err(GitHostError::UnsupportedProvider) => {
+                    PrReviewThread::update_status(pool, thread.id, *status, note).await?;
+                }
+                Err(e) => {
+                    PrReviewThread::update_status(
+                        pool,
+                        thread.id,
+                        PrReviewThreadStatus::Failed,
+                        Some(&format!("Failed to reply to the thread: {e}")),
+                    )
+                    .await?;
+                }
+            }
+        }
+        info!(
+            "Completed review follow-up {} on PR #{}",
+            process.id, pr_merge.pr_info.number
+        );
+    }
+    Ok(pushed)
+}
+
+async fn fail_threads(
+    pool: &sqlx::SqlitePool,
+    threads: &[PrReviewThread],
+    note: &str,
+) -> Result<(), sqlx::Error> {
+    for thread in threads {
+        PrReviewThread::update_status(pool, thread.id, PrReviewThreadStatus::Failed, Some(note))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Follow-up prompt listing the review threads, numbered from 1, with the
+/// diff each one comments on
+fn review_follow_up_prompt(pr_merge: &PrMerge, threads: &[ReviewThread]) -> String {
+    let mut prompt = format!(
+        "Reviewers left unresolved comments on pull request #{number} ({url}):\n",
+        number = pr_merge.pr_info.number,
+        url = pr_merge.pr_info.url,
+    );
+    for (index, thread) in threads.iter().enumerate() {
+        let location = match thread.line {
+            Some(line) => format!("{}:{line}", thread.path),
+            None => thread.path.clone(),
+        };
+        prompt.push_str(&format!("\n[{}] {location}", index + 1));
+        if let Some(url) = &thread.url {
+            prompt.push_str(&format!(" ({url})"));
+        }
+        prompt.push('\n');
+        if let Some(diff_hunk) = thread.diff_hunk.as_deref().filter(|h| !h.is_empty()) {
+            prompt.push_str(&format!("```diff\n{}\n```\n", diff_hunk.trim_end()));
+        }
+        for comment in &thread.comments {
+            let body = comment.body.trim().replace('\n', "\n  ");
+            prompt.push_str(&format!("> {}: {body}\n", comment.author));
+        }
+    }
+    prompt.push_str(
+        "\nAddress each thread: change the code where the reviewer is right, and leave it as \
+         it is where the change would be wrong or out of scope. Commit your changes but do \
+         not push; the branch is pushed for you when you finish.\n\nEnd your final message \
+         with one line per thread, in this form:\n\
+         [1] addressed: <what you changed>\n\
+         [2] skipped: <why you left the code as it is>",
+    );
+    prompt
+}
+
+/// Outcome the agent reported for each thread number, from lines such as
+/// `[1] addressed: renamed the helper` in its final message
+fn parse_thread_outcomes(summary: &str) -> HashMap<usize, (PrReviewThreadStatus, String)> {
+    summary
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().trim_start_matches(['-', '*', ' ']);
+            let (number, rest) = line.strip_prefix('[')?.split_once(']')?;
+            let number: usize = number.trim().parse().ok()?;
+            let (outcome, text) = rest.trim().split_once(':')?;
+            let status = match outcome.trim().trim_matches('*').to_lowercase().as_str() {
+                "addressed" => PrReviewThreadStatus::Addressed,
+                "skipped" => PrReviewThreadStatus::Skipped,
+                _ => return None,
+            };
+            Some((number, (status, text.trim().to_string())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::merge::{MergeStatus, PullRequestInfo};
+
+    use super::*;
+    use crate::services::git_host::ReviewThreadComment;
+
+    #[test]
+    fn follow_up_prompt_numbers_threads_with_their_diff() {
+        let pr_merge = PrMerge {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            repo_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            target_branch_name: "main".to_string(),
+            pr_info: PullRequestInfo {
+                number: 7,
+                url: "https://github.com/o/r/pull/7".to_string(),
+                status: MergeStatus::Open,
+                merged_at: None,
+                merge_commit_sha: None,
+                is_draft: false,
+            },
+            checks: Vec::new(),
+            ci_fix_attempts: 0,
+            description_process_id: None,
+            description_user_section: None,
+            auto_merge: false,
+        };
+        let threads = vec![ReviewThread {
+            id: "PRRT_1".to_string(),
+            path: "src/lib.rs".to_string(),
+            line: Some(12),
+            diff_hunk: Some("@@ -10,3 +10,3 @@\n-fn foo() {}\n+fn bar() {}".to_string()),
+            url: None,
+            comments: vec![
+                ReviewThreadComment {
+                    author: "alice".to_string(),
+                    body: "Rename this\nto baz".to_string(),
+                },
+                ReviewThreadComment {
+                    author: "bob".to_string(),
+                    body: "+1".to_string(),
+                },
+            ],
+        }];
+
+        let prompt = review_follow_up_prompt(&pr_merge, &threads);
+        assert!(prompt.contains("pull request #7"));
+        assert!(prompt.contains("[1] src/lib.rs:12\n```diff\n@@ -10,3 +10,3 @@"));
+        assert!(prompt.contains("> alice: Rename this\n  to baz\n> bob: +1\n"));
+        assert!(prompt.ends_with("[2] skipped: <why you left the code as it is>"));
+    }
+
+    #[test]
+    fn thread_outcomes_are_parsed_from_the_final_message() {
+        let summary = "I renamed the helper.\n\n\
+                       [1] addressed: renamed `foo` to `bar`\n\
+                       - [2] Skipped: the name matches the public API\n\
+                       [3] ignored: nothing\n\
+                       [x] addressed: not a number";
+        let outcomes = parse_thread_outcomes(summary);
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(
+            outcomes[&1],
+            (
+                PrReviewThreadStatus::Addressed,
+                "renamed `foo` to `bar`".to_string()
+            )
+        );
+        assert_eq!(
+            outcomes[&2],
+            (
+                PrReviewThreadStatus::Skipped,
+                "the name matches the public API".to_string()
+            )
+        );
+    }
+}
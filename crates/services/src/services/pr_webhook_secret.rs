@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use super::{config::PrWebhookConfig, credential_file};
+
+#[derive(Default, Serialize, Deserialize)]
+struct StoredSecret {
+    secret: Option<String>,
+}
+
+/// Service for managing the shared secret git hosts sign webhook deliveries
+/// with, kept out of the config file. Loaded into memory on startup and
+/// persisted to disk on every change.
+pub struct PrWebhookSecret {
+    path: PathBuf,
+    inner: RwLock<Option<String>>,
+}
+
+impl PrWebhookSecret {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            inner: RwLock::new(None),
+        }
+    }
+
+    pub async fn load(&self) -> std::io::Result<()> {
+        let secret =
+            credential_file::load::<StoredSecret>(&self.path)?.and_then(|stored| stored.secret);
+        *self.inner.write().await = secret;
+        Ok(())
+    }
+
+    pub async fn get(&self) -> Option<String> {
+        self.inner.read().await.clone()
+    }
+
+    pub async fn is_set(&self) -> bool {
+        self.inner.read().await.is_some()
+    }
+
+    pub async fn set(&self, secret: &str) -> std::io::Result<()> {
+        let mut inner = self.inner.write().await;
+        let secret = Some(secret.to_string());
+        credential_file::save(
+            &self.path,
+            &StoredSecret {
+                secret: secret.clone(),
+            },
+        )?;
+        *inner = secret;
+        Ok(())
+    }
+
+    pub async fn clear(&self) -> std::io::Result<()> {
+        let mut inner = self.inner.write().await;
+        if inner.is_none() {
+            return Ok(());
+        }
+        credential_file::save(&self.path, &StoredSecret::default())?;
+        *inner = None;
+        Ok(())
+    }
+
+    /// The secret to verify deliveries with, when the endpoint is enabled
+    pub async fn active(&self, config: &PrWebhookConfig) -> Option<String> {
+        if !config.enabled {
+            return None;
+        }
+        self.get().await
+    }
+
+    /// Move a secret read from an older config file into the store, unless
+    /// one is already stored
+    pub async fn import_legacy_secret(&self, config: &mut PrWebhookConfig) -> std::io::Result<()> {
+        let Some(secret) = config.secret.take() else {
+            return Ok(());
+        };
+        let secret = secret.trim();
+        if secret.is_empty() || self.is_set().await {
+            return Ok(());
+        }
+        self.set(secret).await
+    }
+}
@@ -8,6 +8,7 @@ use db::models::{
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
 };
+use git::SparseCone;
 use sqlx::SqlitePool;
 use thiserror::Error;
 use uuid::Uuid;
@@ -235,9 +236,10 @@ impl ProjectService {
                 let repo_path = repo.path.clone();
                 let mode = query.mode.clone();
                 let query_str = query_str.to_string();
+                let sparse_cone = repo.sparse_checkout.as_deref().and_then(SparseCone::parse);
                 async move {
                     let results = cache
-                        .search_repo(&repo_path, &query_str, mode)
+                        .search_repo(&repo_path, &query_str, mode, sparse_cone.as_ref())
                         .await
                         .unwrap_or_else(|e| {
                             tracing::warn!("Search failed for repo {}: {}", repo_name, e);
@@ -0,0 +1,168 @@
+//! Outcome of the per-repo quality gates for a workspace branch, used to gate
+//! merges, pushes and PRs.
+
+use db::models::{
+    execution_process::ExecutionProcessStatus,
+    quality_gate::{LatestQualityGateRun, QualityGate},
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Maximum gate output included in a follow-up prompt
+const MAX_OUTPUT_CHARS: usize = 12_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityGateStatus {
+    NotRun,
+    Running,
+    Passed,
+    Failed,
+    /// The latest run checked an older commit than the current branch head
+    Outdated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QualityGateResult {
+    pub gate_id: Uuid,
+    pub name: String,
+    pub required: bool,
+    pub status: QualityGateStatus,
+    /// Execution process of the latest run
+    pub execution_process_id: Option<Uuid>,
+    /// Commit checked by the latest run
+    pub head_commit: Option<String>,
+}
+
+impl QualityGateResult {
+    /// A required gate that has not passed at the current head
+    pub fn is_blocking(&self) -> bool {
+        self.required && self.status != QualityGateStatus::Passed
+    }
+}
+
+/// Result of each gate given its latest run and the branch head
+pub fn gate_results(
+    gates: Vec<QualityGate>,
+    latest_runs: &[LatestQualityGateRun],
+    head_commit: Option<&str>,
+) -> Vec<QualityGateResult> {
+    gates
+        .into_iter()
+        .map(|gate| {
+            let run = latest_runs.iter().find(|r| r.quality_gate_id == gate.id);
+            let status = match run {
+                None => QualityGateStatus::NotRun,
+                Some(run) if run.status == ExecutionProcessStatus::Running => {
+                    QualityGateStatus::Running
+                }
+                Some(run) if head_commit != Some(run.head_commit.as_str()) => {
+                    QualityGateStatus::Outdated
+                }
+                Some(run)
+                    if run.status == ExecutionProcessStatus::Completed
+                        && run.exit_code == Some(0) =>
+                {
+                    QualityGateStatus::Passed
+                }
+                Some(_) => QualityGateStatus::Failed,
+            };
+            QualityGateResult {
+                gate_id: gate.id,
+                name: gate.name,
+                required: gate.required,
+                status,
+                execution_process_id: run.map(|r| r.execution_process_id),
+                head_commit: run.map(|r| r.head_commit.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Follow-up prompt asking the coding agent to fix a failed gate
+pub fn failure_follow_up_prompt(gate: &QualityGate, repo_name: &str, output: &str) -> String {
+    let output = output.trim();
+    let skip = output.chars().count().saturating_sub(MAX_OUTPUT_CHARS);
+    let tail: String = output.chars().skip(skip).collect();
+    format!(
+        "The \"{name}\" quality gate failed in the `{repo_name}` repository. It runs:\n\n\
+         ```sh\n{script}\n```\n\n\
+         Output{truncated}:\n\n```\n{tail}\n```\n\n\
+         Fix the underlying problems so that the gate passes. Do not weaken or skip the check.",
+        name = gate.name,
+        script = gate.script.trim(),
+        truncated = if skip > 0 { " (truncated)" } else { "" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn gate(name: &str, required: bool) -> QualityGate {
+        QualityGate {
+            id: Uuid::new_v4(),
+            repo_id: Uuid::nil(),
+            name: name.to_string(),
+            script: format!("make {name}"),
+            required,
+            sort_order: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn run(
+        gate: &QualityGate,
+        head: &str,
+        status: ExecutionProcessStatus,
+        exit_code: Option<i64>,
+    ) -> LatestQualityGateRun {
+        LatestQualityGateRun {
+            quality_gate_id: gate.id,
+            execution_process_id: Uuid::new_v4(),
+            head_commit: head.to_string(),
+            status,
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn results_reflect_latest_run_at_head() {
+        let gates = vec![
+            gate("lint", true),
+            gate("test", true),
+            gate("typecheck", true),
+            gate("audit", false),
+            gate("secrets", true),
+        ];
+        let runs = vec![
+            run(&gates[0], "abc", ExecutionProcessStatus::Completed, Some(0)),
+            run(&gates[1], "abc", ExecutionProcessStatus::Failed, Some(1)),
+            run(&gates[2], "old", ExecutionProcessStatus::Completed, Some(0)),
+            run(&gates[3], "abc", ExecutionProcessStatus::Failed, Some(2)),
+        ];
+
+        let results = gate_results(gates, &runs, Some("abc"));
+        let statuses: Vec<_> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                QualityGateStatus::Passed,
+                QualityGateStatus::Failed,
+                QualityGateStatus::Outdated,
+                QualityGateStatus::Failed,
+                QualityGateStatus::NotRun,
+            ]
+        );
+        let blocking: Vec<_> = results
+            .iter()
+            .filter(|r| r.is_blocking())
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(blocking, ["test", "typecheck", "secrets"]);
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use db::models::{repo::Repo, workspace::Workspace as DbWorkspace};
+use git::SparseCone;
 use sqlx::{Pool, Sqlite};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
@@ -12,15 +13,27 @@ use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
 pub struct RepoWorkspaceInput {
     pub repo: Repo,
     pub target_branch: String,
+    pub sparse_cone: Option<SparseCone>,
 }
 
 impl RepoWorkspaceInput {
     pub fn new(repo: Repo, target_branch: String) -> Self {
+        let sparse_cone = repo.sparse_checkout.as_deref().and_then(SparseCone::parse);
         Self {
             repo,
             target_branch,
+            sparse_cone,
         }
     }
+
+    /// Apply a per-workspace sparse-checkout override.
+    /// `None` keeps the repo setting; an empty string forces a full checkout.
+    pub fn with_sparse_checkout_override(mut self, sparse_checkout: Option<&str>) -> Self {
+        if let Some(patterns) = sparse_checkout {
+            self.sparse_cone = SparseCone::parse(patterns);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Error)]
@@ -90,10 +103,17 @@ impl WorkspaceManager {
                 &worktree_path,
                 &input.target_branch,
                 true,
+                input.sparse_cone.as_ref(),
             )
             .await
             {
                 Ok(()) => {
+                    WorktreeManager::prepare_checkout(
+                        &input.repo.path,
+                        &worktree_path,
+                        input.repo.init_submodules,
+                    )
+                    .await;
                     created_worktrees.push(RepoWorktree {
                         repo_id: input.repo.id,
                         repo_name: input.repo.name.clone(),
@@ -140,7 +160,7 @@ impl WorkspaceManager {
     /// Ensure all worktrees in a workspace exist (for cold restart scenarios)
     pub async fn ensure_workspace_exists(
         workspace_dir: &Path,
-        repos: &[Repo],
+        repos: &[RepoWorkspaceInput],
         branch_name: &str,
     ) -> Result<(), WorkspaceError> {
         if repos.is_empty() {
@@ -149,7 +169,7 @@ impl WorkspaceManager {
 
         // Try legacy migration first (single repo projects only)
         // Old layout had worktree directly at workspace_dir; new layout has it at workspace_dir/{repo_name}
-        if repos.len() == 1 && Self::migrate_legacy_worktree(workspace_dir, &repos[0]).await? {
+        if repos.len() == 1 && Self::migrate_legacy_worktree(workspace_dir, &repos[0].repo).await? {
             return Ok(());
         }
 
@@ -157,7 +177,8 @@ impl WorkspaceManager {
             tokio::fs::create_dir_all(workspace_dir).await?;
         }
 
-        for repo in repos {
+        for input in repos {
+            let repo = &input.repo;
             let worktree_path = workspace_dir.join(&repo.name);
 
             debug!(
@@ -166,8 +187,17 @@ impl WorkspaceManager {
                 worktree_path.display()
             );
 
-            WorktreeManager::ensure_worktree_exists(&repo.path, branch_name, &worktree_path)
-                .await?;
+            if WorktreeManager::ensure_worktree_exists(
+                &repo.path,
+                branch_name,
+                &worktree_path,
+                input.sparse_cone.as_ref(),
+            )
+            .await?
+            {
+                WorktreeManager::prepare_checkout(&repo.path, &worktree_path, repo.init_submodules)
+                    .await;
+            }
         }
 
         Ok(())
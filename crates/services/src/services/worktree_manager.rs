@@ -7,10 +7,10 @@ use std::{
 
 static WORKSPACE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
-use git::{GitService, GitServiceError};
+use git::{GitService, GitServiceError, SparseCone};
 use git2::{Error as GitError, Repository};
 use thiserror::Error;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 use utils::{path::normalize_macos_private_alias, shell::resolve_executable_path};
 
 // Global synchronization for worktree creation to prevent race conditions
@@ -59,13 +59,14 @@ impl WorktreeManager {
         let _ = WORKSPACE_DIR_OVERRIDE.set(path);
     }
 
-    /// Create a worktree with a new branch
+    /// Create a worktree with a new branch, limited to `sparse_cone` when set
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
         base_branch: &str,
         create_branch: bool,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<(), WorktreeError> {
         if create_branch {
             let repo_path_owned = repo_path.to_path_buf();
@@ -87,16 +88,19 @@ impl WorktreeManager {
             .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
         }
 
-        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
+        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path, sparse_cone).await?;
+        Ok(())
     }
 
     /// Ensure worktree exists, recreating if necessary with proper synchronization
-    /// This is the main entry point for ensuring a worktree exists and prevents race conditions
+    /// This is the main entry point for ensuring a worktree exists and prevents race conditions.
+    /// Returns true if the worktree had to be (re)created.
     pub async fn ensure_worktree_exists(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
-    ) -> Result<(), WorktreeError> {
+        sparse_cone: Option<&SparseCone>,
+    ) -> Result<bool, WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
         // Get or create a lock for this specific worktree path
@@ -114,12 +118,40 @@ impl WorktreeManager {
         // Check if worktree already exists and is properly set up
         if Self::is_worktree_properly_set_up(repo_path, worktree_path).await? {
             trace!("Worktree already properly set up at path: {}", path_str);
-            return Ok(());
+            return Ok(false);
         }
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path, sparse_cone)
+            .await?;
+        Ok(true)
+    }
+
+    /// Check out submodules and Git LFS content in a newly created worktree.
+    /// Failures are logged rather than returned; the worktree stays usable without them.
+    pub async fn prepare_checkout(repo_path: &Path, worktree_path: &Path, init_submodules: bool) {
+        let repo_path_owned = repo_path.to_path_buf();
+        let worktree_path_owned = worktree_path.to_path_buf();
+
+        let result = tokio::task::spawn_blocking(move || {
+            GitService::new().prepare_worktree_checkout(
+                &repo_path_owned,
+                &worktree_path_owned,
+                init_submodules,
+            )
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!(
+                "Failed to check out submodules/LFS content in {}: {}",
+                worktree_path.display(),
+                e
+            ),
+            Err(e) => warn!("Task join error while preparing worktree checkout: {}", e),
+        }
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -127,6 +159,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_cone: Option<&SparseCone>,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -155,6 +188,7 @@ impl WorktreeManager {
             &branch_name_owned,
             &worktree_path_owned,
             &path_str,
+            sparse_cone.cloned(),
         )
         .await
     }
@@ -321,6 +355,7 @@ impl WorktreeManager {
         branch_name: &str,
         worktree_path: &Path,
         path_str: &str,
+        sparse_cone: Option<SparseCone>,
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
@@ -330,7 +365,13 @@ impl WorktreeManager {
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git_service = GitService::new();
-            match git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false) {
+            match git_service.add_worktree_with_sparse_cone(
+                &git_repo_path,
+                &worktree_path,
+                &branch_name,
+                false,
+                sparse_cone.as_ref(),
+            ) {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -355,11 +396,12 @@ impl WorktreeManager {
                     if worktree_path.exists() {
                         std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
-                    if let Err(e2) = git_service.add_worktree(
+                    if let Err(e2) = git_service.add_worktree_with_sparse_cone(
                         &git_repo_path,
                         &worktree_path,
                         &branch_name,
                         false,
+                        sparse_cone.as_ref(),
                     ) {
                         return Err(WorktreeError::GitService(e2));
                     }
@@ -580,6 +622,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &base_worktree_path,
         "main",
         true,
+        None,
     )
     .await
     .unwrap();
@@ -592,6 +635,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &child_worktree_path,
         "main",
         true,
+        None,
     )
     .await
     .unwrap();
@@ -602,6 +646,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &base_worktree_path,
         "wt-child-branch",
         &child_worktree_path,
+        None,
     )
     .await
     .unwrap();
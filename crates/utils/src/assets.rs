@@ -36,6 +36,14 @@ pub fn credentials_path() -> std::path::PathBuf {
     asset_dir().join("credentials.json")
 }
 
+pub fn git_host_credentials_path() -> std::path::PathBuf {
+    asset_dir().join("git_host_credentials.json")
+}
+
+pub fn pr_webhook_secret_path() -> std::path::PathBuf {
+    asset_dir().join("pr_webhook_secret.json")
+}
+
 #[derive(RustEmbed)]
 #[folder = "../../assets/sounds"]
 pub struct SoundAssets;
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use git2::{DiffOptions, Patch};
 use serde::{Deserialize, Serialize};
-use similar::TextDiff;
+use similar::{Algorithm, DiffOp, DiffTag, TextDiff, capture_diff_slices};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -30,6 +30,53 @@ pub struct Diff {
     pub additions: Option<usize>,
     pub deletions: Option<usize>,
     pub repo_id: Option<Uuid>,
+    /// Unified diff hunks computed with the requested view options (ignored
+    /// whitespace, context lines); `None` when the viewer diffs the contents itself
+    pub hunks: Option<Vec<String>>,
+    /// Word-level changes within modified lines, when requested
+    pub word_highlights: Option<Vec<DiffLineHighlight>>,
+}
+
+/// How diffs are computed for review
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct DiffViewOptions {
+    /// Ignore whitespace when comparing lines, like `git diff -w`
+    pub ignore_whitespace: bool,
+    /// Similarity percentage (0-100) at which a removed and an added file are
+    /// paired as a rename or copy; git's default is 50
+    pub rename_threshold: Option<u16>,
+    /// Also detect files copied from other files in the change
+    pub detect_copies: bool,
+    /// Lines of context around each hunk; git's default is 3
+    pub context_lines: Option<u32>,
+    /// Compute word-level highlights for modified lines
+    pub word_diff: bool,
+}
+
+impl DiffViewOptions {
+    /// Whether hunks must be computed on the server rather than by the viewer
+    fn needs_hunks(&self) -> bool {
+        self.ignore_whitespace || self.context_lines.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffSide {
+    Old,
+    New,
+}
+
+/// Changed words within one line of a modified file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLineHighlight {
+    pub side: DiffSide,
+    /// 1-based line number in the old or new content
+    pub line_number: usize,
+    /// Changed `[start, end)` ranges as UTF-16 offsets, matching JavaScript string indices
+    pub ranges: Vec<(usize, usize)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -91,6 +138,196 @@ pub fn compute_line_change_counts(old: &str, new: &str) -> (usize, usize) {
     }
 }
 
+/// Apply view options to a diff whose old and new contents are loaded.
+/// Returns false when nothing is left to show, e.g. a file that only changed
+/// whitespace while whitespace is ignored.
+pub fn apply_view_options(diff: &mut Diff, options: &DiffViewOptions) -> bool {
+    let (Some(old), Some(new)) = (diff.old_content.as_deref(), diff.new_content.as_deref()) else {
+        return true;
+    };
+
+    if options.needs_hunks() {
+        let old = ensure_newline(old);
+        let new = ensure_newline(new);
+        let mut opts = DiffOptions::new();
+        opts.ignore_whitespace(options.ignore_whitespace)
+            .context_lines(options.context_lines.unwrap_or(3));
+
+        match Patch::from_buffers(old.as_bytes(), None, new.as_bytes(), None, Some(&mut opts)) {
+            Ok(mut patch) => {
+                if options.ignore_whitespace
+                    && patch.num_hunks() == 0
+                    && matches!(diff.change, DiffChangeKind::Modified)
+                {
+                    return false;
+                }
+                if let Ok((_, adds, dels)) = patch.line_stats() {
+                    diff.additions = Some(adds);
+                    diff.deletions = Some(dels);
+                }
+                diff.hunks = patch
+                    .to_buf()
+                    .ok()
+                    .and_then(|buf| buf.as_str().map(extract_unified_diff_hunks));
+            }
+            Err(e) => tracing::error!("git2 diff failed: {}", e),
+        }
+    }
+
+    if options.word_diff {
+        diff.word_highlights = Some(compute_word_highlights(old, new, options.ignore_whitespace));
+    }
+
+    true
+}
+
+/// Word-level highlights for lines that were modified in place. Replaced line
+/// blocks are paired up line by line and each pair is diffed by word.
+pub fn compute_word_highlights(
+    old: &str,
+    new: &str,
+    ignore_whitespace: bool,
+) -> Vec<DiffLineHighlight> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let line_key = |line: &&str| -> String {
+        if ignore_whitespace {
+            line.split_whitespace().collect()
+        } else {
+            line.to_string()
+        }
+    };
+    let old_keys: Vec<String> = old_lines.iter().map(line_key).collect();
+    let new_keys: Vec<String> = new_lines.iter().map(line_key).collect();
+
+    let mut highlights = Vec::new();
+    for op in capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys) {
+        let DiffOp::Replace {
+            old_index,
+            old_len,
+            new_index,
+            new_len,
+        } = op
+        else {
+            continue;
+        };
+        for i in 0..old_len.min(new_len) {
+            let (old_ranges, new_ranges) = word_ranges(
+                old_lines[old_index + i],
+                new_lines[new_index + i],
+                ignore_whitespace,
+            );
+            if !old_ranges.is_empty() {
+                highlights.push(DiffLineHighlight {
+                    side: DiffSide::Old,
+                    line_number: old_index + i + 1,
+                    ranges: old_ranges,
+                });
+            }
+            if !new_ranges.is_empty() {
+                highlights.push(DiffLineHighlight {
+                    side: DiffSide::New,
+                    line_number: new_index + i + 1,
+                    ranges: new_ranges,
+                });
+            }
+        }
+    }
+    highlights
+}
+
+type WordRanges = Vec<(usize, usize)>;
+
+fn word_ranges(old: &str, new: &str, ignore_whitespace: bool) -> (WordRanges, WordRanges) {
+    let old_tokens = tokenize_words(old);
+    let new_tokens = tokenize_words(new);
+    let old_offsets = utf16_offsets(&old_tokens);
+    let new_offsets = utf16_offsets(&new_tokens);
+
+    let mut old_ranges = Vec::new();
+    let mut new_ranges = Vec::new();
+    for op in capture_diff_slices(Algorithm::Myers, &old_tokens, &new_tokens) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        if matches!(tag, DiffTag::Delete | DiffTag::Replace) {
+            push_token_range(
+                &mut old_ranges,
+                &old_tokens[old_range.clone()],
+                old_offsets[old_range.start],
+                old_offsets[old_range.end],
+                ignore_whitespace,
+            );
+        }
+        if matches!(tag, DiffTag::Insert | DiffTag::Replace) {
+            push_token_range(
+                &mut new_ranges,
+                &new_tokens[new_range.clone()],
+                new_offsets[new_range.start],
+                new_offsets[new_range.end],
+                ignore_whitespace,
+            );
+        }
+    }
+    (old_ranges, new_ranges)
+}
+
+fn push_token_range(
+    ranges: &mut WordRanges,
+    tokens: &[&str],
+    start: usize,
+    end: usize,
+    ignore_whitespace: bool,
+) {
+    if start == end || (ignore_whitespace && tokens.iter().all(|t| t.trim().is_empty())) {
+        return;
+    }
+    match ranges.last_mut() {
+        Some(last) if last.1 == start => last.1 = end,
+        _ => ranges.push((start, end)),
+    }
+}
+
+/// Split a line into runs of word characters, runs of whitespace and single
+/// punctuation characters
+fn tokenize_words(line: &str) -> Vec<&str> {
+    fn class(c: char) -> u8 {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut prev = None;
+    for (i, c) in line.char_indices() {
+        let current = class(c);
+        if prev.is_some_and(|p| p != current || current == 2) {
+            tokens.push(&line[start..i]);
+            start = i;
+        }
+        prev = Some(current);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+/// UTF-16 offset of the start of each token, plus the end of the line
+fn utf16_offsets(tokens: &[&str]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(tokens.len() + 1);
+    let mut offset = 0;
+    offsets.push(offset);
+    for token in tokens {
+        offset += token.encode_utf16().count();
+        offsets.push(offset);
+    }
+    offsets
+}
+
 // ensure a line ends with a newline character
 fn ensure_newline(line: &str) -> Cow<'_, str> {
     if line.ends_with('\n') {
@@ -246,3 +483,65 @@ pub fn normalize_unified_diff(file_path: &str, unified_diff: &str) -> String {
     let hunks = extract_unified_diff_hunks(unified_diff);
     concatenate_diff_hunks(file_path, &hunks)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modified(old: &str, new: &str) -> Diff {
+        Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some("a.rs".to_string()),
+            new_path: Some("a.rs".to_string()),
+            old_content: Some(old.to_string()),
+            new_content: Some(new.to_string()),
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+            repo_id: None,
+            hunks: None,
+            word_highlights: None,
+        }
+    }
+
+    #[test]
+    fn whitespace_only_changes_are_dropped_when_ignored() {
+        let options = DiffViewOptions {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let mut reformatted = modified("fn a() {\n  b();\n}\n", "fn a() {\n    b();\n}\n");
+        assert!(!apply_view_options(&mut reformatted, &options));
+
+        let mut changed = modified("let x = 1;\nlet y = 2;\n", "let x  = 1;\nlet y = 3;\n");
+        assert!(apply_view_options(&mut changed, &options));
+        assert_eq!((changed.additions, changed.deletions), (Some(1), Some(1)));
+        let hunks = changed.hunks.unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert!(hunks[0].contains("-let y = 2;\n+let y = 3;\n"));
+    }
+
+    #[test]
+    fn word_highlights_cover_changed_words() {
+        let highlights = compute_word_highlights(
+            "keep\nlet total = price * qty;\n",
+            "keep\nlet total = cost * qty;\n",
+            false,
+        );
+        assert_eq!(
+            highlights,
+            [
+                DiffLineHighlight {
+                    side: DiffSide::Old,
+                    line_number: 2,
+                    ranges: vec![(12, 17)],
+                },
+                DiffLineHighlight {
+                    side: DiffSide::New,
+                    line_number: 2,
+                    ranges: vec![(12, 16)],
+                },
+            ]
+        );
+    }
+}
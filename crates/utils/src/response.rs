@@ -49,6 +49,11 @@ impl<T, E> ApiResponse<T, E> {
         self.data
     }
 
+    /// Consumes the response and returns the error data if present.
+    pub fn into_error_data(self) -> Option<E> {
+        self.error_data
+    }
+
     /// Returns a reference to the error message if present.
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
@@ -0,0 +1,132 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import {
+  ArrowCounterClockwiseIcon,
+  SpinnerGap,
+  TrashIcon,
+} from '@phosphor-icons/react';
+import { workspaceArchivesApi } from '@/lib/api';
+import { IconButton } from '@/components/ui-new/primitives/IconButton';
+import type { WorkspaceBranchArchive } from 'shared/types';
+
+export function WorkspaceArchiveList() {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const [busyId, setBusyId] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const { data: archives = [], isLoading } = useQuery({
+    queryKey: ['workspaceArchives'],
+    queryFn: () => workspaceArchivesApi.list(),
+  });
+
+  const run = async (
+    archive: WorkspaceBranchArchive,
+    action: (id: string) => Promise<unknown>,
+    successMessage: string
+  ) => {
+    setBusyId(archive.id);
+    setMessage(null);
+    try {
+      await action(archive.id);
+      setMessage(successMessage);
+    } catch (err) {
+      setMessage(err instanceof Error ? err.message : String(err));
+    } finally {
+      setBusyId(null);
+      await queryClient.invalidateQueries({
+        queryKey: ['workspaceArchives'],
+      });
+    }
+  };
+
+  const handleDelete = (archive: WorkspaceBranchArchive) => {
+    if (
+      !confirm(
+        t('settings.general.git.archives.deleteConfirm', {
+          branch: archive.branch,
+        })
+      )
+    ) {
+      return;
+    }
+    run(
+      archive,
+      workspaceArchivesApi.delete,
+      t('settings.general.git.archives.deleted', { branch: archive.branch })
+    );
+  };
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center py-4">
+        <SpinnerGap className="h-6 w-6 animate-spin" />
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-2">
+      <h3 className="text-sm font-medium text-normal">
+        {t('settings.general.git.archives.title')}
+      </h3>
+      {archives.length === 0 ? (
+        <p className="text-sm text-low">
+          {t('settings.general.git.archives.empty')}
+        </p>
+      ) : (
+        <div className="border rounded-lg overflow-hidden">
+          <div className="max-h-[300px] overflow-auto">
+            {archives.map((archive) => (
+              <div
+                key={archive.id}
+                className="flex items-center gap-2 border-b p-2 last:border-b-0"
+              >
+                <div className="flex-1 min-w-0">
+                  <div className="text-sm font-medium truncate">
+                    {archive.workspace_name ?? archive.branch}
+                  </div>
+                  <div className="text-xs text-low truncate">
+                    {archive.branch} → {archive.target_branch} ·{' '}
+                    {archive.commit_sha.slice(0, 7)} ·{' '}
+                    {new Date(archive.updated_at).toLocaleString()}
+                  </div>
+                </div>
+                {busyId === archive.id ? (
+                  <SpinnerGap className="size-icon-sm animate-spin" />
+                ) : (
+                  <>
+                    <IconButton
+                      icon={ArrowCounterClockwiseIcon}
+                      aria-label={t('settings.general.git.archives.restore')}
+                      title={t('settings.general.git.archives.restore')}
+                      disabled={busyId !== null}
+                      onClick={() =>
+                        run(
+                          archive,
+                          workspaceArchivesApi.restore,
+                          t('settings.general.git.archives.restored', {
+                            branch: archive.branch,
+                          })
+                        )
+                      }
+                    />
+                    <IconButton
+                      icon={TrashIcon}
+                      aria-label={t('settings.general.git.archives.delete')}
+                      title={t('settings.general.git.archives.delete')}
+                      disabled={busyId !== null}
+                      onClick={() => handleDelete(archive)}
+                    />
+                  </>
+                )}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {message && <p className="text-sm text-low">{message}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,109 @@
+import { useTranslation } from 'react-i18next';
+import { useQuery } from '@tanstack/react-query';
+import { ArrowClockwiseIcon, SpinnerGap } from '@phosphor-icons/react';
+import { attemptsApi } from '@/lib/api';
+import { formatFileSize } from '@/lib/utils';
+import { IconButton } from '@/components/ui-new/primitives/IconButton';
+
+const formatSize = (bytes: bigint) => formatFileSize(bytes) || '0 B';
+
+export function WorkspaceDiskUsageList() {
+  const { t } = useTranslation('settings');
+
+  const {
+    data: report,
+    isLoading,
+    isFetching,
+    refetch,
+  } = useQuery({
+    queryKey: ['workspaceDiskUsage'],
+    queryFn: () => attemptsApi.getDiskUsage(),
+  });
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center py-4">
+        <SpinnerGap className="h-6 w-6 animate-spin" />
+      </div>
+    );
+  }
+
+  if (!report) return null;
+
+  const overQuota =
+    report.limit_bytes !== null &&
+    Number(report.total_bytes) > Number(report.limit_bytes);
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between gap-2">
+        <h3 className="text-sm font-medium text-normal">
+          {t('settings.general.git.diskUsage.title')}
+        </h3>
+        <IconButton
+          icon={isFetching ? SpinnerGap : ArrowClockwiseIcon}
+          aria-label={t('settings.general.git.diskUsage.refresh')}
+          title={t('settings.general.git.diskUsage.refresh')}
+          disabled={isFetching}
+          onClick={() => refetch()}
+        />
+      </div>
+      <p className={overQuota ? 'text-sm text-error' : 'text-sm text-low'}>
+        {report.limit_bytes !== null
+          ? t('settings.general.git.diskUsage.totalWithLimit', {
+              total: formatSize(report.total_bytes),
+              limit: formatSize(report.limit_bytes),
+            })
+          : t('settings.general.git.diskUsage.total', {
+              total: formatSize(report.total_bytes),
+            })}
+      </p>
+      {report.workspaces.length === 0 ? (
+        <p className="text-sm text-low">
+          {t('settings.general.git.diskUsage.empty')}
+        </p>
+      ) : (
+        <div className="border rounded-lg overflow-hidden">
+          <div className="max-h-[300px] overflow-auto">
+            {report.workspaces.map((workspace) => (
+              <div
+                key={workspace.workspace_id}
+                className="border-b p-2 last:border-b-0"
+              >
+                <div className="flex items-center justify-between gap-2">
+                  <span className="text-sm font-medium truncate">
+                    {workspace.name ?? workspace.branch}
+                  </span>
+                  <span className="text-sm text-normal shrink-0">
+                    {formatSize(workspace.total_bytes)}
+                  </span>
+                </div>
+                <div className="text-xs text-low truncate">
+                  {workspace.archived &&
+                    `${t('settings.general.git.diskUsage.archived')} · `}
+                  {t('settings.general.git.diskUsage.lastUsed', {
+                    date: new Date(workspace.last_used_at).toLocaleString(),
+                  })}
+                </div>
+                {workspace.repos.map((repo) => (
+                  <div
+                    key={repo.repo_id}
+                    className="flex justify-between gap-2 text-xs text-low"
+                  >
+                    <span className="truncate">{repo.repo_name}</span>
+                    <span className="shrink-0">
+                      {t('settings.general.git.diskUsage.repoSize', {
+                        total: formatSize(repo.total_bytes),
+                        ignored: formatSize(repo.ignored_bytes),
+                      })}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
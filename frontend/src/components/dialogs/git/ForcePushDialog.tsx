@@ -19,11 +19,13 @@ export interface ForcePushDialogProps {
   attemptId: string;
   repoId: string;
   branchName?: string;
+  /** The user already chose to override failing quality gates */
+  bypassQualityGates?: boolean;
 }
 
 const ForcePushDialogImpl = NiceModal.create<ForcePushDialogProps>((props) => {
   const modal = useModal();
-  const { attemptId, repoId, branchName } = props;
+  const { attemptId, repoId, branchName, bypassQualityGates } = props;
   const [error, setError] = useState<string | null>(null);
   const { t } = useTranslation(['tasks', 'common']);
   const branchLabel = branchName ? ` "${branchName}"` : '';
@@ -48,7 +50,10 @@ const ForcePushDialogImpl = NiceModal.create<ForcePushDialogProps>((props) => {
   const handleConfirm = async () => {
     setError(null);
     try {
-      await forcePush.mutateAsync({ repo_id: repoId });
+      await forcePush.mutateAsync({
+        repo_id: repoId,
+        bypass_quality_gates: bypassQualityGates,
+      });
     } catch {
       // Error already handled by onError callback
     }
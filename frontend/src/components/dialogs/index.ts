@@ -89,6 +89,10 @@ export {
   StartReviewDialog,
   type StartReviewDialogProps,
 } from './tasks/StartReviewDialog';
+export {
+  ImportIssuesDialog,
+  type ImportIssuesDialogProps,
+} from './tasks/ImportIssuesDialog';
 
 // Auth dialogs
 export { GhCliSetupDialog } from './auth/GhCliSetupDialog';
@@ -15,6 +15,10 @@ import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import BranchSelector from '@/components/tasks/BranchSelector';
 import { useCallback, useEffect, useMemo, useState } from 'react';
 import { attemptsApi } from '@/lib/api.ts';
+import {
+  confirmQualityGateBypass,
+  formatBlockingGates,
+} from '@/lib/qualityGates';
 import { useTranslation } from 'react-i18next';
 
 import { TaskWithAttemptStatus, Workspace } from 'shared/types';
@@ -85,7 +89,28 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
       setPrBody(task.description || '');
       setError(null);
       setGhCliHelp(null);
-    }, [modal.visible, isLoaded, task]);
+
+      // Prefill with the generated Conventional Commits message when enabled
+      if (config?.conventional_commits_enabled && repoId) {
+        attemptsApi
+          .getMergeCommitMessage(attempt.id, repoId)
+          .then(({ message }) => {
+            const [subject, ...body] = message.split('\n');
+            setPrTitle(subject);
+            setPrBody(body.join('\n').trim() || task.description || '');
+          })
+          .catch((err) => {
+            console.error('Failed to generate PR title:', err);
+          });
+      }
+    }, [
+      modal.visible,
+      isLoaded,
+      task,
+      attempt.id,
+      repoId,
+      config?.conventional_commits_enabled,
+    ]);
 
     // Set default base branch when branches are loaded
     useEffect(() => {
@@ -139,14 +164,38 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         setError(ui.message);
       };
 
-      const result = await attemptsApi.createPR(attempt.id, {
+      const request = {
         title: prTitle,
         body: prBody || null,
         target_branch: prBaseBranch || null,
         draft: isDraft,
         auto_generate_description: autoGenerateDescription,
         repo_id: repoId,
-      });
+      };
+      let result = await attemptsApi.createPR(attempt.id, request);
+
+      // Required quality gates have not passed - offer to open the PR anyway
+      if (!result.success && result.error?.type === 'quality_gates_failed') {
+        const gates = result.error.gates;
+        const bypass = await confirmQualityGateBypass(
+          'pull request',
+          gates,
+          result.error.bypass_allowed
+        );
+        if (!bypass) {
+          setCreatingPR(false);
+          setError(
+            t('createPrDialog.errors.qualityGatesFailed', {
+              gates: formatBlockingGates(gates),
+            })
+          );
+          return;
+        }
+        result = await attemptsApi.createPR(attempt.id, {
+          ...request,
+          bypass_quality_gates: true,
+        });
+      }
 
       if (result.success) {
         setPrTitle('');
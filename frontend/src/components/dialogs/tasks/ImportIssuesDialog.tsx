@@ -0,0 +1,357 @@
+import { useState, useEffect, useMemo, type FormEvent } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import { ArrowSquareOut } from '@phosphor-icons/react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import NiceModal, { useModal } from '@ebay/nice-modal-react';
+import { defineModal } from '@/lib/modals';
+import { repoApi, tasksApi } from '@/lib/api';
+import { useProjectRepos } from '@/hooks';
+import type { GitHostIssue } from 'shared/types';
+
+export interface ImportIssuesDialogProps {
+  projectId: string;
+}
+
+interface IssueFilterInput {
+  labels: string;
+  assignee: string;
+  milestone: string;
+}
+
+const EMPTY_FILTER: IssueFilterInput = {
+  labels: '',
+  assignee: '',
+  milestone: '',
+};
+
+const ImportIssuesDialogImpl = NiceModal.create<ImportIssuesDialogProps>(
+  ({ projectId }) => {
+    const modal = useModal();
+    const { t } = useTranslation('tasks');
+    const queryClient = useQueryClient();
+
+    const [selectedRepoId, setSelectedRepoId] = useState<string | null>(null);
+    const [filterInput, setFilterInput] =
+      useState<IssueFilterInput>(EMPTY_FILTER);
+    const [filter, setFilter] = useState<IssueFilterInput>(EMPTY_FILTER);
+    const [selectedUrls, setSelectedUrls] = useState<Set<string>>(new Set());
+
+    const { data: repos = [], isLoading: isLoadingRepos } = useProjectRepos(
+      projectId,
+      { enabled: modal.visible }
+    );
+
+    useEffect(() => {
+      if (!selectedRepoId && repos.length > 0) {
+        setSelectedRepoId(repos[0].id);
+      }
+    }, [repos, selectedRepoId]);
+
+    const {
+      data: issuesResult,
+      isLoading: isLoadingIssues,
+      error: issuesError,
+    } = useQuery({
+      queryKey: ['repo-issues', selectedRepoId, filter],
+      queryFn: async () => {
+        if (!selectedRepoId) return null;
+        return repoApi.listIssues(selectedRepoId, filter);
+      },
+      enabled: modal.visible && !!selectedRepoId,
+    });
+
+    const { data: taskIssues = [] } = useQuery({
+      queryKey: ['task-issues', projectId],
+      queryFn: () => tasksApi.getIssues(projectId),
+      enabled: modal.visible,
+    });
+
+    const issues = useMemo<GitHostIssue[]>(
+      () => (issuesResult?.success === true ? issuesResult.data : []),
+      [issuesResult]
+    );
+
+    const importedUrls = useMemo(
+      () => new Set(taskIssues.map((link) => link.issue_url)),
+      [taskIssues]
+    );
+
+    let issuesErrorMessage: string | null = null;
+    if (issuesResult?.success === false) {
+      switch (issuesResult.error?.type) {
+        case 'cli_not_installed':
+          issuesErrorMessage = t('importIssues.errors.cliNotInstalled', {
+            provider: issuesResult.error.provider,
+          });
+          break;
+        case 'auth_failed':
+          issuesErrorMessage = issuesResult.error.message;
+          break;
+        case 'unsupported_provider':
+          issuesErrorMessage = t('importIssues.errors.unsupportedProvider');
+          break;
+        default:
+          issuesErrorMessage =
+            issuesResult.message || t('importIssues.errors.failedToLoad');
+      }
+    } else if (issuesError) {
+      issuesErrorMessage = t('importIssues.errors.failedToLoad');
+    }
+
+    const importMutation = useMutation({
+      mutationFn: () => {
+        if (!selectedRepoId) throw new Error('Missing repository');
+        return tasksApi.importIssues({
+          project_id: projectId,
+          repo_id: selectedRepoId,
+          issues: issues.filter((issue) => selectedUrls.has(issue.url)),
+        });
+      },
+      onSuccess: () => {
+        queryClient.invalidateQueries({ queryKey: ['tasks'] });
+        queryClient.invalidateQueries({
+          queryKey: ['task-issues', projectId],
+        });
+        modal.hide();
+      },
+    });
+
+    useEffect(() => {
+      if (!modal.visible) {
+        setSelectedRepoId(null);
+        setFilterInput(EMPTY_FILTER);
+        setFilter(EMPTY_FILTER);
+        setSelectedUrls(new Set());
+      }
+    }, [modal.visible]);
+
+    const toggleIssue = (url: string, checked: boolean) => {
+      setSelectedUrls((prev) => {
+        const next = new Set(prev);
+        if (checked) {
+          next.add(url);
+        } else {
+          next.delete(url);
+        }
+        return next;
+      });
+    };
+
+    const handleApplyFilter = (e: FormEvent) => {
+      e.preventDefault();
+      setFilter(filterInput);
+      setSelectedUrls(new Set());
+    };
+
+    const canImport =
+      !!selectedRepoId && selectedUrls.size > 0 && !importMutation.isPending;
+
+    return (
+      <Dialog
+        open={modal.visible}
+        onOpenChange={(open) => !open && modal.hide()}
+      >
+        <DialogContent className="sm:max-w-[600px]">
+          <DialogHeader>
+            <DialogTitle>{t('importIssues.title')}</DialogTitle>
+            <DialogDescription>
+              {t('importIssues.description')}
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-4 py-2">
+            <div className="space-y-2">
+              <Label>{t('importIssues.repositoryLabel')}</Label>
+              {isLoadingRepos ? (
+                <div className="text-sm text-muted-foreground">
+                  {t('importIssues.loadingRepositories')}
+                </div>
+              ) : (
+                <Select
+                  value={selectedRepoId ?? undefined}
+                  onValueChange={(value) => {
+                    setSelectedRepoId(value);
+                    setSelectedUrls(new Set());
+                  }}
+                >
+                  <SelectTrigger>
+                    <SelectValue
+                      placeholder={t('importIssues.selectRepository')}
+                    />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {repos.map((repo) => (
+                      <SelectItem key={repo.id} value={repo.id}>
+                        {repo.display_name || repo.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              )}
+            </div>
+
+            <form
+              onSubmit={handleApplyFilter}
+              className="grid grid-cols-[1fr_1fr_1fr_auto] gap-2 items-end"
+            >
+              <div className="space-y-1">
+                <Label htmlFor="issue-labels">
+                  {t('importIssues.labelsLabel')}
+                </Label>
+                <Input
+                  id="issue-labels"
+                  value={filterInput.labels}
+                  placeholder={t('importIssues.labelsPlaceholder')}
+                  onChange={(e) =>
+                    setFilterInput((f) => ({ ...f, labels: e.target.value }))
+                  }
+                />
+              </div>
+              <div className="space-y-1">
+                <Label htmlFor="issue-assignee">
+                  {t('importIssues.assigneeLabel')}
+                </Label>
+                <Input
+                  id="issue-assignee"
+                  value={filterInput.assignee}
+                  onChange={(e) =>
+                    setFilterInput((f) => ({
+                      ...f,
+                      assignee: e.target.value,
+                    }))
+                  }
+                />
+              </div>
+              <div className="space-y-1">
+                <Label htmlFor="issue-milestone">
+                  {t('importIssues.milestoneLabel')}
+                </Label>
+                <Input
+                  id="issue-milestone"
+                  value={filterInput.milestone}
+                  onChange={(e) =>
+                    setFilterInput((f) => ({
+                      ...f,
+                      milestone: e.target.value,
+                    }))
+                  }
+                />
+              </div>
+              <Button type="submit" variant="outline">
+                {t('importIssues.applyFilter')}
+              </Button>
+            </form>
+
+            <div className="max-h-[320px] overflow-y-auto border rounded-md">
+              {isLoadingIssues ? (
+                <div className="p-3 text-sm text-muted-foreground">
+                  {t('importIssues.loadingIssues')}
+                </div>
+              ) : issuesErrorMessage ? (
+                <div className="p-3 text-sm text-destructive">
+                  {issuesErrorMessage}
+                </div>
+              ) : issues.length === 0 ? (
+                <div className="p-3 text-sm text-muted-foreground">
+                  {t('importIssues.noIssuesFound')}
+                </div>
+              ) : (
+                issues.map((issue) => {
+                  const imported = importedUrls.has(issue.url);
+                  const id = `issue-${issue.number}`;
+                  return (
+                    <div
+                      key={issue.url}
+                      className="flex items-start gap-2 px-3 py-2 border-b last:border-b-0"
+                    >
+                      <Checkbox
+                        id={id}
+                        className="mt-0.5"
+                        disabled={imported}
+                        checked={imported || selectedUrls.has(issue.url)}
+                        onCheckedChange={(checked) =>
+                          toggleIssue(issue.url, checked === true)
+                        }
+                      />
+                      <Label
+                        htmlFor={id}
+                        className="flex-1 min-w-0 font-normal"
+                      >
+                        <span className="block truncate">
+                          #{String(issue.number)}: {issue.title}
+                        </span>
+                        {(issue.labels.length > 0 || imported) && (
+                          <span className="block text-xs text-muted-foreground truncate">
+                            {imported
+                              ? t('importIssues.alreadyImported')
+                              : issue.labels.join(', ')}
+                          </span>
+                        )}
+                      </Label>
+                      <a
+                        href={issue.url}
+                        target="_blank"
+                        rel="noopener noreferrer"
+                        className="flex-shrink-0 text-muted-foreground hover:text-foreground transition-colors"
+                        title={t('importIssues.openInBrowser')}
+                      >
+                        <ArrowSquareOut className="size-4" />
+                      </a>
+                    </div>
+                  );
+                })
+              )}
+            </div>
+
+            {importMutation.error && (
+              <div className="text-sm text-destructive">
+                {importMutation.error.message}
+              </div>
+            )}
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => modal.hide()}
+              disabled={importMutation.isPending}
+            >
+              {t('common:buttons.cancel')}
+            </Button>
+            <Button
+              onClick={() => importMutation.mutate()}
+              disabled={!canImport}
+            >
+              {importMutation.isPending
+                ? t('importIssues.importing')
+                : t('importIssues.import', { count: selectedUrls.size })}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    );
+  }
+);
+
+export const ImportIssuesDialog = defineModal<ImportIssuesDialogProps, void>(
+  ImportIssuesDialogImpl
+);
@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
 import NiceModal, { useModal } from '@ebay/nice-modal-react';
+import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { defineModal } from '@/lib/modals';
 import {
   Dialog,
@@ -12,10 +13,19 @@ import {
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Button } from '@/components/ui/button';
 import { Checkbox } from '@/components/ui/checkbox';
-import { MessageSquare, AlertCircle, Loader2 } from 'lucide-react';
+import { MessageSquare, AlertCircle, Loader2, Bot } from 'lucide-react';
+import { attemptsApi } from '@/lib/api';
 import { usePrComments } from '@/hooks/usePrComments';
+import {
+  prReviewThreadsKeys,
+  usePrReviewThreads,
+} from '@/hooks/usePrReviewThreads';
 import { PrCommentCard } from '@/components/ui/pr-comment-card';
-import type { UnifiedPrComment } from 'shared/types';
+import type {
+  AddressPrReviewError,
+  AddressPrReviewResponse,
+  UnifiedPrComment,
+} from 'shared/types';
 
 export interface PrCommentsDialogProps {
   attemptId: string;
@@ -40,17 +50,53 @@ const PrCommentsDialogImpl = NiceModal.create<PrCommentsDialogProps>(
       attemptId,
       repoId
     );
+    const { data: reviewThreads = [] } = usePrReviewThreads(
+      attemptId,
+      repoId
+    );
+    const queryClient = useQueryClient();
     const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
+    const [followUpError, setFollowUpError] = useState<string | null>(null);
 
     const comments = data?.comments ?? [];
+    const hasReviewComments = comments.some(
+      (c) => c.comment_type === 'review'
+    );
 
     // Reset selection when dialog opens
     useEffect(() => {
       if (modal.visible) {
         setSelectedIds(new Set());
+        setFollowUpError(null);
       }
     }, [modal.visible]);
 
+    const addressReview = useMutation<AddressPrReviewResponse, unknown>({
+      mutationFn: async () => {
+        const result = await attemptsApi.addressPrReview(attemptId, {
+          repo_id: repoId,
+        });
+        if (!result.success) {
+          throw result.error;
+        }
+        return result.data;
+      },
+      onMutate: () => setFollowUpError(null),
+      onSuccess: () => {
+        queryClient.invalidateQueries({
+          queryKey: prReviewThreadsKeys.byAttempt(attemptId, repoId),
+        });
+      },
+      onError: (err) =>
+        setFollowUpError(
+          t(
+            `tasks:prComments.reviewFollowUp.errors.${
+              (err as AddressPrReviewError | undefined)?.type ?? 'failed'
+            }`
+          )
+        ),
+    });
+
     const toggleSelection = (id: string) => {
       setSelectedIds((prev) => {
         const newSet = new Set(prev);
@@ -130,6 +176,41 @@ const PrCommentsDialogImpl = NiceModal.create<PrCommentsDialogProps>(
                 </p>
               ) : (
                 <>
+                  {followUpError && (
+                    <Alert variant="destructive" className="mb-3">
+                      <AlertCircle className="h-4 w-4" />
+                      <AlertDescription>{followUpError}</AlertDescription>
+                    </Alert>
+                  )}
+                  {reviewThreads.length > 0 && (
+                    <div className="mb-4 space-y-1">
+                      <p className="text-sm font-medium">
+                        {t('tasks:prComments.reviewFollowUp.threadsTitle')}
+                      </p>
+                      {reviewThreads.map((thread) => (
+                        <div
+                          key={thread.id}
+                          className="flex items-baseline gap-2 text-sm min-w-0"
+                        >
+                          <span className="font-mono truncate">
+                            {thread.line != null
+                              ? `${thread.path}:${Number(thread.line)}`
+                              : thread.path}
+                          </span>
+                          <span className="shrink-0 text-muted-foreground">
+                            {t(
+                              `tasks:prComments.reviewFollowUp.status.${thread.status}`
+                            )}
+                          </span>
+                          {thread.note && (
+                            <span className="truncate text-muted-foreground">
+                              {thread.note}
+                            </span>
+                          )}
+                        </div>
+                      ))}
+                    </div>
+                  )}
                   <div className="flex items-center justify-between mb-3">
                     <span className="text-sm text-muted-foreground">
                       {t('tasks:prComments.dialog.selectedCount', {
@@ -197,6 +278,22 @@ const PrCommentsDialogImpl = NiceModal.create<PrCommentsDialogProps>(
 
           {!errorMessage && !isLoading && comments.length > 0 && (
             <DialogFooter className="px-4 py-3 border-t">
+              {hasReviewComments && (
+                <Button
+                  variant="secondary"
+                  className="sm:mr-auto"
+                  onClick={() => addressReview.mutate()}
+                  disabled={addressReview.isPending}
+                  title={t('tasks:prComments.reviewFollowUp.addressHint')}
+                >
+                  {addressReview.isPending ? (
+                    <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+                  ) : (
+                    <Bot className="h-4 w-4 mr-2" />
+                  )}
+                  {t('tasks:prComments.reviewFollowUp.address')}
+                </Button>
+              )}
               <Button variant="outline" onClick={() => handleOpenChange(false)}>
                 {t('common:buttons.cancel')}
               </Button>
@@ -17,6 +17,7 @@ import {
   MessageCircle,
   Menu,
   Plus,
+  Download,
   LogOut,
   LogIn,
 } from 'lucide-react';
@@ -38,6 +39,7 @@ import {
   TooltipTrigger,
 } from '@/components/ui/tooltip';
 import { OAuthDialog } from '@/components/dialogs/global/OAuthDialog';
+import { ImportIssuesDialog } from '@/components/dialogs/tasks/ImportIssuesDialog';
 import { useUserSystem } from '@/components/ConfigProvider';
 import { oauthApi } from '@/lib/api';
 
@@ -119,6 +121,12 @@ export function Navbar() {
     }
   };
 
+  const handleImportIssues = () => {
+    if (projectId) {
+      ImportIssuesDialog.show({ projectId });
+    }
+  };
+
   const handleOpenInIDE = () => {
     handleOpenInEditor();
   };
@@ -232,6 +240,15 @@ export function Navbar() {
                   >
                     <Plus className="h-4 w-4" />
                   </Button>
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    className="h-9 w-9"
+                    onClick={handleImportIssues}
+                    aria-label={t('tasks:importIssues.title')}
+                  >
+                    <Download className="h-4 w-4" />
+                  </Button>
                 </div>
                 <NavDivider />
               </>
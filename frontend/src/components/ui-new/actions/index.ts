@@ -5,6 +5,7 @@ import type { QueryClient } from '@tanstack/react-query';
 import type {
   EditorType,
   ExecutionProcess,
+  ExportFormat,
   Merge,
   Workspace,
 } from 'shared/types';
@@ -55,6 +56,10 @@ import {
   LinkIcon,
   ArrowBendUpRightIcon,
   ProhibitIcon,
+  DownloadSimpleIcon,
+  UploadSimpleIcon,
+  GitBranchIcon,
+  ListChecksIcon,
 } from '@phosphor-icons/react';
 import { useDiffViewStore } from '@/stores/useDiffViewStore';
 import {
@@ -71,8 +76,11 @@ import { ConfirmDialog } from '@/components/ui-new/dialogs/ConfirmDialog';
 import { ChangeTargetDialog } from '@/components/ui-new/dialogs/ChangeTargetDialog';
 import { DeleteWorkspaceDialog } from '@/components/ui-new/dialogs/DeleteWorkspaceDialog';
 import { RebaseDialog } from '@/components/ui-new/dialogs/RebaseDialog';
+import { BackportDialog } from '@/components/ui-new/dialogs/BackportDialog';
+import { ReviewHunksDialog } from '@/components/ui-new/dialogs/ReviewHunksDialog';
 import { ResolveConflictsDialog } from '@/components/ui-new/dialogs/ResolveConflictsDialog';
 import { RenameWorkspaceDialog } from '@/components/ui-new/dialogs/RenameWorkspaceDialog';
+import { MergeDialog } from '@/components/ui-new/dialogs/MergeDialog';
 import { CreatePRDialog } from '@/components/dialogs/tasks/CreatePRDialog';
 import { getIdeName } from '@/components/ide/IdeIcon';
 import { EditorSelectionDialog } from '@/components/dialogs/tasks/EditorSelectionDialog';
@@ -81,6 +89,7 @@ import posthog from 'posthog-js';
 import { WorkspacesGuideDialog } from '@/components/ui-new/dialogs/WorkspacesGuideDialog';
 import { SettingsDialog } from '@/components/ui-new/dialogs/SettingsDialog';
 import { CreateWorkspaceFromPrDialog } from '@/components/dialogs/CreateWorkspaceFromPrDialog';
+import { confirmQualityGateBypass } from '@/lib/qualityGates';
 
 // Mirrored sidebar icon for right sidebar toggle
 const RightSidebarIcon: Icon = forwardRef<SVGSVGElement, IconProps>(
@@ -303,6 +312,32 @@ function invalidateWorkspaceQueries(
   queryClient.invalidateQueries({ queryKey: workspaceSummaryKeys.all });
 }
 
+// Helper to save a workspace export through a temporary download link
+async function downloadWorkspaceExport(
+  workspaceId: string,
+  format: ExportFormat
+) {
+  const file = await attemptsApi.exportChanges(workspaceId, format);
+  const url = URL.createObjectURL(file);
+  const link = document.createElement('a');
+  link.href = url;
+  link.download = file.name;
+  link.click();
+  URL.revokeObjectURL(url);
+}
+
+// Helper to let the user pick a single file; resolves to null when canceled
+function pickFile(accept: string): Promise<File | null> {
+  return new Promise((resolve) => {
+    const input = document.createElement('input');
+    input.type = 'file';
+    input.accept = accept;
+    input.onchange = () => resolve(input.files?.[0] ?? null);
+    input.oncancel = () => resolve(null);
+    input.click();
+  });
+}
+
 // Helper to find the next workspace to navigate to when removing current workspace
 function getNextWorkspaceId(
   activeWorkspaces: SidebarWorkspace[],
@@ -463,6 +498,76 @@ export const Actions = {
     },
   },
 
+  ExportWorkspacePatches: {
+    id: 'export-workspace-patches',
+    label: 'Export as Patches',
+    icon: DownloadSimpleIcon,
+    requiresTarget: ActionTargetType.WORKSPACE,
+    execute: async (_ctx, workspaceId) => {
+      await downloadWorkspaceExport(workspaceId, 'mbox');
+    },
+  },
+
+  ExportWorkspaceDiff: {
+    id: 'export-workspace-diff',
+    label: 'Export as Diff',
+    icon: DownloadSimpleIcon,
+    requiresTarget: ActionTargetType.WORKSPACE,
+    execute: async (_ctx, workspaceId) => {
+      await downloadWorkspaceExport(workspaceId, 'diff');
+    },
+  },
+
+  ExportWorkspaceBundle: {
+    id: 'export-workspace-bundle',
+    label: 'Export as Git Bundle',
+    icon: DownloadSimpleIcon,
+    requiresTarget: ActionTargetType.WORKSPACE,
+    execute: async (_ctx, workspaceId) => {
+      await downloadWorkspaceExport(workspaceId, 'bundle');
+    },
+  },
+
+  ImportIntoNewWorkspace: {
+    id: 'import-into-new-workspace',
+    label: 'Import Patches or Bundle',
+    icon: UploadSimpleIcon,
+    requiresTarget: ActionTargetType.WORKSPACE,
+    execute: async (ctx, workspaceId) => {
+      const file = await pickFile('.mbox,.patch,.diff,.bundle,.tar');
+      if (!file) return;
+      const [workspace, repos] = await Promise.all([
+        getWorkspace(ctx.queryClient, workspaceId),
+        attemptsApi.getRepos(workspaceId),
+      ]);
+      const result = await attemptsApi.importWorkspace(file, {
+        task_id: workspace.task_id,
+        repos: repos.map((r) => ({
+          repo_id: r.id,
+          target_branch: r.target_branch,
+        })),
+      });
+      if (!result.success) {
+        if (result.error?.type === 'apply_failed') {
+          throw new Error(
+            `Could not apply the import to ${result.error.repo_name}: ${result.error.message}`
+          );
+        }
+        if (result.error?.type === 'nothing_to_import') {
+          throw new Error(
+            'The archive has no export for any repo in this workspace'
+          );
+        }
+        throw new Error(result.message ?? 'Failed to import changes');
+      }
+      ctx.queryClient.invalidateQueries({ queryKey: taskKeys.all });
+      ctx.queryClient.invalidateQueries({
+        queryKey: workspaceSummaryKeys.all,
+      });
+      ctx.selectWorkspace(result.data.id);
+    },
+  },
+
   StartReview: {
     id: 'start-review',
     label: 'Start Review',
@@ -1044,16 +1149,9 @@ export const Actions = {
         return;
       }
 
-      const confirmResult = await ConfirmDialog.show({
-        title: 'Merge Branch',
-        message:
-          'Are you sure you want to merge this branch into the target branch?',
-        confirmText: 'Merge',
-        cancelText: 'Cancel',
-      });
+      const result = await MergeDialog.show({ workspaceId, repoId });
 
-      if (confirmResult === 'confirmed') {
-        await attemptsApi.merge(workspaceId, { repo_id: repoId });
+      if (result.action === 'merged') {
         invalidateWorkspaceQueries(ctx.queryClient, workspaceId);
       }
     },
@@ -1075,6 +1173,28 @@ export const Actions = {
     },
   },
 
+  GitBackport: {
+    id: 'git-backport',
+    label: 'Backport to Branches',
+    icon: GitBranchIcon,
+    requiresTarget: ActionTargetType.GIT,
+    isVisible: (ctx) => ctx.hasWorkspace && ctx.hasGitRepos,
+    execute: async (_ctx, workspaceId, repoId) => {
+      await BackportDialog.show({ attemptId: workspaceId, repoId });
+    },
+  },
+
+  GitReviewHunks: {
+    id: 'git-review-hunks',
+    label: 'Review Changes by Hunk',
+    icon: ListChecksIcon,
+    requiresTarget: ActionTargetType.GIT,
+    isVisible: (ctx) => ctx.hasWorkspace && ctx.hasGitRepos,
+    execute: async (_ctx, workspaceId, repoId) => {
+      await ReviewHunksDialog.show({ attemptId: workspaceId, repoId });
+    },
+  },
+
   GitChangeTarget: {
     id: 'git-change-target',
     label: 'Change Target Branch',
@@ -1102,7 +1222,19 @@ export const Actions = {
       ctx.hasOpenPR &&
       ctx.hasUnpushedCommits,
     execute: async (ctx, workspaceId, repoId) => {
-      const result = await attemptsApi.push(workspaceId, { repo_id: repoId });
+      let result = await attemptsApi.push(workspaceId, { repo_id: repoId });
+      if (!result.success && result.error?.type === 'quality_gates_failed') {
+        const bypass = await confirmQualityGateBypass(
+          'push',
+          result.error.gates,
+          result.error.bypass_allowed
+        );
+        if (!bypass) return;
+        result = await attemptsApi.push(workspaceId, {
+          repo_id: repoId,
+          bypass_quality_gates: true,
+        });
+      }
       if (!result.success) {
         if (result.error?.type === 'force_push_required') {
           throw new Error(
@@ -146,6 +146,16 @@ export const Pages: Record<StaticPageId, CommandBarPage> = {
           { type: 'action', action: Actions.DeleteWorkspace },
         ],
       },
+      {
+        type: 'group',
+        label: 'Export & Import',
+        items: [
+          { type: 'action', action: Actions.ExportWorkspacePatches },
+          { type: 'action', action: Actions.ExportWorkspaceDiff },
+          { type: 'action', action: Actions.ExportWorkspaceBundle },
+          { type: 'action', action: Actions.ImportIntoNewWorkspace },
+        ],
+      },
       {
         type: 'group',
         label: 'Scripts',
@@ -218,6 +228,8 @@ export const Pages: Record<StaticPageId, CommandBarPage> = {
           { type: 'action', action: Actions.GitMerge },
           { type: 'action', action: Actions.GitPush },
           { type: 'action', action: Actions.GitRebase },
+          { type: 'action', action: Actions.GitBackport },
+          { type: 'action', action: Actions.GitReviewHunks },
           { type: 'action', action: Actions.GitChangeTarget },
         ],
       },
@@ -231,6 +231,7 @@ export function CreateChatBoxContainer() {
           repo_id: r.id,
           target_branch: targetBranches[r.id] ?? 'main',
         })),
+        linked_issue_simple_id: linkedIssue?.simpleId,
       },
       linkToIssue: linkedIssue
         ? {
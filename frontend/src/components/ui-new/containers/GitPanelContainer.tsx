@@ -1,4 +1,5 @@
 import { useState, useCallback, useMemo, useEffect, useRef } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
 import { useActions } from '@/contexts/ActionsContext';
 import { usePush } from '@/hooks/usePush';
 import { useRenameBranch } from '@/hooks/useRenameBranch';
@@ -8,8 +9,15 @@ import { ForcePushDialog } from '@/components/dialogs/git/ForcePushDialog';
 import { CommandBarDialog } from '@/components/ui-new/dialogs/CommandBarDialog';
 import { GitPanel, type RepoInfo } from '@/components/ui-new/views/GitPanel';
 import { Actions } from '@/components/ui-new/actions';
+import { attemptsApi } from '@/lib/api';
+import { confirmQualityGateBypass } from '@/lib/qualityGates';
 import type { RepoAction } from '@/components/ui-new/primitives/RepoCard';
-import type { Workspace, RepoWithTargetBranch, Merge } from 'shared/types';
+import type {
+  Workspace,
+  RepoWithTargetBranch,
+  Merge,
+  PrCheck,
+} from 'shared/types';
 
 export interface GitPanelContainerProps {
   selectedWorkspace: Workspace | undefined;
@@ -23,6 +31,7 @@ export function GitPanelContainer({
   repos,
 }: GitPanelContainerProps) {
   const { executeAction } = useActions();
+  const queryClient = useQueryClient();
 
   // Hooks for branch management (moved from WorkspacesLayout)
   const renameBranch = useRenameBranch(selectedWorkspace?.id);
@@ -44,6 +53,10 @@ export function GitPanelContainer({
         let prNumber: number | undefined;
         let prUrl: string | undefined;
         let prStatus: 'open' | 'merged' | 'closed' | 'unknown' | undefined;
+        let prIsDraft = false;
+        let prAutoMerge = false;
+        let prChecks: PrCheck[] = [];
+        let ciFixAttempts = 0;
 
         if (repoStatus?.merges) {
           const openPR = repoStatus.merges.find(
@@ -59,6 +72,13 @@ export function GitPanelContainer({
             prUrl = relevantPR.pr_info.url;
             prStatus = relevantPR.pr_info.status;
           }
+          // Checks only matter while the PR can still change
+          if (openPR && openPR.type === 'pr') {
+            prIsDraft = openPR.pr_info.is_draft;
+            prAutoMerge = openPR.auto_merge;
+            prChecks = openPR.checks;
+            ciFixAttempts = Number(openPR.ci_fix_attempts);
+          }
         }
 
         return {
@@ -71,7 +91,12 @@ export function GitPanelContainer({
           prNumber,
           prUrl,
           prStatus,
+          prIsDraft,
+          prAutoMerge,
           isTargetRemote: repoStatus?.is_target_remote ?? false,
+          qualityGates: repoStatus?.quality_gates ?? [],
+          prChecks,
+          ciFixAttempts,
         };
       }),
     [repos, branchStatus]
@@ -110,16 +135,36 @@ export function GitPanelContainer({
       }, 2000);
     },
     // onError
-    async (err, errorData) => {
+    async (err, errorData, params) => {
       const repoId = currentPushRepoRef.current;
       if (!repoId) return;
 
+      // Required quality gates have not passed - offer to push anyway
+      if (errorData?.type === 'quality_gates_failed') {
+        setPushStates((prev) => ({ ...prev, [repoId]: 'idle' }));
+        const bypass = await confirmQualityGateBypass(
+          'push',
+          errorData.gates,
+          errorData.bypass_allowed
+        );
+        if (bypass) {
+          setPushStates((prev) => ({ ...prev, [repoId]: 'pending' }));
+          pushMutation.mutate({
+            ...params,
+            repo_id: repoId,
+            bypass_quality_gates: true,
+          });
+        }
+        return;
+      }
+
       // Handle force push required - show confirmation dialog
       if (errorData?.type === 'force_push_required' && selectedWorkspace?.id) {
         setPushStates((prev) => ({ ...prev, [repoId]: 'idle' }));
         await ForcePushDialog.show({
           attemptId: selectedWorkspace.id,
           repoId,
+          bypassQualityGates: params?.bypass_quality_gates ?? false,
         });
         return;
       }
@@ -142,6 +187,16 @@ export function GitPanelContainer({
     }
   );
 
+  // Repos whose PR draft state is being changed
+  const [draftTogglePending, setDraftTogglePending] = useState<
+    Record<string, boolean>
+  >({});
+
+  // Repos whose PR auto-merge flag is being changed
+  const [autoMergeTogglePending, setAutoMergeTogglePending] = useState<
+    Record<string, boolean>
+  >({});
+
   // Clean up timeout on unmount
   useEffect(() => {
     return () => {
@@ -167,9 +222,11 @@ export function GitPanelContainer({
           isPushPending: state === 'pending',
           isPushSuccess: state === 'success',
           isPushError: state === 'error',
+          isDraftTogglePending: draftTogglePending[repo.id] ?? false,
+          isAutoMergeTogglePending: autoMergeTogglePending[repo.id] ?? false,
         };
       }),
-    [repoInfos, pushStates]
+    [repoInfos, pushStates, draftTogglePending, autoMergeTogglePending]
   );
 
   // Handle opening command bar for repo actions
@@ -207,6 +264,125 @@ export function GitPanelContainer({
     [selectedWorkspace, executeAction]
   );
 
+  const showGateError = useCallback((message: string) => {
+    ConfirmDialog.show({
+      title: 'Error',
+      message,
+      confirmText: 'OK',
+      showCancelButton: false,
+      variant: 'destructive',
+    });
+  }, []);
+
+  const handleRunQualityGates = useCallback(
+    async (repoId: string) => {
+      if (!selectedWorkspace?.id) return;
+      const result = await attemptsApi.runQualityGates(selectedWorkspace.id, {
+        repo_id: repoId,
+      });
+      if (!result.success) {
+        showGateError(
+          result.error?.type === 'process_already_running'
+            ? 'Wait for the running process to finish before running the quality gates.'
+            : result.message || 'Failed to run quality gates'
+        );
+      }
+      queryClient.invalidateQueries({
+        queryKey: ['branchStatus', selectedWorkspace.id],
+      });
+    },
+    [selectedWorkspace?.id, queryClient, showGateError]
+  );
+
+  const handleSendGateFailure = useCallback(
+    async (executionProcessId: string) => {
+      if (!selectedWorkspace?.id) return;
+      const result = await attemptsApi.sendQualityGateFailureToAgent(
+        selectedWorkspace.id,
+        { execution_process_id: executionProcessId }
+      );
+      if (!result.success) {
+        const messages = {
+          gate_not_failed: 'This quality gate run did not fail.',
+          process_already_running:
+            'Wait for the running process to finish before sending the failure to the agent.',
+          no_agent_configured:
+            'Start a coding agent in this workspace before sending it gate failures.',
+        };
+        showGateError(
+          result.error
+            ? messages[result.error.type]
+            : result.message || 'Failed to send gate failure to the agent'
+        );
+      }
+    },
+    [selectedWorkspace?.id, showGateError]
+  );
+
+  const handleToggleDraft = useCallback(
+    async (repoId: string, draft: boolean) => {
+      if (!selectedWorkspace?.id) return;
+      setDraftTogglePending((prev) => ({ ...prev, [repoId]: true }));
+      try {
+        const result = await attemptsApi.setPrDraft(selectedWorkspace.id, {
+          repo_id: repoId,
+          draft,
+        });
+        if (!result.success) {
+          const messages = {
+            no_pr_attached: 'This repository has no open pull request.',
+            unsupported_provider:
+              'The git host does not support draft pull requests.',
+            cli_not_installed:
+              "Install the git host's CLI to update the pull request.",
+            cli_not_logged_in:
+              "Log in to the git host's CLI to update the pull request.",
+          };
+          showGateError(
+            result.error
+              ? messages[result.error.type]
+              : result.message || 'Failed to update the pull request'
+          );
+        }
+      } finally {
+        setDraftTogglePending((prev) => ({ ...prev, [repoId]: false }));
+      }
+      queryClient.invalidateQueries({
+        queryKey: ['branchStatus', selectedWorkspace.id],
+      });
+    },
+    [selectedWorkspace?.id, queryClient, showGateError]
+  );
+
+  const handleToggleAutoMerge = useCallback(
+    async (repoId: string, autoMerge: boolean) => {
+      if (!selectedWorkspace?.id) return;
+      setAutoMergeTogglePending((prev) => ({ ...prev, [repoId]: true }));
+      try {
+        const result = await attemptsApi.setPrAutoMerge(
+          selectedWorkspace.id,
+          {
+            repo_id: repoId,
+            auto_merge: autoMerge,
+          }
+        );
+        if (!result.success) {
+          showGateError(
+            result.error?.type === 'no_pr_attached'
+              ? 'This repository has no open pull request.'
+              : result.message || 'Failed to update the pull request'
+          );
+        }
+      } finally {
+        setAutoMergeTogglePending((prev) => ({ ...prev, [repoId]: false }));
+      }
+      queryClient.invalidateQueries({
+        queryKey: ['branchStatus', selectedWorkspace.id],
+      });
+    },
+    [selectedWorkspace?.id, queryClient, showGateError]
+  );
+
   // Handle push button click - use mutation for proper state tracking
   const handlePushClick = useCallback(
     (repoId: string) => {
@@ -234,6 +410,10 @@ export function GitPanelContainer({
       onWorkingBranchNameChange={handleBranchNameChange}
       onActionsClick={handleActionsClick}
       onPushClick={handlePushClick}
+      onToggleDraft={handleToggleDraft}
+      onToggleAutoMerge={handleToggleAutoMerge}
+      onRunQualityGates={handleRunQualityGates}
+      onSendGateFailure={handleSendGateFailure}
       onMoreClick={handleMoreClick}
       onAddRepo={() => console.log('Add repo clicked')}
     />
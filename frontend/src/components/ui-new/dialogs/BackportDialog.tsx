@@ -0,0 +1,227 @@
+import { useMemo, useState } from 'react';
+import { SpinnerIcon } from '@phosphor-icons/react';
+import { useTranslation } from 'react-i18next';
+import { useQueryClient } from '@tanstack/react-query';
+import NiceModal, { useModal } from '@ebay/nice-modal-react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { defineModal } from '@/lib/modals';
+import { attemptsApi } from '@/lib/api';
+import { useRepoBranches } from '@/hooks/useRepoBranches';
+import { useAttemptRepo } from '@/hooks/useAttemptRepo';
+import type { BackportResult } from 'shared/types';
+
+export interface BackportDialogProps {
+  attemptId: string;
+  repoId: string;
+}
+
+function BackportResultRow({ result }: { result: BackportResult }) {
+  const { t } = useTranslation('tasks');
+  const { status, pr } = result;
+  return (
+    <li className="space-y-1 border-b py-2 last:border-b-0">
+      <div className="flex items-center justify-between gap-2 text-sm">
+        <span className="font-medium truncate">{result.target_branch}</span>
+        <span
+          className={
+            status.type === 'applied' ? 'text-success' : 'text-destructive'
+          }
+        >
+          {t(`backport.status.${status.type}`)}
+        </span>
+      </div>
+      {result.workspace && (
+        <p className="text-xs text-muted-foreground truncate">
+          {result.workspace.branch}
+        </p>
+      )}
+      {status.type === 'conflicts' && (
+        <p className="text-xs text-muted-foreground">
+          {status.execution_process_id
+            ? t('backport.conflictsAgent', {
+                files: status.conflicted_files.join(', '),
+              })
+            : t('backport.conflicts', {
+                files: status.conflicted_files.join(', '),
+              })}
+        </p>
+      )}
+      {status.type === 'failed' && (
+        <p className="text-xs text-destructive">{status.message}</p>
+      )}
+      {pr?.type === 'created' && (
+        <a
+          href={pr.url}
+          target="_blank"
+          rel="noopener noreferrer"
+          className="block text-xs text-muted-foreground underline truncate"
+        >
+          {t('backport.prLink')}: {pr.url}
+        </a>
+      )}
+      {pr?.type === 'failed' && (
+        <p className="text-xs text-destructive">
+          {t('backport.prFailed', { message: pr.message })}
+        </p>
+      )}
+    </li>
+  );
+}
+
+const BackportDialogImpl = NiceModal.create<BackportDialogProps>(
+  ({ attemptId, repoId }) => {
+    const modal = useModal();
+    const { t } = useTranslation(['tasks', 'common']);
+    const queryClient = useQueryClient();
+    const [filter, setFilter] = useState('');
+    const [selected, setSelected] = useState<string[]>([]);
+    const [useAgent, setUseAgent] = useState(false);
+    const [createPrs, setCreatePrs] = useState(false);
+    const [pending, setPending] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+    const [results, setResults] = useState<BackportResult[] | null>(null);
+
+    const { data: branches = [], isLoading: branchesLoading } =
+      useRepoBranches(repoId);
+    const { repos, isLoading: reposLoading } = useAttemptRepo(attemptId);
+    const targetBranch = repos.find((r) => r.id === repoId)?.target_branch;
+
+    const candidates = useMemo(
+      () =>
+        branches
+          .filter((b) => !b.is_remote && b.name !== targetBranch)
+          .filter((b) => b.name.toLowerCase().includes(filter.toLowerCase())),
+      [branches, targetBranch, filter]
+    );
+
+    const toggle = (name: string, checked: boolean) =>
+      setSelected((prev) =>
+        checked ? [...prev, name] : prev.filter((b) => b !== name)
+      );
+
+    const handleConfirm = async () => {
+      setPending(true);
+      setError(null);
+      try {
+        const backported = await attemptsApi.backport(attemptId, {
+          repo_id: repoId,
+          target_branches: selected,
+          resolve_conflicts_with_agent: useAgent,
+          create_prs: createPrs,
+        });
+        setResults(backported);
+        await queryClient.invalidateQueries({ queryKey: ['workspaces'] });
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setPending(false);
+      }
+    };
+
+    const isLoading = branchesLoading || reposLoading;
+
+    return (
+      <Dialog
+        open={modal.visible}
+        onOpenChange={(open) => !open && modal.hide()}
+      >
+        <DialogContent className="sm:max-w-md">
+          <DialogHeader>
+            <DialogTitle>{t('backport.dialog.title')}</DialogTitle>
+            <DialogDescription>
+              {t('backport.dialog.description')}
+            </DialogDescription>
+          </DialogHeader>
+
+          {results ? (
+            <ul className="max-h-[320px] overflow-auto">
+              {results.map((result) => (
+                <BackportResultRow
+                  key={result.target_branch}
+                  result={result}
+                />
+              ))}
+            </ul>
+          ) : isLoading ? (
+            <div className="flex items-center justify-center py-8">
+              <SpinnerIcon className="h-6 w-6 animate-spin text-muted-foreground" />
+            </div>
+          ) : (
+            <div className="space-y-3">
+              <Input
+                value={filter}
+                onChange={(e) => setFilter(e.target.value)}
+                placeholder={t('backport.dialog.filterPlaceholder')}
+              />
+              <div className="max-h-[240px] overflow-auto space-y-2">
+                {candidates.map((branch) => (
+                  <label
+                    key={branch.name}
+                    className="flex items-center gap-2 text-sm cursor-pointer"
+                  >
+                    <Checkbox
+                      checked={selected.includes(branch.name)}
+                      onCheckedChange={(checked) =>
+                        toggle(branch.name, checked)
+                      }
+                    />
+                    <span className="truncate">{branch.name}</span>
+                  </label>
+                ))}
+              </div>
+              <label className="flex items-center gap-2 text-sm cursor-pointer">
+                <Checkbox checked={useAgent} onCheckedChange={setUseAgent} />
+                <span>{t('backport.dialog.useAgent')}</span>
+              </label>
+              <label className="flex items-center gap-2 text-sm cursor-pointer">
+                <Checkbox checked={createPrs} onCheckedChange={setCreatePrs} />
+                <span>{t('backport.dialog.createPrs')}</span>
+              </label>
+              {error && <p className="text-sm text-destructive">{error}</p>}
+            </div>
+          )}
+
+          <DialogFooter>
+            {results ? (
+              <Button onClick={() => modal.hide()}>
+                {t('common:buttons.close')}
+              </Button>
+            ) : (
+              <>
+                <Button
+                  variant="outline"
+                  onClick={() => modal.hide()}
+                  disabled={pending}
+                >
+                  {t('common:buttons.cancel')}
+                </Button>
+                <Button
+                  onClick={handleConfirm}
+                  disabled={isLoading || pending || selected.length === 0}
+                >
+                  {pending
+                    ? t('backport.dialog.inProgress')
+                    : t('backport.dialog.action', { count: selected.length })}
+                </Button>
+              </>
+            )}
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    );
+  }
+);
+
+export const BackportDialog = defineModal<BackportDialogProps, void>(
+  BackportDialogImpl
+);
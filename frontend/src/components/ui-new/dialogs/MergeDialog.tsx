@@ -0,0 +1,172 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Textarea } from '@/components/ui/textarea';
+import NiceModal, { useModal } from '@ebay/nice-modal-react';
+import { defineModal } from '@/lib/modals';
+import { useMutation, useQuery } from '@tanstack/react-query';
+import { ApiError, attemptsApi } from '@/lib/api';
+import { formatBlockingGates } from '@/lib/qualityGates';
+import type { MergeError, QualityGateResult } from 'shared/types';
+
+export interface MergeDialogProps {
+  workspaceId: string;
+  repoId: string;
+}
+
+export type MergeDialogResult = {
+  action: 'merged' | 'canceled';
+};
+
+const MergeDialogImpl = NiceModal.create<MergeDialogProps>(
+  ({ workspaceId, repoId }) => {
+    const modal = useModal();
+    const { t } = useTranslation(['common']);
+    const [message, setMessage] = useState('');
+    const [error, setError] = useState<string | null>(null);
+    const [blockingGates, setBlockingGates] = useState<
+      QualityGateResult[] | null
+    >(null);
+
+    const { data: generated, isLoading } = useQuery({
+      queryKey: ['mergeCommitMessage', workspaceId, repoId],
+      queryFn: () => attemptsApi.getMergeCommitMessage(workspaceId, repoId),
+      enabled: modal.visible,
+      staleTime: 0,
+    });
+
+    useEffect(() => {
+      if (generated) {
+        setMessage(generated.message);
+      }
+    }, [generated]);
+
+    const mergeMutation = useMutation({
+      mutationFn: ({
+        commitMessage,
+        bypassQualityGates,
+      }: {
+        commitMessage: string;
+        bypassQualityGates: boolean;
+      }) =>
+        attemptsApi.merge(workspaceId, {
+          repo_id: repoId,
+          commit_message: commitMessage,
+          bypass_quality_gates: bypassQualityGates,
+        }),
+      onSuccess: () => {
+        modal.resolve({ action: 'merged' } as MergeDialogResult);
+        modal.hide();
+      },
+      onError: (err: unknown) => {
+        const errorData =
+          err instanceof ApiError
+            ? (err as ApiError<MergeError>).error_data
+            : undefined;
+        if (errorData?.type === 'quality_gates_failed') {
+          const gates = formatBlockingGates(errorData.gates);
+          if (!errorData.bypass_allowed) {
+            setError(t('workspaces.merge.qualityGatesBlocked', { gates }));
+            return;
+          }
+          setBlockingGates(errorData.gates);
+          setError(t('workspaces.merge.qualityGatesFailed', { gates }));
+          return;
+        }
+        setError(
+          err instanceof Error ? err.message : t('workspaces.merge.error')
+        );
+      },
+    });
+
+    const handleConfirm = () => {
+      setError(null);
+      mergeMutation.mutate({
+        commitMessage: message.trim(),
+        bypassQualityGates: blockingGates !== null,
+      });
+    };
+
+    const handleCancel = () => {
+      modal.resolve({ action: 'canceled' } as MergeDialogResult);
+      modal.hide();
+    };
+
+    const handleOpenChange = (open: boolean) => {
+      if (!open) {
+        handleCancel();
+      }
+    };
+
+    return (
+      <Dialog open={modal.visible} onOpenChange={handleOpenChange}>
+        <DialogContent className="sm:max-w-lg">
+          <DialogHeader>
+            <DialogTitle>{t('workspaces.merge.title')}</DialogTitle>
+            <DialogDescription>
+              {t('workspaces.merge.description')}
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-2">
+            <label
+              htmlFor="merge-commit-message"
+              className="text-sm font-medium"
+            >
+              {t('workspaces.merge.messageLabel')}
+            </label>
+            <Textarea
+              id="merge-commit-message"
+              value={message}
+              onChange={(e) => {
+                setMessage(e.target.value);
+                setError(null);
+                setBlockingGates(null);
+              }}
+              placeholder={
+                isLoading ? t('workspaces.merge.loading') : undefined
+              }
+              disabled={isLoading || mergeMutation.isPending}
+              className="min-h-[140px] font-mono text-sm"
+              autoFocus
+            />
+            {error && <p className="text-sm text-destructive">{error}</p>}
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={handleCancel}
+              disabled={mergeMutation.isPending}
+            >
+              {t('buttons.cancel')}
+            </Button>
+            <Button
+              onClick={handleConfirm}
+              variant={blockingGates ? 'destructive' : 'default'}
+              disabled={isLoading || mergeMutation.isPending || !message.trim()}
+            >
+              {mergeMutation.isPending
+                ? t('workspaces.merge.merging')
+                : blockingGates
+                  ? t('workspaces.merge.mergeAnyway')
+                  : t('workspaces.merge.action')}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    );
+  }
+);
+
+export const MergeDialog = defineModal<MergeDialogProps, MergeDialogResult>(
+  MergeDialogImpl
+);
@@ -0,0 +1,284 @@
+import { useState } from 'react';
+import { SpinnerIcon } from '@phosphor-icons/react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import NiceModal, { useModal } from '@ebay/nice-modal-react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Textarea } from '@/components/ui/textarea';
+import { defineModal } from '@/lib/modals';
+import { attemptsApi, type Result } from '@/lib/api';
+import type {
+  DiffHunk,
+  FileHunks,
+  HunkReviewError,
+  HunkScope,
+  HunkSelection,
+} from 'shared/types';
+
+export interface ReviewHunksDialogProps {
+  attemptId: string;
+  repoId: string;
+}
+
+const SCOPES: HunkScope[] = ['uncommitted', 'staged', 'committed'];
+
+// Files without hunks (binary or mode-only changes) are selected as a whole
+const hunkKey = (path: string, hunk?: DiffHunk) =>
+  `${path}\n${hunk?.header ?? ''}`;
+
+function buildSelection(
+  files: FileHunks[],
+  selected: Set<string>
+): HunkSelection[] {
+  return files.flatMap((file) => {
+    if (file.hunks.length === 0) {
+      return selected.has(hunkKey(file.path)) ? [{ path: file.path }] : [];
+    }
+    const hunks = file.hunks
+      .filter((hunk) => selected.has(hunkKey(file.path, hunk)))
+      .map((hunk) => hunk.header);
+    return hunks.length > 0 ? [{ path: file.path, hunks }] : [];
+  });
+}
+
+function HunkLines({ hunk }: { hunk: DiffHunk }) {
+  return (
+    <pre className="text-xs font-mono overflow-x-auto bg-muted/40 p-1">
+      {hunk.lines.map((line, i) => (
+        <div
+          key={i}
+          className={
+            line.startsWith('+')
+              ? 'text-success'
+              : line.startsWith('-')
+                ? 'text-destructive'
+                : 'text-muted-foreground'
+          }
+        >
+          {line || ' '}
+        </div>
+      ))}
+    </pre>
+  );
+}
+
+const ReviewHunksDialogImpl = NiceModal.create<ReviewHunksDialogProps>(
+  ({ attemptId, repoId }) => {
+    const modal = useModal();
+    const { t } = useTranslation(['tasks', 'common']);
+    const queryClient = useQueryClient();
+    const [scope, setScope] = useState<HunkScope>('uncommitted');
+    const [selected, setSelected] = useState<Set<string>>(new Set());
+    const [feedback, setFeedback] = useState('');
+    const [pending, setPending] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    const queryKey = ['workspaceHunks', attemptId, repoId, scope];
+    const { data: files = [], isLoading } = useQuery({
+      queryKey,
+      queryFn: () => attemptsApi.listHunks(attemptId, repoId, scope),
+    });
+
+    const changeScope = (next: HunkScope) => {
+      setScope(next);
+      setSelected(new Set());
+      setError(null);
+    };
+
+    const toggle = (keys: string[], checked: boolean) =>
+      setSelected((prev) => {
+        const next = new Set(prev);
+        keys.forEach((key) => (checked ? next.add(key) : next.delete(key)));
+        return next;
+      });
+
+    const run = async (
+      action: () => Promise<Result<unknown, HunkReviewError>>
+    ) => {
+      setPending(true);
+      setError(null);
+      try {
+        const result = await action();
+        if (!result.success) {
+          setError(
+            result.error
+              ? t(`hunks.errors.${result.error.type}`)
+              : (result.message ?? null)
+          );
+        } else {
+          setSelected(new Set());
+          setFeedback('');
+        }
+        await queryClient.invalidateQueries({ queryKey });
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setPending(false);
+      }
+    };
+
+    const selection = buildSelection(files, selected);
+
+    const handleAccept = () =>
+      run(() =>
+        attemptsApi.acceptHunks(attemptId, {
+          repo_id: repoId,
+          files: selection,
+        })
+      );
+
+    const handleUnstage = () =>
+      run(() =>
+        attemptsApi.unstageHunks(attemptId, {
+          repo_id: repoId,
+          files: selection,
+        })
+      );
+
+    const handleReject = () =>
+      run(() =>
+        attemptsApi.rejectHunks(attemptId, {
+          repo_id: repoId,
+          scope,
+          files: selection,
+          feedback: feedback.trim() || undefined,
+        })
+      );
+
+    return (
+      <Dialog
+        open={modal.visible}
+        onOpenChange={(open) => !open && modal.hide()}
+      >
+        <DialogContent className="sm:max-w-2xl">
+          <DialogHeader>
+            <DialogTitle>{t('hunks.dialog.title')}</DialogTitle>
+            <DialogDescription>
+              {t('hunks.dialog.description')}
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="flex gap-2">
+            {SCOPES.map((value) => (
+              <Button
+                key={value}
+                size="sm"
+                variant={scope === value ? 'default' : 'outline'}
+                onClick={() => changeScope(value)}
+                disabled={pending}
+              >
+                {t(`hunks.scope.${value}`)}
+              </Button>
+            ))}
+          </div>
+
+          {isLoading ? (
+            <div className="flex items-center justify-center py-8">
+              <SpinnerIcon className="h-6 w-6 animate-spin text-muted-foreground" />
+            </div>
+          ) : files.length === 0 ? (
+            <p className="text-sm text-muted-foreground py-4">
+              {t('hunks.dialog.empty')}
+            </p>
+          ) : (
+            <div className="max-h-[400px] overflow-auto space-y-3">
+              {files.map((file) => {
+                const keys =
+                  file.hunks.length === 0
+                    ? [hunkKey(file.path)]
+                    : file.hunks.map((hunk) => hunkKey(file.path, hunk));
+                return (
+                  <div key={file.path} className="space-y-1">
+                    <label className="flex items-center gap-2 text-sm font-medium cursor-pointer">
+                      <Checkbox
+                        checked={keys.every((key) => selected.has(key))}
+                        onCheckedChange={(checked) => toggle(keys, checked)}
+                      />
+                      <span className="truncate">{file.path}</span>
+                    </label>
+                    {file.hunks.map((hunk) => {
+                      const key = hunkKey(file.path, hunk);
+                      return (
+                        <div key={key} className="ml-6 space-y-1">
+                          <label className="flex items-center gap-2 text-xs cursor-pointer">
+                            <Checkbox
+                              checked={selected.has(key)}
+                              onCheckedChange={(checked) =>
+                                toggle([key], checked)
+                              }
+                            />
+                            <span className="font-mono truncate">
+                              {hunk.header}
+                            </span>
+                          </label>
+                          <HunkLines hunk={hunk} />
+                        </div>
+                      );
+                    })}
+                  </div>
+                );
+              })}
+            </div>
+          )}
+
+          <Textarea
+            value={feedback}
+            onChange={(e) => setFeedback(e.target.value)}
+            placeholder={t('hunks.dialog.feedbackPlaceholder')}
+            disabled={pending}
+          />
+          {error && <p className="text-sm text-destructive">{error}</p>}
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => modal.hide()}
+              disabled={pending}
+            >
+              {t('common:buttons.close')}
+            </Button>
+            <Button
+              variant="destructive"
+              onClick={handleReject}
+              disabled={pending || selection.length === 0}
+            >
+              {scope === 'committed'
+                ? t('hunks.dialog.revert', { count: selection.length })
+                : t('hunks.dialog.reject', { count: selection.length })}
+            </Button>
+            {scope === 'uncommitted' && (
+              <Button
+                onClick={handleAccept}
+                disabled={pending || selection.length === 0}
+              >
+                {t('hunks.dialog.accept', { count: selection.length })}
+              </Button>
+            )}
+            {scope === 'staged' && (
+              <Button
+                variant="outline"
+                onClick={handleUnstage}
+                disabled={pending || selection.length === 0}
+              >
+                {t('hunks.dialog.unstage', { count: selection.length })}
+              </Button>
+            )}
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    );
+  }
+);
+
+export const ReviewHunksDialog = defineModal<ReviewHunksDialogProps, void>(
+  ReviewHunksDialogImpl
+);
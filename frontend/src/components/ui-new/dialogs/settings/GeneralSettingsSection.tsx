@@ -9,11 +9,24 @@ import {
 import { FolderPickerDialog } from '@/components/dialogs/shared/FolderPickerDialog';
 import {
   type BaseCodingAgent,
+  type BranchArchiveMode,
+  type CiAutoFixConfig,
+  type CommitHookMode,
+  type CommitIdentityConfig,
+  type CommitIdentityMode,
+  type CommitSigningFormat,
   DEFAULT_COMMIT_REMINDER_PROMPT,
   DEFAULT_PR_DESCRIPTION_PROMPT,
   EditorType,
   type ExecutorProfileId,
+  type GitHostMapping,
+  type IssueDoneAction,
+  type MergeMethod,
+  type PrLifecycleConfig,
+  type PrWebhookConfig,
+  type QuotaExceededAction,
   type SendMessageShortcut,
+  type WorkspaceQuotaConfig,
   SoundFile,
   ThemeMode,
   UiLanguage,
@@ -24,6 +37,8 @@ import { toPrettyCase } from '@/utils/string';
 import { useTheme } from '@/components/ThemeProvider';
 import { useUserSystem } from '@/components/ConfigProvider';
 import { TagManager } from '@/components/TagManager';
+import { WorkspaceArchiveList } from '@/components/WorkspaceArchiveList';
+import { WorkspaceDiskUsageList } from '@/components/WorkspaceDiskUsageList';
 import { cn } from '@/lib/utils';
 import { PrimaryButton } from '../../primitives/PrimaryButton';
 import { IconButton } from '../../primitives/IconButton';
@@ -44,6 +59,8 @@ import {
   SettingsTextarea,
 } from './SettingsComponents';
 import { useSettingsDirty } from './SettingsDirtyContext';
+import { GitHostMappingsField } from './GitHostMappingsField';
+import { PrWebhookSecretField } from './PrWebhookSecretField';
 
 export function GeneralSettingsSection() {
   const { t } = useTranslation(['settings', 'common']);
@@ -238,6 +255,61 @@ export function GeneralSettingsSection() {
     label: toPrettyCase(sound),
   }));
 
+  const commitIdentityModes: CommitIdentityMode[] = ['user', 'bot'];
+  const commitIdentityOptions = commitIdentityModes.map((mode) => ({
+    value: mode,
+    label: t(`settings.general.commitIdentity.mode.${mode}`),
+  }));
+
+  const signingFormats: CommitSigningFormat[] = ['none', 'gpg', 'ssh'];
+  const signingFormatOptions = signingFormats.map((format) => ({
+    value: format,
+    label: t(`settings.general.commitIdentity.signingFormat.${format}`),
+  }));
+
+  const commitHookModes: CommitHookMode[] = ['run', 'run_and_retry', 'skip'];
+  const commitHookOptions = commitHookModes.map((mode) => ({
+    value: mode,
+    label: t(`settings.general.commits.hooks.${mode}`),
+  }));
+
+  const updateCommitIdentity = (patch: Partial<CommitIdentityConfig>) => {
+    if (!draft) return;
+    updateDraft({ commit_identity: { ...draft.commit_identity, ...patch } });
+  };
+
+  const updateCiAutoFix = (patch: Partial<CiAutoFixConfig>) => {
+    if (!draft) return;
+    updateDraft({ ci_auto_fix: { ...draft.ci_auto_fix, ...patch } });
+  };
+
+  const updatePrLifecycle = (patch: Partial<PrLifecycleConfig>) => {
+    if (!draft) return;
+    updateDraft({ pr_lifecycle: { ...draft.pr_lifecycle, ...patch } });
+  };
+
+  const updatePrWebhooks = (patch: Partial<PrWebhookConfig>) => {
+    if (!draft) return;
+    updateDraft({ pr_webhooks: { ...draft.pr_webhooks, ...patch } });
+  };
+
+  const updateWorkspaceQuota = (patch: Partial<WorkspaceQuotaConfig>) => {
+    if (!draft) return;
+    updateDraft({ workspace_quota: { ...draft.workspace_quota, ...patch } });
+  };
+
+  // Replaced rather than merged, so that removed hosts are dropped
+  const updateGitHosts = (gitHosts: GitHostMapping[]) => {
+    setDraft((prev: typeof config) => {
+      if (!prev) return prev;
+      const next = { ...prev, git_hosts: gitHosts };
+      if (!isEqual(next, config)) {
+        setDirty(true);
+      }
+      return next;
+    });
+  };
+
   return (
     <>
       {/* Status messages */}
@@ -514,6 +586,19 @@ export function GeneralSettingsSection() {
           />
         </SettingsField>
 
+        <SettingsField
+          label={t('settings.general.git.branchTemplate.label')}
+          description={t('settings.general.git.branchTemplate.helper')}
+        >
+          <SettingsInput
+            value={draft?.git_branch_template ?? ''}
+            onChange={(value) =>
+              updateDraft({ git_branch_template: value || null })
+            }
+            placeholder="{prefix}/{short_id}-{slug}"
+          />
+        </SettingsField>
+
         <SettingsField
           label={t('settings.general.git.workspaceDir.label')}
           description={t('settings.general.git.workspaceDir.helper')}
@@ -537,6 +622,82 @@ export function GeneralSettingsSection() {
             </PrimaryButton>
           </div>
         </SettingsField>
+
+        <SettingsField
+          label={t('settings.general.git.branchArchive.label')}
+          description={t('settings.general.git.branchArchive.helper')}
+        >
+          <SettingsSelect
+            value={draft?.branch_archive ?? 'refs'}
+            options={[
+              {
+                value: 'refs' as BranchArchiveMode,
+                label: t('settings.general.git.branchArchive.refs'),
+              },
+              {
+                value: 'bundle' as BranchArchiveMode,
+                label: t('settings.general.git.branchArchive.bundle'),
+              },
+              {
+                value: 'off' as BranchArchiveMode,
+                label: t('settings.general.git.branchArchive.off'),
+              },
+            ]}
+            onChange={(value: BranchArchiveMode) =>
+              updateDraft({ branch_archive: value })
+            }
+          />
+        </SettingsField>
+
+        <WorkspaceArchiveList />
+
+        <SettingsField
+          label={t('settings.general.git.quota.limit.label')}
+          description={t('settings.general.git.quota.limit.helper')}
+        >
+          <SettingsInput
+            value={draft?.workspace_quota.limit_mb?.toString() ?? ''}
+            onChange={(value) => {
+              const mb = parseInt(value, 10);
+              updateWorkspaceQuota({
+                // Sent as a JSON number
+                limit_mb:
+                  Number.isNaN(mb) || mb <= 0
+                    ? null
+                    : (mb as unknown as bigint),
+              });
+            }}
+            placeholder={t('settings.general.git.quota.limit.placeholder')}
+          />
+        </SettingsField>
+
+        <SettingsField
+          label={t('settings.general.git.quota.action.label')}
+          description={t('settings.general.git.quota.action.helper')}
+        >
+          <SettingsSelect
+            value={draft?.workspace_quota.action ?? 'warn'}
+            options={[
+              {
+                value: 'warn' as QuotaExceededAction,
+                label: t('settings.general.git.quota.action.warn'),
+              },
+              {
+                value: 'refuse' as QuotaExceededAction,
+                label: t('settings.general.git.quota.action.refuse'),
+              },
+              {
+                value: 'cleanup_archived' as QuotaExceededAction,
+                label: t('settings.general.git.quota.action.cleanupArchived'),
+              },
+            ]}
+            onChange={(value: QuotaExceededAction) =>
+              updateWorkspaceQuota({ action: value })
+            }
+          />
+        </SettingsField>
+
+        <WorkspaceDiskUsageList />
       </SettingsCard>
 
       {/* Pull Requests */}
@@ -585,6 +746,162 @@ export function GeneralSettingsSection() {
             disabled={draft?.pr_auto_description_prompt == null}
           />
         </SettingsField>
+
+        <SettingsCheckbox
+          id="pr-refresh-description"
+          label={t(
+            'settings.general.pullRequests.lifecycle.refreshDescription'
+          )}
+          description={t(
+            'settings.general.pullRequests.lifecycle.refreshDescriptionHelper'
+          )}
+          checked={draft?.pr_lifecycle.refresh_description ?? false}
+          onChange={(checked) =>
+            updatePrLifecycle({ refresh_description: checked })
+          }
+        />
+
+        <SettingsCheckbox
+          id="pr-auto-ready"
+          label={t('settings.general.pullRequests.lifecycle.autoReady')}
+          description={t(
+            'settings.general.pullRequests.lifecycle.autoReadyHelper'
+          )}
+          checked={draft?.pr_lifecycle.auto_ready ?? false}
+          onChange={(checked) => updatePrLifecycle({ auto_ready: checked })}
+        />
+
+        <SettingsField
+          label={t('settings.general.pullRequests.lifecycle.mergeMethod')}
+          description={t(
+            'settings.general.pullRequests.lifecycle.mergeMethodHelper'
+          )}
+        >
+          <SettingsSelect
+            value={draft?.pr_lifecycle.merge_method ?? 'squash'}
+            options={[
+              {
+                value: 'squash' as MergeMethod,
+                label: t('settings.general.pullRequests.lifecycle.squash'),
+              },
+              {
+                value: 'merge' as MergeMethod,
+                label: t('settings.general.pullRequests.lifecycle.merge'),
+              },
+              {
+                value: 'rebase' as MergeMethod,
+                label: t('settings.general.pullRequests.lifecycle.rebase'),
+              },
+            ]}
+            onChange={(value: MergeMethod) =>
+              updatePrLifecycle({ merge_method: value })
+            }
+          />
+        </SettingsField>
+
+        <SettingsField
+          label={t('settings.general.pullRequests.issueDoneAction.label')}
+          description={t(
+            'settings.general.pullRequests.issueDoneAction.helper'
+          )}
+        >
+          <SettingsSelect
+            value={draft?.issue_done_action ?? 'close'}
+            options={[
+              {
+                value: 'close' as IssueDoneAction,
+                label: t('settings.general.pullRequests.issueDoneAction.close'),
+              },
+              {
+                value: 'comment' as IssueDoneAction,
+                label: t(
+                  'settings.general.pullRequests.issueDoneAction.comment'
+                ),
+              },
+              {
+                value: 'nothing' as IssueDoneAction,
+                label: t(
+                  'settings.general.pullRequests.issueDoneAction.nothing'
+                ),
+              },
+            ]}
+            onChange={(value: IssueDoneAction) =>
+              updateDraft({ issue_done_action: value })
+            }
+          />
+        </SettingsField>
+
+        <SettingsCheckbox
+          id="ci-auto-fix"
+          label={t('settings.general.pullRequests.ciAutoFix.label')}
+          description={t('settings.general.pullRequests.ciAutoFix.helper')}
+          checked={draft?.ci_auto_fix.enabled ?? false}
+          onChange={(checked) => updateCiAutoFix({ enabled: checked })}
+        />
+
+        {draft?.ci_auto_fix.enabled && (
+          <>
+            <SettingsField
+              label={t('settings.general.pullRequests.ciAutoFix.maxAttempts')}
+              description={t(
+                'settings.general.pullRequests.ciAutoFix.maxAttemptsHelper'
+              )}
+            >
+              <SettingsInput
+                value={draft.ci_auto_fix.max_attempts.toString()}
+                onChange={(value) => {
+                  const attempts = parseInt(value, 10);
+                  updateCiAutoFix({
+                    max_attempts: Number.isNaN(attempts)
+                      ? 0
+                      : Math.max(attempts, 0),
+                  });
+                }}
+                placeholder="3"
+              />
+            </SettingsField>
+
+            <SettingsCheckbox
+              id="ci-auto-fix-logs"
+              label={t('settings.general.pullRequests.ciAutoFix.includeLogs')}
+              description={t(
+                'settings.general.pullRequests.ciAutoFix.includeLogsHelper'
+              )}
+              checked={draft.ci_auto_fix.include_logs}
+              onChange={(checked) => updateCiAutoFix({ include_logs: checked })}
+            />
+          </>
+        )}
+
+        <SettingsCheckbox
+          id="pr-webhooks"
+          label={t('settings.general.pullRequests.webhooks.label')}
+          description={t('settings.general.pullRequests.webhooks.helper')}
+          checked={draft?.pr_webhooks.enabled ?? false}
+          onChange={(checked) => updatePrWebhooks({ enabled: checked })}
+        />
+
+        {draft?.pr_webhooks.enabled && (
+          <>
+            <SettingsField
+              label={t('settings.general.pullRequests.webhooks.endpoint')}
+              description={t(
+                'settings.general.pullRequests.webhooks.endpointHelper'
+              )}
+            >
+              <p className="text-sm text-high font-mono break-all">
+                {`${window.location.origin}/api/webhooks/git-host`}
+              </p>
+            </SettingsField>
+
+            <PrWebhookSecretField />
+          </>
+        )}
+
+        <GitHostMappingsField
+          value={draft?.git_hosts ?? []}
+          onChange={updateGitHosts}
+        />
       </SettingsCard>
 
       {/* Commits */}
@@ -636,6 +953,122 @@ export function GeneralSettingsSection() {
             </SettingsField>
           </>
         )}
+
+        <SettingsCheckbox
+          id="conventional-commits"
+          label={t('settings.general.commits.conventional.label')}
+          description={t('settings.general.commits.conventional.helper')}
+          checked={draft?.conventional_commits_enabled ?? false}
+          onChange={(checked) =>
+            updateDraft({ conventional_commits_enabled: checked })
+          }
+        />
+
+        <SettingsCheckbox
+          id="quality-gate-bypass"
+          label={t('settings.general.commits.qualityGateBypass.label')}
+          description={t('settings.general.commits.qualityGateBypass.helper')}
+          checked={draft?.allow_quality_gate_bypass ?? false}
+          onChange={(checked) =>
+            updateDraft({ allow_quality_gate_bypass: checked })
+          }
+        />
+
+        <SettingsField
+          label={t('settings.general.commits.hooks.label')}
+          description={t('settings.general.commits.hooks.helper')}
+        >
+          <SettingsSelect
+            value={draft?.commit_hooks ?? 'run'}
+            options={commitHookOptions}
+            onChange={(value) => updateDraft({ commit_hooks: value })}
+          />
+        </SettingsField>
+      </SettingsCard>
+
+      {/* Commit identity */}
+      <SettingsCard
+        title={t('settings.general.commitIdentity.title')}
+        description={t('settings.general.commitIdentity.description')}
+      >
+        <SettingsField
+          label={t('settings.general.commitIdentity.mode.label')}
+          description={t('settings.general.commitIdentity.mode.helper')}
+        >
+          <SettingsSelect
+            value={draft?.commit_identity.mode}
+            options={commitIdentityOptions}
+            onChange={(value) => updateCommitIdentity({ mode: value })}
+          />
+        </SettingsField>
+
+        {draft?.commit_identity.mode === 'bot' && (
+          <>
+            <SettingsField
+              label={t('settings.general.commitIdentity.botName.label')}
+            >
+              <SettingsInput
+                value={draft.commit_identity.bot_name}
+                onChange={(value) => updateCommitIdentity({ bot_name: value })}
+                placeholder="Vibe Kanban"
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.general.commitIdentity.botEmail.label')}
+            >
+              <SettingsInput
+                value={draft.commit_identity.bot_email}
+                onChange={(value) => updateCommitIdentity({ bot_email: value })}
+                placeholder="noreply@vibekanban.com"
+              />
+            </SettingsField>
+          </>
+        )}
+
+        <SettingsCheckbox
+          id="co-author-trailers"
+          label={t('settings.general.commitIdentity.coAuthor.label')}
+          description={t('settings.general.commitIdentity.coAuthor.helper')}
+          checked={draft?.commit_identity.co_author_trailers ?? false}
+          onChange={(checked) =>
+            updateCommitIdentity({ co_author_trailers: checked })
+          }
+        />
+
+        <SettingsField
+          label={t('settings.general.commitIdentity.signingFormat.label')}
+          description={t(
+            'settings.general.commitIdentity.signingFormat.helper'
+          )}
+        >
+          <SettingsSelect
+            value={draft?.commit_identity.signing_format}
+            options={signingFormatOptions}
+            onChange={(value) =>
+              updateCommitIdentity({ signing_format: value })
+            }
+          />
+        </SettingsField>
+
+        {draft && draft.commit_identity.signing_format !== 'none' && (
+          <SettingsField
+            label={t('settings.general.commitIdentity.signingKey.label')}
+            description={t(
+              'settings.general.commitIdentity.signingKey.helper'
+            )}
+          >
+            <SettingsInput
+              value={draft.commit_identity.signing_key ?? ''}
+              onChange={(value) =>
+                updateCommitIdentity({ signing_key: value || null })
+              }
+              placeholder={t(
+                'settings.general.commitIdentity.signingKey.placeholder'
+              )}
+            />
+          </SettingsField>
+        )}
       </SettingsCard>
 
       {/* Notifications */}
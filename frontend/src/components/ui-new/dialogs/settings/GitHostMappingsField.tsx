@@ -0,0 +1,160 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { PlusIcon, TrashIcon } from '@phosphor-icons/react';
+import type { GitHostMapping, ProviderKind } from 'shared/types';
+import { configApi } from '@/lib/api';
+import { PrimaryButton } from '../../primitives/PrimaryButton';
+import { IconButton } from '../../primitives/IconButton';
+import {
+  SettingsField,
+  SettingsInput,
+  SettingsSelect,
+} from './SettingsComponents';
+
+const PROVIDERS: { value: ProviderKind; label: string }[] = [
+  { value: 'git_lab', label: 'GitLab' },
+  { value: 'gitea', label: 'Gitea / Forgejo' },
+  { value: 'bitbucket_server', label: 'Bitbucket Server' },
+  { value: 'bitbucket', label: 'Bitbucket Cloud' },
+  { value: 'git_hub', label: 'GitHub' },
+  { value: 'azure_dev_ops', label: 'Azure DevOps' },
+];
+
+// Tokens are stored apart from the config, so they are saved immediately
+function GitHostTokenInput({
+  host,
+  hasToken,
+}: {
+  host: string;
+  hasToken: boolean;
+}) {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const [token, setToken] = useState('');
+  const [pending, setPending] = useState(false);
+
+  const run = async (action: () => Promise<void>) => {
+    setPending(true);
+    try {
+      await action();
+      setToken('');
+      await queryClient.invalidateQueries({ queryKey: ['gitHostTokens'] });
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <div className="flex gap-2">
+      <div className="flex-1">
+        <SettingsInput
+          type="password"
+          value={token}
+          onChange={setToken}
+          disabled={pending || !host}
+          placeholder={t(
+            hasToken
+              ? 'settings.general.pullRequests.gitHosts.tokenSaved'
+              : 'settings.general.pullRequests.gitHosts.tokenPlaceholder'
+          )}
+        />
+      </div>
+      <PrimaryButton
+        variant="secondary"
+        disabled={pending || !host || !token.trim()}
+        onClick={() => run(() => configApi.setGitHostToken({ host, token }))}
+      >
+        {t('settings.general.pullRequests.gitHosts.saveToken')}
+      </PrimaryButton>
+      {hasToken && (
+        <PrimaryButton
+          variant="tertiary"
+          disabled={pending}
+          onClick={() => run(() => configApi.deleteGitHostToken(host))}
+        >
+          {t('settings.general.pullRequests.gitHosts.clearToken')}
+        </PrimaryButton>
+      )}
+    </div>
+  );
+}
+
+// Self-hosted git hosts, mapped to a provider with optional API credentials
+export function GitHostMappingsField({
+  value,
+  onChange,
+}: {
+  value: GitHostMapping[];
+  onChange: (value: GitHostMapping[]) => void;
+}) {
+  const { t } = useTranslation('settings');
+  const { data: tokenHosts = [] } = useQuery({
+    queryKey: ['gitHostTokens'],
+    queryFn: configApi.listGitHostTokens,
+  });
+
+  const update = (index: number, patch: Partial<GitHostMapping>) =>
+    onChange(value.map((m, i) => (i === index ? { ...m, ...patch } : m)));
+
+  return (
+    <SettingsField
+      label={t('settings.general.pullRequests.gitHosts.label')}
+      description={t('settings.general.pullRequests.gitHosts.helper')}
+    >
+      <div className="space-y-3">
+        {value.map((mapping, index) => (
+          <div key={index} className="space-y-2 border rounded-sm p-base">
+            <div className="flex gap-2">
+              <div className="flex-1">
+                <SettingsInput
+                  value={mapping.host}
+                  onChange={(host) => update(index, { host: host.trim() })}
+                  placeholder="git.example.com"
+                />
+              </div>
+              <div className="w-40">
+                <SettingsSelect
+                  value={mapping.provider}
+                  options={PROVIDERS}
+                  onChange={(provider) => update(index, { provider })}
+                />
+              </div>
+              <IconButton
+                icon={TrashIcon}
+                onClick={() => onChange(value.filter((_, i) => i !== index))}
+                aria-label={t('settings.general.pullRequests.gitHosts.remove')}
+                title={t('settings.general.pullRequests.gitHosts.remove')}
+              />
+            </div>
+            <SettingsInput
+              value={mapping.api_url ?? ''}
+              onChange={(apiUrl) =>
+                update(index, { api_url: apiUrl.trim() || null })
+              }
+              placeholder={t(
+                'settings.general.pullRequests.gitHosts.apiUrlPlaceholder'
+              )}
+            />
+            <GitHostTokenInput
+              host={mapping.host}
+              hasToken={tokenHosts.includes(mapping.host.toLowerCase())}
+            />
+          </div>
+        ))}
+        <PrimaryButton
+          variant="tertiary"
+          onClick={() =>
+            onChange([
+              ...value,
+              { host: '', provider: 'git_lab', api_url: null },
+            ])
+          }
+        >
+          <PlusIcon className="size-icon-sm" weight="bold" />
+          {t('settings.general.pullRequests.gitHosts.add')}
+        </PrimaryButton>
+      </div>
+    </SettingsField>
+  );
+}
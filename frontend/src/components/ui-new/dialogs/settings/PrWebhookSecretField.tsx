@@ -0,0 +1,82 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { ArrowsClockwiseIcon } from '@phosphor-icons/react';
+import { configApi } from '@/lib/api';
+import { PrimaryButton } from '../../primitives/PrimaryButton';
+import { SettingsField, SettingsInput } from './SettingsComponents';
+
+function generateWebhookSecret(): string {
+  const bytes = crypto.getRandomValues(new Uint8Array(24));
+  return Array.from(bytes, (b) => b.toString(16).padStart(2, '0')).join('');
+}
+
+// The secret is stored apart from the config and never read back, so it is
+// saved immediately and only shown while it is being entered
+export function PrWebhookSecretField() {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const { data: isSet = false } = useQuery({
+    queryKey: ['prWebhookSecret'],
+    queryFn: configApi.getPrWebhookSecretStatus,
+  });
+  const [secret, setSecret] = useState('');
+  const [pending, setPending] = useState(false);
+
+  const run = async (action: () => Promise<void>) => {
+    setPending(true);
+    try {
+      await action();
+      setSecret('');
+      await queryClient.invalidateQueries({ queryKey: ['prWebhookSecret'] });
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <SettingsField
+      label={t('settings.general.pullRequests.webhooks.secret')}
+      description={t('settings.general.pullRequests.webhooks.secretHelper')}
+    >
+      <div className="flex gap-2">
+        <div className="flex-1">
+          <SettingsInput
+            value={secret}
+            onChange={setSecret}
+            disabled={pending}
+            placeholder={t(
+              isSet
+                ? 'settings.general.pullRequests.webhooks.secretSaved'
+                : 'settings.general.pullRequests.webhooks.secretPlaceholder'
+            )}
+          />
+        </div>
+        <PrimaryButton
+          variant="tertiary"
+          disabled={pending}
+          onClick={() => setSecret(generateWebhookSecret())}
+        >
+          <ArrowsClockwiseIcon className="size-icon-sm" weight="bold" />
+          {t('settings.general.pullRequests.webhooks.generate')}
+        </PrimaryButton>
+        <PrimaryButton
+          variant="secondary"
+          disabled={pending || !secret.trim()}
+          onClick={() => run(() => configApi.setPrWebhookSecret({ secret }))}
+        >
+          {t('settings.general.pullRequests.webhooks.saveSecret')}
+        </PrimaryButton>
+        {isSet && (
+          <PrimaryButton
+            variant="tertiary"
+            disabled={pending}
+            onClick={() => run(configApi.deletePrWebhookSecret)}
+          >
+            {t('settings.general.pullRequests.webhooks.clearSecret')}
+          </PrimaryButton>
+        )}
+      </div>
+    </SettingsField>
+  );
+}
@@ -2,11 +2,23 @@ import { useCallback, useEffect, useMemo, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { isEqual } from 'lodash';
-import { GitBranchIcon, SpinnerIcon } from '@phosphor-icons/react';
+import {
+  GitBranchIcon,
+  PlusIcon,
+  SpinnerIcon,
+  TrashIcon,
+} from '@phosphor-icons/react';
 import { useRepoBranches } from '@/hooks/useRepoBranches';
 import { useScriptPlaceholders } from '@/hooks/useScriptPlaceholders';
 import { repoApi } from '@/lib/api';
-import type { Repo, UpdateRepo } from 'shared/types';
+import type {
+  CommitIdentityMode,
+  CommitSigningFormat,
+  QualityGate,
+  QualityGateInput,
+  Repo,
+  UpdateRepo,
+} from 'shared/types';
 import { SearchableDropdownContainer } from '../../containers/SearchableDropdownContainer';
 import {
   DropdownMenu,
@@ -22,6 +34,7 @@ import {
   SettingsTextarea,
   SettingsCheckbox,
   SettingsSaveBar,
+  SettingsSelect,
 } from './SettingsComponents';
 
 interface RepoScriptsFormState {
@@ -33,6 +46,14 @@ interface RepoScriptsFormState {
   cleanup_script: string;
   archive_script: string;
   copy_files: string;
+  init_submodules: boolean;
+  sparse_checkout: string;
+  commit_message_template: string;
+  commit_subject_max_length: string;
+  commit_identity_mode: CommitIdentityMode | 'inherit';
+  commit_signing_format: CommitSigningFormat | 'inherit';
+  commit_signing_key: string;
+  branch_name_template: string;
   dev_server_script: string;
 }
 
@@ -46,10 +67,27 @@ function repoToFormState(repo: Repo): RepoScriptsFormState {
     cleanup_script: repo.cleanup_script ?? '',
     archive_script: repo.archive_script ?? '',
     copy_files: repo.copy_files ?? '',
+    init_submodules: repo.init_submodules,
+    sparse_checkout: repo.sparse_checkout ?? '',
+    commit_message_template: repo.commit_message_template ?? '',
+    commit_subject_max_length: repo.commit_subject_max_length?.toString() ?? '',
+    commit_identity_mode: repo.commit_identity_mode ?? 'inherit',
+    commit_signing_format: repo.commit_signing_format ?? 'inherit',
+    commit_signing_key: repo.commit_signing_key ?? '',
+    branch_name_template: repo.branch_name_template ?? '',
     dev_server_script: repo.dev_server_script ?? '',
   };
 }
 
+function gatesToInputs(gates: QualityGate[]): QualityGateInput[] {
+  return gates.map(({ id, name, script, required }) => ({
+    id,
+    name,
+    script,
+    required,
+  }));
+}
+
 interface ReposSettingsSectionProps {
   initialState?: { repoId?: string };
 }
@@ -94,6 +132,16 @@ export function ReposSettingsSection({
 
   const [selectedRepo, setSelectedRepo] = useState<Repo | null>(null);
 
+  // Quality gates are stored separately from the repo row
+  const { data: qualityGates } = useQuery({
+    queryKey: ['repoQualityGates', selectedRepoId],
+    queryFn: () => repoApi.getQualityGates(selectedRepoId),
+    enabled: !!selectedRepoId,
+  });
+  const [gatesDraft, setGatesDraft] = useState<QualityGateInput[] | null>(
+    null
+  );
+
   // Form state
   const [draft, setDraft] = useState<RepoScriptsFormState | null>(null);
   const [saving, setSaving] = useState(false);
@@ -109,12 +157,17 @@ export function ReposSettingsSection({
     return !isEqual(draft, repoToFormState(selectedRepo));
   }, [draft, selectedRepo]);
 
+  const hasUnsavedGateChanges = useMemo(() => {
+    if (!gatesDraft || !qualityGates) return false;
+    return !isEqual(gatesDraft, gatesToInputs(qualityGates));
+  }, [gatesDraft, qualityGates]);
+
   // Handle repo selection
   const handleRepoSelect = useCallback(
     (id: string) => {
       if (id === selectedRepoId) return;
 
-      if (hasUnsavedChanges) {
+      if (hasUnsavedChanges || hasUnsavedGateChanges) {
         const confirmed = window.confirm(
           t('settings.repos.save.confirmSwitch')
         );
@@ -125,9 +178,10 @@ export function ReposSettingsSection({
         setError(null);
       }
 
+      setGatesDraft(null);
       setSelectedRepoId(id);
     },
-    [hasUnsavedChanges, selectedRepoId, t]
+    [hasUnsavedChanges, hasUnsavedGateChanges, selectedRepoId, t]
   );
 
   // Populate draft from server data
@@ -152,6 +206,11 @@ export function ReposSettingsSection({
     setDraft(repoToFormState(nextRepo));
   }, [repos, selectedRepoId, hasUnsavedChanges]);
 
+  useEffect(() => {
+    if (!qualityGates || hasUnsavedGateChanges) return;
+    setGatesDraft(gatesToInputs(qualityGates));
+  }, [qualityGates, hasUnsavedGateChanges]);
+
   const handleSave = async () => {
     if (!draft || !selectedRepo) return;
 
@@ -169,6 +228,21 @@ export function ReposSettingsSection({
         archive_script: draft.archive_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
         parallel_setup_script: draft.parallel_setup_script,
+        init_submodules: draft.init_submodules,
+        sparse_checkout: draft.sparse_checkout.trim() || null,
+        commit_message_template: draft.commit_message_template.trim() || null,
+        commit_subject_max_length:
+          parseInt(draft.commit_subject_max_length.trim(), 10) || null,
+        commit_identity_mode:
+          draft.commit_identity_mode === 'inherit'
+            ? null
+            : draft.commit_identity_mode,
+        commit_signing_format:
+          draft.commit_signing_format === 'inherit'
+            ? null
+            : draft.commit_signing_format,
+        commit_signing_key: draft.commit_signing_key.trim() || null,
+        branch_name_template: draft.branch_name_template.trim() || null,
         dev_server_script: draft.dev_server_script.trim() || null,
       };
 
@@ -178,6 +252,23 @@ export function ReposSettingsSection({
       queryClient.setQueryData(['repos'], (old: Repo[] | undefined) =>
         old?.map((r) => (r.id === updatedRepo.id ? updatedRepo : r))
       );
+
+      if (hasUnsavedGateChanges && gatesDraft) {
+        const savedGates = await repoApi.updateQualityGates(
+          selectedRepo.id,
+          gatesDraft.map((gate) => ({
+            ...gate,
+            name: gate.name.trim(),
+            script: gate.script.trim(),
+          }))
+        );
+        queryClient.setQueryData(
+          ['repoQualityGates', selectedRepo.id],
+          savedGates
+        );
+        setGatesDraft(gatesToInputs(savedGates));
+      }
+
       setSuccess(true);
       setTimeout(() => setSuccess(false), 3000);
     } catch (err) {
@@ -192,6 +283,7 @@ export function ReposSettingsSection({
   const handleDiscard = () => {
     if (!selectedRepo) return;
     setDraft(repoToFormState(selectedRepo));
+    if (qualityGates) setGatesDraft(gatesToInputs(qualityGates));
   };
 
   const updateDraft = (updates: Partial<RepoScriptsFormState>) => {
@@ -201,6 +293,23 @@ export function ReposSettingsSection({
     });
   };
 
+  const updateGate = (index: number, updates: Partial<QualityGateInput>) => {
+    setGatesDraft((prev) =>
+      prev?.map((gate, i) => (i === index ? { ...gate, ...updates } : gate))
+    );
+  };
+
+  const addGate = () => {
+    setGatesDraft((prev) => [
+      ...(prev ?? []),
+      { id: null, name: '', script: '', required: true },
+    ]);
+  };
+
+  const removeGate = (index: number) => {
+    setGatesDraft((prev) => prev?.filter((_, i) => i !== index) ?? null);
+  };
+
   if (reposLoading) {
     return (
       <div className="flex items-center justify-center py-8 gap-2">
@@ -450,10 +559,227 @@ export function ReposSettingsSection({
                 rows={3}
               />
             </SettingsField>
+
+            <SettingsCheckbox
+              id="init-submodules"
+              label={t('settings.repos.scripts.submodules.label')}
+              description={t('settings.repos.scripts.submodules.helper')}
+              checked={draft.init_submodules}
+              onChange={(checked) => updateDraft({ init_submodules: checked })}
+            />
+
+            <SettingsField
+              label={t('settings.repos.scripts.sparseCheckout.label')}
+              description={t('settings.repos.scripts.sparseCheckout.helper')}
+            >
+              <SettingsTextarea
+                value={draft.sparse_checkout}
+                onChange={(value) => updateDraft({ sparse_checkout: value })}
+                placeholder={t(
+                  'settings.repos.scripts.sparseCheckout.placeholder'
+                )}
+                rows={3}
+                monospace
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.commitMessage.label')}
+              description={t('settings.repos.scripts.commitMessage.helper')}
+            >
+              <SettingsTextarea
+                value={draft.commit_message_template}
+                onChange={(value) =>
+                  updateDraft({ commit_message_template: value })
+                }
+                placeholder={t(
+                  'settings.repos.scripts.commitMessage.placeholder'
+                )}
+                rows={3}
+                monospace
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.branchNameTemplate.label')}
+              description={t(
+                'settings.repos.scripts.branchNameTemplate.helper'
+              )}
+            >
+              <SettingsInput
+                value={draft.branch_name_template}
+                onChange={(value) =>
+                  updateDraft({ branch_name_template: value })
+                }
+                placeholder="{prefix}/{short_id}-{slug}"
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.commitSubjectMaxLength.label')}
+              description={t(
+                'settings.repos.scripts.commitSubjectMaxLength.helper'
+              )}
+            >
+              <SettingsInput
+                value={draft.commit_subject_max_length}
+                onChange={(value) =>
+                  updateDraft({ commit_subject_max_length: value })
+                }
+                placeholder="72"
+                error={
+                  draft.commit_subject_max_length.trim() !== '' &&
+                  !/^\d+$/.test(draft.commit_subject_max_length.trim())
+                }
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.commitIdentity.label')}
+              description={t('settings.repos.scripts.commitIdentity.helper')}
+            >
+              <SettingsSelect
+                value={draft.commit_identity_mode}
+                options={[
+                  {
+                    value: 'inherit',
+                    label: t('settings.repos.scripts.commitIdentity.inherit'),
+                  },
+                  {
+                    value: 'user',
+                    label: t('settings.general.commitIdentity.mode.user'),
+                  },
+                  {
+                    value: 'bot',
+                    label: t('settings.general.commitIdentity.mode.bot'),
+                  },
+                ]}
+                onChange={(value) =>
+                  updateDraft({ commit_identity_mode: value })
+                }
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.commitSigning.label')}
+              description={t('settings.repos.scripts.commitSigning.helper')}
+            >
+              <SettingsSelect
+                value={draft.commit_signing_format}
+                options={[
+                  {
+                    value: 'inherit',
+                    label: t('settings.repos.scripts.commitIdentity.inherit'),
+                  },
+                  {
+                    value: 'none',
+                    label: t(
+                      'settings.general.commitIdentity.signingFormat.none'
+                    ),
+                  },
+                  { value: 'gpg', label: 'GPG' },
+                  { value: 'ssh', label: 'SSH' },
+                ]}
+                onChange={(value) =>
+                  updateDraft({ commit_signing_format: value })
+                }
+              />
+            </SettingsField>
+
+            {draft.commit_signing_format !== 'none' && (
+              <SettingsField
+                label={t('settings.general.commitIdentity.signingKey.label')}
+                description={t(
+                  'settings.repos.scripts.commitSigningKey.helper'
+                )}
+              >
+                <SettingsInput
+                  value={draft.commit_signing_key}
+                  onChange={(value) =>
+                    updateDraft({ commit_signing_key: value })
+                  }
+                  placeholder={t(
+                    'settings.general.commitIdentity.signingKey.placeholder'
+                  )}
+                />
+              </SettingsField>
+            )}
           </SettingsCard>
 
+          {/* Quality gates */}
+          {gatesDraft && (
+            <SettingsCard
+              title={t('settings.repos.qualityGates.title')}
+              description={t('settings.repos.qualityGates.description')}
+              headerAction={
+                <button
+                  className="p-half rounded-sm hover:bg-secondary text-low hover:text-normal"
+                  onClick={addGate}
+                  title={t('settings.repos.qualityGates.add')}
+                >
+                  <PlusIcon className="size-icon-xs" weight="bold" />
+                </button>
+              }
+            >
+              {gatesDraft.length === 0 && (
+                <p className="text-sm text-low">
+                  {t('settings.repos.qualityGates.empty')}
+                </p>
+              )}
+              {gatesDraft.map((gate, index) => (
+                <div
+                  key={gate.id ?? `new-${index}`}
+                  className="space-y-base bg-secondary rounded-sm p-base"
+                >
+                  <div className="flex items-center gap-base">
+                    <div className="flex-1">
+                      <SettingsInput
+                        value={gate.name}
+                        onChange={(value) => updateGate(index, { name: value })}
+                        placeholder={t(
+                          'settings.repos.qualityGates.namePlaceholder'
+                        )}
+                        error={!gate.name.trim()}
+                      />
+                    </div>
+                    <button
+                      className="p-half rounded-sm hover:bg-primary text-low hover:text-error"
+                      onClick={() => removeGate(index)}
+                      title={t('settings.repos.qualityGates.remove')}
+                    >
+                      <TrashIcon className="size-icon-xs" />
+                    </button>
+                  </div>
+                  <SettingsTextarea
+                    value={gate.script}
+                    onChange={(value) => updateGate(index, { script: value })}
+                    placeholder={t(
+                      'settings.repos.qualityGates.scriptPlaceholder'
+                    )}
+                    rows={2}
+                    monospace
+                  />
+                  <SettingsCheckbox
+                    id={`quality-gate-required-${index}`}
+                    label={t('settings.repos.qualityGates.required.label')}
+                    description={t(
+                      'settings.repos.qualityGates.required.helper'
+                    )}
+                    checked={gate.required}
+                    onChange={(checked) =>
+                      updateGate(index, { required: checked })
+                    }
+                  />
+                </div>
+              ))}
+            </SettingsCard>
+          )}
+
           <SettingsSaveBar
-            show={hasUnsavedChanges}
+            show={hasUnsavedChanges || hasUnsavedGateChanges}
+            saveDisabled={gatesDraft?.some(
+              (gate) => !gate.name.trim() || !gate.script.trim()
+            )}
             saving={saving}
             onSave={handleSave}
             onDiscard={handleDiscard}
@@ -285,16 +285,18 @@ export function SettingsInput({
   placeholder,
   error,
   disabled,
+  type = 'text',
 }: {
   value: string;
   onChange: (value: string) => void;
   placeholder?: string;
   error?: boolean;
   disabled?: boolean;
+  type?: 'text' | 'password';
 }) {
   return (
     <input
-      type="text"
+      type={type}
       value={value}
       onChange={(e) => onChange(e.target.value)}
       placeholder={placeholder}
@@ -26,6 +26,7 @@ const RUN_REASON_LABELS: Record<ExecutionProcessRunReason, string> = {
   setupscript: 'Setup Script',
   cleanupscript: 'Cleanup Script',
   archivescript: 'Archive Script',
+  qualitygate: 'Quality Gate',
   devserver: 'Dev Server',
 };
 
@@ -35,6 +36,7 @@ const RUN_REASON_ICONS: Record<ExecutionProcessRunReason, typeof TerminalIcon> =
     setupscript: GearIcon,
     cleanupscript: GearIcon,
     archivescript: GearIcon,
+    qualitygate: GearIcon,
     devserver: GlobeIcon,
   };
 
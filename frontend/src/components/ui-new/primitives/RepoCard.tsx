@@ -12,6 +12,13 @@ import {
   SpinnerGapIcon,
   WarningCircleIcon,
   DotsThreeIcon,
+  XCircleIcon,
+  ClockCounterClockwiseIcon,
+  CircleDashedIcon,
+  PlayIcon,
+  RobotIcon,
+  EyeIcon,
+  PencilSimpleLineIcon,
 } from '@phosphor-icons/react';
 import { useTranslation } from 'react-i18next';
 import {
@@ -22,6 +29,7 @@ import {
 } from './Dropdown';
 import { SplitButton, type SplitButtonOption } from './SplitButton';
 import { useRepoAction } from '@/stores/useUiPreferencesStore';
+import type { PrCheck, QualityGateResult } from 'shared/types';
 
 export type RepoAction =
   | 'pull-request'
@@ -48,17 +56,28 @@ interface RepoCardProps {
   prNumber?: number;
   prUrl?: string;
   prStatus?: 'open' | 'merged' | 'closed' | 'unknown';
+  prIsDraft?: boolean;
+  isDraftTogglePending?: boolean;
+  prAutoMerge?: boolean;
+  isAutoMergeTogglePending?: boolean;
   showPushButton?: boolean;
   isPushPending?: boolean;
   isPushSuccess?: boolean;
   isPushError?: boolean;
   isTargetRemote?: boolean;
+  qualityGates?: QualityGateResult[];
+  prChecks?: PrCheck[];
+  ciFixAttempts?: number;
   branchDropdownContent?: React.ReactNode;
   onChangeTarget?: () => void;
   onRebase?: () => void;
   onActionsClick?: (action: RepoAction) => void;
   onPushClick?: () => void;
+  onToggleDraft?: (draft: boolean) => void;
+  onToggleAutoMerge?: (autoMerge: boolean) => void;
   onMoreClick?: () => void;
+  onRunQualityGates?: () => void;
+  onSendGateFailure?: (executionProcessId: string) => void;
 }
 
 export function RepoCard({
@@ -70,17 +89,28 @@ export function RepoCard({
   prNumber,
   prUrl,
   prStatus,
+  prIsDraft = false,
+  isDraftTogglePending = false,
+  prAutoMerge = false,
+  isAutoMergeTogglePending = false,
   showPushButton = false,
   isPushPending = false,
   isPushSuccess = false,
   isPushError = false,
   isTargetRemote = false,
+  qualityGates = [],
+  prChecks = [],
+  ciFixAttempts = 0,
   branchDropdownContent,
   onChangeTarget,
   onRebase,
   onActionsClick,
   onPushClick,
+  onToggleDraft,
+  onToggleAutoMerge,
   onMoreClick,
+  onRunQualityGates,
+  onSendGateFailure,
 }: RepoCardProps) {
   const { t } = useTranslation('tasks');
   const { t: tCommon } = useTranslation('common');
@@ -112,6 +142,8 @@ export function RepoCard({
     );
   }, [availableActionOptions, selectedAction]);
 
+  const isRunningGates = qualityGates.some((gate) => gate.status === 'running');
+
   return (
     <div className="bg-primary rounded-sm my-base p-base space-y-base">
       <div className="font-medium">{name}</div>
@@ -202,6 +234,56 @@ export function RepoCard({
               {t('git.pr.open', { number: prNumber })}
             </span>
           )}
+          {hasPrOpen && prIsDraft && (
+            <span className="px-half rounded-sm bg-panel text-low text-xs">
+              {t('git.pr.draft')}
+            </span>
+          )}
+          {/* Draft toggle - marks the PR ready or converts it back */}
+          {hasPrOpen && onToggleDraft && (
+            <button
+              onClick={() => onToggleDraft(!prIsDraft)}
+              disabled={isDraftTogglePending}
+              title={
+                prIsDraft ? t('git.pr.markReady') : t('git.pr.convertToDraft')
+              }
+              className="inline-flex items-center gap-half px-base py-half rounded-sm bg-panel text-normal hover:bg-tertiary text-sm font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              {isDraftTogglePending ? (
+                <SpinnerGapIcon className="size-icon-xs animate-spin" />
+              ) : prIsDraft ? (
+                <EyeIcon className="size-icon-xs" weight="bold" />
+              ) : (
+                <PencilSimpleLineIcon className="size-icon-xs" weight="bold" />
+              )}
+              {prIsDraft && t('git.pr.markReady')}
+            </button>
+          )}
+          {/* Auto-merge toggle - merges once approved with passing checks */}
+          {hasPrOpen && onToggleAutoMerge && (
+            <button
+              onClick={() => onToggleAutoMerge(!prAutoMerge)}
+              disabled={isAutoMergeTogglePending}
+              aria-pressed={prAutoMerge}
+              title={
+                prAutoMerge
+                  ? t('git.pr.disableAutoMerge')
+                  : t('git.pr.enableAutoMerge')
+              }
+              className={`inline-flex items-center gap-half px-base py-half rounded-sm text-sm font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed ${
+                prAutoMerge
+                  ? 'bg-brand/10 text-brand hover:bg-brand/20'
+                  : 'bg-panel text-normal hover:bg-tertiary'
+              }`}
+            >
+              {isAutoMergeTogglePending ? (
+                <SpinnerGapIcon className="size-icon-xs animate-spin" />
+              ) : (
+                <GitMergeIcon className="size-icon-xs" weight="bold" />
+              )}
+              {prAutoMerge && t('git.pr.autoMerge')}
+            </button>
+          )}
           {/* Push button - shows loading/success/error state */}
           {(showPushButton ||
             isPushPending ||
@@ -239,6 +321,103 @@ export function RepoCard({
         </div>
       )}
 
+      {/* CI checks reported by the git host for the open PR */}
+      {prChecks.length > 0 && (
+        <div className="space-y-half">
+          <div className="flex items-center justify-between gap-base">
+            <span className="text-sm font-medium text-normal">
+              {t('git.ciChecks.title')}
+            </span>
+            {ciFixAttempts > 0 && (
+              <span className="text-xs text-low">
+                {t('git.ciChecks.fixAttempts', { count: ciFixAttempts })}
+              </span>
+            )}
+          </div>
+          {prChecks.map((check) => (
+            <div
+              key={check.name}
+              className="flex items-center gap-half text-sm"
+            >
+              <CheckStatusIcon status={check.status} />
+              <span className="min-w-0 flex-1 truncate text-normal">
+                {check.name}
+                {!check.required && (
+                  <span className="text-low">
+                    {' '}
+                    {t('git.ciChecks.optional')}
+                  </span>
+                )}
+              </span>
+              <span className="text-xs text-low shrink-0">
+                {t(`git.ciChecks.status.${check.status}`)}
+              </span>
+              {check.url && (
+                <button
+                  onClick={() => window.open(check.url ?? '', '_blank')}
+                  className="flex items-center justify-center p-1 rounded hover:bg-tertiary text-low hover:text-base transition-colors shrink-0"
+                  title={t('git.ciChecks.viewDetails')}
+                >
+                  <ArrowSquareOutIcon className="size-icon-xs" />
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+
+      {/* Quality gates - required gates must pass before merge, push or PR */}
+      {qualityGates.length > 0 && (
+        <div className="space-y-half">
+          <div className="flex items-center justify-between gap-base">
+            <span className="text-sm font-medium text-normal">
+              {t('git.qualityGates.title')}
+            </span>
+            <button
+              onClick={onRunQualityGates}
+              disabled={isRunningGates}
+              className="inline-flex items-center gap-half px-base py-half rounded-sm bg-panel text-normal hover:bg-tertiary text-sm transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              <PlayIcon className="size-icon-xs" weight="fill" />
+              {t('git.qualityGates.run')}
+            </button>
+          </div>
+          {qualityGates.map((gate) => (
+            <div
+              key={gate.gate_id}
+              className="flex items-center gap-half text-sm"
+            >
+              <CheckStatusIcon status={gate.status} />
+              <span className="min-w-0 flex-1 truncate text-normal">
+                {gate.name}
+                {!gate.required && (
+                  <span className="text-low">
+                    {' '}
+                    {t('git.qualityGates.optional')}
+                  </span>
+                )}
+              </span>
+              <span className="text-xs text-low shrink-0">
+                {t(`git.qualityGates.status.${gate.status}`)}
+              </span>
+              {gate.status === 'failed' && gate.execution_process_id && (
+                <button
+                  onClick={() => {
+                    if (gate.execution_process_id) {
+                      onSendGateFailure?.(gate.execution_process_id);
+                    }
+                  }}
+                  className="flex items-center justify-center p-1 rounded hover:bg-tertiary text-low hover:text-base transition-colors shrink-0"
+                  title={t('git.qualityGates.sendToAgent')}
+                >
+                  <RobotIcon className="size-icon-xs" />
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Actions row - only show when there are available actions */}
       {availableActionOptions.length > 0 && (
         <div className="my-base">
@@ -253,3 +432,41 @@ export function RepoCard({
     </div>
   );
 }
+
+function CheckStatusIcon({
+  status,
+}: {
+  status: QualityGateResult['status'] | PrCheck['status'];
+}) {
+  switch (status) {
+    case 'passed':
+      return (
+        <CheckCircleIcon
+          className="size-icon-xs text-success shrink-0"
+          weight="fill"
+        />
+      );
+    case 'failed':
+      return (
+        <XCircleIcon
+          className="size-icon-xs text-error shrink-0"
+          weight="fill"
+        />
+      );
+    case 'running':
+    case 'pending':
+      return (
+        <SpinnerGapIcon
+          className="size-icon-xs text-low animate-spin shrink-0"
+        />
+      );
+    case 'outdated':
+      return (
+        <ClockCounterClockwiseIcon className="size-icon-xs text-low shrink-0" />
+      );
+    case 'not_run':
+    case 'skipped':
+    default:
+      return <CircleDashedIcon className="size-icon-xs text-low shrink-0" />;
+  }
+}
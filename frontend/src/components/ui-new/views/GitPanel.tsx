@@ -7,6 +7,7 @@ import {
 } from '@/components/ui-new/primitives/RepoCard';
 import { InputField } from '@/components/ui-new/primitives/InputField';
 import { ErrorAlert } from '@/components/ui-new/primitives/ErrorAlert';
+import type { PrCheck, QualityGateResult } from 'shared/types';
 
 export interface RepoInfo {
   id: string;
@@ -18,11 +19,18 @@ export interface RepoInfo {
   prNumber?: number;
   prUrl?: string;
   prStatus?: 'open' | 'merged' | 'closed' | 'unknown';
+  prIsDraft?: boolean;
+  isDraftTogglePending?: boolean;
+  prAutoMerge?: boolean;
+  isAutoMergeTogglePending?: boolean;
   showPushButton?: boolean;
   isPushPending?: boolean;
   isPushSuccess?: boolean;
   isPushError?: boolean;
   isTargetRemote?: boolean;
+  qualityGates?: QualityGateResult[];
+  prChecks?: PrCheck[];
+  ciFixAttempts?: number;
 }
 
 interface GitPanelProps {
@@ -31,6 +39,10 @@ interface GitPanelProps {
   onWorkingBranchNameChange: (name: string) => void;
   onActionsClick?: (repoId: string, action: RepoAction) => void;
   onPushClick?: (repoId: string) => void;
+  onToggleDraft?: (repoId: string, draft: boolean) => void;
+  onToggleAutoMerge?: (repoId: string, autoMerge: boolean) => void;
+  onRunQualityGates?: (repoId: string) => void;
+  onSendGateFailure?: (executionProcessId: string) => void;
   onMoreClick?: (repoId: string) => void;
   onAddRepo?: () => void;
   className?: string;
@@ -43,6 +55,10 @@ export function GitPanel({
   onWorkingBranchNameChange,
   onActionsClick,
   onPushClick,
+  onToggleDraft,
+  onToggleAutoMerge,
+  onRunQualityGates,
+  onSendGateFailure,
   onMoreClick,
   className,
   error,
@@ -69,16 +85,29 @@ export function GitPanel({
             prNumber={repo.prNumber}
             prUrl={repo.prUrl}
             prStatus={repo.prStatus}
+            prIsDraft={repo.prIsDraft}
+            isDraftTogglePending={repo.isDraftTogglePending}
+            prAutoMerge={repo.prAutoMerge}
+            isAutoMergeTogglePending={repo.isAutoMergeTogglePending}
             showPushButton={repo.showPushButton}
             isPushPending={repo.isPushPending}
             isPushSuccess={repo.isPushSuccess}
             isPushError={repo.isPushError}
             isTargetRemote={repo.isTargetRemote}
+            qualityGates={repo.qualityGates}
+            prChecks={repo.prChecks}
+            ciFixAttempts={repo.ciFixAttempts}
             onChangeTarget={() => onActionsClick?.(repo.id, 'change-target')}
             onRebase={() => onActionsClick?.(repo.id, 'rebase')}
             onActionsClick={(action) => onActionsClick?.(repo.id, action)}
             onPushClick={() => onPushClick?.(repo.id)}
+            onToggleDraft={(draft) => onToggleDraft?.(repo.id, draft)}
+            onToggleAutoMerge={(autoMerge) =>
+              onToggleAutoMerge?.(repo.id, autoMerge)
+            }
             onMoreClick={() => onMoreClick?.(repo.id)}
+            onRunQualityGates={() => onRunQualityGates?.(repo.id)}
+            onSendGateFailure={onSendGateFailure}
           />
         ))}
         <div className="bg-primary flex flex-col gap-base w-full p-base rounded-sm my-base">
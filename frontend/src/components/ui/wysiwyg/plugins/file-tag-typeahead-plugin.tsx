@@ -103,7 +103,11 @@ export function FileTagTypeaheadPlugin({
       const localFilePaths = new Set(localFiles.map((f) => f.path));
 
       // Here query is a string, including possible empty string ''
-      searchTagsAndFiles(query, { repoIds, projectId })
+      searchTagsAndFiles(query, {
+        repoIds,
+        projectId,
+        workspaceId: workspaceContext?.workspaceId,
+      })
         .then((serverResults) => {
           // Separate tags and files from server results
           const tagResults = serverResults.filter((r) => r.type === 'tag');
@@ -132,7 +136,7 @@ export function FileTagTypeaheadPlugin({
           console.error('Failed to search tags/files', err);
         });
     },
-    [repoIds, projectId, diffPaths]
+    [repoIds, projectId, diffPaths, workspaceContext?.workspaceId]
   );
 
   return (
@@ -5,6 +5,7 @@ export const PROCESS_RUN_REASONS = {
   SETUP_SCRIPT: 'setupscript' as ExecutionProcessRunReason,
   CLEANUP_SCRIPT: 'cleanupscript' as ExecutionProcessRunReason,
   ARCHIVE_SCRIPT: 'archivescript' as ExecutionProcessRunReason,
+  QUALITY_GATE: 'qualitygate' as ExecutionProcessRunReason,
   CODING_AGENT: 'codingagent' as ExecutionProcessRunReason,
   DEV_SERVER: 'devserver' as ExecutionProcessRunReason,
 } as const;
@@ -0,0 +1,32 @@
+import { useQuery } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+import type { PrReviewThread } from 'shared/types';
+
+export const prReviewThreadsKeys = {
+  all: ['prReviewThreads'] as const,
+  byAttempt: (attemptId: string | undefined, repoId: string | undefined) =>
+    ['prReviewThreads', attemptId, repoId] as const,
+};
+
+type Options = {
+  enabled?: boolean;
+};
+
+export function usePrReviewThreads(
+  attemptId?: string,
+  repoId?: string,
+  opts?: Options
+) {
+  const enabled = (opts?.enabled ?? true) && !!attemptId && !!repoId;
+
+  return useQuery<PrReviewThread[]>({
+    queryKey: prReviewThreadsKeys.byAttempt(attemptId, repoId),
+    queryFn: () => attemptsApi.getPrReviewThreads(attemptId!, repoId!),
+    enabled,
+    // Threads move on as the PR monitor completes follow-ups
+    refetchInterval: (query) =>
+      query.state.data?.some((thread) => thread.status === 'in_progress')
+        ? 15_000
+        : false,
+  });
+}
@@ -10,6 +10,7 @@ import {
   CreatePrApiRequest,
   CreateTask,
   CreateAndStartTaskRequest,
+  ImportIssuesRequest,
   CreateTaskAttemptBody,
   CreateTag,
   DirectoryListResponse,
@@ -26,6 +27,7 @@ import {
   SearchMode,
   SearchResult,
   Task,
+  TaskIssue,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -43,9 +45,23 @@ import {
   RebaseTaskAttemptRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
+  WidenSparseCheckoutRequest,
+  WorkspaceCommit,
+  FileBlameRange,
+  ExportFormat,
+  ImportWorkspaceRequest,
+  ImportWorkspaceError,
+  SquashCommitsRequest,
+  RewordCommitRequest,
+  DiskUsageReport,
+  DropCommitRequest,
+  RewriteHistoryResponse,
+  WidenSparseCheckoutResponse,
   RenameBranchRequest,
   RenameBranchResponse,
   CheckEditorAvailabilityResponse,
+  SetGitHostTokenRequest,
+  SetPrWebhookSecretRequest,
   AvailabilityInfo,
   BaseCodingAgent,
   ExecutorProfileId,
@@ -78,11 +94,22 @@ import {
   CurrentUserResponse,
   QueueStatus,
   PrCommentsResponse,
+  PrReviewThread,
+  AddressPrReviewRequest,
+  AddressPrReviewResponse,
+  AddressPrReviewError,
+  SetPrDraftRequest,
+  SetPrDraftError,
+  SetPrAutoMergeRequest,
+  SetPrAutoMergeError,
   MergeTaskAttemptRequest,
+  MergeCommitMessageResponse,
   PushTaskAttemptRequest,
   RepoBranchStatus,
   AbortConflictsRequest,
   ContinueRebaseRequest,
+  ResolveConflictsWithAgentRequest,
+  ResolveConflictsError,
   Session,
   Workspace,
   StartReviewRequest,
@@ -90,11 +117,29 @@ import {
   OpenPrInfo,
   GitRemote,
   ListPrsError,
+  GitHostIssue,
+  ListIssuesError,
   CreateWorkspaceFromPrBody,
   CreateWorkspaceFromPrResponse,
   CreateFromPrError,
   MigrationRequest,
   MigrationResponse,
+  MergeError,
+  QualityGate,
+  QualityGateInput,
+  RunQualityGatesRequest,
+  SendQualityGateFailureRequest,
+  QualityGateFollowUpError,
+  WorkspaceBranchArchive,
+  BackportRequest,
+  BackportResult,
+  HunkScope,
+  FileHunks,
+  AcceptHunksRequest,
+  UnstageHunksRequest,
+  RejectHunksRequest,
+  RejectHunksResponse,
+  HunkReviewError,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -369,6 +414,21 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getIssues: async (projectId: string): Promise<TaskIssue[]> => {
+    const response = await makeRequest(
+      `/api/tasks/issues?project_id=${projectId}`
+    );
+    return handleApiResponse<TaskIssue[]>(response);
+  },
+
+  importIssues: async (data: ImportIssuesRequest): Promise<Task[]> => {
+    const response = await makeRequest(`/api/tasks/import-issues`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task[]>(response);
+  },
 };
 
 // Sessions API
@@ -456,6 +516,12 @@ export const attemptsApi = {
     return handleApiResponse<number>(response);
   },
 
+  /** Disk usage per workspace and repo, with the configured quota */
+  getDiskUsage: async (): Promise<DiskUsageReport> => {
+    const response = await makeRequest('/api/task-attempts/disk-usage');
+    return handleApiResponse<DiskUsageReport>(response);
+  },
+
   get: async (attemptId: string): Promise<Workspace> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}`);
     return handleApiResponse<Workspace>(response);
@@ -593,7 +659,17 @@ export const attemptsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponse<void, MergeError>(response);
+  },
+
+  getMergeCommitMessage: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<MergeCommitMessageResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/merge/commit-message?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<MergeCommitMessageResponse>(response);
   },
 
   push: async (
@@ -635,6 +711,200 @@ export const attemptsApi = {
     return handleApiResponseAsResult<void, GitOperationError>(response);
   },
 
+  /**
+   * Cherry-pick the workspace changes onto other branches, one derived
+   * workspace per target branch.
+   */
+  backport: async (
+    attemptId: string,
+    data: BackportRequest
+  ): Promise<BackportResult[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/backport`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<BackportResult[]>(response);
+  },
+
+  listHunks: async (
+    attemptId: string,
+    repoId: string,
+    scope: HunkScope
+  ): Promise<FileHunks[]> => {
+    const params = new URLSearchParams({ repo_id: repoId, scope });
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/hunks?${params.toString()}`
+    );
+    return handleApiResponse<FileHunks[]>(response);
+  },
+
+  /** Stage the selected uncommitted hunks to mark them as accepted. */
+  acceptHunks: async (
+    attemptId: string,
+    data: AcceptHunksRequest
+  ): Promise<Result<void, HunkReviewError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/hunks/accept`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, HunkReviewError>(response);
+  },
+
+  /** Move accepted hunks back to the changes still to review. */
+  unstageHunks: async (
+    attemptId: string,
+    data: UnstageHunksRequest
+  ): Promise<Result<void, HunkReviewError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/hunks/unstage`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, HunkReviewError>(response);
+  },
+
+  /**
+   * Discard uncommitted hunks or revert committed ones, optionally sending
+   * feedback to the coding agent.
+   */
+  rejectHunks: async (
+    attemptId: string,
+    data: RejectHunksRequest
+  ): Promise<Result<RejectHunksResponse, HunkReviewError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/hunks/reject`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<RejectHunksResponse, HunkReviewError>(
+      response
+    );
+  },
+
+  getCommits: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<WorkspaceCommit[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<WorkspaceCommit[]>(response);
+  },
+
+  getFileBlame: async (
+    attemptId: string,
+    repoId: string,
+    path: string
+  ): Promise<FileBlameRange[]> => {
+    const params = new URLSearchParams({ repo_id: repoId, path });
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/blame?${params.toString()}`
+    );
+    return handleApiResponse<FileBlameRange[]>(response);
+  },
+
+  /**
+   * Download the workspace changes. Without a repo, every repo with commits
+   * is archived into one tar file.
+   */
+  exportChanges: async (
+    attemptId: string,
+    format: ExportFormat,
+    repoId?: string
+  ): Promise<File> => {
+    const params = new URLSearchParams({ format });
+    if (repoId) params.set('repo_id', repoId);
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/export?${params.toString()}`
+    );
+    if (!response.ok) {
+      await handleApiResponse<void>(response);
+    }
+    const disposition = response.headers.get('Content-Disposition') ?? '';
+    const fileName =
+      /filename="([^"]+)"/.exec(disposition)?.[1] ?? `changes.${format}`;
+    return new File([await response.blob()], fileName);
+  },
+
+  /**
+   * Create a workspace for a task from a file produced by `exportChanges`.
+   */
+  importWorkspace: async (
+    file: File,
+    data: ImportWorkspaceRequest
+  ): Promise<Result<Workspace, ImportWorkspaceError>> => {
+    const formData = new FormData();
+    formData.append('metadata', JSON.stringify(data));
+    formData.append('file', file);
+
+    const response = await fetch('/api/task-attempts/import', {
+      method: 'POST',
+      body: formData,
+      credentials: 'include',
+    });
+    return handleApiResponseAsResult<Workspace, ImportWorkspaceError>(
+      response
+    );
+  },
+
+  squashCommits: async (
+    attemptId: string,
+    data: SquashCommitsRequest
+  ): Promise<Result<RewriteHistoryResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits/squash`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<RewriteHistoryResponse, GitOperationError>(
+      response
+    );
+  },
+
+  rewordCommit: async (
+    attemptId: string,
+    data: RewordCommitRequest
+  ): Promise<Result<RewriteHistoryResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits/reword`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<RewriteHistoryResponse, GitOperationError>(
+      response
+    );
+  },
+
+  dropCommit: async (
+    attemptId: string,
+    data: DropCommitRequest
+  ): Promise<Result<RewriteHistoryResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits/drop`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<RewriteHistoryResponse, GitOperationError>(
+      response
+    );
+  },
+
   change_target_branch: async (
     attemptId: string,
     data: ChangeTargetBranchRequest
@@ -649,6 +919,20 @@ export const attemptsApi = {
     return handleApiResponse<ChangeTargetBranchResponse>(response);
   },
 
+  widenSparseCheckout: async (
+    attemptId: string,
+    data: WidenSparseCheckoutRequest
+  ): Promise<WidenSparseCheckoutResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/sparse-checkout/widen`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<WidenSparseCheckoutResponse>(response);
+  },
+
   renameBranch: async (
     attemptId: string,
     newBranchName: string
@@ -694,6 +978,22 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  resolveConflictsWithAgent: async (
+    attemptId: string,
+    data: ResolveConflictsWithAgentRequest
+  ): Promise<Result<ExecutionProcess, ResolveConflictsError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve-with-agent`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<ExecutionProcess, ResolveConflictsError>(
+      response
+    );
+  },
+
   createPR: async (
     attemptId: string,
     data: CreatePrApiRequest
@@ -753,6 +1053,39 @@ export const attemptsApi = {
     );
   },
 
+  runQualityGates: async (
+    attemptId: string,
+    data: RunQualityGatesRequest
+  ): Promise<Result<ExecutionProcess, RunScriptError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/quality-gates/run`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<ExecutionProcess, RunScriptError>(
+      response
+    );
+  },
+
+  sendQualityGateFailureToAgent: async (
+    attemptId: string,
+    data: SendQualityGateFailureRequest
+  ): Promise<Result<ExecutionProcess, QualityGateFollowUpError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/quality-gates/send-to-agent`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<
+      ExecutionProcess,
+      QualityGateFollowUpError
+    >(response);
+  },
+
   runArchiveScript: async (
     attemptId: string
   ): Promise<Result<ExecutionProcess, RunScriptError>> => {
@@ -777,6 +1110,64 @@ export const attemptsApi = {
     return handleApiResponse<PrCommentsResponse>(response);
   },
 
+  getPrReviewThreads: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<PrReviewThread[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/review-threads?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<PrReviewThread[]>(response);
+  },
+
+  /** Send the PR's unresolved review threads to the agent */
+  addressPrReview: async (
+    attemptId: string,
+    data: AddressPrReviewRequest
+  ): Promise<Result<AddressPrReviewResponse, AddressPrReviewError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/review-follow-up`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<
+      AddressPrReviewResponse,
+      AddressPrReviewError
+    >(response);
+  },
+
+  /** Mark the PR ready for review, or convert it back to a draft */
+  setPrDraft: async (
+    attemptId: string,
+    data: SetPrDraftRequest
+  ): Promise<Result<void, SetPrDraftError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/draft`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, SetPrDraftError>(response);
+  },
+
+  /** Merge the PR once it is approved and its checks pass, or stop doing so */
+  setPrAutoMerge: async (
+    attemptId: string,
+    data: SetPrAutoMergeRequest
+  ): Promise<Result<void, SetPrAutoMergeError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/auto-merge`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, SetPrAutoMergeError>(response);
+  },
+
   /** Mark all coding agent turns for a workspace as seen */
   markSeen: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
@@ -885,6 +1276,22 @@ export const repoApi = {
     return handleApiResponse<Repo>(response);
   },
 
+  getQualityGates: async (repoId: string): Promise<QualityGate[]> => {
+    const response = await makeRequest(`/api/repos/${repoId}/quality-gates`);
+    return handleApiResponse<QualityGate[]>(response);
+  },
+
+  updateQualityGates: async (
+    repoId: string,
+    gates: QualityGateInput[]
+  ): Promise<QualityGate[]> => {
+    const response = await makeRequest(`/api/repos/${repoId}/quality-gates`, {
+      method: 'PUT',
+      body: JSON.stringify(gates),
+    });
+    return handleApiResponse<QualityGate[]>(response);
+  },
+
   getBranches: async (repoId: string): Promise<GitBranch[]> => {
     const response = await makeRequest(`/api/repos/${repoId}/branches`);
     return handleApiResponse<GitBranch[]>(response);
@@ -945,6 +1352,23 @@ export const repoApi = {
     return handleApiResponseAsResult<OpenPrInfo[], ListPrsError>(response);
   },
 
+  listIssues: async (
+    repoId: string,
+    filter: { labels?: string; assignee?: string; milestone?: string }
+  ): Promise<Result<GitHostIssue[], ListIssuesError>> => {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(filter)) {
+      if (value?.trim()) params.set(key, value.trim());
+    }
+    const query = params.toString();
+    const response = await makeRequest(
+      `/api/repos/${repoId}/issues${query ? `?${query}` : ''}`
+    );
+    return handleApiResponseAsResult<GitHostIssue[], ListIssuesError>(
+      response
+    );
+  },
+
   listRemotes: async (repoId: string): Promise<GitRemote[]> => {
     const response = await makeRequest(`/api/repos/${repoId}/remotes`);
     return handleApiResponse<GitRemote[]>(response);
@@ -980,6 +1404,43 @@ export const configApi = {
     );
     return handleApiResponse<AvailabilityInfo>(response);
   },
+  listGitHostTokens: async (): Promise<string[]> => {
+    const response = await makeRequest('/api/git-host-tokens');
+    return handleApiResponse<string[]>(response);
+  },
+  setGitHostToken: async (data: SetGitHostTokenRequest): Promise<void> => {
+    const response = await makeRequest('/api/git-host-tokens', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<void>(response);
+  },
+  deleteGitHostToken: async (host: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/git-host-tokens/${encodeURIComponent(host)}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
+  },
+  getPrWebhookSecretStatus: async (): Promise<boolean> => {
+    const response = await makeRequest('/api/pr-webhook-secret');
+    return handleApiResponse<boolean>(response);
+  },
+  setPrWebhookSecret: async (
+    data: SetPrWebhookSecretRequest
+  ): Promise<void> => {
+    const response = await makeRequest('/api/pr-webhook-secret', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<void>(response);
+  },
+  deletePrWebhookSecret: async (): Promise<void> => {
+    const response = await makeRequest('/api/pr-webhook-secret', {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
 };
 
 // Task Tags APIs (all tags are global)
@@ -1447,14 +1908,41 @@ export const searchApi = {
     repoIds: string[],
     query: string,
     mode?: SearchMode,
-    options?: RequestInit
+    options?: RequestInit,
+    workspaceId?: string
   ): Promise<SearchResult[]> => {
     const repoIdsParam = repoIds.join(',');
     const modeParam = mode ? `&mode=${encodeURIComponent(mode)}` : '';
+    const workspaceParam = workspaceId
+      ? `&workspace_id=${encodeURIComponent(workspaceId)}`
+      : '';
     const response = await makeRequest(
-      `/api/search?q=${encodeURIComponent(query)}&repo_ids=${encodeURIComponent(repoIdsParam)}${modeParam}`,
+      `/api/search?q=${encodeURIComponent(query)}&repo_ids=${encodeURIComponent(repoIdsParam)}${modeParam}${workspaceParam}`,
       options
     );
     return handleApiResponse<SearchResult[]>(response);
   },
 };
+
+// Workspace branch archives
+export const workspaceArchivesApi = {
+  list: async (): Promise<WorkspaceBranchArchive[]> => {
+    const response = await makeRequest('/api/workspace-archives');
+    return handleApiResponse<WorkspaceBranchArchive[]>(response);
+  },
+
+  restore: async (archiveId: string): Promise<Workspace> => {
+    const response = await makeRequest(
+      `/api/workspace-archives/${archiveId}/restore`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<Workspace>(response);
+  },
+
+  delete: async (archiveId: string): Promise<void> => {
+    const response = await makeRequest(`/api/workspace-archives/${archiveId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
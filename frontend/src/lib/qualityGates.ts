@@ -0,0 +1,45 @@
+import { ConfirmDialog } from '@/components/ui-new/dialogs/ConfirmDialog';
+import type { QualityGateResult, QualityGateStatus } from 'shared/types';
+
+export const qualityGateStatusLabels: Record<QualityGateStatus, string> = {
+  not_run: 'Not run',
+  running: 'Running',
+  passed: 'Passed',
+  failed: 'Failed',
+  outdated: 'Outdated',
+};
+
+export function formatBlockingGates(gates: QualityGateResult[]): string {
+  return gates
+    .map((gate) => `${gate.name} (${qualityGateStatusLabels[gate.status]})`)
+    .join(', ');
+}
+
+/**
+ * Ask whether to go ahead with a merge, push or PR even though required
+ * quality gates have not passed. Resolves to true when the user overrides.
+ * When the settings do not allow overrides, only explains why it was blocked.
+ */
+export async function confirmQualityGateBypass(
+  operation: 'merge' | 'push' | 'pull request',
+  gates: QualityGateResult[],
+  bypassAllowed: boolean
+): Promise<boolean> {
+  if (!bypassAllowed) {
+    await ConfirmDialog.show({
+      title: 'Quality gates not passing',
+      message: `Required quality gates have not passed at the current commit: ${formatBlockingGates(gates)}. Fix the failures and run the gates again before the ${operation}.`,
+      confirmText: 'OK',
+      showCancelButton: false,
+      variant: 'destructive',
+    });
+    return false;
+  }
+  const result = await ConfirmDialog.show({
+    title: 'Quality gates not passing',
+    message: `Required quality gates have not passed at the current commit: ${formatBlockingGates(gates)}. Continue with the ${operation} anyway?`,
+    confirmText: 'Override and continue',
+    variant: 'destructive',
+  });
+  return result === 'confirmed';
+}
@@ -14,6 +14,8 @@ export interface SearchResultItem {
 export interface SearchOptions {
   repoIds?: string[];
   projectId?: string;
+  /** Limit results to the workspace's sparse checkout */
+  workspaceId?: string;
 }
 
 export async function searchTagsAndFiles(
@@ -33,7 +35,13 @@ export async function searchTagsAndFiles(
   if (query.length > 0) {
     let fileResults: SearchResult[] = [];
     if (options?.repoIds && options.repoIds.length > 0) {
-      fileResults = await searchApi.searchFiles(options.repoIds, query);
+      fileResults = await searchApi.searchFiles(
+        options.repoIds,
+        query,
+        undefined,
+        undefined,
+        options.workspaceId
+      );
     } else if (options?.projectId) {
       fileResults = await projectsApi.searchFiles(options.projectId, query);
     }
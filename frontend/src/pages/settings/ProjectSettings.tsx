@@ -31,11 +31,13 @@ import type { Project, Repo, UpdateProject } from 'shared/types';
 
 interface ProjectFormState {
   name: string;
+  branch_name_template: string;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
   return {
     name: project.name,
+    branch_name_template: project.branch_name_template ?? '',
   };
 }
 
@@ -292,6 +294,7 @@ export function ProjectSettings() {
     try {
       const updateData: UpdateProject = {
         name: draft.name.trim(),
+        branch_name_template: draft.branch_name_template.trim() || null,
       };
 
       updateProject.mutate({
@@ -425,6 +428,24 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-2">
+                <Label htmlFor="project-branch-template">
+                  {t('settings.projects.general.branchTemplate.label')}
+                </Label>
+                <Input
+                  id="project-branch-template"
+                  type="text"
+                  value={draft.branch_name_template}
+                  onChange={(e) =>
+                    updateDraft({ branch_name_template: e.target.value })
+                  }
+                  placeholder="{prefix}/{short_id}-{slug}"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.general.branchTemplate.helper')}
+                </p>
+              </div>
+
               {/* Save Button */}
               <div className="flex items-center justify-between pt-4 border-t">
                 {hasUnsavedChanges ? (
@@ -4,11 +4,15 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, 
+/**
+ * Branch naming template; overrides the global template
+ */
+branch_name_template: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, };
+export type UpdateProject = { name: string | null, branch_name_template?: string | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, 
 /**
@@ -18,19 +22,104 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, init_submodules: boolean, 
+/**
+ * Newline-separated directories for a cone-mode sparse checkout
+ */
+sparse_checkout: string | null, 
+/**
+ * Layout for generated Conventional Commits messages, e.g. `{type}{scope}: {description}`
+ */
+commit_message_template: string | null, 
+/**
+ * Maximum subject line length for generated commit messages (72 when unset)
+ */
+commit_subject_max_length: number | null, 
+/**
+ * Overrides the global commit identity when set
+ */
+commit_identity_mode: CommitIdentityMode | null, 
+/**
+ * Overrides the global commit signing format when set
+ */
+commit_signing_format: CommitSigningFormat | null, 
+/**
+ * GPG key id or SSH key path; falls back to the global key, then `user.signingkey`
+ */
+commit_signing_key: string | null, 
+/**
+ * Branch naming template; overrides the project's and the global template
+ */
+branch_name_template: string | null, created_at: Date, updated_at: Date, };
+
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, init_submodules?: boolean | null, sparse_checkout?: string | null, commit_message_template?: string | null, commit_subject_max_length?: number | null, commit_identity_mode?: CommitIdentityMode | null, commit_signing_format?: CommitSigningFormat | null, commit_signing_key?: string | null, branch_name_template?: string | null, };
+
+export type CommitIdentityMode = "user" | "bot";
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, };
+export type CommitSigningFormat = "none" | "gpg" | "ssh";
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
 export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
-export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+export type QualityGate = { id: string, repo_id: string, name: string, script: string, 
+/**
+ * Merging, pushing and opening a PR are blocked until a required gate passes
+ */
+required: boolean, sort_order: bigint, created_at: Date, updated_at: Date, };
+
+export type QualityGateInput = { 
+/**
+ * Existing gate to update; a new gate is created when `None`
+ */
+id: string | null, name: string, script: string, required: boolean, };
+
+export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, 
+/**
+ * Per-workspace sparse-checkout override. `None` inherits the repo setting,
+ * an empty string forces a full checkout.
+ */
+sparse_checkout: string | null, created_at: Date, updated_at: Date, };
+
+export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, sparse_checkout: string | null, };
+
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, init_submodules: boolean, 
+/**
+ * Newline-separated directories for a cone-mode sparse checkout
+ */
+sparse_checkout: string | null, 
+/**
+ * Layout for generated Conventional Commits messages, e.g. `{type}{scope}: {description}`
+ */
+commit_message_template: string | null, 
+/**
+ * Maximum subject line length for generated commit messages (72 when unset)
+ */
+commit_subject_max_length: number | null, 
+/**
+ * Overrides the global commit identity when set
+ */
+commit_identity_mode: CommitIdentityMode | null, 
+/**
+ * Overrides the global commit signing format when set
+ */
+commit_signing_format: CommitSigningFormat | null, 
+/**
+ * GPG key id or SSH key path; falls back to the global key, then `user.signingkey`
+ */
+commit_signing_key: string | null, 
+/**
+ * Branch naming template; overrides the project's and the global template
+ */
+branch_name_template: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+export type BranchArchiveStorage = "ref" | "bundle";
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type WorkspaceBranchArchive = { id: string, workspace_id: string, task_id: string, repo_id: string, workspace_name: string | null, branch: string, target_branch: string, commit_sha: string, storage: BranchArchiveStorage, 
+/**
+ * Archive ref name or bundle file path
+ */
+location: string, created_at: Date, updated_at: Date, };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -146,7 +235,7 @@ dropped: boolean, started_at: string, completed_at: string | null, created_at: s
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "archivescript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "archivescript" | "qualitygate" | "codingagent" | "devserver";
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
@@ -154,11 +243,71 @@ export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMe
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
 
-export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, 
+/**
+ * CI checks on the PR head as of the last poll
+ */
+checks: Array<PrCheck>, 
+/**
+ * Follow-ups sent to the agent to fix failing checks since they last passed
+ */
+ci_fix_attempts: bigint, 
+/**
+ * Coding agent run refreshing the PR description, until it is checked
+ */
+description_process_id: string | null, 
+/**
+ * User-maintained section of the description when the refresh started
+ */
+description_user_section: string | null, 
+/**
+ * Merge the PR once it is approved and its required checks pass
+ */
+auto_merge: boolean, };
+
+export type PrCheck = { name: string, status: PrCheckStatus, 
+/**
+ * Whether the target branch requires the check to pass before merging
+ */
+required: boolean, 
+/**
+ * Link to the check's details or job page
+ */
+url: string | null, description: string | null, };
+
+export type PrCheckStatus = "pending" | "passed" | "failed" | "skipped";
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
-export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, is_draft: boolean, };
+
+export type PrReviewThread = { id: string, merge_id: string, 
+/**
+ * Provider id of the thread, used to reply to and resolve it
+ */
+thread_id: string, path: string, line: bigint | null, url: string | null, 
+/**
+ * Agent run that was asked to address the thread
+ */
+execution_process_id: string | null, status: PrReviewThreadStatus, 
+/**
+ * Reason the thread was skipped, or the error that failed it
+ */
+note: string | null, created_at: Date, updated_at: Date, };
+
+export type PrReviewThreadStatus = "in_progress" | "addressed" | "skipped" | "failed";
+
+export type TaskIssue = { id: string, task_id: string, 
+/**
+ * Repo whose git host the issue is on
+ */
+repo_id: string, issue_url: string, issue_number: bigint, state: TaskIssueState, 
+/**
+ * Whether the issue got the comment left once the task is done
+ */
+done_commented: boolean, created_at: Date, updated_at: Date, };
+
+export type TaskIssueState = "open" | "closed";
 
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
@@ -174,10 +323,54 @@ contentOmitted: boolean,
 /**
  * Optional precomputed stats for omitted content
  */
-additions: number | null, deletions: number | null, repoId: string | null, };
+additions: number | null, deletions: number | null, repoId: string | null, 
+/**
+ * Unified diff hunks computed with the requested view options (ignored
+ * whitespace, context lines); `None` when the viewer diffs the contents itself
+ */
+hunks: Array<string> | null, 
+/**
+ * Word-level changes within modified lines, when requested
+ */
+wordHighlights: Array<DiffLineHighlight> | null, };
 
 export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
 
+export type DiffViewOptions = { 
+/**
+ * Ignore whitespace when comparing lines, like `git diff -w`
+ */
+ignore_whitespace: boolean, 
+/**
+ * Similarity percentage (0-100) at which a removed and an added file are
+ * paired as a rename or copy; git's default is 50
+ */
+rename_threshold: number | null, 
+/**
+ * Also detect files copied from other files in the change
+ */
+detect_copies: boolean, 
+/**
+ * Lines of context around each hunk; git's default is 3
+ */
+context_lines: number | null, 
+/**
+ * Compute word-level highlights for modified lines
+ */
+word_diff: boolean, };
+
+export type DiffSide = "old" | "new";
+
+export type DiffLineHighlight = { side: DiffSide, 
+/**
+ * 1-based line number in the old or new content
+ */
+lineNumber: number, 
+/**
+ * Changed `[start, end)` ranges as UTF-16 offsets, matching JavaScript string indices
+ */
+ranges: Array<[number, number]>, };
+
 export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
 
 export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
@@ -266,6 +459,10 @@ export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, }
 
 export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
 
+export type SetGitHostTokenRequest = { host: string, token: string, };
+
+export type SetPrWebhookSecretRequest = { secret: string, };
+
 export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
 
 export type CheckEditorAvailabilityResponse = { available: boolean, };
@@ -282,9 +479,42 @@ export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: st
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+export type WidenSparseCheckoutRequest = { repo_id: string, 
+/**
+ * Repository-relative directories to add to the sparse-checkout cone
+ */
+paths: Array<string>, };
 
-export type PushTaskAttemptRequest = { repo_id: string, };
+export type WidenSparseCheckoutResponse = { repo_id: string, 
+/**
+ * Directories now checked out, or `null` if the worktree is a full checkout
+ */
+sparse_checkout: string | null, };
+
+export type MergeTaskAttemptRequest = { repo_id: string, 
+/**
+ * Overrides the generated squash-merge commit message
+ */
+commit_message?: string | null, 
+/**
+ * Merge even though required quality gates have not passed
+ */
+bypass_quality_gates?: boolean | null, };
+
+export type MergeCommitMessageResponse = { message: string, };
+
+export type MergeError = { "type": "quality_gates_failed", gates: Array<QualityGateResult>, 
+/**
+ * Whether the user may override the gates, see the
+ * `allow_quality_gate_bypass` setting
+ */
+bypass_allowed: boolean, };
+
+export type PushTaskAttemptRequest = { repo_id: string, 
+/**
+ * Push even though required quality gates have not passed
+ */
+bypass_quality_gates?: boolean | null, };
 
 export type RenameBranchRequest = { new_branch_name: string, };
 
@@ -298,17 +528,42 @@ export type OpenEditorRequest = { editor_type: string | null, file_path: string
 
 export type OpenEditorResponse = { url: string | null, };
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type ImportIssuesRequest = { project_id: string, 
+/**
+ * Repo whose git host the issues are on
+ */
+repo_id: string, issues: Array<GitHostIssue>, };
+
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * `simple_id` of the remote issue the workspace will be linked to, used
+ * for the `{issue}` branch name value
+ */
+linked_issue_simple_id?: string, };
 
-export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, 
+/**
+ * Open the PR even though required quality gates have not passed
+ */
+bypass_quality_gates?: boolean | null, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * `simple_id` of the remote issue the workspace will be linked to, used
+ * for the `{issue}` branch name value
+ */
+linked_issue_simple_id?: string, };
 
-export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+export type WorkspaceRepoInput = { repo_id: string, target_branch: string, 
+/**
+ * Overrides the repo's sparse-checkout directories for this attempt.
+ * An empty string forces a full checkout.
+ */
+sparse_checkout?: string, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
@@ -322,14 +577,150 @@ export type ContinueRebaseRequest = { repo_id: string, };
 
 export type AbortConflictsRequest = { repo_id: string, };
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, target_branch: string, } | { "type": "rebase_in_progress" };
+export type ResolveConflictsWithAgentRequest = { repo_id: string, };
+
+export type ResolveConflictsError = { "type": "no_conflicts" } | { "type": "process_already_running" } | { "type": "no_agent_configured" };
+
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, target_branch: string, } | { "type": "rebase_in_progress" } | { "type": "process_already_running" };
+
+export type WorkspaceCommit = { 
+/**
+ * `None` for commits made outside an execution process, or whose hash
+ * changed after a history rewrite
+ */
+execution_process_id: string | null, sha: string, subject: string, message: string, author_name: string, author_email: string, authored_at: Date, files_changed: number, additions: number, deletions: number, };
+
+export type FileBlameRange = { 
+/**
+ * `None` when no execution process can be matched to the change
+ */
+execution_process_id: string | null, session_id: string | null, coding_agent_turn_id: string | null, 
+/**
+ * The prompt of the coding agent turn that produced the change
+ */
+prompt: string | null, 
+/**
+ * 1-based line number of the first line in the range
+ */
+start_line: number, line_count: number, 
+/**
+ * `None` for lines that are not committed yet
+ */
+commit_sha: string | null, };
+
+export type ImportWorkspaceRequest = { task_id: string, repos: Array<WorkspaceRepoInput>, };
 
-export type PushError = { "type": "force_push_required" };
+export type ImportWorkspaceError = { "type": "nothing_to_import" } | { "type": "apply_failed", repo_name: string, message: string, };
 
-export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" };
+export type BackportRequest = { repo_id: string, target_branches: Array<string>, 
+/**
+ * Start a coding agent in every derived workspace that stops on conflicts
+ */
+resolve_conflicts_with_agent: boolean, 
+/**
+ * Open a pull request for every derived workspace the changes applied to
+ */
+create_prs: boolean, };
+
+export type BackportStatus = { "type": "applied" } | { "type": "conflicts", message: string, conflicted_files: Array<string>, 
+/**
+ * Coding agent started to resolve the conflicts
+ */
+execution_process_id: string | null, } | { "type": "failed", message: string, };
+
+export type BackportPr = { "type": "created", url: string, } | { "type": "failed", message: string, };
+
+export type BackportResult = { target_branch: string, 
+/**
+ * Derived workspace; `None` when it could not be created
+ */
+workspace: Workspace | null, status: BackportStatus, 
+/**
+ * Pull request of the derived workspace, when requested and the changes
+ * applied
+ */
+pr: BackportPr | null, };
+
+export type SquashCommitsRequest = { repo_id: string, 
+/**
+ * Oldest commit of the range to squash
+ */
+from: string, 
+/**
+ * Newest commit of the range to squash
+ */
+to: string, 
+/**
+ * Defaults to the messages of all squashed commits
+ */
+message: string | null, };
+
+export type RewordCommitRequest = { repo_id: string, commit: string, message: string, };
+
+export type DropCommitRequest = { repo_id: string, commit: string, };
+
+export type RewriteHistoryResponse = { repo_id: string, head_commit: string, };
+
+export type AcceptHunksRequest = { repo_id: string, files: Array<HunkSelection>, };
+
+export type UnstageHunksRequest = { repo_id: string, files: Array<HunkSelection>, };
+
+export type RejectHunksRequest = { repo_id: string, scope: HunkScope, files: Array<HunkSelection>, 
+/**
+ * Sent to the coding agent together with the rejected changes
+ */
+feedback?: string, };
+
+export type RejectHunksResponse = { 
+/**
+ * Commit reverting the rejected hunks, for the committed scope
+ */
+revert_commit: string | null, 
+/**
+ * Coding agent follow-up started with the feedback
+ */
+execution_process_id: string | null, };
+
+export type HunkReviewError = { "type": "process_already_running" } | { "type": "diff_changed", message: string, };
+
+export type PushError = { "type": "force_push_required" } | { "type": "quality_gates_failed", gates: Array<QualityGateResult>, 
+/**
+ * Whether the user may override the gates, see the
+ * `allow_quality_gate_bypass` setting
+ */
+bypass_allowed: boolean, };
+
+export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" } | { "type": "quality_gates_failed", gates: Array<QualityGateResult>, 
+/**
+ * Whether the user may override the gates, see the
+ * `allow_quality_gate_bypass` setting
+ */
+bypass_allowed: boolean, };
 
 export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
 
+export type RunQualityGatesRequest = { repo_id: string, };
+
+export type SendQualityGateFailureRequest = { 
+/**
+ * Execution process of the failed gate run
+ */
+execution_process_id: string, };
+
+export type QualityGateFollowUpError = { "type": "gate_not_failed" } | { "type": "process_already_running" } | { "type": "no_agent_configured" };
+
+export type QualityGateStatus = "not_run" | "running" | "passed" | "failed" | "outdated";
+
+export type QualityGateResult = { gate_id: string, name: string, required: boolean, status: QualityGateStatus, 
+/**
+ * Execution process of the latest run
+ */
+execution_process_id: string | null, 
+/**
+ * Commit checked by the latest run
+ */
+head_commit: string | null, };
+
 export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
 
 export type AttachExistingPrRequest = { repo_id: string, };
@@ -340,16 +731,62 @@ export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "cli_n
 
 export type GetPrCommentsQuery = { repo_id: string, };
 
+export type AddressPrReviewRequest = { repo_id: string, };
+
+export type AddressPrReviewResponse = { 
+/**
+ * Threads sent to the agent
+ */
+threads: Array<PrReviewThread>, };
+
+export type AddressPrReviewError = { "type": "no_pr_attached" } | { "type": "no_unresolved_threads" } | { "type": "no_coding_agent" } | { "type": "agent_busy" } | { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, };
+
+export type SetPrDraftRequest = { repo_id: string, 
+/**
+ * Convert the PR to a draft, or mark it ready for review
+ */
+draft: boolean, };
+
+export type SetPrDraftError = { "type": "no_pr_attached" } | { "type": "unsupported_provider" } | { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, };
+
+export type SetPrAutoMergeRequest = { repo_id: string, 
+/**
+ * Merge the PR once it is approved and its required checks pass
+ */
+auto_merge: boolean, };
+
+export type SetPrAutoMergeError = { "type": "no_pr_attached" };
+
 export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
 
-export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
+export type ProviderKind = "git_hub" | "azure_dev_ops" | "git_lab" | "gitea" | "bitbucket" | "bitbucket_server" | "unknown";
+
+export type GitHostMapping = { 
+/**
+ * Host name as it appears in remote URLs, e.g. `git.example.com`
+ */
+host: string, provider: ProviderKind, 
+/**
+ * API base URL; defaults to the provider's API on `host`
+ */
+api_url: string | null, };
+
+export type MergeMethod = "merge" | "squash" | "rebase";
 
 export type OpenPrInfo = { number: bigint, url: string, title: string, head_branch: string, base_branch: string, };
 
+export type GitHostIssue = { number: bigint, url: string, title: string, body: string | null, is_open: boolean, labels: Array<string>, 
+/**
+ * Logins of the assigned users
+ */
+assignees: Array<string>, milestone: string | null, };
+
 export type GitRemote = { name: string, url: string, };
 
 export type ListPrsError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "auth_failed", message: string, } | { "type": "unsupported_provider" };
 
+export type ListIssuesError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "auth_failed", message: string, } | { "type": "unsupported_provider" };
+
 export type CreateWorkspaceFromPrBody = { repo_id: string, pr_number: bigint, pr_title: string, pr_url: string, head_branch: string, base_branch: string, run_setup: boolean, remote_name: string | null, };
 
 export type CreateWorkspaceFromPrResponse = { workspace: Workspace, task: Task, };
@@ -372,7 +809,11 @@ conflicted_files: Array<string>,
 /**
  * True if the target branch is a remote branch (merging not allowed, must use PR)
  */
-is_target_remote: boolean, };
+is_target_remote: boolean, 
+/**
+ * Latest result of each quality gate configured for the repo
+ */
+quality_gates: Array<QualityGateResult>, };
 
 export type UpdateWorkspace = { archived: boolean | null, pinned: boolean | null, name: string | null, };
 
@@ -424,13 +865,56 @@ export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+export type RepoDiskUsage = { repo_id: string, repo_name: string, total_bytes: bigint, 
+/**
+ * Bytes in git-ignored paths, such as dependency and build directories
+ */
+ignored_bytes: bigint, };
+
+export type WorkspaceDiskUsage = { workspace_id: string, task_id: string, name: string | null, branch: string, archived: boolean, 
+/**
+ * Last activity in the workspace; the least recently used archived
+ * workspaces are cleaned up first when the quota is exceeded
+ */
+last_used_at: string, total_bytes: bigint, repos: Array<RepoDiskUsage>, };
+
+export type DiskUsageReport = { 
+/**
+ * Size of the whole workspace directory, including worktrees that no
+ * longer belong to a workspace
+ */
+total_bytes: bigint, limit_bytes: bigint | null, workspaces: Array<WorkspaceDiskUsage>, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, 
+/**
+ * Generate Conventional Commits messages for auto-commits and merges
+ */
+conventional_commits_enabled: boolean, commit_identity: CommitIdentityConfig, send_message_shortcut: SendMessageShortcut, branch_archive: BranchArchiveMode, workspace_quota: WorkspaceQuotaConfig, 
+/**
+ * Branch naming template, e.g. `{prefix}/{short_id}-{slug}`. Projects and
+ * repos can override it.
+ */
+git_branch_template: string | null, 
+/**
+ * Hook failures are logged to the process and sent to the agent as a
+ * follow-up
+ */
+commit_hooks: CommitHookMode, 
+/**
+ * Let merges, pushes and PRs go ahead when the user overrides required
+ * quality gates that have not passed
+ */
+allow_quality_gate_bypass: boolean, 
+/**
+ * Self-hosted git hosts, with their provider and API credentials
+ */
+git_hosts: Array<GitHostMapping>, ci_auto_fix: CiAutoFixConfig, pr_webhooks: PrWebhookConfig, pr_lifecycle: PrLifecycleConfig, issue_done_action: IssueDoneAction, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -452,6 +936,58 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type SendMessageShortcut = "ModifierEnter" | "Enter";
 
+export type BranchArchiveMode = "off" | "refs" | "bundle";
+
+export type QuotaExceededAction = "warn" | "refuse" | "cleanup_archived";
+
+export type WorkspaceQuotaConfig = { 
+/**
+ * No quota when unset
+ */
+limit_mb: bigint | null, action: QuotaExceededAction, };
+
+export type CommitHookMode = "run" | "skip" | "run_and_retry";
+
+export type CiAutoFixConfig = { enabled: boolean, 
+/**
+ * Follow-ups per PR until its checks pass again
+ */
+max_attempts: number, 
+/**
+ * Include the failing job's log in the follow-up, where the git host
+ * provides it
+ */
+include_logs: boolean, };
+
+export type PrWebhookConfig = { enabled: boolean, };
+
+export type PrLifecycleConfig = { 
+/**
+ * Have the agent rewrite the PR description after follow-ups are pushed
+ */
+refresh_description: boolean, 
+/**
+ * Mark draft PRs ready for review once the required quality gates pass
+ * on the pushed branch
+ */
+auto_ready: boolean, 
+/**
+ * How PRs with auto-merge turned on are merged
+ */
+merge_method: MergeMethod, };
+
+export type IssueDoneAction = "nothing" | "comment" | "close";
+
+export type CommitIdentityConfig = { mode: CommitIdentityMode, bot_name: string, bot_email: string, 
+/**
+ * Credit the executor profile with a `Co-authored-by` trailer
+ */
+co_author_trailers: boolean, signing_format: CommitSigningFormat, 
+/**
+ * GPG key id or SSH key path; git's `user.signingkey` when unset
+ */
+signing_key: string | null, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type QueuedMessage = { 
@@ -472,13 +1008,52 @@ export type QueueStatus = { "status": "empty" } | { "status": "queued", message:
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
+export type CommitInfo = { sha: string, subject: string, message: string, author_name: string, author_email: string, authored_at: Date, files_changed: number, additions: number, deletions: number, };
+
+export type BlameRange = { 
+/**
+ * 1-based line number of the first line in the range
+ */
+start_line: number, line_count: number, 
+/**
+ * `None` for lines that are not committed yet
+ */
+commit_sha: string | null, };
+
+export type ExportFormat = "mbox" | "diff" | "bundle";
+
+export type HunkScope = "uncommitted" | "staged" | "committed";
+
+export type DiffHunk = { 
+/**
+ * The `@@ -a,b +c,d @@` line, which identifies the hunk within its file
+ */
+header: string, 
+/**
+ * Hunk lines with their ` `, `+` or `-` prefix
+ */
+lines: Array<string>, };
+
+export type FileHunks = { path: string, 
+/**
+ * Empty for binary files and mode-only changes, which can only be
+ * selected as a whole
+ */
+hunks: Array<DiffHunk>, };
+
+export type HunkSelection = { path: string, 
+/**
+ * Headers of the selected hunks; the whole file when `None`
+ */
+hunks?: Array<string>, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest | { "type": "ReviewRequest" } & ReviewRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "ArchiveScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "ArchiveScript" | "QualityGate" | "DevServer" | "ToolInstallScript";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**